@@ -408,6 +408,7 @@ fn create_v2_config(ctx: &MigrationContext) -> Result<()> {
         graph_config,
         mcp: ctx.v1_config.mcp,
         bm25: ctx.v1_config.bm25,
+        change_log: None,
     };
 
     // Create local instance config
@@ -75,6 +75,18 @@ pub struct DbConfig {
     pub mcp: bool,
     #[serde(default = "default_true", skip_serializing_if = "is_true")]
     pub bm25: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_log: Option<ChangeLogConfig>,
+}
+
+/// Records writes so they can be streamed from `/changes`, old changes are pruned once past
+/// either limit
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChangeLogConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_events: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -189,6 +201,7 @@ impl Default for DbConfig {
             graph_config: GraphConfig::default(),
             mcp: true,
             bm25: true,
+            change_log: None,
         }
     }
 }
@@ -271,7 +284,8 @@ impl<'a> InstanceInfo<'a> {
             },
             "db_max_size_gb": db_config.vector_config.db_max_size_gb,
             "mcp": db_config.mcp,
-            "bm25": db_config.bm25
+            "bm25": db_config.bm25,
+            "change_log": db_config.change_log
         })
     }
 }
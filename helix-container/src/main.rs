@@ -6,10 +6,14 @@ use helix_db::helix_engine::{
 };
 use helix_db::helix_gateway::mcp::mcp::{MCPHandlerFn, MCPHandlerSubmission};
 use helix_db::helix_gateway::{
+    batch::{BatchHandlerFn, BatchHandlerSubmission},
     gateway::{GatewayOpts, HelixGateway},
     router::router::{HandlerFn, HandlerSubmission},
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
@@ -152,6 +156,29 @@ fn main() {
         })
         .collect::<HashMap<String, MCPHandlerFn>>();
 
+    // queries that can run inside a /batch transaction
+    let batch_routes = inventory::iter::<BatchHandlerSubmission>
+        .into_iter()
+        .map(|submission| {
+            let handler = &submission.0;
+            let func: BatchHandlerFn = Arc::new(handler.func);
+            (handler.name.to_string(), func)
+        })
+        .collect::<HashMap<String, BatchHandlerFn>>();
+
+    // routes that open a write transaction, read-only API keys can't call these
+    let write_routes: HashSet<String> = inventory::iter::<HandlerSubmission>
+        .into_iter()
+        .filter(|submission| submission.0.is_mut)
+        .map(|submission| submission.0.name.to_string())
+        .chain(
+            inventory::iter::<MCPHandlerSubmission>
+                .into_iter()
+                .filter(|submission| submission.0.is_mut)
+                .map(|submission| submission.0.name.to_string()),
+        )
+        .collect();
+
     println!("Routes: {:?}", query_routes.keys());
     let gateway = HelixGateway::new(
         &format!("0.0.0.0:{port}"),
@@ -160,7 +187,9 @@ fn main() {
         Some(query_routes),
         Some(mcp_routes),
         Some(opts),
-    );
+    )
+    .with_batch_routes(batch_routes)
+    .with_write_routes(write_routes);
 
     gateway.run().expect("Failed to run gateway")
 }
@@ -152,6 +152,14 @@ impl HBM25Config {
             b: 0.75,
         })
     }
+
+    /// Document count and average length of the index, `None` before the first insert
+    pub fn metadata(&self, txn: &RoTxn) -> Result<Option<BM25Metadata>, GraphError> {
+        match self.metadata_db.get(txn, METADATA_KEY)? {
+            Some(data) => Ok(Some(bincode::deserialize(data)?)),
+            None => Ok(None),
+        }
+    }
 }
 
 impl BM25 for HBM25Config {
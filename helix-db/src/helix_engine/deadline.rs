@@ -0,0 +1,83 @@
+//! Per-request deadlines for cooperative cancellation.
+//!
+//! The worker running a request sets its deadline with [`set_deadline`] before calling the
+//! handler. Long-running traversals call [`check_deadline`] inside their loops and bail
+//! out with [`GraphError::QueryTimeout`] once it has passed, which drops the transaction.
+
+use std::{cell::Cell, time::Instant};
+
+use crate::helix_engine::types::GraphError;
+
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Restores the previous deadline of the thread when dropped
+pub struct DeadlineGuard {
+    previous: Option<Instant>,
+}
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        DEADLINE.with(|d| d.set(self.previous));
+    }
+}
+
+/// Sets the deadline of the current thread until the returned guard is dropped
+#[must_use]
+pub fn set_deadline(deadline: Option<Instant>) -> DeadlineGuard {
+    DeadlineGuard {
+        previous: DEADLINE.with(|d| d.replace(deadline)),
+    }
+}
+
+/// Whether the current thread has a deadline that has passed
+#[inline]
+pub fn deadline_exceeded() -> bool {
+    DEADLINE.with(|d| d.get().is_some_and(|deadline| Instant::now() >= deadline))
+}
+
+#[inline]
+pub fn check_deadline() -> Result<(), GraphError> {
+    match deadline_exceeded() {
+        true => Err(GraphError::QueryTimeout),
+        false => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_no_deadline() {
+        assert!(!deadline_exceeded());
+        assert!(check_deadline().is_ok());
+    }
+
+    #[test]
+    fn test_deadline_passed() {
+        let _guard = set_deadline(Some(Instant::now() - Duration::from_millis(1)));
+        assert!(deadline_exceeded());
+        assert!(matches!(check_deadline(), Err(GraphError::QueryTimeout)));
+    }
+
+    #[test]
+    fn test_deadline_in_future() {
+        let _guard = set_deadline(Some(Instant::now() + Duration::from_secs(60)));
+        assert!(check_deadline().is_ok());
+    }
+
+    #[test]
+    fn test_guard_restores_previous_deadline() {
+        let outer = set_deadline(Some(Instant::now() + Duration::from_secs(60)));
+        {
+            let _inner = set_deadline(Some(Instant::now() - Duration::from_millis(1)));
+            assert!(deadline_exceeded());
+        }
+        assert!(!deadline_exceeded());
+        drop(outer);
+        assert!(!deadline_exceeded());
+    }
+}
@@ -0,0 +1,190 @@
+//! Ordered log of the node, edge and vector writes made to the graph.
+//!
+//! Writes record a [`ChangeEvent`] inside the transaction making them, so the log only ever
+//! holds committed changes, in commit order. Events are keyed by a sequence number starting
+//! at 1 that keeps increasing even once old events are pruned, which lets readers resume
+//! from the last event they saw.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use heed3::{
+    Database, Env, RoTxn, RwTxn,
+    byteorder::BE,
+    types::{Bytes, U64},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    helix_engine::{traversal_core::config::ChangeLogConfig, types::GraphError},
+    utils::{items::Edge, properties::ImmutablePropertiesMap},
+};
+
+const DB_CHANGES: &str = "changes"; // for the change log (seq -> event)
+
+/// Holds the last sequence number handed out, events start at 1
+const LAST_SEQ_KEY: u64 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOp {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Node,
+    Edge,
+    Vector,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEvent {
+    pub seq: u64,
+    /// When the change was made, in milliseconds since the unix epoch
+    pub timestamp_ms: u64,
+    pub op: ChangeOp,
+    pub kind: ItemKind,
+    pub id: String,
+    pub label: String,
+    /// The item as queries return it after the change, `null` for deletes
+    pub item: sonic_rs::Value,
+}
+
+/// An edge as the change log records it, the ids of its endpoints being uuids like its own
+#[derive(Serialize)]
+pub struct EdgeItem<'a, 'arena> {
+    pub id: String,
+    pub label: &'arena str,
+    pub version: u8,
+    pub from_node: String,
+    pub to_node: String,
+    pub properties: &'a Option<ImmutablePropertiesMap<'arena>>,
+}
+
+impl<'a, 'arena> From<&'a Edge<'arena>> for EdgeItem<'a, 'arena> {
+    fn from(edge: &'a Edge<'arena>) -> Self {
+        let uuid = |id| uuid::Uuid::from_u128(id).to_string();
+        Self {
+            id: uuid(edge.id),
+            label: edge.label,
+            version: edge.version,
+            from_node: uuid(edge.from_node),
+            to_node: uuid(edge.to_node),
+            properties: &edge.properties,
+        }
+    }
+}
+
+pub struct ChangeLog {
+    db: Database<U64<BE>, Bytes>,
+    config: ChangeLogConfig,
+}
+
+impl ChangeLog {
+    pub fn new(env: &Env, txn: &mut RwTxn, config: ChangeLogConfig) -> Result<Self, GraphError> {
+        // Changes: [seq]->[json of the change event]
+        //          [8 bytes]->[dynamic]
+        let db = env
+            .database_options()
+            .types::<U64<BE>, Bytes>()
+            .name(DB_CHANGES)
+            .create(txn)?;
+        Ok(Self { db, config })
+    }
+
+    /// Appends a change and prunes the ones past retention, returning its sequence number
+    pub fn record(
+        &self,
+        txn: &mut RwTxn,
+        op: ChangeOp,
+        kind: ItemKind,
+        id: u128,
+        label: &str,
+        item: sonic_rs::Value,
+    ) -> Result<u64, GraphError> {
+        let seq = self.last_seq(txn)? + 1;
+        let event = ChangeEvent {
+            seq,
+            timestamp_ms: now_ms(),
+            op,
+            kind,
+            id: uuid::Uuid::from_u128(id).to_string(),
+            label: label.to_string(),
+            item,
+        };
+        self.db.put(txn, &seq, &sonic_rs::to_vec(&event)?)?;
+        self.db.put(txn, &LAST_SEQ_KEY, &seq.to_be_bytes())?;
+        self.prune(txn, seq, event.timestamp_ms)?;
+        Ok(seq)
+    }
+
+    /// Sequence number of the latest change, 0 before the first one
+    pub fn last_seq(&self, txn: &RoTxn) -> Result<u64, GraphError> {
+        match self.db.get(txn, &LAST_SEQ_KEY)? {
+            Some(bytes) => {
+                let bytes = bytes.try_into().map_err(|_| {
+                    GraphError::DecodeError("change log sequence is not a u64".to_string())
+                })?;
+                Ok(u64::from_be_bytes(bytes))
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Sequence number of the oldest change still kept
+    pub fn first_seq(&self, txn: &RoTxn) -> Result<Option<u64>, GraphError> {
+        match self.db.range(txn, &(LAST_SEQ_KEY + 1..))?.next() {
+            Some(entry) => Ok(Some(entry?.0)),
+            None => Ok(None),
+        }
+    }
+
+    /// Up to `limit` changes made after the change `since`, oldest first
+    pub fn read(
+        &self,
+        txn: &RoTxn,
+        since: u64,
+        limit: usize,
+    ) -> Result<Vec<ChangeEvent>, GraphError> {
+        self.db
+            .range(txn, &(since.saturating_add(1)..))?
+            .take(limit)
+            .map(|entry| Ok(sonic_rs::from_slice(entry?.1)?))
+            .collect()
+    }
+
+    fn prune(&self, txn: &mut RwTxn, last_seq: u64, now_ms: u64) -> Result<(), GraphError> {
+        // sequence numbers have no gaps, so the changes past `max_events` are a prefix
+        let mut keep_from = match self.config.max_events {
+            Some(max) => (last_seq + 1).saturating_sub(max).max(1),
+            None => 1,
+        };
+
+        if let Some(secs) = self.config.retention_secs {
+            let cutoff = now_ms.saturating_sub(secs.saturating_mul(1000));
+            for entry in self.db.range(txn, &(keep_from..=last_seq))? {
+                let (seq, bytes) = entry?;
+                let event: ChangeEvent = sonic_rs::from_slice(bytes)?;
+                if event.timestamp_ms >= cutoff {
+                    break;
+                }
+                keep_from = seq + 1;
+            }
+        }
+
+        if keep_from > 1 {
+            self.db.delete_range(txn, &(LAST_SEQ_KEY + 1..keep_from))?;
+        }
+        Ok(())
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
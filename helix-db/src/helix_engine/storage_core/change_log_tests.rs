@@ -0,0 +1,229 @@
+use std::{thread, time::Duration};
+
+use bumpalo::Bump;
+use heed3::RoTxn;
+use sonic_rs::JsonValueTrait;
+use tempfile::TempDir;
+
+use super::{
+    HelixGraphStorage,
+    change_log::{ChangeEvent, ChangeOp, ItemKind},
+    storage_methods::StorageMethods,
+};
+use crate::{
+    helix_engine::{
+        traversal_core::{
+            config::{ChangeLogConfig, Config},
+            ops::{
+                g::G,
+                source::{add_e::AddEAdapter, add_n::AddNAdapter, n_from_id::NFromIdAdapter},
+                util::update::UpdateAdapter,
+                vectors::insert::InsertVAdapter,
+            },
+        },
+        vector_core::vector::HVector,
+    },
+    protocol::value::Value,
+};
+
+type Filter = fn(&HVector, &RoTxn) -> bool;
+
+fn setup_test_storage(change_log: Option<ChangeLogConfig>) -> (HelixGraphStorage, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        change_log,
+        ..Default::default()
+    };
+    let storage =
+        HelixGraphStorage::new(temp_dir.path().to_str().unwrap(), config, Default::default())
+            .unwrap();
+    (storage, temp_dir)
+}
+
+fn add_node(storage: &HelixGraphStorage, label: &'static str) -> u128 {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let node = G::new_mut(storage, &arena, &mut txn)
+        .add_n(label, None, None)
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+    node.id()
+}
+
+fn read_all(storage: &HelixGraphStorage) -> Vec<ChangeEvent> {
+    let txn = storage.graph_env.read_txn().unwrap();
+    storage
+        .change_log
+        .as_ref()
+        .unwrap()
+        .read(&txn, 0, usize::MAX)
+        .unwrap()
+}
+
+fn ops(changes: &[ChangeEvent]) -> Vec<(u64, ChangeOp, ItemKind)> {
+    changes.iter().map(|c| (c.seq, c.op, c.kind)).collect()
+}
+
+#[test]
+fn test_records_writes_in_order() {
+    let (storage, _temp_dir) = setup_test_storage(Some(ChangeLogConfig::default()));
+    let user = add_node(&storage, "user");
+    let org = add_node(&storage, "org");
+
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_edge("member_of", None, user, org, false)
+        .collect_to_obj()
+        .unwrap();
+    let nodes = G::new(&storage, &txn, &arena)
+        .n_from_id(&user)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    G::new_mut_from_iter(&storage, &mut txn, nodes.into_iter(), &arena)
+        .update(&[("name", Value::from("john"))])
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .insert_v::<Filter>(&[1.0, 1.0, 1.0], "embedding", None)
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+
+    let changes = read_all(&storage);
+    assert_eq!(
+        ops(&changes),
+        vec![
+            (1, ChangeOp::Create, ItemKind::Node),
+            (2, ChangeOp::Create, ItemKind::Node),
+            (3, ChangeOp::Create, ItemKind::Edge),
+            (4, ChangeOp::Update, ItemKind::Node),
+            (5, ChangeOp::Create, ItemKind::Vector),
+        ]
+    );
+    assert_eq!(changes[0].id, uuid::Uuid::from_u128(user).to_string());
+    assert_eq!(changes[2].label, "member_of");
+    assert_eq!(changes[3].item["properties"]["name"].as_str(), Some("john"));
+    assert_eq!(changes[2].item["to_node"].as_str(), Some(changes[1].id.as_str()));
+}
+
+#[test]
+fn test_records_deletes_with_cascaded_edges() {
+    let (storage, _temp_dir) = setup_test_storage(Some(ChangeLogConfig::default()));
+    let user = add_node(&storage, "user");
+    let org = add_node(&storage, "org");
+
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_edge("member_of", None, user, org, false)
+        .collect_to_obj()
+        .unwrap();
+    storage.drop_node(&mut txn, &user).unwrap();
+    txn.commit().unwrap();
+
+    let changes = read_all(&storage);
+    assert_eq!(
+        ops(&changes[3..]),
+        vec![
+            (4, ChangeOp::Delete, ItemKind::Edge),
+            (5, ChangeOp::Delete, ItemKind::Node),
+        ]
+    );
+    assert_eq!(changes[4].label, "user");
+    assert!(changes[4].item.is_null());
+}
+
+#[test]
+fn test_aborted_writes_are_not_recorded() {
+    let (storage, _temp_dir) = setup_test_storage(Some(ChangeLogConfig::default()));
+    add_node(&storage, "user");
+
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_n("user", None, None)
+        .collect_to_obj()
+        .unwrap();
+    txn.abort();
+
+    add_node(&storage, "org");
+    let changes = read_all(&storage);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[1].seq, 2);
+    assert_eq!(changes[1].label, "org");
+}
+
+#[test]
+fn test_read_since() {
+    let (storage, _temp_dir) = setup_test_storage(Some(ChangeLogConfig::default()));
+    for _ in 0..5 {
+        add_node(&storage, "user");
+    }
+
+    let change_log = storage.change_log.as_ref().unwrap();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let seqs = |since, limit| -> Vec<u64> {
+        change_log
+            .read(&txn, since, limit)
+            .unwrap()
+            .iter()
+            .map(|c| c.seq)
+            .collect()
+    };
+    assert_eq!(seqs(2, 10), vec![3, 4, 5]);
+    assert_eq!(seqs(0, 2), vec![1, 2]);
+    assert!(seqs(5, 10).is_empty());
+    assert_eq!(change_log.last_seq(&txn).unwrap(), 5);
+}
+
+#[test]
+fn test_prunes_past_max_events() {
+    let config = ChangeLogConfig {
+        max_events: Some(3),
+        retention_secs: None,
+    };
+    let (storage, _temp_dir) = setup_test_storage(Some(config));
+    for _ in 0..5 {
+        add_node(&storage, "user");
+    }
+
+    let changes = read_all(&storage);
+    assert_eq!(changes.iter().map(|c| c.seq).collect::<Vec<_>>(), vec![3, 4, 5]);
+    let txn = storage.graph_env.read_txn().unwrap();
+    let change_log = storage.change_log.as_ref().unwrap();
+    assert_eq!(change_log.first_seq(&txn).unwrap(), Some(3));
+    assert_eq!(change_log.last_seq(&txn).unwrap(), 5);
+}
+
+#[test]
+fn test_prunes_past_retention() {
+    let config = ChangeLogConfig {
+        max_events: None,
+        retention_secs: Some(0),
+    };
+    let (storage, _temp_dir) = setup_test_storage(Some(config));
+    add_node(&storage, "user");
+    thread::sleep(Duration::from_millis(5));
+    add_node(&storage, "org");
+
+    let changes = read_all(&storage);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].seq, 2);
+}
+
+#[test]
+fn test_disabled_change_log() {
+    let (storage, _temp_dir) = setup_test_storage(None);
+    add_node(&storage, "user");
+
+    assert!(storage.change_log.is_none());
+    let txn = storage.graph_env.read_txn().unwrap();
+    assert!(storage.graph_env.open_database::<heed3::types::Bytes, heed3::types::Bytes>(
+        &txn,
+        Some("changes")
+    )
+    .unwrap()
+    .is_none());
+}
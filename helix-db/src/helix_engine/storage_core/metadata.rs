@@ -15,10 +15,14 @@ pub enum StorageMetadata {
     /// Stores VectorEndianness so the vectors can be migrated to native-endian
     /// when the database is copied to a machine with a different endianness.
     VectorNativeEndianness { vector_endianness: VectorEndianness },
+    /// The first version that keys the secondary indices over `Date` fields by instant.
+    /// Vectors are still stored in native-endian.
+    DateIndexKeys { vector_endianness: VectorEndianness },
 }
 
 mod storage_version_tag {
     pub const VECTOR_NATIVE_ENDIANNESS: u64 = 1;
+    pub const DATE_INDEX_KEYS: u64 = 2;
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -75,6 +79,10 @@ impl StorageMetadata {
                 )?;
                 vector_endianness.save(txn, metadata_db)?;
             }
+            Self::DateIndexKeys { vector_endianness } => {
+                Self::save_version(storage_version_tag::DATE_INDEX_KEYS, txn, metadata_db)?;
+                vector_endianness.save(txn, metadata_db)?;
+            }
         }
 
         Ok(())
@@ -89,6 +97,9 @@ impl StorageMetadata {
             storage_version_tag::VECTOR_NATIVE_ENDIANNESS => {
                 Self::parse_vector_native_endianness(txn, metadata_db)
             }
+            storage_version_tag::DATE_INDEX_KEYS => Ok(Self::DateIndexKeys {
+                vector_endianness: VectorEndianness::read(txn, metadata_db)?,
+            }),
             _ => Err(GraphError::New(format!(
                 "storage metadata version tag unknown: {version}"
             ))),
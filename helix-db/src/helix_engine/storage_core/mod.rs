@@ -1,4 +1,6 @@
+pub mod change_log;
 pub mod metadata;
+pub mod stats;
 pub mod storage_methods;
 pub mod storage_migration;
 pub mod version_info;
@@ -7,11 +9,15 @@ pub mod version_info;
 mod storage_migration_tests;
 #[cfg(test)]
 mod storage_concurrent_tests;
+#[cfg(test)]
+mod change_log_tests;
 
 use crate::{
     helix_engine::{
         bm25::bm25::HBM25Config,
         storage_core::{
+            change_log::{ChangeLog, ChangeOp, ItemKind},
+            stats::StatsCache,
             storage_methods::{DBMethods, StorageMethods},
             version_info::VersionInfo,
         },
@@ -22,12 +28,15 @@ use crate::{
             vector_core::{HNSWConfig, VectorCore},
         },
     },
+    protocol::value::Value,
     utils::{
         items::{Edge, Node},
         label_hash::hash_label,
     },
 };
+use chrono::{DateTime, Utc};
 use heed3::{Database, DatabaseFlags, Env, EnvOpenOptions, RoTxn, RwTxn, byteorder::BE, types::*};
+use serde::Serialize;
 use std::{
     collections::{HashMap, HashSet},
     fs,
@@ -41,6 +50,10 @@ const DB_OUT_EDGES: &str = "out_edges"; // for outgoing edge indices (o:)
 const DB_IN_EDGES: &str = "in_edges"; // for incoming edge indices (i:)
 const DB_STORAGE_METADATA: &str = "storage_metadata"; // for storage metadata key/value pairs
 
+/// Starts every date key of a secondary index. No bincode encoded value starts with it, as
+/// it would be an enum variant far past the last one of `Value`.
+pub const DATE_INDEX_KEY_PREFIX: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFE];
+
 pub type NodeId = u128;
 pub type EdgeId = u128;
 
@@ -58,10 +71,15 @@ pub struct HelixGraphStorage {
     pub out_edges_db: Database<Bytes, Bytes>,
     pub in_edges_db: Database<Bytes, Bytes>,
     pub secondary_indices: HashMap<String, Database<Bytes, U128<BE>>>,
+    /// Secondary indices over `Date` fields, whose keys sort in time
+    pub date_indices: HashSet<String>,
     pub vectors: VectorCore,
     pub bm25: Option<HBM25Config>,
     pub metadata_db: Database<Bytes, Bytes>,
     pub version_info: VersionInfo,
+    /// Records committed writes, `None` unless configured
+    pub change_log: Option<ChangeLog>,
+    pub stats_cache: StatsCache,
 
     pub storage_config: StorageConfig,
 }
@@ -140,8 +158,10 @@ impl HelixGraphStorage {
             .name(DB_STORAGE_METADATA)
             .create(&mut wtxn)?;
 
+        let graph_config = config.get_graph_config();
+        let date_indices = graph_config.date_indices.unwrap_or_default().into_iter().collect();
         let mut secondary_indices = HashMap::new();
-        if let Some(indexes) = config.get_graph_config().secondary_indices {
+        if let Some(indexes) = graph_config.secondary_indices {
             for index in indexes {
                 secondary_indices.insert(
                     index.clone(),
@@ -170,6 +190,12 @@ impl HelixGraphStorage {
             .then(|| HBM25Config::new(&graph_env, &mut wtxn))
             .transpose()?;
 
+        let change_log = config
+            .change_log
+            .clone()
+            .map(|log_config| ChangeLog::new(&graph_env, &mut wtxn, log_config))
+            .transpose()?;
+
         let storage_config = StorageConfig::new(
             config.schema,
             config.graphvis_node_label,
@@ -185,11 +211,14 @@ impl HelixGraphStorage {
             out_edges_db,
             in_edges_db,
             secondary_indices,
+            date_indices,
             vectors,
             bm25,
             metadata_db,
             storage_config,
             version_info,
+            change_log,
+            stats_cache: StatsCache::default(),
         };
 
         storage_migration::migrate(&mut storage)?;
@@ -197,6 +226,50 @@ impl HelixGraphStorage {
         Ok(storage)
     }
 
+    /// Records a created or updated item in the change log, if there is one
+    pub fn record_write<T: Serialize>(
+        &self,
+        txn: &mut RwTxn,
+        op: ChangeOp,
+        kind: ItemKind,
+        id: u128,
+        label: &str,
+        item: &T,
+    ) -> Result<(), GraphError> {
+        if let Some(log) = &self.change_log {
+            log.record(txn, op, kind, id, label, sonic_rs::to_value(item)?)?;
+        }
+        Ok(())
+    }
+
+    /// Records a deleted item in the change log, if there is one
+    pub fn record_delete(
+        &self,
+        txn: &mut RwTxn,
+        kind: ItemKind,
+        id: u128,
+        label: &str,
+    ) -> Result<(), GraphError> {
+        if let Some(log) = &self.change_log {
+            log.record(txn, ChangeOp::Delete, kind, id, label, sonic_rs::Value::new())?;
+        }
+        Ok(())
+    }
+
+    /// Records the delete of an edge removed along with one of its endpoints
+    fn record_edge_delete(
+        &self,
+        txn: &mut RwTxn,
+        edge_id: &u128,
+        arena: &bumpalo::Bump,
+    ) -> Result<(), GraphError> {
+        if self.change_log.is_some() {
+            let edge = self.get_edge(txn, edge_id, arena)?;
+            self.record_delete(txn, ItemKind::Edge, edge.id, edge.label)?;
+        }
+        Ok(())
+    }
+
     /// Used because in the case the key changes in the future.
     /// Believed to not introduce any overhead being inline and using a reference.
     #[must_use]
@@ -254,6 +327,37 @@ impl HelixGraphStorage {
         key
     }
 
+    /// Secondary index key generator.
+    ///
+    /// In the indices over `Date` fields (see [`HelixGraphStorage::date_indices`]), dates are
+    /// keyed by the instant they name:
+    ///
+    /// key = `DATE_INDEX_KEY_PREFIX(4)` | `seconds(8)` | `nanos(4)`                 ← 16 B
+    ///
+    /// The seconds are big endian with the sign bit flipped, so the keys sort in time and a
+    /// range of dates is read with a single seek. Every other value is bincode encoded.
+    pub fn index_key(&self, index: &str, value: &Value) -> Result<Vec<u8>, GraphError> {
+        match self
+            .date_indices
+            .contains(index)
+            .then(|| value.as_datetime())
+            .flatten()
+        {
+            Some(date) => Ok(Self::date_index_key(&date).to_vec()),
+            None => Ok(bincode::serialize(value)?),
+        }
+    }
+
+    /// The index key of a date, see [`HelixGraphStorage::index_key`].
+    #[inline(always)]
+    pub fn date_index_key(date: &DateTime<Utc>) -> [u8; 16] {
+        let mut key = [0u8; 16];
+        key[0..4].copy_from_slice(&DATE_INDEX_KEY_PREFIX);
+        key[4..12].copy_from_slice(&((date.timestamp() as u64) ^ (1 << 63)).to_be_bytes());
+        key[12..16].copy_from_slice(&date.timestamp_subsec_nanos().to_be_bytes());
+        key
+    }
+
     /// Unpacks the 32 byte array into an (edge_id, node_id) tuple of u128s.
     ///
     /// Returns (edge_id, node_id)
@@ -272,6 +376,58 @@ impl HelixGraphStorage {
         );
         Ok((edge_id, node_id))
     }
+
+    /// Number of outgoing edges with the given label hash for a node.
+    ///
+    /// The adjacency databases are `DUP_SORT | DUP_FIXED`, so this only walks the fixed size
+    /// values stored under a single key and never reads the edge or node records.
+    #[inline]
+    pub fn out_degree(
+        &self,
+        txn: &RoTxn,
+        node_id: &u128,
+        label_hash: &[u8; 4],
+    ) -> Result<usize, GraphError> {
+        Self::adjacency_count(
+            &self.out_edges_db,
+            txn,
+            &Self::out_edge_key(node_id, label_hash),
+        )
+    }
+
+    /// Number of incoming edges with the given label hash for a node.
+    ///
+    /// See [`HelixGraphStorage::out_degree`].
+    #[inline]
+    pub fn in_degree(
+        &self,
+        txn: &RoTxn,
+        node_id: &u128,
+        label_hash: &[u8; 4],
+    ) -> Result<usize, GraphError> {
+        Self::adjacency_count(
+            &self.in_edges_db,
+            txn,
+            &Self::in_edge_key(node_id, label_hash),
+        )
+    }
+
+    /// Number of edges stored under an adjacency key.
+    ///
+    /// heed has no binding for `mdb_cursor_count`, so the edges are walked, but none of them
+    /// is decoded.
+    #[inline(always)]
+    fn adjacency_count(
+        db: &Database<Bytes, Bytes>,
+        txn: &RoTxn,
+        key: &[u8; 20],
+    ) -> Result<usize, GraphError> {
+        match db.lazily_decode_data().get_duplicates(txn, key)? {
+            Some(mut iter) => iter.try_fold(0, |count, item| item.map(|_| count + 1)),
+            None => Ok(0),
+        }
+        .map_err(GraphError::from)
+    }
 }
 
 impl StorageConfig {
@@ -389,6 +545,7 @@ impl StorageMethods for HelixGraphStorage {
         // println!("Deleting edges: {}", );
         // Delete all related data
         for edge in edges {
+            self.record_edge_delete(txn, &edge, &arena)?;
             self.edges_db.delete(txn, Self::edge_key(&edge))?;
         }
         for label_bytes in out_edges.iter() {
@@ -420,13 +577,13 @@ impl StorageMethods for HelixGraphStorage {
         for (index_name, db) in &self.secondary_indices {
             // Use get_property like we do when adding, to handle id, label, and regular properties consistently
             match node.get_property(index_name) {
-                Some(value) => match bincode::serialize(value) {
+                Some(value) => match self.index_key(index_name, value) {
                     Ok(serialized) => {
                         if let Err(e) = db.delete_one_duplicate(txn, &serialized, &node.id) {
                             return Err(GraphError::from(e));
                         }
                     }
-                    Err(e) => return Err(GraphError::from(e)),
+                    Err(e) => return Err(e),
                 },
                 None => {
                     // Property not found - this is expected for some indices
@@ -437,6 +594,7 @@ impl StorageMethods for HelixGraphStorage {
 
         // Delete node data and label
         self.nodes_db.delete(txn, Self::node_key(id))?;
+        self.record_delete(txn, ItemKind::Node, node.id, node.label)?;
 
         Ok(())
     }
@@ -464,6 +622,7 @@ impl StorageMethods for HelixGraphStorage {
             &Self::in_edge_key(&edge.to_node, &label_hash),
             &in_edge_value,
         )?;
+        self.record_delete(txn, ItemKind::Edge, edge.id, edge.label)?;
 
         Ok(())
     }
@@ -511,6 +670,7 @@ impl StorageMethods for HelixGraphStorage {
         // println!("Deleting edges: {}", );
         // Delete all related data
         for edge in edges {
+            self.record_edge_delete(txn, &edge, &arena)?;
             self.edges_db.delete(txn, Self::edge_key(&edge))?;
         }
         for label_bytes in out_edges.iter() {
@@ -538,7 +698,14 @@ impl StorageMethods for HelixGraphStorage {
         }
 
         // Delete vector data
+        let vector = match self.change_log {
+            Some(_) => self.vectors.get_vector_properties(txn, *id, &arena).ok().flatten(),
+            None => None,
+        };
         self.vectors.delete(txn, *id, &arena)?;
+        if let Some(vector) = vector {
+            self.record_delete(txn, ItemKind::Vector, vector.id, vector.label)?;
+        }
 
         Ok(())
     }
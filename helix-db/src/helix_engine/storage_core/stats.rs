@@ -0,0 +1,156 @@
+//! Counts of the items stored in the graph, by label.
+//!
+//! Every section is read in its own short-lived read transaction so collecting the stats of a
+//! large graph doesn't keep old pages alive for the whole scan. Totals come from the length of
+//! each database, but counting by label still reads every item, so the result is reused for
+//! [`STATS_TTL`].
+
+use std::{
+    collections::BTreeMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::helix_engine::{
+    storage_core::HelixGraphStorage,
+    traversal_core::LMDB_STRING_HEADER_LENGTH,
+    types::GraphError,
+    vector_core::vector_without_data::VectorWithoutData,
+};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct LabelCounts {
+    pub total: u64,
+    pub by_label: BTreeMap<String, u64>,
+}
+
+impl LabelCounts {
+    fn add(&mut self, label: &str) {
+        match self.by_label.get_mut(label) {
+            Some(count) => *count += 1,
+            None => {
+                self.by_label.insert(label.to_string(), 1);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bm25Stats {
+    pub total_docs: u64,
+    pub avgdl: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StorageStats {
+    pub nodes: LabelCounts,
+    pub edges: LabelCounts,
+    /// Vectors that haven't been deleted
+    pub vectors: LabelCounts,
+    /// Entries of the HNSW index across all of its levels, including deleted vectors
+    pub num_inserted_vectors: u64,
+    /// `None` when BM25 is disabled
+    pub bm25: Option<Bm25Stats>,
+    /// Size of the data file on disk
+    pub disk_size_bytes: u64,
+    /// Size the data file can grow to, set by `db_max_size_gb`
+    pub max_size_bytes: u64,
+}
+
+/// How long the stats are reused before the items are counted again
+pub const STATS_TTL: Duration = Duration::from_secs(10);
+
+/// The last stats collected and when, shared by the requests within [`STATS_TTL`]
+#[derive(Debug, Default)]
+pub struct StatsCache {
+    last: Mutex<Option<(Instant, StorageStats)>>,
+}
+
+/// The label nodes and edges are stored with, which precedes the rest of their fields
+fn stored_label(value: &[u8]) -> Result<&[u8], GraphError> {
+    let corrupted = || GraphError::DecodeError("stored item has no label".to_string());
+    let header = value.get(..LMDB_STRING_HEADER_LENGTH).ok_or_else(corrupted)?;
+    let len = u64::from_le_bytes(header.try_into().map_err(|_| corrupted())?) as usize;
+    value
+        .get(LMDB_STRING_HEADER_LENGTH..LMDB_STRING_HEADER_LENGTH + len)
+        .ok_or_else(corrupted)
+}
+
+impl HelixGraphStorage {
+    /// The stats collected within the last [`STATS_TTL`], or fresh ones.
+    ///
+    /// Requests arriving while the stats are collected wait for them rather than count again.
+    pub fn stats(&self) -> Result<StorageStats, GraphError> {
+        let mut last = self.stats_cache.last.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((collected_at, stats)) = last.as_ref()
+            && collected_at.elapsed() < STATS_TTL
+        {
+            return Ok(stats.clone());
+        }
+        let stats = self.collect_stats()?;
+        *last = Some((Instant::now(), stats.clone()));
+        Ok(stats)
+    }
+
+    fn collect_stats(&self) -> Result<StorageStats, GraphError> {
+        let mut nodes = LabelCounts::default();
+        {
+            let txn = self.graph_env.read_txn()?;
+            nodes.total = self.nodes_db.len(&txn)?;
+            for entry in self.nodes_db.iter(&txn)? {
+                let (_, value) = entry?;
+                nodes.add(&String::from_utf8_lossy(stored_label(value)?));
+            }
+        }
+
+        let mut edges = LabelCounts::default();
+        {
+            let txn = self.graph_env.read_txn()?;
+            edges.total = self.edges_db.len(&txn)?;
+            for entry in self.edges_db.iter(&txn)? {
+                let (_, value) = entry?;
+                edges.add(&String::from_utf8_lossy(stored_label(value)?));
+            }
+        }
+
+        let mut vectors = LabelCounts::default();
+        let num_inserted_vectors = {
+            let txn = self.graph_env.read_txn()?;
+            let mut arena = bumpalo::Bump::new();
+            for entry in self.vectors.vector_properties_db.iter(&txn)? {
+                let (id, value) = entry?;
+                arena.reset();
+                let vector = VectorWithoutData::from_bincode_bytes(&arena, value, id)?;
+                if !vector.deleted {
+                    vectors.add(vector.label);
+                }
+            }
+            // deleted vectors keep their properties, so they can't be counted by length
+            vectors.total = vectors.by_label.values().sum();
+            self.vectors.num_inserted_vectors(&txn)?
+        };
+
+        let bm25 = match &self.bm25 {
+            Some(bm25) => {
+                let txn = self.graph_env.read_txn()?;
+                let (total_docs, avgdl) = bm25
+                    .metadata(&txn)?
+                    .map_or((0, 0.0), |m| (m.total_docs, m.avgdl));
+                Some(Bm25Stats { total_docs, avgdl })
+            }
+            None => None,
+        };
+
+        Ok(StorageStats {
+            nodes,
+            edges,
+            vectors,
+            num_inserted_vectors,
+            bm25,
+            disk_size_bytes: self.graph_env.real_disk_size()?,
+            max_size_bytes: self.graph_env.info().map_size as u64,
+        })
+    }
+}
@@ -25,13 +25,31 @@ pub fn migrate(storage: &mut HelixGraphStorage) -> Result<(), GraphError> {
             }
             StorageMetadata::VectorNativeEndianness {
                 vector_endianness: NATIVE_VECTOR_ENDIANNESS,
+            } => rekey_date_index_entries(storage)?,
+            StorageMetadata::VectorNativeEndianness {
+                vector_endianness: currently_stored_vector_endianness,
+            } => convert_vectors_to_native_endianness(currently_stored_vector_endianness, storage)?,
+            StorageMetadata::DateIndexKeys {
+                vector_endianness: NATIVE_VECTOR_ENDIANNESS,
             } => {
                 // If the vectors are in the native vector endianness, we're done migrating them
                 break;
             }
-            StorageMetadata::VectorNativeEndianness {
+            StorageMetadata::DateIndexKeys {
                 vector_endianness: currently_stored_vector_endianness,
-            } => convert_vectors_to_native_endianness(currently_stored_vector_endianness, storage)?,
+            } => {
+                // the date index keys are already migrated, only the vectors need converting
+                convert_all_vectors(currently_stored_vector_endianness, storage)?;
+
+                let metadata = StorageMetadata::DateIndexKeys {
+                    vector_endianness: NATIVE_VECTOR_ENDIANNESS,
+                };
+                let mut txn = storage.graph_env.write_txn()?;
+                metadata.save(&mut txn, &storage.metadata_db)?;
+                txn.commit()?;
+
+                metadata
+            }
         };
     }
 
@@ -467,3 +485,48 @@ fn remove_orphaned_vector_edges(storage: &HelixGraphStorage) -> Result<(), Graph
 
     Ok(())
 }
+
+/// Moves the entries of `Date` indices written before dates had their own index keys
+/// (see [`HelixGraphStorage::index_key`]) to the key they're looked up by now.
+pub(crate) fn rekey_date_index_entries(
+    storage: &HelixGraphStorage,
+) -> Result<StorageMetadata, GraphError> {
+    for (name, db) in &storage.secondary_indices {
+        if !storage.date_indices.contains(name) {
+            continue;
+        }
+        let mut stale = Vec::new();
+        {
+            let txn = storage.graph_env.read_txn()?;
+            for kv in db.iter(&txn)? {
+                let (key, node_id) = kv?;
+                // date keys don't decode, so entries already moved are skipped
+                let Ok(value) = bincode::deserialize::<Value>(key) else {
+                    continue;
+                };
+                let new_key = storage.index_key(name, &value)?;
+                if new_key != key {
+                    stale.push((key.to_vec(), new_key, node_id));
+                }
+            }
+        }
+
+        for chunk in stale.into_iter().chunks(64).into_iter() {
+            let mut txn = storage.graph_env.write_txn()?;
+            for (old_key, new_key, node_id) in chunk {
+                db.delete_one_duplicate(&mut txn, &old_key, &node_id)?;
+                db.put(&mut txn, &new_key, &node_id)?;
+            }
+            txn.commit()?;
+        }
+    }
+
+    let metadata = StorageMetadata::DateIndexKeys {
+        vector_endianness: NATIVE_VECTOR_ENDIANNESS,
+    };
+    let mut txn = storage.graph_env.write_txn()?;
+    metadata.save(&mut txn, &storage.metadata_db)?;
+    txn.commit()?;
+
+    Ok(metadata)
+}
@@ -402,10 +402,7 @@ fn test_migrate_empty_database() {
     let txn = storage.graph_env.read_txn().unwrap();
     let metadata = StorageMetadata::read(&txn, &storage.metadata_db).unwrap();
 
-    assert!(matches!(
-        metadata,
-        StorageMetadata::VectorNativeEndianness { .. }
-    ));
+    assert!(matches!(metadata, StorageMetadata::DateIndexKeys { .. }));
 }
 
 #[test]
@@ -429,10 +426,10 @@ fn test_migrate_pre_metadata_to_native() {
         let metadata = StorageMetadata::read(&txn, &storage.metadata_db).unwrap();
 
         match metadata {
-            StorageMetadata::VectorNativeEndianness { vector_endianness } => {
+            StorageMetadata::DateIndexKeys { vector_endianness } => {
                 assert_eq!(vector_endianness, NATIVE_VECTOR_ENDIANNESS);
             }
-            _ => panic!("Expected VectorNativeEndianness metadata"),
+            _ => panic!("Expected DateIndexKeys metadata"),
         }
     } // txn dropped here
 
@@ -538,6 +535,82 @@ fn test_migrate_idempotency() {
     assert_eq!(vectors_after_second, vectors_after_third);
 }
 
+#[test]
+fn test_migrate_rekeys_date_index_entries() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config::default();
+    let graph_config = config.graph_config.as_mut().unwrap();
+    graph_config.secondary_indices = Some(vec!["created_at".to_string(), "title".to_string()]);
+    graph_config.date_indices = Some(vec!["created_at".to_string()]);
+    let mut storage = HelixGraphStorage::new(
+        temp_dir.path().to_str().unwrap(),
+        config,
+        VersionInfo::default(),
+    )
+    .unwrap();
+
+    // entries as they were keyed before dates had their own keys
+    set_metadata(
+        &mut storage,
+        StorageMetadata::VectorNativeEndianness {
+            vector_endianness: NATIVE_VECTOR_ENDIANNESS,
+        },
+    )
+    .unwrap();
+    let date = Value::from("2024-01-01T00:00:00+00:00");
+    let name = Value::from("john");
+    {
+        let db = &storage.secondary_indices["created_at"];
+        let mut txn = storage.graph_env.write_txn().unwrap();
+        db.put(&mut txn, &bincode::serialize(&date).unwrap(), &1).unwrap();
+        db.put(&mut txn, &bincode::serialize(&name).unwrap(), &2).unwrap();
+        let db = &storage.secondary_indices["title"];
+        db.put(&mut txn, &bincode::serialize(&date).unwrap(), &3).unwrap();
+        txn.commit().unwrap();
+    }
+
+    migrate(&mut storage).unwrap();
+    migrate(&mut storage).unwrap();
+
+    let db = &storage.secondary_indices["created_at"];
+    let txn = storage.graph_env.read_txn().unwrap();
+    let entries = db
+        .iter(&txn)
+        .unwrap()
+        .map(|kv| kv.map(|(key, id)| (key.to_vec(), id)))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        entries,
+        [
+            (bincode::serialize(&name).unwrap(), 2),
+            (storage.index_key("created_at", &date).unwrap(), 1),
+        ]
+    );
+    // only indices over Date fields are rekeyed
+    let db = &storage.secondary_indices["title"];
+    assert_eq!(
+        db.get(&txn, &bincode::serialize(&date).unwrap()).unwrap(),
+        Some(3)
+    );
+    drop(txn);
+
+    // once the version is stored the indices aren't scanned again
+    {
+        let db = &storage.secondary_indices["created_at"];
+        let mut txn = storage.graph_env.write_txn().unwrap();
+        db.put(&mut txn, &bincode::serialize(&date).unwrap(), &4).unwrap();
+        txn.commit().unwrap();
+    }
+    migrate(&mut storage).unwrap();
+    let db = &storage.secondary_indices["created_at"];
+    let txn = storage.graph_env.read_txn().unwrap();
+    assert_eq!(
+        db.get(&txn, &bincode::serialize(&date).unwrap()).unwrap(),
+        Some(4)
+    );
+}
+
 #[test]
 fn test_migrate_with_properties() {
     let (mut storage, _temp_dir) = setup_test_storage();
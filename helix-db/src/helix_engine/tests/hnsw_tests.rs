@@ -3,10 +3,14 @@ use heed3::{Env, EnvOpenOptions, RoTxn};
 use rand::Rng;
 use tempfile::TempDir;
 
-use crate::helix_engine::vector_core::{
-    hnsw::HNSW,
-    vector::HVector,
-    vector_core::{HNSWConfig, VectorCore},
+use crate::helix_engine::{
+    deadline::set_deadline,
+    types::VectorError,
+    vector_core::{
+        hnsw::HNSW,
+        vector::HVector,
+        vector_core::{HNSWConfig, VectorCore},
+    },
 };
 
 type Filter = fn(&HVector, &RoTxn) -> bool;
@@ -70,3 +74,28 @@ fn test_hnsw_search_returns_results() {
         .unwrap();
     assert!(!results.is_empty());
 }
+
+#[test]
+fn test_hnsw_search_cancelled_after_deadline() {
+    let (env, _temp_dir) = setup_env();
+    let mut txn = env.write_txn().unwrap();
+    let index = VectorCore::new(&env, &mut txn, HNSWConfig::new(None, None, None)).unwrap();
+
+    let mut rng = rand::rng();
+    for _ in 0..32 {
+        let arena = Bump::new();
+        let vector: Vec<f64> = (0..4).map(|_| rng.random_range(0.0..1.0)).collect();
+        let data = arena.alloc_slice_copy(&vector);
+        let _ = index
+            .insert::<Filter>(&mut txn, "vector", data, None, &arena)
+            .unwrap();
+    }
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = env.read_txn().unwrap();
+    let query = [0.5, 0.5, 0.5, 0.5];
+    let _deadline = set_deadline(Some(std::time::Instant::now()));
+    let result = index.search::<Filter>(&txn, &query, 5, "vector", None, false, &arena);
+    assert!(matches!(result, Err(VectorError::QueryTimeout)));
+}
@@ -0,0 +1,247 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{
+            ops::{
+                g::G,
+                source::{
+                    add_e::AddEAdapter, add_n::AddNAdapter, n_from_id::NFromIdAdapter,
+                    n_from_type::NFromTypeAdapter,
+                },
+                util::{centrality::CentralityAdapter, degree::DegreeAdapter},
+            },
+            traversal_iter::RoTraversalIterator,
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    props,
+    protocol::value::Value,
+};
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let storage = HelixGraphStorage::new(
+        db_path,
+        crate::helix_engine::traversal_core::config::Config::default(),
+        Default::default(),
+    )
+    .unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+/// Adds one `person` node per name and a `knows` edge for every (from, to) index pair.
+fn build_graph(storage: &HelixGraphStorage, names: &[&str], edges: &[(usize, usize)]) -> Vec<u128> {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let node_ids: Vec<_> = names
+        .iter()
+        .map(|name| {
+            G::new_mut(storage, &arena, &mut txn)
+                .add_n("person", props_option(&arena, props!("name" => *name)), None)
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect();
+    for (from, to) in edges {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_edge("knows", None, node_ids[*from], node_ids[*to], false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+    node_ids
+}
+
+#[test]
+fn test_degree_counts() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = build_graph(&storage, &["A", "B", "C"], &[(0, 1), (0, 2), (1, 0)]);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let out = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_degree_to_val("knows")
+        .unwrap();
+    let in_ = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .in_degree_to_val("knows")
+        .unwrap();
+    let both = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .degree_to_val("knows")
+        .unwrap();
+    let other_label = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_degree_to_val("likes")
+        .unwrap();
+
+    assert_eq!(out, Value::from(2usize));
+    assert_eq!(in_, Value::from(1usize));
+    assert_eq!(both, Value::from(3usize));
+    assert_eq!(other_label, Value::from(0usize));
+}
+
+#[test]
+fn test_degree_sums_over_traversal() {
+    let (_temp_dir, storage) = setup_test_db();
+    build_graph(&storage, &["A", "B", "C"], &[(0, 1), (0, 2), (1, 2)]);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let total = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .out_degree_to_val("knows")
+        .unwrap();
+
+    assert_eq!(total, Value::from(3usize));
+}
+
+#[test]
+fn test_triangle_count() {
+    let (_temp_dir, storage) = setup_test_db();
+    // A-B-C form a triangle (in mixed directions), D hangs off C
+    let ids = build_graph(
+        &storage,
+        &["A", "B", "C", "D"],
+        &[(0, 1), (2, 1), (0, 2), (2, 3)],
+    );
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let a = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .triangle_count_to_val("knows")
+        .unwrap();
+    let d = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[3])
+        .triangle_count_to_val("knows")
+        .unwrap();
+    assert_eq!(a, Value::from(1usize));
+    assert_eq!(d, Value::from(0usize));
+
+    let scored = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .triangles("knows")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(scored.len(), 4);
+    for item in scored {
+        let expected = if item.id() == ids[3] { 0.0 } else { 1.0 };
+        assert_eq!(item.score(), expected);
+    }
+}
+
+#[test]
+fn test_betweenness_chain() {
+    let (_temp_dir, storage) = setup_test_db();
+    // A -> B -> C -> D
+    let ids = build_graph(
+        &storage,
+        &["A", "B", "C", "D"],
+        &[(0, 1), (1, 2), (2, 3)],
+    );
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let scored = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .betweenness("knows", None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(scored.len(), 4);
+    // B and C each sit on two shortest paths, the ends on none
+    let scores = scored
+        .iter()
+        .map(|item| match item {
+            TraversalValue::NodeWithScore { node, score } => (node.id, *score),
+            _ => panic!("expected a scored node"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(scores[0].1, 2.0);
+    assert_eq!(scores[1].1, 2.0);
+    assert!(scores[..2].iter().any(|(id, _)| *id == ids[1]));
+    assert!(scores[..2].iter().any(|(id, _)| *id == ids[2]));
+    assert_eq!(scores[2].1, 0.0);
+    assert_eq!(scores[3].1, 0.0);
+    assert!(scored[0].get_property("name").is_some());
+}
+
+#[test]
+fn test_betweenness_sampled_is_scaled() {
+    let (_temp_dir, storage) = setup_test_db();
+    // star with every leaf pointing at the hub and the hub pointing back
+    let names = ["hub", "a", "b", "c", "d", "e"];
+    let mut edges = Vec::new();
+    for leaf in 1..names.len() {
+        edges.push((leaf, 0));
+        edges.push((0, leaf));
+    }
+    let ids = build_graph(&storage, &names, &edges);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let scored = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .betweenness("knows", Some(3))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(scored.len(), names.len());
+    assert_eq!(scored[0].id(), ids[0]);
+    assert!(scored[0].score() > 0.0);
+    assert!(scored[1..].iter().all(|item| item.score() == 0.0));
+}
+
+#[test]
+fn test_centrality_errors_are_returned() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let failing = || RoTraversalIterator {
+        storage: &storage,
+        arena: &arena,
+        txn: &txn,
+        inner: std::iter::once(Err(GraphError::New("storage failure".to_string()))),
+    };
+
+    assert!(failing().out_degree_to_val("knows").is_err());
+    assert!(failing().degree_to_val("knows").is_err());
+    assert!(failing().triangle_count_to_val("knows").is_err());
+}
+
+#[test]
+fn test_centrality_in_mutating_traversal() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = build_graph(&storage, &["A", "B", "C"], &[(0, 1), (1, 2), (2, 0)]);
+
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let nodes = ids
+        .iter()
+        .map(|id| storage.get_node(&txn, id, &arena).map(TraversalValue::Node))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let count = G::new_mut_from_iter(&storage, &mut txn, nodes.clone().into_iter(), &arena)
+        .triangle_count_to_val("knows")
+        .unwrap();
+    assert_eq!(count, Value::from(3usize));
+
+    let scored = G::new_mut_from_iter(&storage, &mut txn, nodes.into_iter(), &arena)
+        .betweenness("knows", None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(scored.len(), 3);
+    assert!(scored.iter().all(|item| item.score() == 1.0));
+}
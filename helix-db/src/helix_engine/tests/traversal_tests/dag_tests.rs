@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use crate::helix_engine::{
+    storage_core::HelixGraphStorage,
+    traversal_core::ops::{
+        g::G,
+        source::{
+            add_e::AddEAdapter, add_n::AddNAdapter, n_from_id::NFromIdAdapter,
+            n_from_type::NFromTypeAdapter,
+        },
+        util::dag::DagAdapter,
+    },
+    types::GraphError,
+};
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let storage = HelixGraphStorage::new(
+        db_path,
+        crate::helix_engine::traversal_core::config::Config::default(),
+        Default::default(),
+    )
+    .unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+/// Adds `count` `task` nodes and a `depends_on` edge for every (from, to) index pair.
+fn build_graph(storage: &HelixGraphStorage, count: usize, edges: &[(usize, usize)]) -> Vec<u128> {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let node_ids: Vec<_> = (0..count)
+        .map(|_| {
+            G::new_mut(storage, &arena, &mut txn)
+                .add_n("task", None, None)
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect();
+    for (from, to) in edges {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_edge("depends_on", None, node_ids[*from], node_ids[*to], false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+    node_ids
+}
+
+#[test]
+fn test_topological_sort_orders_dependencies() {
+    let (_temp_dir, storage) = setup_test_db();
+    // 0 -> 1 -> 3, 0 -> 2 -> 3, and 4 unrelated
+    let ids = build_graph(&storage, 5, &[(0, 1), (0, 2), (1, 3), (2, 3)]);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let sorted = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .topological_sort("depends_on")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .into_iter()
+        .map(|item| item.id())
+        .collect::<Vec<_>>();
+
+    // every reachable node once, but not the unrelated one
+    assert_eq!(sorted.len(), 4);
+    assert!(!sorted.contains(&ids[4]));
+    let position = |id: u128| sorted.iter().position(|other| *other == id).unwrap();
+    for (from, to) in [(0, 1), (0, 2), (1, 3), (2, 3)] {
+        assert!(position(ids[from]) < position(ids[to]));
+    }
+}
+
+#[test]
+fn test_topological_sort_rejects_cycles() {
+    let (_temp_dir, storage) = setup_test_db();
+    build_graph(&storage, 3, &[(0, 1), (1, 2), (2, 1)]);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let result = G::new(&storage, &txn, &arena)
+        .n_from_type("task")
+        .topological_sort("depends_on")
+        .collect::<Result<Vec<_>, _>>();
+
+    assert!(matches!(result, Err(GraphError::CycleDetected(_))));
+}
+
+#[test]
+fn test_has_cycle() {
+    let (_temp_dir, storage) = setup_test_db();
+    // 0 -> 1 -> 2 -> 1 is a cycle only reachable from 0, 1 and 2; 3 -> 4 is acyclic
+    let ids = build_graph(&storage, 5, &[(0, 1), (1, 2), (2, 1), (3, 4)]);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let from_start = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .has_cycle("depends_on")
+        .unwrap();
+    let from_acyclic = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[3])
+        .has_cycle("depends_on")
+        .unwrap();
+    let whole_graph = G::new(&storage, &txn, &arena)
+        .n_from_type("task")
+        .has_cycle("depends_on")
+        .unwrap();
+    let other_label = G::new(&storage, &txn, &arena)
+        .n_from_type("task")
+        .has_cycle("blocks")
+        .unwrap();
+
+    assert!(from_start);
+    assert!(!from_acyclic);
+    assert!(whole_graph);
+    assert!(!other_label);
+}
+
+#[test]
+fn test_add_acyclic_edge() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = build_graph(&storage, 3, &[(0, 1), (1, 2)]);
+
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    // 2 -> 0 closes 0 -> 1 -> 2
+    let closing = G::new_mut(&storage, &arena, &mut txn)
+        .add_acyclic_edge("depends_on", None, ids[2], ids[0])
+        .collect_to_obj();
+    assert!(matches!(closing, Err(GraphError::CycleDetected(_))));
+
+    let self_loop = G::new_mut(&storage, &arena, &mut txn)
+        .add_acyclic_edge("depends_on", None, ids[1], ids[1])
+        .collect_to_obj();
+    assert!(matches!(self_loop, Err(GraphError::CycleDetected(_))));
+
+    // a shortcut keeps the graph acyclic and other labels are not checked
+    let shortcut = G::new_mut(&storage, &arena, &mut txn)
+        .add_acyclic_edge("depends_on", None, ids[0], ids[2])
+        .collect_to_obj();
+    assert!(shortcut.is_ok());
+    let other_label = G::new_mut(&storage, &arena, &mut txn)
+        .add_acyclic_edge("blocks", None, ids[2], ids[0])
+        .collect_to_obj();
+    assert!(other_label.is_ok());
+    txn.commit().unwrap();
+
+    let txn = storage.graph_env.read_txn().unwrap();
+    let cyclic = G::new(&storage, &txn, &arena)
+        .n_from_type("task")
+        .has_cycle("depends_on")
+        .unwrap();
+    assert!(!cyclic);
+}
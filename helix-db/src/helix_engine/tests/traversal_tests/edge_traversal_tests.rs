@@ -13,13 +13,17 @@ use crate::{
                 in_::in_e::InEdgesAdapter,
                 out::{out::OutAdapter, out_e::OutEdgesAdapter},
                 source::{
-                    add_e::AddEAdapter, add_n::AddNAdapter, e_from_id::EFromIdAdapter,
-                    e_from_type::EFromTypeAdapter, n_from_id::NFromIdAdapter,
+                    add_e::{AddEAdapter, EdgeCardinality, EdgeConstraints},
+                    add_n::AddNAdapter,
+                    e_from_id::EFromIdAdapter,
+                    e_from_type::EFromTypeAdapter,
+                    n_from_id::NFromIdAdapter,
                 },
                 vectors::insert::InsertVAdapter,
             },
             traversal_value::TraversalValue,
         },
+        types::GraphError,
         vector_core::vector::HVector,
     },
     props,
@@ -242,3 +246,104 @@ fn test_vector_edges_roundtrip() {
         other => panic!("unexpected traversal value: {other:?}"),
     }
 }
+
+#[test]
+fn test_add_constrained_edge_enforces_cardinality() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let ids = (0..3)
+        .map(|_| {
+            G::new_mut(&storage, &arena, &mut txn)
+                .add_n("person", None, None)
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect::<Vec<_>>();
+    let many_to_one = EdgeConstraints {
+        cardinality: EdgeCardinality::ManyToOne,
+        ..Default::default()
+    };
+    let one_to_many = EdgeConstraints {
+        cardinality: EdgeCardinality::OneToMany,
+        ..Default::default()
+    };
+
+    // each source has at most one outgoing edge
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_constrained_edge("lives_in", None, ids[0], ids[2], many_to_one)
+        .collect_to_obj()
+        .unwrap();
+    let second_out = G::new_mut(&storage, &arena, &mut txn)
+        .add_constrained_edge("lives_in", None, ids[0], ids[1], many_to_one)
+        .collect_to_obj();
+    assert!(matches!(second_out, Err(GraphError::CardinalityViolation(_))));
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_constrained_edge("lives_in", None, ids[1], ids[2], many_to_one)
+        .collect_to_obj()
+        .unwrap();
+
+    // each target has at most one incoming edge
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_constrained_edge("parent_of", None, ids[0], ids[1], one_to_many)
+        .collect_to_obj()
+        .unwrap();
+    let second_in = G::new_mut(&storage, &arena, &mut txn)
+        .add_constrained_edge("parent_of", None, ids[2], ids[1], one_to_many)
+        .collect_to_obj();
+    assert!(matches!(second_in, Err(GraphError::CardinalityViolation(_))));
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_constrained_edge("parent_of", None, ids[0], ids[2], one_to_many)
+        .collect_to_obj()
+        .unwrap();
+}
+
+#[test]
+fn test_add_constrained_edge_replaces_existing() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let ids = (0..3)
+        .map(|_| {
+            G::new_mut(&storage, &arena, &mut txn)
+                .add_n("person", None, None)
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect::<Vec<_>>();
+    let one_to_one = EdgeConstraints {
+        cardinality: EdgeCardinality::OneToOne,
+        replace: true,
+        ..Default::default()
+    };
+
+    let first = G::new_mut(&storage, &arena, &mut txn)
+        .add_constrained_edge("married_to", None, ids[0], ids[1], one_to_one)
+        .collect_to_obj()
+        .unwrap();
+    let second = G::new_mut(&storage, &arena, &mut txn)
+        .add_constrained_edge("married_to", None, ids[2], ids[1], one_to_one)
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let edges = G::new(&storage, &txn, &arena)
+        .e_from_type("married_to")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edge_id(&edges[0]), second.id());
+    assert_ne!(first.id(), second.id());
+    let replaced = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .out_e("married_to")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(replaced.is_empty());
+}
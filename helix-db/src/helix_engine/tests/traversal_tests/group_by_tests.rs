@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::ops::{
+            g::G,
+            source::{add_n::AddNAdapter, n_from_type::NFromTypeAdapter},
+            util::{
+                aggregate::AggregateAdapter,
+                group_by::{GroupByAdapter, GroupKey},
+            },
+        },
+    },
+    props,
+    protocol::value::Value,
+    utils::{
+        aggregate::{Aggregate, Aggregation, AggregationKind},
+        group_by::GroupBy,
+    },
+};
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let storage = HelixGraphStorage::new(
+        db_path,
+        crate::helix_engine::traversal_core::config::Config::default(),
+        Default::default(),
+    )
+    .unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+fn add_orders(storage: &HelixGraphStorage) {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    for (country, amount, customer) in [
+        ("uk", 10, "ann"),
+        ("uk", 30, "bob"),
+        ("uk", 20, "ann"),
+        ("fr", 5, "cat"),
+    ] {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_n(
+                "order",
+                props_option(
+                    &arena,
+                    props!("country" => country, "amount" => amount, "customer" => customer),
+                ),
+                None,
+            )
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+}
+
+const AGGREGATIONS: [Aggregation; 5] = [
+    Aggregation {
+        name: "total",
+        kind: AggregationKind::Sum,
+        property: "amount",
+    },
+    Aggregation {
+        name: "average",
+        kind: AggregationKind::Avg,
+        property: "amount",
+    },
+    Aggregation {
+        name: "smallest",
+        kind: AggregationKind::Min,
+        property: "amount",
+    },
+    Aggregation {
+        name: "largest",
+        kind: AggregationKind::Max,
+        property: "amount",
+    },
+    Aggregation {
+        name: "customers",
+        kind: AggregationKind::CountDistinct,
+        property: "customer",
+    },
+];
+
+#[test]
+fn test_group_by_aggregations() {
+    let (_temp_dir, storage) = setup_test_db();
+    add_orders(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let groups = G::new(&storage, &txn, &arena)
+        .n_from_type("order")
+        .group_by(&["country".to_string()], false, &AGGREGATIONS)
+        .unwrap();
+    let GroupBy::Group(groups) = groups else {
+        panic!("expected groups");
+    };
+
+    let uk = &groups["uk"];
+    assert_eq!(uk.count, 3);
+    assert!(matches!(uk.aggregates["total"], Value::I64(60)));
+    assert_eq!(uk.aggregates["average"], Value::F64(20.0));
+    assert_eq!(uk.aggregates["smallest"], Value::I32(10));
+    assert_eq!(uk.aggregates["largest"], Value::I32(30));
+    assert!(matches!(uk.aggregates["customers"], Value::I64(2)));
+
+    let fr = &groups["fr"];
+    assert!(matches!(fr.aggregates["total"], Value::I64(5)));
+    assert!(matches!(fr.aggregates["customers"], Value::I64(1)));
+}
+
+#[test]
+fn test_group_by_computed_key() {
+    let (_temp_dir, storage) = setup_test_db();
+    add_orders(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let groups = G::new(&storage, &txn, &arena)
+        .n_from_type("order")
+        .group_by_keys(
+            &[GroupKey::computed("large", |val| {
+                Value::from(val.get_property("amount").is_some_and(|v| *v >= 20))
+            })],
+            false,
+            &AGGREGATIONS[..1],
+        )
+        .unwrap();
+    let GroupBy::Group(groups) = groups else {
+        panic!("expected groups");
+    };
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups["true"].count, 2);
+    assert!(matches!(groups["true"].aggregates["total"], Value::I64(50)));
+    assert_eq!(groups["false"].count, 2);
+    assert!(matches!(groups["false"].aggregates["total"], Value::I64(15)));
+}
+
+#[test]
+fn test_aggregate_by_collect() {
+    let (_temp_dir, storage) = setup_test_db();
+    add_orders(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let aggregate = G::new(&storage, &txn, &arena)
+        .n_from_type("order")
+        .aggregate_by(
+            &["customer".to_string()],
+            false,
+            &[Aggregation {
+                name: "amounts",
+                kind: AggregationKind::Collect,
+                property: "amount",
+            }],
+        )
+        .unwrap();
+    let Aggregate::Group(groups) = aggregate else {
+        panic!("expected groups");
+    };
+
+    let ann = &groups["ann"];
+    assert_eq!(ann.values.len(), 2);
+    let Value::Array(amounts) = &ann.aggregates["amounts"] else {
+        panic!("expected an array");
+    };
+    let mut amounts = amounts.clone();
+    amounts.sort();
+    assert_eq!(amounts, vec![Value::I32(10), Value::I32(20)]);
+}
+
+#[test]
+fn test_group_by_without_aggregations_serializes_as_before() {
+    let (_temp_dir, storage) = setup_test_db();
+    add_orders(&storage);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let groups = G::new(&storage, &txn, &arena)
+        .n_from_type("order")
+        .group_by(&["country".to_string()], true, &[])
+        .unwrap();
+    let json = sonic_rs::to_string(&groups).unwrap();
+    assert!(!json.contains("aggregates"));
+}
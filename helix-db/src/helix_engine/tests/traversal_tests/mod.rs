@@ -1,12 +1,18 @@
+pub mod centrality_tests;
 pub mod count_tests;
+pub mod dag_tests;
 pub mod drop_tests;
 pub mod edge_traversal_tests;
 pub mod filter_tests;
+pub mod group_by_tests;
 pub mod node_traversal_tests;
+pub mod page_tests;
 pub mod range_tests;
 pub mod secondary_index_tests;
 pub mod shortest_path_tests;
+pub mod subgraph_tests;
 pub mod test_utils;
 pub mod update_tests;
+pub mod upsert_tests;
 pub mod util_tests;
 pub mod vector_traversal_tests;
@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::{
+            ops::{
+                g::G,
+                in_::in_e::InEdgesAdapter,
+                out::out_e::OutEdgesAdapter,
+                source::{
+                    add_e::AddEAdapter, add_n::AddNAdapter, e_from_type::EFromTypeAdapter,
+                    n_from_id::NFromIdAdapter, n_from_index::NFromIndexAdapter,
+                    n_from_type::NFromTypeAdapter,
+                },
+                util::{
+                    filter_ref::FilterRefAdapter,
+                    page::{Cursor, CursorSource},
+                    range::RangeAdapter,
+                },
+            },
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    props,
+    protocol::value::Value,
+};
+
+const SIZE: usize = 3;
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let mut config = crate::helix_engine::traversal_core::config::Config::default();
+    config.graph_config.as_mut().unwrap().secondary_indices = Some(vec!["team".to_string()]);
+    let storage = HelixGraphStorage::new(db_path, config, Default::default()).unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+/// Adds `count` `person` nodes in team "a", each followed by a `follows` edge from the first
+/// node, and returns the node IDs
+fn build_graph(storage: &HelixGraphStorage, count: usize) -> Vec<u128> {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let ids: Vec<_> = (0..count)
+        .map(|i| {
+            G::new_mut(storage, &arena, &mut txn)
+                .add_n(
+                    "person",
+                    props_option(&arena, props! { "team" => "a", "age" => i as i64 }),
+                    Some(&["team"]),
+                )
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect();
+    for to in &ids[1..] {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_edge("follows", None, ids[0], *to, false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+    ids
+}
+
+fn ids(page: &[TraversalValue]) -> Vec<u128> {
+    page.iter().map(|item| item.id()).collect()
+}
+
+/// Reads pages until there is no cursor left, passing each cursor to `page`
+fn read_pages(mut page: impl FnMut(&str) -> (Vec<u128>, Option<String>)) -> Vec<Vec<u128>> {
+    let mut pages = vec![];
+    let mut cursor = String::new();
+    loop {
+        let (ids, next) = page(&cursor);
+        pages.push(ids);
+        match next {
+            Some(next) => cursor = next,
+            None => return pages,
+        }
+    }
+}
+
+#[test]
+fn test_page_nodes_by_type() {
+    let (_temp_dir, storage) = setup_test_db();
+    let mut node_ids = build_graph(&storage, 8);
+    node_ids.sort();
+
+    let pages = read_pages(|cursor| {
+        let arena = Bump::new();
+        let txn = storage.graph_env.read_txn().unwrap();
+        let after = Cursor::resume(cursor, CursorSource::Nodes, "person").unwrap();
+        let page = G::new(&storage, &txn, &arena)
+            .n_from_type_after("person", after)
+            .range(0, SIZE)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        (ids(&page), Cursor::next_page(&page, SIZE, CursorSource::Nodes, "person").unwrap())
+    });
+
+    assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 3, 2]);
+    assert_eq!(pages.concat(), node_ids);
+}
+
+#[test]
+fn test_page_filtered_nodes() {
+    let (_temp_dir, storage) = setup_test_db();
+    build_graph(&storage, 10);
+
+    let pages = read_pages(|cursor| {
+        let arena = Bump::new();
+        let txn = storage.graph_env.read_txn().unwrap();
+        let after = Cursor::resume(cursor, CursorSource::Nodes, "person").unwrap();
+        let page = G::new(&storage, &txn, &arena)
+            .n_from_type_after("person", after)
+            .filter_ref(|val, _| match val {
+                Ok(TraversalValue::Node(node)) => {
+                    Ok(matches!(node.get_property("age"), Some(Value::I64(age)) if *age >= 4))
+                }
+                _ => Ok(false),
+            })
+            .range(0, SIZE)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        (ids(&page), Cursor::next_page(&page, SIZE, CursorSource::Nodes, "person").unwrap())
+    });
+
+    // a full last page is followed by an empty one
+    assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 3, 0]);
+}
+
+#[test]
+fn test_page_nodes_from_index() {
+    let (_temp_dir, storage) = setup_test_db();
+    let mut node_ids = build_graph(&storage, 7);
+    node_ids.sort();
+
+    let pages = read_pages(|cursor| {
+        let arena = Bump::new();
+        let txn = storage.graph_env.read_txn().unwrap();
+        let after = Cursor::resume(cursor, CursorSource::Index, "person").unwrap();
+        let page = G::new(&storage, &txn, &arena)
+            .n_from_index_after("person", "team", &"a".to_string(), after)
+            .range(0, SIZE)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        (ids(&page), Cursor::next_page(&page, SIZE, CursorSource::Index, "person").unwrap())
+    });
+
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages.concat(), node_ids);
+}
+
+#[test]
+fn test_page_edges() {
+    let (_temp_dir, storage) = setup_test_db();
+    let node_ids = build_graph(&storage, 8);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let mut edge_ids = ids(&G::new(&storage, &txn, &arena)
+        .e_from_type("follows")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap());
+    edge_ids.sort();
+    drop(txn);
+
+    let by_type = read_pages(|cursor| {
+        let arena = Bump::new();
+        let txn = storage.graph_env.read_txn().unwrap();
+        let after = Cursor::resume(cursor, CursorSource::Edges, "follows").unwrap();
+        let page = G::new(&storage, &txn, &arena)
+            .e_from_type_after("follows", after)
+            .range(0, SIZE)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        (ids(&page), Cursor::next_page(&page, SIZE, CursorSource::Edges, "follows").unwrap())
+    });
+    assert_eq!(by_type.concat(), edge_ids);
+
+    let out_edges = read_pages(|cursor| {
+        let arena = Bump::new();
+        let txn = storage.graph_env.read_txn().unwrap();
+        let after = Cursor::resume(cursor, CursorSource::OutEdges, "follows").unwrap();
+        let page = G::new(&storage, &txn, &arena)
+            .n_from_id(&node_ids[0])
+            .out_e_after("follows", after)
+            .range(0, SIZE)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        (ids(&page), Cursor::next_page(&page, SIZE, CursorSource::OutEdges, "follows").unwrap())
+    });
+    assert_eq!(out_edges.iter().map(Vec::len).collect::<Vec<_>>(), vec![3, 3, 1]);
+    assert_eq!(out_edges.concat(), edge_ids);
+
+    // the last node only has the edge from the first one
+    let in_edges = read_pages(|cursor| {
+        let arena = Bump::new();
+        let txn = storage.graph_env.read_txn().unwrap();
+        let after = Cursor::resume(cursor, CursorSource::InEdges, "follows").unwrap();
+        let page = G::new(&storage, &txn, &arena)
+            .n_from_id(&node_ids[7])
+            .in_e_after("follows", after)
+            .range(0, SIZE)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        (ids(&page), Cursor::next_page(&page, SIZE, CursorSource::InEdges, "follows").unwrap())
+    });
+    assert_eq!(in_edges.len(), 1);
+    assert_eq!(in_edges[0].len(), 1);
+}
+
+#[test]
+fn test_cursor_from_another_traversal_is_rejected() {
+    let (_temp_dir, storage) = setup_test_db();
+    build_graph(&storage, 4);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let page = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .range(0, SIZE)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let cursor = Cursor::next_page(&page, SIZE, CursorSource::Nodes, "person")
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        Cursor::resume(&cursor, CursorSource::Nodes, "person").unwrap(),
+        Some(page[2].id())
+    );
+    assert!(matches!(
+        Cursor::resume(&cursor, CursorSource::Nodes, "company"),
+        Err(GraphError::InvalidCursor(_))
+    ));
+    assert!(matches!(
+        Cursor::resume(&cursor, CursorSource::Edges, "person"),
+        Err(GraphError::InvalidCursor(_))
+    ));
+    assert!(matches!(
+        Cursor::resume("not a cursor", CursorSource::Nodes, "person"),
+        Err(GraphError::InvalidCursor(_))
+    ));
+    assert!(matches!(
+        Cursor::resume(&cursor[..cursor.len() - 2], CursorSource::Nodes, "person"),
+        Err(GraphError::InvalidCursor(_))
+    ));
+    assert!(matches!(
+        Cursor::next_page(&page, -1, CursorSource::Nodes, "person"),
+        Err(GraphError::DecodeError(_))
+    ));
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{ops::Bound, sync::Arc};
 
 use bumpalo::Bump;
 use tempfile::TempDir;
@@ -12,6 +12,7 @@ use crate::{
                 g::G,
                 source::{
                     add_n::AddNAdapter, n_from_id::NFromIdAdapter, n_from_index::NFromIndexAdapter,
+                    n_from_index_range::NFromIndexRangeAdapter,
                 },
                 util::{drop::Drop, update::UpdateAdapter},
             },
@@ -27,7 +28,9 @@ fn setup_indexed_db() -> (TempDir, Arc<HelixGraphStorage>) {
     let temp_dir = TempDir::new().unwrap();
     let db_path = temp_dir.path().to_str().unwrap();
     let mut config = crate::helix_engine::traversal_core::config::Config::default();
-    config.graph_config.as_mut().unwrap().secondary_indices = Some(vec!["name".to_string()]);
+    let graph_config = config.graph_config.as_mut().unwrap();
+    graph_config.secondary_indices = Some(vec!["name".to_string(), "created_at".to_string()]);
+    graph_config.date_indices = Some(vec!["created_at".to_string()]);
     let storage = HelixGraphStorage::new(db_path, config, Default::default()).unwrap();
     (temp_dir, Arc::new(storage))
 }
@@ -133,3 +136,108 @@ fn test_update_of_secondary_indices() {
         .collect::<Result<Vec<_>,_>>().unwrap();
     assert!(john_nodes.is_empty());
 }
+
+#[test]
+fn test_n_from_index_range() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    for (name, created_at) in [
+        ("a", "2024-01-01T00:00:00+00:00"),
+        ("b", "2024-02-01T00:00:00+00:00"),
+        ("c", "2024-03-01T00:00:00+00:00"),
+        ("d", "2024-02-01T01:00:00+02:00"),
+        ("z", "1969-07-20T20:17:00+00:00"),
+    ] {
+        G::new_mut(&storage, &arena, &mut txn)
+            .add_n(
+                "event",
+                props_option(&arena, props! { "name" => name, "created_at" => created_at }),
+                Some(&["created_at"]),
+            )
+            .collect_to_obj().unwrap();
+    }
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_n(
+            "person",
+            props_option(&arena, props! { "created_at" => "2024-02-15T00:00:00+00:00" }),
+            Some(&["created_at"]),
+        )
+        .collect_to_obj().unwrap();
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    // the nodes come back in time order
+    let names = |lower, upper| {
+        G::new(&storage, &txn, &arena)
+            .n_from_index_range("event", "created_at", lower, upper)
+            .collect::<Result<Vec<_>,_>>().unwrap()
+            .iter()
+            .map(|node| node.get_property("name").unwrap().inner_stringify())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        names(
+            Bound::Excluded(Value::from("2024-01-01T00:00:00+00:00")),
+            Bound::Unbounded
+        ),
+        ["d", "b", "c"]
+    );
+    assert_eq!(
+        names(
+            Bound::Included(Value::from("2024-01-01T00:00:00+00:00")),
+            Bound::Excluded(Value::from("2024-03-01T00:00:00+00:00"))
+        ),
+        ["a", "d", "b"]
+    );
+    assert_eq!(
+        names(Bound::Unbounded, Bound::Excluded(Value::from("2023-12-31T00:00:00+00:00"))),
+        ["z"]
+    );
+
+    let result = G::new(&storage, &txn, &arena)
+        .n_from_index_range(
+            "event",
+            "created_at",
+            Bound::Included(Value::from(3)),
+            Bound::Unbounded,
+        )
+        .collect::<Result<Vec<_>, _>>();
+    assert!(result.is_err());
+    let result = G::new(&storage, &txn, &arena)
+        .n_from_index_range("event", "missing", Bound::Unbounded, Bound::Unbounded)
+        .collect::<Result<Vec<_>, _>>();
+    assert!(result.is_err());
+    let result = G::new(&storage, &txn, &arena)
+        .n_from_index_range("event", "name", Bound::Unbounded, Bound::Unbounded)
+        .collect::<Result<Vec<_>, _>>();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_string_index_keeps_dates_as_written() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    // the same instant written two ways is two different strings in a String index
+    for name in ["2024-02-01T00:00:00+00:00", "2024-02-01T01:00:00+01:00"] {
+        G::new_mut(&storage, &arena, &mut txn)
+            .add_n("person", props_option(&arena, props! { "name" => name }), Some(&["name"]))
+            .collect_to_obj().unwrap();
+    }
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    for name in ["2024-02-01T00:00:00+00:00", "2024-02-01T01:00:00+01:00"] {
+        let nodes = G::new(&storage, &txn, &arena)
+            .n_from_index("person", "name", &name.to_string())
+            .collect::<Result<Vec<_>,_>>().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].get_property("name").unwrap().inner_stringify(), name);
+    }
+}
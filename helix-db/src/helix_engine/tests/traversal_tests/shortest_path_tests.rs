@@ -6,6 +6,7 @@ use tempfile::TempDir;
 use super::test_utils::props_option;
 use crate::{
     helix_engine::{
+        deadline::set_deadline,
         storage_core::HelixGraphStorage,
         traversal_core::{
             ops::{
@@ -15,6 +16,7 @@ use crate::{
             },
             traversal_value::TraversalValue,
         },
+        types::GraphError,
     },
     props,
 };
@@ -905,3 +907,44 @@ fn test_astar_custom_weight_and_heuristic() {
         panic!("expected path");
     }
 }
+
+#[test]
+fn test_shortest_path_cancelled_after_deadline() {
+    let (_temp_dir, storage) = setup_test_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let node_ids: Vec<_> = (0..3)
+        .map(|_| {
+            G::new_mut(&storage, &arena, &mut txn)
+                .add_n("person", None, None)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()[0]
+                .id()
+        })
+        .collect();
+    for pair in node_ids.windows(2) {
+        G::new_mut(&storage, &arena, &mut txn)
+            .add_edge("knows", None, pair[0], pair[1], false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let _deadline = set_deadline(Some(std::time::Instant::now()));
+    for algorithm in [PathAlgorithm::BFS, PathAlgorithm::Dijkstra] {
+        let result = G::new(&storage, &txn, &arena)
+            .n_from_id(&node_ids[0])
+            .shortest_path_with_algorithm(
+                Some("knows"),
+                None,
+                Some(&node_ids[2]),
+                algorithm,
+                default_weight_fn,
+            )
+            .collect::<Result<Vec<_>, _>>();
+        assert!(matches!(result, Err(GraphError::QueryTimeout)));
+    }
+}
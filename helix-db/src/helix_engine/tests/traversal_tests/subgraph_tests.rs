@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use sonic_rs::JsonValueTrait;
+use tempfile::TempDir;
+
+use super::test_utils::props_option;
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::ops::{
+            g::G,
+            source::{add_e::AddEAdapter, add_n::AddNAdapter, n_from_id::NFromIdAdapter},
+            util::subgraph::SubgraphAdapter,
+        },
+    },
+    props,
+};
+
+fn setup_test_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let storage = HelixGraphStorage::new(
+        db_path,
+        crate::helix_engine::traversal_core::config::Config::default(),
+        Default::default(),
+    )
+    .unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+/// Adds one `person` node per name and an edge for every (from, to, label) triple.
+fn build_graph(
+    storage: &HelixGraphStorage,
+    names: &[&str],
+    edges: &[(usize, usize, &str)],
+) -> Vec<u128> {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let node_ids: Vec<_> = names
+        .iter()
+        .map(|name| {
+            G::new_mut(storage, &arena, &mut txn)
+                .add_n(
+                    "person",
+                    props_option(&arena, props!("name" => *name)),
+                    None,
+                )
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect();
+    for (from, to, label) in edges {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_edge(label, None, node_ids[*from], node_ids[*to], false)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+    node_ids
+}
+
+#[test]
+fn test_ego_graph_hops() {
+    let (_temp_dir, storage) = setup_test_db();
+    // A -> B <- C -> D, with A <-> C closing the loop
+    let ids = build_graph(
+        &storage,
+        &["A", "B", "C", "D"],
+        &[
+            (0, 1, "knows"),
+            (2, 1, "knows"),
+            (2, 3, "knows"),
+            (0, 2, "knows"),
+            (2, 0, "knows"),
+        ],
+    );
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let one_hop = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[1])
+        .ego_graph(&["knows"], 1, None)
+        .unwrap();
+    let mut node_ids = one_hop.nodes.iter().map(|n| n.id).collect::<Vec<_>>();
+    node_ids.sort_unstable();
+    let mut expected = vec![ids[0], ids[1], ids[2]];
+    expected.sort_unstable();
+    assert_eq!(node_ids, expected);
+    // every edge between A, B and C, but not the one to D
+    assert_eq!(one_hop.edges.len(), 4);
+
+    let two_hops = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[1])
+        .ego_graph(&["knows"], 2, None)
+        .unwrap();
+    assert_eq!(two_hops.nodes.len(), 4);
+    assert_eq!(two_hops.edges.len(), 5);
+
+    let seeds_only = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[1])
+        .ego_graph(&["knows"], 0, None)
+        .unwrap();
+    assert_eq!(seeds_only.nodes.len(), 1);
+    assert!(seeds_only.edges.is_empty());
+}
+
+#[test]
+fn test_ego_graph_labels_and_max_nodes() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = build_graph(
+        &storage,
+        &["A", "B", "C", "D"],
+        &[(0, 1, "knows"), (0, 2, "likes"), (3, 0, "knows")],
+    );
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let knows = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .ego_graph(&["knows"], 1, None)
+        .unwrap();
+    assert_eq!(knows.nodes.len(), 3);
+    assert!(knows.nodes.iter().all(|n| n.id != ids[2]));
+    assert!(knows.edges.iter().all(|e| e.label == "knows"));
+
+    let all_labels = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .ego_graph(&[], 1, None)
+        .unwrap();
+    assert_eq!(all_labels.nodes.len(), 4);
+    assert_eq!(all_labels.edges.len(), 3);
+
+    let capped = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .ego_graph(&[], 1, Some(2))
+        .unwrap();
+    assert_eq!(capped.nodes.len(), 2);
+    assert_eq!(capped.nodes[0].id, ids[0]);
+    assert_eq!(capped.edges.len(), 1);
+}
+
+#[test]
+fn test_ego_graph_serialization() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = build_graph(&storage, &["A", "B"], &[(0, 1, "knows")]);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let subgraph = G::new(&storage, &txn, &arena)
+        .n_from_id(&ids[0])
+        .ego_graph(&["knows"], 1, None)
+        .unwrap();
+
+    let json: sonic_rs::Value =
+        sonic_rs::from_str(&sonic_rs::to_string(&subgraph).unwrap()).unwrap();
+    let from = uuid::Uuid::from_u128(ids[0]).to_string();
+    let to = uuid::Uuid::from_u128(ids[1]).to_string();
+    assert_eq!(json["nodes"][0]["id"].as_str(), Some(from.as_str()));
+    assert_eq!(json["nodes"][0]["title"].as_str(), Some(from.as_str()));
+    assert_eq!(json["nodes"][0]["label"].as_str(), Some("person"));
+    assert_eq!(json["nodes"][0]["properties"]["name"].as_str(), Some("A"));
+    assert_eq!(json["edges"][0]["from"].as_str(), Some(from.as_str()));
+    assert_eq!(json["edges"][0]["to"].as_str(), Some(to.as_str()));
+    assert_eq!(json["edges"][0]["label"].as_str(), Some("knows"));
+}
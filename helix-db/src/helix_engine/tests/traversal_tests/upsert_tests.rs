@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use tempfile::TempDir;
+
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::ops::{
+            g::G,
+            out::out_e::OutEdgesAdapter,
+            source::{
+                add_e::AddEAdapter, add_n::AddNAdapter, n_from_index::NFromIndexAdapter,
+                n_from_type::NFromTypeAdapter, upsert_e::UpsertEAdapter, upsert_n::UpsertNAdapter,
+            },
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+};
+
+fn setup_indexed_db() -> (TempDir, Arc<HelixGraphStorage>) {
+    let temp_dir = TempDir::new().unwrap();
+    let db_path = temp_dir.path().to_str().unwrap();
+    let mut config = crate::helix_engine::traversal_core::config::Config::default();
+    config.graph_config.as_mut().unwrap().secondary_indices = Some(vec!["email".to_string()]);
+    let storage = HelixGraphStorage::new(db_path, config, Default::default()).unwrap();
+    (temp_dir, Arc::new(storage))
+}
+
+#[test]
+fn test_upsert_n_creates_then_updates() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let created = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_n(
+            "user",
+            ("email", Value::from("a@b.c")),
+            &[("name", Value::from("Ann"))],
+            &[("created_at", Value::from(1))],
+            Some(&["email"]),
+        )
+        .collect_to_obj()
+        .unwrap();
+    let updated = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_n(
+            "user",
+            ("email", Value::from("a@b.c")),
+            &[("name", Value::from("Anna"))],
+            &[("created_at", Value::from(2))],
+            Some(&["email"]),
+        )
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+
+    assert_eq!(created.id(), updated.id());
+    assert_eq!(updated.get_property("name"), Some(&Value::from("Anna")));
+    assert_eq!(updated.get_property("email"), Some(&Value::from("a@b.c")));
+    assert_eq!(updated.get_property("created_at"), Some(&Value::from(1)));
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let users = G::new(&storage, &txn, &arena)
+        .n_from_type("user")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(users.len(), 1);
+    let by_email = G::new(&storage, &txn, &arena)
+        .n_from_index("user", "email", &"a@b.c".to_string())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(by_email.len(), 1);
+    assert_eq!(by_email[0].get_property("name"), Some(&Value::from("Anna")));
+}
+
+#[test]
+fn test_upsert_n_only_matches_same_label() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let admin = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_n(
+            "admin",
+            ("email", Value::from("a@b.c")),
+            &[],
+            &[],
+            Some(&["email"]),
+        )
+        .collect_to_obj()
+        .unwrap();
+    let user = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_n(
+            "user",
+            ("email", Value::from("a@b.c")),
+            &[],
+            &[],
+            Some(&["email"]),
+        )
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+
+    assert_ne!(admin.id(), user.id());
+    assert_eq!(admin.label(), "admin");
+    assert_eq!(user.label(), "user");
+}
+
+#[test]
+fn test_upsert_n_missing_index() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let result = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_n("user", ("name", Value::from("Ann")), &[], &[], None)
+        .collect_to_obj();
+    assert!(matches!(result, Err(GraphError::New(_))));
+}
+
+#[test]
+fn test_upsert_e_does_not_duplicate_edges() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let a = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("user", None, None)
+        .collect_to_obj()
+        .unwrap();
+    let b = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("user", None, None)
+        .collect_to_obj()
+        .unwrap();
+
+    let first = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_e("follows", &[], a.id(), b.id())
+        .collect_to_obj()
+        .unwrap();
+    let second = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_e("follows", &[("since", Value::from(2024))], a.id(), b.id())
+        .collect_to_obj()
+        .unwrap();
+    let reverse = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_e("follows", &[], b.id(), a.id())
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+
+    assert_eq!(first.id(), second.id());
+    assert_ne!(first.id(), reverse.id());
+    assert_eq!(second.get_property("since"), Some(&Value::from(2024)));
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let edges = G::from_iter(&storage, &txn, std::iter::once(a), &arena)
+        .out_e("follows")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(edges.len(), 1);
+    assert_eq!(edges[0].get_property("since"), Some(&Value::from(2024)));
+}
+
+#[test]
+fn test_upsert_acyclic_e_rejects_cycle() {
+    let (_temp_dir, storage) = setup_indexed_db();
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+
+    let a = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("task", None, None)
+        .collect_to_obj()
+        .unwrap();
+    let b = G::new_mut(&storage, &arena, &mut txn)
+        .add_n("task", None, None)
+        .collect_to_obj()
+        .unwrap();
+    G::new_mut(&storage, &arena, &mut txn)
+        .add_edge("depends_on", None, a.id(), b.id(), false)
+        .collect_to_obj()
+        .unwrap();
+
+    let existing = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_acyclic_e("depends_on", &[], a.id(), b.id())
+        .collect::<Result<Vec<_>, _>>();
+    assert!(existing.is_ok());
+    let cycle = G::new_mut(&storage, &arena, &mut txn)
+        .upsert_acyclic_e("depends_on", &[], b.id(), a.id())
+        .collect::<Result<Vec<_>, _>>();
+    assert!(matches!(cycle, Err(GraphError::CycleDetected(_))));
+}
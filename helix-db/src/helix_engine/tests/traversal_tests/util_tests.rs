@@ -13,13 +13,18 @@ use crate::{
                     add_n::AddNAdapter,
                     n_from_type::NFromTypeAdapter,
                 },
-                util::{dedup::DedupAdapter, order::OrderByAdapter},
+                util::{
+                    dedup::DedupAdapter,
+                    order::{OrderByAdapter, OrderKey},
+                    range::RangeAdapter,
+                },
                 vectors::{insert::InsertVAdapter, search::SearchVAdapter},
             },
         },
         vector_core::vector::HVector,
     },
     props,
+    protocol::value::Value,
 };
 
 use heed3::RoTxn;
@@ -328,3 +333,134 @@ fn test_dedup() {
     assert_eq!(traversal.len(), 1);
     assert_eq!(traversal[0].id(), node2.id());
 }
+
+/// Adds `person` nodes with the given `(score, name)` properties, leaving out `None`s.
+fn add_people(
+    storage: &HelixGraphStorage,
+    people: &[(Option<Value>, &str)],
+) -> Vec<u128> {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let ids = people
+        .iter()
+        .map(|(score, name)| {
+            let props = match score {
+                Some(score) => props! { "score" => score.clone(), "name" => *name },
+                None => props! { "name" => *name },
+            };
+            G::new_mut(storage, &arena, &mut txn)
+                .add_n("person", props_option(&arena, props), None)
+                .collect_to_obj()
+                .unwrap()
+                .id()
+        })
+        .collect();
+    txn.commit().unwrap();
+    ids
+}
+
+#[test]
+fn test_order_by_multiple_keys() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = add_people(
+        &storage,
+        &[
+            (Some(Value::I32(1)), "b"),
+            (Some(Value::I32(2)), "c"),
+            (Some(Value::I32(2)), "a"),
+            (None, "d"),
+        ],
+    );
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let ordered = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by(&[OrderKey::desc("score"), OrderKey::asc("name")])
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(ordered, vec![ids[2], ids[1], ids[0], ids[3]]);
+
+    let ordered = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by(&[OrderKey::desc("score").nulls_first()])
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(ordered[0], ids[3]);
+    assert_eq!(ordered[3], ids[0]);
+}
+
+#[test]
+fn test_order_by_mixed_numeric_types() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = add_people(
+        &storage,
+        &[
+            (Some(Value::F64(2.5)), "a"),
+            (Some(Value::I32(3)), "b"),
+            (Some(Value::I32(2)), "c"),
+        ],
+    );
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let ordered = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by_asc("score")
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(ordered, vec![ids[2], ids[0], ids[1]]);
+}
+
+#[test]
+fn test_order_by_computed_key() {
+    let (_temp_dir, storage) = setup_test_db();
+    let ids = add_people(
+        &storage,
+        &[
+            (Some(Value::I32(7)), "a"),
+            (Some(Value::I32(12)), "b"),
+            (Some(Value::I32(9)), "c"),
+        ],
+    );
+
+    // ordered on the score's last digit
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let ordered = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by(&[OrderKey::asc("last_digit").by(|val| match val.get_property("score") {
+            Some(Value::I32(score)) => Value::I32(score % 10),
+            _ => Value::Empty,
+        })])
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(ordered, vec![ids[1], ids[0], ids[2]]);
+}
+
+#[test]
+fn test_order_by_top_k_matches_full_sort() {
+    let (_temp_dir, storage) = setup_test_db();
+    let people = (0..20)
+        .map(|i| (Some(Value::I32((i * 7) % 5)), "p"))
+        .collect::<Vec<_>>();
+    add_people(&storage, &people);
+
+    let arena = Bump::new();
+    let txn = storage.graph_env.read_txn().unwrap();
+    let keys = [OrderKey::desc("score")];
+    let sorted = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by(&keys)
+        .range(2, 6)
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    let top_k = G::new(&storage, &txn, &arena)
+        .n_from_type("person")
+        .order_by_top_k(&keys, 6)
+        .range(2, 6)
+        .map(|item| item.unwrap().id())
+        .collect::<Vec<_>>();
+    assert_eq!(sorted.len(), 4);
+    assert_eq!(top_k, sorted);
+}
@@ -1,6 +1,6 @@
 use crate::{
     helix_engine::types::GraphError,
-    helixc::analyzer::{INTROSPECTION_DATA, SECONDARY_INDICES},
+    helixc::analyzer::{DATE_INDICES, INTROSPECTION_DATA, SECONDARY_INDICES},
 };
 use serde::{Deserialize, Serialize};
 use std::{fmt, path::PathBuf};
@@ -25,6 +25,18 @@ impl Default for VectorConfig {
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GraphConfig {
     pub secondary_indices: Option<Vec<String>>,
+    /// Secondary indices over fields the schema declares as `Date`
+    #[serde(default)]
+    pub date_indices: Option<Vec<String>>,
+}
+
+/// Retention of the change log, which records every committed write when configured
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeLogConfig {
+    /// Most changes kept, older ones are pruned as new ones are recorded
+    pub max_events: Option<u64>,
+    /// How long changes are kept for, in seconds
+    pub retention_secs: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -37,6 +49,8 @@ pub struct Config {
     pub schema: Option<String>,
     pub embedding_model: Option<String>,
     pub graphvis_node_label: Option<String>,
+    #[serde(default)]
+    pub change_log: Option<ChangeLogConfig>,
 }
 
 impl Config {
@@ -59,6 +73,7 @@ impl Config {
             }),
             graph_config: Some(GraphConfig {
                 secondary_indices: None,
+                date_indices: None,
             }),
             db_max_size_gb: Some(db_max_size_gb),
             mcp: Some(mcp),
@@ -66,6 +81,7 @@ impl Config {
             schema,
             embedding_model,
             graphvis_node_label,
+            change_log: None,
         }
     }
 
@@ -164,6 +180,7 @@ impl Default for Config {
             }),
             graph_config: Some(GraphConfig {
                 secondary_indices: None,
+                date_indices: None,
             }),
             db_max_size_gb: Some(10),
             mcp: Some(true),
@@ -171,6 +188,7 @@ impl Default for Config {
             schema: None,
             embedding_model: Some("text-embedding-ada-002".to_string()),
             graphvis_node_label: None,
+            change_log: None,
         }
     }
 }
@@ -226,6 +244,23 @@ impl fmt::Display for Config {
                 None => "None".to_string(),
             }
         )?;
+        writeln!(
+            f,
+            "date_indices: {},",
+            match DATE_INDICES.get() {
+                Some(indices) => {
+                    format!(
+                        "Some(vec![{}])",
+                        indices
+                            .iter()
+                            .map(|i| format!("\"{i}\".to_string()"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                }
+                None => "None".to_string(),
+            }
+        )?;
         writeln!(f, "}}),")?;
         writeln!(
             f,
@@ -257,6 +292,17 @@ impl fmt::Display for Config {
                 None => "None".to_string(),
             }
         )?;
+        writeln!(
+            f,
+            "change_log: {},",
+            match &self.change_log {
+                Some(log) => format!(
+                    "Some(ChangeLogConfig {{ max_events: {:?}, retention_secs: {:?} }})",
+                    log.max_events, log.retention_secs
+                ),
+                None => "None".to_string(),
+            }
+        )?;
         writeln!(f, "}})")?;
         writeln!(f, "}}")?;
         Ok(())
@@ -25,6 +25,24 @@ pub trait InEdgesAdapter<'db, 'arena, 'txn, 's, I>:
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Returns the incoming edges with the given label whose ID comes after `after`, or all of
+    /// them if it is `None`.
+    ///
+    /// The edges of a node are sorted by ID, but are the values of a single LMDB key that can
+    /// only be walked, so resuming walks the IDs of the edges before `after`, without reading
+    /// the edges. `after` applies to the edges of every node, so it is only meant for the edges
+    /// of a single node.
+    fn in_e_after(
+        self,
+        edge_label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
 }
 
 impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
@@ -39,6 +57,20 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
         'arena,
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        self.in_e_after(edge_label, None)
+    }
+
+    #[inline]
+    fn in_e_after(
+        self,
+        edge_label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     > {
         let iter = self
             .inner
@@ -59,6 +91,14 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
                     .get_duplicates(self.txn, &prefix)
                 {
                     Ok(Some(iter)) => {
+                        let iter = iter.skip_while(move |item| match (item, after) {
+                            (Ok((_, data)), Some(after)) => data
+                                .decode()
+                                .ok()
+                                .and_then(|data| HelixGraphStorage::unpack_adj_edge_data(data).ok())
+                                .is_some_and(|(edge_id, _)| edge_id <= after),
+                            _ => false,
+                        });
                         let iter = iter.map(|item| match item {
                             Ok((_, data)) => match data.decode() {
                                 Ok(data) => {
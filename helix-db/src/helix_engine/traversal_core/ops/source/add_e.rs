@@ -1,12 +1,74 @@
 use crate::{
     helix_engine::{
-        storage_core::HelixGraphStorage,
-        traversal_core::{traversal_iter::RwTraversalIterator, traversal_value::TraversalValue},
+        storage_core::{
+            HelixGraphStorage,
+            change_log::{ChangeOp, EdgeItem, ItemKind},
+            storage_methods::StorageMethods,
+        },
+        traversal_core::{
+            ops::util::dag::would_create_cycle, traversal_iter::RwTraversalIterator,
+            traversal_value::TraversalValue,
+        },
         types::GraphError,
     },
-    utils::{id::v6_uuid, items::Edge, label_hash::hash_label, properties::ImmutablePropertiesMap},
+    utils::{
+        id::{ID, v6_uuid},
+        items::Edge,
+        label_hash::hash_label,
+        properties::ImmutablePropertiesMap,
+    },
 };
-use heed3::{PutFlags, RwTxn};
+use heed3::{PutFlags, RoTxn, RwTxn};
+use std::fmt;
+
+/// How many edges of one label may meet at each end, declared on the edge schema as e.g.
+/// `E::LivesIn MANY_TO_ONE { ... }`.
+///
+/// A `ONE` side may take part in at most one edge of the label: with `MANY_TO_ONE` every
+/// source node has at most one outgoing edge, with `ONE_TO_MANY` every target node has at most
+/// one incoming edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeCardinality {
+    OneToOne,
+    OneToMany,
+    ManyToOne,
+    #[default]
+    ManyToMany,
+}
+
+impl EdgeCardinality {
+    /// Whether a source node may have at most one outgoing edge of the label
+    pub fn single_out(&self) -> bool {
+        matches!(self, EdgeCardinality::OneToOne | EdgeCardinality::ManyToOne)
+    }
+
+    /// Whether a target node may have at most one incoming edge of the label
+    pub fn single_in(&self) -> bool {
+        matches!(self, EdgeCardinality::OneToOne | EdgeCardinality::OneToMany)
+    }
+}
+
+impl fmt::Display for EdgeCardinality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EdgeCardinality::OneToOne => write!(f, "ONE_TO_ONE"),
+            EdgeCardinality::OneToMany => write!(f, "ONE_TO_MANY"),
+            EdgeCardinality::ManyToOne => write!(f, "MANY_TO_ONE"),
+            EdgeCardinality::ManyToMany => write!(f, "MANY_TO_MANY"),
+        }
+    }
+}
+
+/// The schema constraints of an edge type that are checked before an edge is added
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EdgeConstraints {
+    /// Reject edges that would create a cycle of the label (`ACYCLIC`)
+    pub acyclic: bool,
+    pub cardinality: EdgeCardinality,
+    /// Drop the edges that would exceed the cardinality instead of rejecting the new one
+    /// (`REPLACE`)
+    pub replace: bool,
+}
 
 pub struct AddE<'db, 'arena, 'txn>
 where
@@ -43,6 +105,41 @@ pub trait AddEAdapter<'db, 'arena, 'txn, 's>:
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Adds an edge like [`add_edge`](Self::add_edge), unless the edge would create a cycle of
+    /// `label` edges, in which case a [`GraphError::CycleDetected`] is returned instead.
+    fn add_acyclic_edge(
+        self,
+        label: &'arena str,
+        properties: Option<ImmutablePropertiesMap<'arena>>,
+        from_node: u128,
+        to_node: u128,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+
+    /// Adds an edge like [`add_edge`](Self::add_edge) after checking the edge type's
+    /// `constraints`.
+    ///
+    /// An edge that would exceed the cardinality returns a
+    /// [`GraphError::CardinalityViolation`], unless `replace` is set, in which case the
+    /// existing edges in the way are dropped first.
+    fn add_constrained_edge(
+        self,
+        label: &'arena str,
+        properties: Option<ImmutablePropertiesMap<'arena>>,
+        from_node: u128,
+        to_node: u128,
+        constraints: EdgeConstraints,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
 }
 
 impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
@@ -63,74 +160,243 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     > {
-        let version = self.storage.version_info.get_latest(label);
-        let edge = Edge {
-            id: v6_uuid(),
+        let result = insert_edge(
+            self.storage,
+            self.txn,
             label,
-            version,
             properties,
             from_node,
             to_node,
-        };
+        );
 
-        let mut result: Result<TraversalValue, GraphError> = Ok(TraversalValue::Empty);
-
-        match edge.to_bincode_bytes() {
-            Ok(bytes) => {
-                if let Err(e) = self.storage.edges_db.put_with_flags(
-                    self.txn,
-                    PutFlags::APPEND,
-                    HelixGraphStorage::edge_key(&edge.id),
-                    &bytes,
-                ) {
-                    result = Err(GraphError::from(e));
-                }
-            }
-            Err(e) => result = Err(GraphError::from(e)),
+        RwTraversalIterator {
+            arena: self.arena,
+            storage: self.storage,
+            txn: self.txn,
+            inner: std::iter::once(result), // TODO: change to support adding multiple edges
         }
+    }
 
-        let label_hash = hash_label(edge.label, None);
+    #[inline(always)]
+    fn add_acyclic_edge(
+        self,
+        label: &'arena str,
+        properties: Option<ImmutablePropertiesMap<'arena>>,
+        from_node: u128,
+        to_node: u128,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        self.add_constrained_edge(
+            label,
+            properties,
+            from_node,
+            to_node,
+            EdgeConstraints {
+                acyclic: true,
+                ..Default::default()
+            },
+        )
+    }
 
-        match self.storage.out_edges_db.put_with_flags(
+    #[inline(always)]
+    fn add_constrained_edge(
+        self,
+        label: &'arena str,
+        properties: Option<ImmutablePropertiesMap<'arena>>,
+        from_node: u128,
+        to_node: u128,
+        constraints: EdgeConstraints,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let result = check_constraints(
+            self.storage,
             self.txn,
-            PutFlags::APPEND_DUP,
-            &HelixGraphStorage::out_edge_key(&from_node, &label_hash),
-            &HelixGraphStorage::pack_edge_data(&edge.id, &to_node),
-        ) {
-            Ok(_) => {}
-            Err(e) => {
-                println!(
-                    "add_e => error adding out edge between {from_node:?} and {to_node:?}: {e:?}"
-                );
-                result = Err(GraphError::from(e));
+            label,
+            from_node,
+            to_node,
+            &constraints,
+        )
+        .and_then(|_| {
+            insert_edge(
+                self.storage,
+                self.txn,
+                label,
+                properties,
+                from_node,
+                to_node,
+            )
+        });
+
+        RwTraversalIterator {
+            arena: self.arena,
+            storage: self.storage,
+            txn: self.txn,
+            inner: std::iter::once(result),
+        }
+    }
+}
+
+/// Checks that a `label` edge from `from_node` to `to_node` satisfies `constraints`, dropping
+/// the edges that would exceed the cardinality when `replace` is set.
+fn check_constraints(
+    storage: &HelixGraphStorage,
+    txn: &mut RwTxn,
+    label: &str,
+    from_node: u128,
+    to_node: u128,
+    constraints: &EdgeConstraints,
+) -> Result<(), GraphError> {
+    let label_hash = hash_label(label, None);
+    if constraints.acyclic && would_create_cycle(storage, txn, from_node, to_node, &label_hash)? {
+        return Err(GraphError::CycleDetected(format!(
+            "adding a {label} edge from {} to {} would create a cycle",
+            ID::from(from_node).stringify(),
+            ID::from(to_node).stringify()
+        )));
+    }
+
+    let conflicts = cardinality_conflicts(
+        storage,
+        txn,
+        &label_hash,
+        from_node,
+        to_node,
+        constraints.cardinality,
+    )?;
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    if !constraints.replace {
+        return Err(GraphError::CardinalityViolation(format!(
+            "adding a {label} edge from {} to {} would break its {} cardinality",
+            ID::from(from_node).stringify(),
+            ID::from(to_node).stringify(),
+            constraints.cardinality
+        )));
+    }
+    for edge_id in conflicts {
+        storage.drop_edge(txn, &edge_id)?;
+    }
+    Ok(())
+}
+
+/// The existing `label` edges that a new edge from `from_node` to `to_node` would put past
+/// `cardinality`.
+fn cardinality_conflicts(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    label_hash: &[u8; 4],
+    from_node: u128,
+    to_node: u128,
+    cardinality: EdgeCardinality,
+) -> Result<Vec<u128>, GraphError> {
+    let mut keys = Vec::with_capacity(2);
+    if cardinality.single_out() {
+        keys.push((&storage.out_edges_db, HelixGraphStorage::out_edge_key(&from_node, label_hash)));
+    }
+    if cardinality.single_in() {
+        keys.push((&storage.in_edges_db, HelixGraphStorage::in_edge_key(&to_node, label_hash)));
+    }
+
+    let mut conflicts = Vec::new();
+    for (db, key) in keys {
+        let Some(edges) = db.get_duplicates(txn, &key)? else {
+            continue;
+        };
+        for item in edges {
+            let (_, value) = item?;
+            let (edge_id, _) = HelixGraphStorage::unpack_adj_edge_data(value)?;
+            if !conflicts.contains(&edge_id) {
+                conflicts.push(edge_id);
             }
         }
+    }
+    Ok(conflicts)
+}
 
-        match self.storage.in_edges_db.put_with_flags(
-            self.txn,
-            PutFlags::APPEND_DUP,
-            &HelixGraphStorage::in_edge_key(&to_node, &label_hash),
-            &HelixGraphStorage::pack_edge_data(&edge.id, &from_node),
-        ) {
-            Ok(_) => {}
-            Err(e) => {
-                println!(
-                    "add_e => error adding in edge between {from_node:?} and {to_node:?}: {e:?}"
-                );
+fn insert_edge<'db, 'arena>(
+    storage: &'db HelixGraphStorage,
+    txn: &mut RwTxn<'db>,
+    label: &'arena str,
+    properties: Option<ImmutablePropertiesMap<'arena>>,
+    from_node: u128,
+    to_node: u128,
+) -> Result<TraversalValue<'arena>, GraphError> {
+    let version = storage.version_info.get_latest(label);
+    let edge = Edge {
+        id: v6_uuid(),
+        label,
+        version,
+        properties,
+        from_node,
+        to_node,
+    };
+
+    let mut result: Result<TraversalValue, GraphError> = Ok(TraversalValue::Empty);
+
+    match edge.to_bincode_bytes() {
+        Ok(bytes) => {
+            if let Err(e) = storage.edges_db.put_with_flags(
+                txn,
+                PutFlags::APPEND,
+                HelixGraphStorage::edge_key(&edge.id),
+                &bytes,
+            ) {
                 result = Err(GraphError::from(e));
             }
         }
+        Err(e) => result = Err(GraphError::from(e)),
+    }
 
-        let result = match result {
-            Ok(_) => Ok(TraversalValue::Edge(edge)),
-            Err(e) => Err(e),
-        };
+    let label_hash = hash_label(edge.label, None);
 
-        RwTraversalIterator {
-            arena: self.arena,
-            storage: self.storage,
-            txn: self.txn,
-            inner: std::iter::once(result), // TODO: change to support adding multiple edges
+    match storage.out_edges_db.put_with_flags(
+        txn,
+        PutFlags::APPEND_DUP,
+        &HelixGraphStorage::out_edge_key(&from_node, &label_hash),
+        &HelixGraphStorage::pack_edge_data(&edge.id, &to_node),
+    ) {
+        Ok(_) => {}
+        Err(e) => {
+            println!("add_e => error adding out edge between {from_node:?} and {to_node:?}: {e:?}");
+            result = Err(GraphError::from(e));
         }
     }
+
+    match storage.in_edges_db.put_with_flags(
+        txn,
+        PutFlags::APPEND_DUP,
+        &HelixGraphStorage::in_edge_key(&to_node, &label_hash),
+        &HelixGraphStorage::pack_edge_data(&edge.id, &from_node),
+    ) {
+        Ok(_) => {}
+        Err(e) => {
+            println!("add_e => error adding in edge between {from_node:?} and {to_node:?}: {e:?}");
+            result = Err(GraphError::from(e));
+        }
+    }
+
+    if result.is_ok() {
+        storage.record_write(
+            txn,
+            ChangeOp::Create,
+            ItemKind::Edge,
+            edge.id,
+            edge.label,
+            &EdgeItem::from(&edge),
+        )?;
+    }
+
+    match result {
+        Ok(_) => Ok(TraversalValue::Edge(edge)),
+        Err(e) => Err(e),
+    }
 }
@@ -1,7 +1,10 @@
 use crate::{
     helix_engine::{
         bm25::bm25::{BM25, BM25Flatten},
-        storage_core::HelixGraphStorage,
+        storage_core::{
+            HelixGraphStorage,
+            change_log::{ChangeOp, ItemKind},
+        },
         traversal_core::{traversal_iter::RwTraversalIterator, traversal_value::TraversalValue},
         types::GraphError,
     },
@@ -89,7 +92,7 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
                         None => continue,
                     };
                     // look into if there is a way to serialize to a slice
-                    match bincode::serialize(&key) {
+                    match self.storage.index_key(index, key) {
                         Ok(serialized) => {
                             // possibly append dup
 
@@ -102,7 +105,7 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
                                 result = Err(GraphError::from(e));
                             }
                         }
-                        Err(e) => result = Err(GraphError::from(e)),
+                        Err(e) => result = Err(e),
                     }
                 }
                 None => {
@@ -123,6 +126,19 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
             }
         }
 
+        if result.is_ok()
+            && let Err(e) = self.storage.record_write(
+                self.txn,
+                ChangeOp::Create,
+                ItemKind::Node,
+                node.id,
+                node.label,
+                &node,
+            )
+        {
+            result = Err(e);
+        }
+
         if result.is_ok() {
             result = Ok(TraversalValue::Node(node));
         } else {
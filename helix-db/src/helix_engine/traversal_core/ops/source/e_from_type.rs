@@ -1,3 +1,5 @@
+use std::ops::Bound;
+
 use crate::{
     helix_engine::{
         traversal_core::{
@@ -18,7 +20,7 @@ where
     'arena: 'txn,
 {
     pub arena: &'arena bumpalo::Bump,
-    pub iter: heed3::RoIter<'txn, U128<BE>, heed3::types::LazyDecode<Bytes>>,
+    pub iter: heed3::RoRange<'txn, U128<BE>, heed3::types::LazyDecode<Bytes>>,
     pub label: &'s [u8],
 }
 
@@ -80,6 +82,19 @@ pub trait EFromTypeAdapter<'db, 'arena, 'txn, 's>:
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Returns the edges with the given label whose ID comes after `after`, or all of them if
+    /// it is `None`. Edges are stored in ID order, like nodes in `n_from_type_after`.
+    fn e_from_type_after(
+        self,
+        label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
 }
 impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
     EFromTypeAdapter<'db, 'arena, 'txn, 's> for RoTraversalIterator<'db, 'arena, 'txn, I>
@@ -94,11 +109,26 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     > {
+        self.e_from_type_after(label, None)
+    }
+
+    #[inline]
+    fn e_from_type_after(
+        self,
+        label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
         let iter = self
             .storage
             .edges_db
             .lazily_decode_data()
-            .iter(self.txn)
+            .range(self.txn, &(start, Bound::Unbounded))
             .unwrap();
         RoTraversalIterator {
             storage: self.storage,
@@ -5,6 +5,9 @@ pub mod e_from_id;
 pub mod e_from_type;
 pub mod n_from_id;
 pub mod n_from_index;
+pub mod n_from_index_range;
 pub mod n_from_type;
+pub mod upsert_e;
+pub mod upsert_n;
 pub mod v_from_id;
 pub mod v_from_type;
\ No newline at end of file
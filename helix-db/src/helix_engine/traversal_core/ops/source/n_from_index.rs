@@ -32,6 +32,26 @@ pub trait NFromIndexAdapter<'db, 'arena, 'txn, 's, K: Into<Value> + Serialize>:
     >
     where
         K: Into<Value> + Serialize + Clone;
+
+    /// Returns the nodes from the secondary index whose ID comes after `after`, or all of them
+    /// if it is `None`.
+    ///
+    /// The IDs stored under a key are sorted, but are the values of a single LMDB key that can
+    /// only be walked, so resuming walks the IDs before `after`, without reading their nodes.
+    fn n_from_index_after(
+        self,
+        label: &'s str,
+        index: &'s str,
+        key: &'s K,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: Into<Value> + Serialize + Clone;
 }
 
 impl<
@@ -48,7 +68,26 @@ impl<
         self,
         label: &'s str,
         index: &'s str,
-        key: &K,
+        key: &'s K,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: Into<Value> + Serialize + Clone,
+    {
+        self.n_from_index_after(label, index, key, None)
+    }
+
+    #[inline]
+    fn n_from_index_after(
+        self,
+        label: &'s str,
+        index: &'s str,
+        key: &'s K,
+        after: Option<u128>,
     ) -> RoTraversalIterator<
         'db,
         'arena,
@@ -68,8 +107,11 @@ impl<
             .unwrap();
         let label_as_bytes = label.as_bytes();
         let res = db
-            .prefix_iter(self.txn, &bincode::serialize(&Value::from(key)).unwrap())
+            .prefix_iter(self.txn, &self.storage.index_key(index, &Value::from(key)).unwrap())
             .unwrap()
+            .skip_while(move |item| {
+                matches!((item, after), (Ok((_, node_id)), Some(after)) if *node_id <= after)
+            })
             .filter_map(move |item| {
                 if let Ok((_, node_id)) = item &&
                  let Some(value) = self.storage.nodes_db.get(self.txn, &node_id).ok()? {
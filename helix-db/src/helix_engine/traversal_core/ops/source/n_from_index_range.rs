@@ -0,0 +1,152 @@
+use std::ops::Bound;
+
+use crate::{
+    helix_engine::{
+        storage_core::{DATE_INDEX_KEY_PREFIX, HelixGraphStorage},
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue, LMDB_STRING_HEADER_LENGTH},
+        types::GraphError,
+    },
+    protocol::value::Value, utils::items::Node,
+};
+
+pub trait NFromIndexRangeAdapter<'db, 'arena, 'txn, 's>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Returns a new iterator that will return the nodes whose secondary index key falls
+    /// between `lower` and `upper`.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The name of the secondary index.
+    /// * `lower` - The lower bound of the keys to return.
+    /// * `upper` - The upper bound of the keys to return.
+    ///
+    /// The index must be over a `Date` field and the bounds must be dates, which are keyed
+    /// in time order there (see
+    /// [`HelixGraphStorage::index_key`]), so only the keys in range are read.
+    fn n_from_index_range(
+        self,
+        label: &'s str,
+        index: &'s str,
+        lower: Bound<Value>,
+        upper: Bound<Value>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+}
+
+impl<
+    'db,
+    'arena,
+    'txn,
+    's,
+    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+> NFromIndexRangeAdapter<'db, 'arena, 'txn, 's> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    #[inline]
+    fn n_from_index_range(
+        self,
+        label: &'s str,
+        index: &'s str,
+        lower: Bound<Value>,
+        upper: Bound<Value>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let label_as_bytes = label.as_bytes();
+        let range = (|| {
+            let db = self.storage.secondary_indices.get(index).ok_or_else(|| {
+                GraphError::New(format!("Secondary Index {index} not found"))
+            })?;
+            if !self.storage.date_indices.contains(index) {
+                return Err(GraphError::New(format!(
+                    "Secondary Index {index} is not over a Date field"
+                )));
+            }
+            let lower = date_key_bound(lower)?;
+            let upper = date_key_bound(upper)?;
+            // the date keys sort in time, so the range is read with a single seek
+            let lower = match &lower {
+                Bound::Unbounded => Bound::Included(&DATE_INDEX_KEY_PREFIX[..]),
+                bound => bound.as_ref().map(|key| &key[..]),
+            };
+            let upper = match &upper {
+                Bound::Unbounded => Bound::Excluded(&DATE_KEYS_END[..]),
+                bound => bound.as_ref().map(|key| &key[..]),
+            };
+            Ok::<_, GraphError>(db.range(self.txn, &(lower, upper))?)
+        })();
+        let (range, error) = match range {
+            Ok(range) => (Some(range), None),
+            Err(e) => (None, Some(Err(e))),
+        };
+
+        let res = error.into_iter().chain(range.into_iter().flatten().filter_map(move |item| {
+            let node_id = match item {
+                Ok((_, node_id)) => node_id,
+                Err(e) => return Some(Err(GraphError::from(e))),
+            };
+            let value = match self.storage.nodes_db.get(self.txn, &node_id) {
+                Ok(Some(value)) => value,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(GraphError::from(e))),
+            };
+            assert!(
+                value.len() >= LMDB_STRING_HEADER_LENGTH,
+                "value length does not contain header which means the `label` field was missing from the node on insertion"
+            );
+            let length_of_label_in_lmdb =
+                u64::from_le_bytes(value[..LMDB_STRING_HEADER_LENGTH].try_into().unwrap()) as usize;
+
+            if length_of_label_in_lmdb != label.len() {
+                return None;
+            }
+
+            assert!(
+                value.len() >= length_of_label_in_lmdb + LMDB_STRING_HEADER_LENGTH,
+                "value length is not at least the header length plus the label length meaning there has been a corruption on node insertion"
+            );
+            let label_in_lmdb = &value[LMDB_STRING_HEADER_LENGTH
+                ..LMDB_STRING_HEADER_LENGTH + length_of_label_in_lmdb];
+
+            if label_in_lmdb != label_as_bytes {
+                return None;
+            }
+            match Node::<'arena>::from_bincode_bytes(node_id, value, self.arena) {
+                Ok(node) => Some(Ok(TraversalValue::Node(node))),
+                Err(e) => Some(Err(GraphError::ConversionError(e.to_string()))),
+            }
+        }));
+
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: res,
+        }
+    }
+}
+
+/// The end of the date keys, the prefix following [`DATE_INDEX_KEY_PREFIX`]
+const DATE_KEYS_END: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+
+/// A bound on the index key of the date the bound value holds.
+fn date_key_bound(bound: Bound<Value>) -> Result<Bound<[u8; 16]>, GraphError> {
+    let key = |value: Value| {
+        value
+            .as_datetime()
+            .map(|date| HelixGraphStorage::date_index_key(&date))
+            .ok_or_else(|| GraphError::New(format!("Index range bound {value:?} is not a date")))
+    };
+    Ok(match bound {
+        Bound::Included(value) => Bound::Included(key(value)?),
+        Bound::Excluded(value) => Bound::Excluded(key(value)?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
@@ -1,3 +1,5 @@
+use std::ops::Bound;
+
 use crate::{
     helix_engine::{
         traversal_core::{
@@ -35,6 +37,22 @@ pub trait NFromTypeAdapter<'db, 'arena, 'txn, 's>:
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Returns the nodes with the given label whose ID comes after `after`, or all of them if
+    /// it is `None`.
+    ///
+    /// Nodes are stored in ID order, so a page of nodes is resumed by seeking to the ID of the
+    /// last node of the previous page instead of skipping the nodes before it.
+    fn n_from_type_after(
+        self,
+        label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
 }
 impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
     NFromTypeAdapter<'db, 'arena, 'txn, 's> for RoTraversalIterator<'db, 'arena, 'txn, I>
@@ -48,9 +66,25 @@ impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, Gr
         'arena,
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        self.n_from_type_after(label, None)
+    }
+
+    #[inline]
+    fn n_from_type_after(
+        self,
+        label: &'s str,
+        after: Option<u128>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     > {
         let label_as_bytes = label.as_bytes();
-        let iter = self.storage.nodes_db.iter(self.txn).unwrap().filter_map(move |item| {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        let nodes = self.storage.nodes_db.range(self.txn, &(start, Bound::Unbounded)).unwrap();
+        let iter = nodes.filter_map(move |item| {
             if let Ok((id, value)) = item {
                 assert!(
                     value.len() >= LMDB_STRING_HEADER_LENGTH,
@@ -0,0 +1,211 @@
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{
+            ops::{
+                source::add_e::{AddEAdapter, EdgeConstraints},
+                util::update::UpdateAdapter,
+            },
+            traversal_iter::RwTraversalIterator,
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::{items::Edge, label_hash::hash_label, properties::ImmutablePropertiesMap},
+};
+use heed3::RoTxn;
+
+pub trait UpsertEAdapter<'db, 'arena, 'txn>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Finds the `label` edge from `from_node` to `to_node` and updates it with `properties`,
+    /// or adds a new edge with the properties if there is none.
+    ///
+    /// The lookup walks the `out_edges_db` duplicates of `from_node` for `label`, so it never
+    /// creates a second edge of the same label between the two nodes.
+    fn upsert_e(
+        self,
+        label: &'arena str,
+        properties: &[(&'static str, Value)],
+        from_node: u128,
+        to_node: u128,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+
+    /// Upserts an edge like [`upsert_e`](Self::upsert_e), but adds a missing edge with
+    /// [`add_acyclic_edge`](AddEAdapter::add_acyclic_edge), so an edge that would create a
+    /// cycle of `label` edges is rejected.
+    fn upsert_acyclic_e(
+        self,
+        label: &'arena str,
+        properties: &[(&'static str, Value)],
+        from_node: u128,
+        to_node: u128,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+
+    /// Upserts an edge like [`upsert_e`](Self::upsert_e), but adds a missing edge with
+    /// [`add_constrained_edge`](AddEAdapter::add_constrained_edge), so the edge type's
+    /// cardinality is kept.
+    fn upsert_constrained_e(
+        self,
+        label: &'arena str,
+        properties: &[(&'static str, Value)],
+        from_node: u128,
+        to_node: u128,
+        constraints: EdgeConstraints,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    UpsertEAdapter<'db, 'arena, 'txn> for RwTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn upsert_e(
+        self,
+        label: &'arena str,
+        properties: &[(&'static str, Value)],
+        from_node: u128,
+        to_node: u128,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        upsert_edge(self, label, properties, from_node, to_node, EdgeConstraints::default())
+    }
+
+    fn upsert_acyclic_e(
+        self,
+        label: &'arena str,
+        properties: &[(&'static str, Value)],
+        from_node: u128,
+        to_node: u128,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let constraints = EdgeConstraints {
+            acyclic: true,
+            ..Default::default()
+        };
+        upsert_edge(self, label, properties, from_node, to_node, constraints)
+    }
+
+    fn upsert_constrained_e(
+        self,
+        label: &'arena str,
+        properties: &[(&'static str, Value)],
+        from_node: u128,
+        to_node: u128,
+        constraints: EdgeConstraints,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        upsert_edge(self, label, properties, from_node, to_node, constraints)
+    }
+}
+
+fn upsert_edge<'db, 'arena, 'txn, I>(
+    iter: RwTraversalIterator<'db, 'arena, 'txn, I>,
+    label: &'arena str,
+    properties: &[(&'static str, Value)],
+    from_node: u128,
+    to_node: u128,
+    constraints: EdgeConstraints,
+) -> RwTraversalIterator<
+    'db,
+    'arena,
+    'txn,
+    std::vec::IntoIter<Result<TraversalValue<'arena>, GraphError>>,
+> {
+    let existing = find_edge(
+        iter.storage,
+        iter.txn,
+        iter.arena,
+        label,
+        from_node,
+        to_node,
+    );
+
+    let results: Vec<Result<TraversalValue<'arena>, GraphError>> = match existing {
+        Ok(Some(edge)) if properties.is_empty() => vec![Ok(TraversalValue::Edge(edge))],
+        Ok(Some(edge)) => RwTraversalIterator {
+            storage: iter.storage,
+            arena: iter.arena,
+            txn: &mut *iter.txn,
+            inner: std::iter::once(Ok(TraversalValue::Edge(edge))),
+        }
+        .update(properties)
+        .collect(),
+        Ok(None) => {
+            let props = (!properties.is_empty()).then(|| {
+                ImmutablePropertiesMap::new(
+                    properties.len(),
+                    properties.iter().cloned(),
+                    iter.arena,
+                )
+            });
+            let adder = RwTraversalIterator {
+                storage: iter.storage,
+                arena: iter.arena,
+                txn: &mut *iter.txn,
+                inner: std::iter::empty(),
+            };
+            adder
+                .add_constrained_edge(label, props, from_node, to_node, constraints)
+                .collect()
+        }
+        Err(e) => vec![Err(e)],
+    };
+
+    RwTraversalIterator {
+        storage: iter.storage,
+        arena: iter.arena,
+        txn: iter.txn,
+        inner: results.into_iter(),
+    }
+}
+
+/// The first `label` edge from `from_node` to `to_node`.
+fn find_edge<'arena>(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    arena: &'arena bumpalo::Bump,
+    label: &str,
+    from_node: u128,
+    to_node: u128,
+) -> Result<Option<Edge<'arena>>, GraphError> {
+    let label_hash = hash_label(label, None);
+    let key = HelixGraphStorage::out_edge_key(&from_node, &label_hash);
+    let Some(edges) = storage.out_edges_db.get_duplicates(txn, &key)? else {
+        return Ok(None);
+    };
+    for item in edges {
+        let (_, value) = item?;
+        let (edge_id, node_id) = HelixGraphStorage::unpack_adj_edge_data(value)?;
+        if node_id == to_node {
+            return storage.get_edge(txn, &edge_id, arena).map(Some);
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,134 @@
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{
+            ops::{source::add_n::AddNAdapter, util::update::UpdateAdapter},
+            traversal_iter::RwTraversalIterator,
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::{items::Node, properties::ImmutablePropertiesMap},
+};
+use heed3::RoTxn;
+
+pub trait UpsertNAdapter<'db, 'arena, 'txn, 's>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Finds the `label` node whose indexed `key` property has the given value and updates it
+    /// with `properties`, or adds a new node with the key and properties if there is none.
+    ///
+    /// `defaults` are only set on a new node, for properties not in `properties`. The lookup
+    /// uses the secondary index named by `key`, so upserting the same key twice in the same
+    /// write transaction returns the same node.
+    fn upsert_n(
+        self,
+        label: &'arena str,
+        key: (&'static str, Value),
+        properties: &[(&'static str, Value)],
+        defaults: &[(&'static str, Value)],
+        secondary_indices: Option<&'s [&str]>,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+}
+
+impl<'db, 'arena, 'txn, 's, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    UpsertNAdapter<'db, 'arena, 'txn, 's> for RwTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn upsert_n(
+        self,
+        label: &'arena str,
+        key: (&'static str, Value),
+        properties: &[(&'static str, Value)],
+        defaults: &[(&'static str, Value)],
+        secondary_indices: Option<&'s [&str]>,
+    ) -> RwTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let (key_name, key_value) = key;
+        let existing = find_node_by_index(
+            self.storage,
+            self.txn,
+            self.arena,
+            label,
+            key_name,
+            &key_value,
+        );
+
+        let results: Vec<Result<TraversalValue<'arena>, GraphError>> = match existing {
+            Ok(Some(node)) if properties.is_empty() => vec![Ok(TraversalValue::Node(node))],
+            Ok(Some(node)) => RwTraversalIterator {
+                storage: self.storage,
+                arena: self.arena,
+                txn: &mut *self.txn,
+                inner: std::iter::once(Ok(TraversalValue::Node(node))),
+            }
+            .update(properties)
+            .collect(),
+            Ok(None) => {
+                let props = std::iter::once((key_name, key_value)).chain(
+                    properties
+                        .iter()
+                        .chain(defaults.iter().filter(|(name, _)| {
+                            !properties.iter().any(|(property, _)| property == name)
+                        }))
+                        .filter(|(name, _)| *name != key_name)
+                        .cloned(),
+                );
+                let len = props.clone().count();
+                let props = ImmutablePropertiesMap::new(len, props, self.arena);
+                RwTraversalIterator {
+                    storage: self.storage,
+                    arena: self.arena,
+                    txn: &mut *self.txn,
+                    inner: std::iter::empty(),
+                }
+                .add_n(label, Some(props), secondary_indices)
+                .collect()
+            }
+            Err(e) => vec![Err(e)],
+        };
+
+        RwTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: results.into_iter(),
+        }
+    }
+}
+
+/// The first `label` node whose `index` property has the given value.
+fn find_node_by_index<'arena>(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    arena: &'arena bumpalo::Bump,
+    label: &str,
+    index: &str,
+    value: &Value,
+) -> Result<Option<Node<'arena>>, GraphError> {
+    let db = storage
+        .secondary_indices
+        .get(index)
+        .ok_or_else(|| GraphError::New(format!("Secondary Index {index} not found")))?;
+    let key = storage.index_key(index, value)?;
+    let Some(ids) = db.get_duplicates(txn, &key)? else {
+        return Ok(None);
+    };
+    for item in ids {
+        let (_, node_id) = item?;
+        let node = storage.get_node(txn, &node_id, arena)?;
+        if node.label == label {
+            return Ok(Some(node));
+        }
+    }
+    Ok(None)
+}
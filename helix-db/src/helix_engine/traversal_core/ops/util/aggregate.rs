@@ -5,14 +5,17 @@ use crate::{
         traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
         types::GraphError,
     },
-    utils::aggregate::{Aggregate, AggregateItem},
+    utils::aggregate::{Aggregate, AggregateItem, Aggregation, GroupAccumulators},
 };
 
 pub trait AggregateAdapter<'arena>: Iterator {
+    /// Groups the items by the values of `properties`, keeping the items of each group and
+    /// computing `aggregations` over them in the same pass.
     fn aggregate_by(
         self,
         properties: &[String],
         should_count: bool,
+        aggregations: &[Aggregation],
     ) -> Result<Aggregate<'arena>, GraphError>;
 }
 
@@ -23,8 +26,9 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
         self,
         properties: &[String],
         should_count: bool,
+        aggregations: &[Aggregation],
     ) -> Result<Aggregate<'arena>, GraphError> {
-        let mut groups: HashMap<String, AggregateItem> = HashMap::new();
+        let mut groups: HashMap<String, (AggregateItem, GroupAccumulators)> = HashMap::new();
 
         for item in self.inner {
             let item = item?;
@@ -45,11 +49,25 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
             }
             let key = key_parts.join("_");
 
-            let group = groups.entry(key).or_default();
+            let (group, accumulators) = groups.entry(key).or_insert_with(|| {
+                (
+                    AggregateItem::default(),
+                    GroupAccumulators::new(aggregations),
+                )
+            });
+            accumulators.update(aggregations, &item)?;
             group.values.insert(item);
             group.count += 1;
         }
 
+        let groups = groups
+            .into_iter()
+            .map(|(key, (mut group, accumulators))| {
+                group.aggregates = accumulators.finish(aggregations);
+                (key, group)
+            })
+            .collect();
+
         if should_count {
             Ok(Aggregate::Count(groups))
         } else {
@@ -0,0 +1,370 @@
+use crate::{
+    helix_engine::{
+        deadline::check_deadline,
+        storage_core::HelixGraphStorage,
+        traversal_core::{
+            traversal_iter::{RoTraversalIterator, RwTraversalIterator},
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::{items::Node, label_hash::hash_label},
+};
+use heed3::RoTxn;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+pub trait CentralityAdapter<'db, 'arena, 'txn>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Returns the nodes in the traversal scored by their betweenness centrality, highest first.
+    ///
+    /// The graph used is the subgraph made of the nodes in the traversal and the `edge_label`
+    /// edges between them. When `samples` is smaller than the number of nodes, Brandes'
+    /// algorithm is only run from that many randomly picked sources and the scores are scaled
+    /// up, which gives an approximation. Otherwise every node is used as a source.
+    fn betweenness(
+        self,
+        edge_label: &str,
+        samples: Option<usize>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+
+    /// Returns the nodes in the traversal scored by the number of triangles they are part of.
+    ///
+    /// `edge_label` edges are treated as undirected and the whole graph is considered,
+    /// not just the nodes in the traversal.
+    fn triangles(
+        self,
+        edge_label: &str,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+
+    /// Returns the sum of the triangle counts of the nodes in the traversal.
+    ///
+    /// For a single node this is the number of triangles it is part of.
+    fn triangle_count_to_val(self, edge_label: &str) -> Result<Value, GraphError>;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    CentralityAdapter<'db, 'arena, 'txn> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn betweenness(
+        self,
+        edge_label: &str,
+        samples: Option<usize>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        betweenness(self.storage, self.txn, self.arena, self.inner, edge_label, samples)
+    }
+
+    fn triangles(
+        self,
+        edge_label: &str,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        triangles(self.storage, self.txn, self.arena, self.inner, edge_label)
+    }
+
+    fn triangle_count_to_val(self, edge_label: &str) -> Result<Value, GraphError> {
+        triangle_count(self.storage, self.txn, self.inner, edge_label)
+    }
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    CentralityAdapter<'db, 'arena, 'txn> for RwTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn betweenness(
+        self,
+        edge_label: &str,
+        samples: Option<usize>,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        betweenness(self.storage, self.txn, self.arena, self.inner, edge_label, samples)
+    }
+
+    fn triangles(
+        self,
+        edge_label: &str,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        triangles(self.storage, self.txn, self.arena, self.inner, edge_label)
+    }
+
+    fn triangle_count_to_val(self, edge_label: &str) -> Result<Value, GraphError> {
+        triangle_count(self.storage, self.txn, self.inner, edge_label)
+    }
+}
+
+fn betweenness<'db, 'arena, 'txn>(
+    storage: &'db HelixGraphStorage,
+    txn: &'txn RoTxn<'db>,
+    arena: &'arena bumpalo::Bump,
+    iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    edge_label: &str,
+    samples: Option<usize>,
+) -> RoTraversalIterator<
+    'db,
+    'arena,
+    'txn,
+    impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+> {
+    let label_hash = hash_label(edge_label, None);
+    let scored = iter
+        .filter_map(|item| match item {
+            Ok(TraversalValue::Node(node)) => Some(Ok(node)),
+            Ok(TraversalValue::NodeWithScore { node, .. }) => Some(Ok(node)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .and_then(|nodes| {
+            betweenness_scores(storage, txn, &nodes, &label_hash, samples).map(|scores| {
+                let mut scored = nodes.into_iter().zip(scores).collect::<Vec<_>>();
+                scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+                scored
+            })
+        });
+
+    let iter: Vec<Result<TraversalValue<'arena>, GraphError>> = match scored {
+        Ok(scored) => scored
+            .into_iter()
+            .map(|(node, score)| Ok(TraversalValue::NodeWithScore { node, score }))
+            .collect(),
+        Err(e) => vec![Err(e)],
+    };
+
+    RoTraversalIterator {
+        storage,
+        arena,
+        txn,
+        inner: iter.into_iter(),
+    }
+}
+
+fn triangles<'db, 'arena, 'txn>(
+    storage: &'db HelixGraphStorage,
+    txn: &'txn RoTxn<'db>,
+    arena: &'arena bumpalo::Bump,
+    iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    edge_label: &str,
+) -> RoTraversalIterator<
+    'db,
+    'arena,
+    'txn,
+    impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+> {
+    let label_hash = hash_label(edge_label, None);
+    let mut neighbours = HashMap::new();
+    let iter = iter.filter_map(move |item| {
+        let node = match item {
+            Ok(TraversalValue::Node(node)) => node,
+            Ok(TraversalValue::NodeWithScore { node, .. }) => node,
+            Ok(_) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(
+            node_triangles(storage, txn, &node.id, &label_hash, &mut neighbours).map(|count| {
+                TraversalValue::NodeWithScore {
+                    node,
+                    score: count as f64,
+                }
+            }),
+        )
+    });
+
+    RoTraversalIterator {
+        storage,
+        arena,
+        txn,
+        inner: iter,
+    }
+}
+
+fn triangle_count<'arena>(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    edge_label: &str,
+) -> Result<Value, GraphError> {
+    let label_hash = hash_label(edge_label, None);
+    let mut neighbours = HashMap::new();
+    let mut total = 0;
+    for item in iter {
+        total += node_triangles(storage, txn, &item?.id(), &label_hash, &mut neighbours)?;
+    }
+    Ok(Value::from(total))
+}
+
+/// Brandes' betweenness over the subgraph induced by `nodes`.
+///
+/// The returned scores are in the same order as `nodes`.
+fn betweenness_scores(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    nodes: &[Node<'_>],
+    label_hash: &[u8; 4],
+    samples: Option<usize>,
+) -> Result<Vec<f64>, GraphError> {
+    let n = nodes.len();
+    let index: HashMap<u128, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.id, i))
+        .collect();
+
+    let mut adjacency = Vec::with_capacity(n);
+    for node in nodes {
+        let mut out = Vec::new();
+        let key = HelixGraphStorage::out_edge_key(&node.id, label_hash);
+        if let Some(iter) = storage.out_edges_db.get_duplicates(txn, &key)? {
+            for item in iter {
+                let (_, value) = item?;
+                let (_, to_node) = HelixGraphStorage::unpack_adj_edge_data(value)?;
+                if let Some(&to) = index.get(&to_node) {
+                    out.push(to);
+                }
+            }
+        }
+        out.sort_unstable();
+        out.dedup();
+        adjacency.push(out);
+    }
+
+    let sources = match samples {
+        Some(k) if k < n => rand::seq::index::sample(&mut rand::rng(), n, k).into_vec(),
+        _ => (0..n).collect(),
+    };
+
+    let mut centrality = vec![0.0; n];
+    let mut sigma = vec![0.0f64; n];
+    let mut dist = vec![-1i64; n];
+    let mut delta = vec![0.0f64; n];
+    let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut order = Vec::with_capacity(n);
+    let mut queue = VecDeque::new();
+
+    for &s in &sources {
+        check_deadline()?;
+        sigma.fill(0.0);
+        dist.fill(-1);
+        delta.fill(0.0);
+        preds.iter_mut().for_each(Vec::clear);
+        order.clear();
+
+        sigma[s] = 1.0;
+        dist[s] = 0;
+        queue.push_back(s);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for &w in &adjacency[v] {
+                if dist[w] < 0 {
+                    dist[w] = dist[v] + 1;
+                    queue.push_back(w);
+                }
+                if dist[w] == dist[v] + 1 {
+                    sigma[w] += sigma[v];
+                    preds[w].push(v);
+                }
+            }
+        }
+
+        while let Some(w) = order.pop() {
+            for &v in &preds[w] {
+                delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+            }
+            if w != s {
+                centrality[w] += delta[w];
+            }
+        }
+    }
+
+    if !sources.is_empty() && sources.len() < n {
+        let scale = n as f64 / sources.len() as f64;
+        centrality.iter_mut().for_each(|c| *c *= scale);
+    }
+
+    Ok(centrality)
+}
+
+/// Ids of the nodes connected to `node_id` by an `edge_label` edge in either direction.
+fn undirected_neighbours(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    node_id: &u128,
+    label_hash: &[u8; 4],
+) -> Result<HashSet<u128>, GraphError> {
+    let mut neighbours = HashSet::new();
+    let out_key = HelixGraphStorage::out_edge_key(node_id, label_hash);
+    let in_key = HelixGraphStorage::in_edge_key(node_id, label_hash);
+    for (db, key) in [
+        (&storage.out_edges_db, out_key),
+        (&storage.in_edges_db, in_key),
+    ] {
+        if let Some(iter) = db.get_duplicates(txn, &key)? {
+            for item in iter {
+                let (_, value) = item?;
+                let (_, other) = HelixGraphStorage::unpack_adj_edge_data(value)?;
+                if other != *node_id {
+                    neighbours.insert(other);
+                }
+            }
+        }
+    }
+    Ok(neighbours)
+}
+
+/// Number of triangles `node_id` is part of.
+///
+/// Neighbour sets are cached in `cache` so they are only read once per traversal.
+fn node_triangles(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    node_id: &u128,
+    label_hash: &[u8; 4],
+    cache: &mut HashMap<u128, HashSet<u128>>,
+) -> Result<usize, GraphError> {
+    if !cache.contains_key(node_id) {
+        let neighbours = undirected_neighbours(storage, txn, node_id, label_hash)?;
+        cache.insert(*node_id, neighbours);
+    }
+    let neighbours = cache[node_id].iter().copied().collect::<Vec<_>>();
+
+    let mut links = 0;
+    for neighbour in &neighbours {
+        if !cache.contains_key(neighbour) {
+            let next = undirected_neighbours(storage, txn, neighbour, label_hash)?;
+            cache.insert(*neighbour, next);
+        }
+        links += cache[neighbour]
+            .iter()
+            .filter(|other| *other != node_id && cache[node_id].contains(*other))
+            .count();
+    }
+    // every triangle is seen once from each of the two neighbours
+    Ok(links / 2)
+}
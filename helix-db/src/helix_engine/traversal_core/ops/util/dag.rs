@@ -0,0 +1,250 @@
+use crate::{
+    helix_engine::{
+        deadline::check_deadline,
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        types::GraphError,
+    },
+    utils::{id::ID, label_hash::hash_label},
+};
+use heed3::RoTxn;
+use std::collections::{HashMap, HashSet, VecDeque, hash_map::Entry};
+
+pub trait DagAdapter<'db, 'arena, 'txn>:
+    Iterator<Item = Result<TraversalValue<'arena>, GraphError>>
+{
+    /// Returns the nodes in the traversal and every node reachable from them through
+    /// `edge_label` edges, in topological order.
+    ///
+    /// For every `edge_label` edge between two of the returned nodes, the node the edge starts
+    /// from comes first. Kahn's algorithm is used, so a [`GraphError::CycleDetected`] is
+    /// returned if the reachable nodes contain a cycle.
+    fn topological_sort(
+        self,
+        edge_label: &str,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+
+    /// Returns whether a cycle of `edge_label` edges can be reached from the nodes in the
+    /// traversal.
+    fn has_cycle(self, edge_label: &str) -> Result<bool, GraphError>;
+}
+
+impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    DagAdapter<'db, 'arena, 'txn> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn topological_sort(
+        self,
+        edge_label: &str,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        let label_hash = hash_label(edge_label, None);
+        let sorted = self
+            .inner
+            .filter_map(|item| match item {
+                Ok(TraversalValue::Node(node)) => Some(Ok(node.id)),
+                Ok(TraversalValue::NodeWithScore { node, .. }) => Some(Ok(node.id)),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|seeds| kahn_sort(self.storage, self.txn, &seeds, &label_hash));
+
+        let iter: Vec<Result<TraversalValue<'arena>, GraphError>> = match sorted {
+            Ok(sorted) => sorted
+                .into_iter()
+                .map(|id| {
+                    self.storage
+                        .get_node(self.txn, &id, self.arena)
+                        .map(TraversalValue::Node)
+                })
+                .collect(),
+            Err(e) => vec![Err(e)],
+        };
+
+        RoTraversalIterator {
+            storage: self.storage,
+            arena: self.arena,
+            txn: self.txn,
+            inner: iter.into_iter(),
+        }
+    }
+
+    fn has_cycle(self, edge_label: &str) -> Result<bool, GraphError> {
+        let label_hash = hash_label(edge_label, None);
+        let mut states = HashMap::new();
+        for item in self.inner {
+            let id = item?.id();
+            if find_cycle(self.storage, self.txn, id, &label_hash, &mut states)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Returns whether adding an `edge_label` edge from `from_node` to `to_node` would create a
+/// cycle, which is the case when `from_node` can already be reached from `to_node`.
+pub fn would_create_cycle(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    from_node: u128,
+    to_node: u128,
+    label_hash: &[u8; 4],
+) -> Result<bool, GraphError> {
+    if from_node == to_node {
+        return Ok(true);
+    }
+    let mut visited = HashSet::from([to_node]);
+    let mut stack = vec![to_node];
+    while let Some(current) = stack.pop() {
+        check_deadline()?;
+        for next in out_neighbours(storage, txn, &current, label_hash)? {
+            if next == from_node {
+                return Ok(true);
+            }
+            if visited.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Ids of the nodes `node_id` has an outgoing `edge_label` edge to.
+fn out_neighbours(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    node_id: &u128,
+    label_hash: &[u8; 4],
+) -> Result<Vec<u128>, GraphError> {
+    let mut neighbours = Vec::new();
+    let key = HelixGraphStorage::out_edge_key(node_id, label_hash);
+    if let Some(iter) = storage.out_edges_db.get_duplicates(txn, &key)? {
+        for item in iter {
+            let (_, value) = item?;
+            let (_, to_node) = HelixGraphStorage::unpack_adj_edge_data(value)?;
+            neighbours.push(to_node);
+        }
+    }
+    Ok(neighbours)
+}
+
+/// Topologically sorts `seeds` and the nodes reachable from them.
+///
+/// Nodes with no ordering between them are kept in the order they were reached.
+fn kahn_sort(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    seeds: &[u128],
+    label_hash: &[u8; 4],
+) -> Result<Vec<u128>, GraphError> {
+    let mut reached = Vec::new();
+    let mut adjacency: HashMap<u128, Vec<u128>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for &seed in seeds {
+        if let Entry::Vacant(entry) = adjacency.entry(seed) {
+            entry.insert(Vec::new());
+            reached.push(seed);
+            queue.push_back(seed);
+        }
+    }
+    while let Some(current) = queue.pop_front() {
+        check_deadline()?;
+        let neighbours = out_neighbours(storage, txn, &current, label_hash)?;
+        for next in &neighbours {
+            if let Entry::Vacant(entry) = adjacency.entry(*next) {
+                entry.insert(Vec::new());
+                reached.push(*next);
+                queue.push_back(*next);
+            }
+        }
+        adjacency.insert(current, neighbours);
+    }
+
+    let mut in_degree: HashMap<u128, usize> = reached.iter().map(|id| (*id, 0)).collect();
+    for neighbours in adjacency.values() {
+        for next in neighbours {
+            *in_degree.entry(*next).or_default() += 1;
+        }
+    }
+
+    let mut ready = reached
+        .iter()
+        .filter(|id| in_degree[*id] == 0)
+        .copied()
+        .collect::<VecDeque<_>>();
+    let mut sorted = Vec::with_capacity(reached.len());
+    while let Some(current) = ready.pop_front() {
+        sorted.push(current);
+        for next in &adjacency[&current] {
+            let degree = in_degree.get_mut(next).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                ready.push_back(*next);
+            }
+        }
+    }
+
+    if sorted.len() < reached.len() {
+        let on_cycle = reached
+            .iter()
+            .find(|id| in_degree[*id] > 0)
+            .map(|id| ID::from(*id).stringify())
+            .unwrap_or_default();
+        return Err(GraphError::CycleDetected(format!(
+            "node {on_cycle} is part of a cycle"
+        )));
+    }
+    Ok(sorted)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    InProgress,
+    Done,
+}
+
+/// Depth first search from `start` that returns whether it finds an edge back to a node that is
+/// still on the search path.
+///
+/// `states` is shared between searches so nodes are only explored once per traversal.
+fn find_cycle(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    start: u128,
+    label_hash: &[u8; 4],
+    states: &mut HashMap<u128, VisitState>,
+) -> Result<bool, GraphError> {
+    if states.contains_key(&start) {
+        return Ok(false);
+    }
+    states.insert(start, VisitState::InProgress);
+    let mut stack = vec![(start, out_neighbours(storage, txn, &start, label_hash)?)];
+    while let Some((node, neighbours)) = stack.last_mut() {
+        match neighbours.pop() {
+            Some(next) => match states.get(&next) {
+                Some(VisitState::InProgress) => return Ok(true),
+                Some(VisitState::Done) => {}
+                None => {
+                    states.insert(next, VisitState::InProgress);
+                    let next_neighbours = out_neighbours(storage, txn, &next, label_hash)?;
+                    stack.push((next, next_neighbours));
+                }
+            },
+            None => {
+                states.insert(*node, VisitState::Done);
+                stack.pop();
+            }
+        }
+    }
+    Ok(false)
+}
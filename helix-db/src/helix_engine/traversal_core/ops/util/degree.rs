@@ -0,0 +1,102 @@
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::{
+            traversal_iter::{RoTraversalIterator, RwTraversalIterator},
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    protocol::value::Value,
+    utils::label_hash::hash_label,
+};
+use heed3::RoTxn;
+use serde::Deserialize;
+
+/// Which adjacency lists a degree is counted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Out,
+    In,
+    Both,
+}
+
+pub trait DegreeAdapter<'arena>: Iterator {
+    /// Returns the total number of outgoing `edge_label` edges of the items in the traversal.
+    ///
+    /// For a single node this is its out degree. The neighbouring nodes are never loaded.
+    fn out_degree_to_val(self, edge_label: &str) -> Result<Value, GraphError>;
+
+    /// Returns the total number of incoming `edge_label` edges of the items in the traversal.
+    fn in_degree_to_val(self, edge_label: &str) -> Result<Value, GraphError>;
+
+    /// Returns the total number of incoming and outgoing `edge_label` edges of the items in the
+    /// traversal.
+    fn degree_to_val(self, edge_label: &str) -> Result<Value, GraphError>;
+}
+
+/// Degree of a single node for an edge label.
+#[inline]
+pub fn node_degree(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    node_id: &u128,
+    label_hash: &[u8; 4],
+    direction: Direction,
+) -> Result<usize, GraphError> {
+    match direction {
+        Direction::Out => storage.out_degree(txn, node_id, label_hash),
+        Direction::In => storage.in_degree(txn, node_id, label_hash),
+        Direction::Both => Ok(storage.out_degree(txn, node_id, label_hash)?
+            + storage.in_degree(txn, node_id, label_hash)?),
+    }
+}
+
+#[inline]
+fn sum_degrees<'arena>(
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+    iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    edge_label: &str,
+    direction: Direction,
+) -> Result<Value, GraphError> {
+    let label_hash = hash_label(edge_label, None);
+    let mut total = 0;
+    for item in iter {
+        total += node_degree(storage, txn, &item?.id(), &label_hash, direction)?;
+    }
+    Ok(Value::from(total))
+}
+
+impl<'db, 'arena: 'txn, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    DegreeAdapter<'arena> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn out_degree_to_val(self, edge_label: &str) -> Result<Value, GraphError> {
+        sum_degrees(self.storage, self.txn, self.inner, edge_label, Direction::Out)
+    }
+
+    fn in_degree_to_val(self, edge_label: &str) -> Result<Value, GraphError> {
+        sum_degrees(self.storage, self.txn, self.inner, edge_label, Direction::In)
+    }
+
+    fn degree_to_val(self, edge_label: &str) -> Result<Value, GraphError> {
+        sum_degrees(self.storage, self.txn, self.inner, edge_label, Direction::Both)
+    }
+}
+
+impl<'db, 'arena: 'txn, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    DegreeAdapter<'arena> for RwTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn out_degree_to_val(self, edge_label: &str) -> Result<Value, GraphError> {
+        sum_degrees(self.storage, self.txn, self.inner, edge_label, Direction::Out)
+    }
+
+    fn in_degree_to_val(self, edge_label: &str) -> Result<Value, GraphError> {
+        sum_degrees(self.storage, self.txn, self.inner, edge_label, Direction::In)
+    }
+
+    fn degree_to_val(self, edge_label: &str) -> Result<Value, GraphError> {
+        sum_degrees(self.storage, self.txn, self.inner, edge_label, Direction::Both)
+    }
+}
@@ -3,20 +3,89 @@ use crate::{
         traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
         types::GraphError,
     },
-    utils::group_by::{GroupBy, GroupByItem},
+    protocol::value::Value,
+    utils::{
+        aggregate::{Aggregation, GroupAccumulators},
+        group_by::{GroupBy, GroupByItem},
+    },
 };
 use std::collections::HashMap;
 
+/// A value items are grouped on: a property, or a value computed from each item such as a
+/// date truncated to the day. The key's `name` is what the value is returned under.
+#[derive(Clone, Copy, Debug)]
+pub struct GroupKey<'a> {
+    pub name: &'a str,
+    pub computed: Option<fn(&TraversalValue) -> Value>,
+}
+
+impl<'a> GroupKey<'a> {
+    pub const fn property(name: &'a str) -> Self {
+        Self {
+            name,
+            computed: None,
+        }
+    }
+
+    pub const fn computed(name: &'a str, computed: fn(&TraversalValue) -> Value) -> Self {
+        Self {
+            name,
+            computed: Some(computed),
+        }
+    }
+
+    /// The key's value for `item`, or `None` if it is missing or empty
+    fn value(&self, item: &TraversalValue) -> Option<Value> {
+        match self.computed {
+            Some(computed) => Some(computed(item)).filter(|value| !matches!(value, Value::Empty)),
+            None => item.get_property(self.name).cloned(),
+        }
+    }
+}
+
 pub trait GroupByAdapter: Iterator {
-    fn group_by(self, properties: &[String], should_count: bool) -> Result<GroupBy, GraphError>;
+    /// Groups the items by the values of `properties`, counting the items in each group and
+    /// computing `aggregations` over them in the same pass.
+    fn group_by(
+        self,
+        properties: &[String],
+        should_count: bool,
+        aggregations: &[Aggregation],
+    ) -> Result<GroupBy, GraphError>;
+
+    /// Like [`GroupByAdapter::group_by`], but groups on `keys`, which may be computed.
+    fn group_by_keys(
+        self,
+        keys: &[GroupKey],
+        should_count: bool,
+        aggregations: &[Aggregation],
+    ) -> Result<GroupBy, GraphError>;
 }
 
 impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
     GroupByAdapter for RoTraversalIterator<'db, 'arena, 'txn, I>
 {
+    fn group_by(
+        self,
+        properties: &[String],
+        should_count: bool,
+        aggregations: &[Aggregation],
+    ) -> Result<GroupBy, GraphError> {
+        let keys = properties
+            .iter()
+            .map(|property| GroupKey::property(property))
+            .collect::<Vec<_>>();
+        self.group_by_keys(&keys, should_count, aggregations)
+    }
+
     // TODO: optimize this
-    fn group_by(self, properties: &[String], should_count: bool) -> Result<GroupBy, GraphError> {
-        let mut groups: HashMap<String, GroupByItem> = HashMap::new();
+    fn group_by_keys(
+        self,
+        keys: &[GroupKey],
+        should_count: bool,
+        aggregations: &[Aggregation],
+    ) -> Result<GroupBy, GraphError> {
+        let mut groups: HashMap<String, (GroupByItem, GroupAccumulators)> = HashMap::new();
 
         for item in self.inner {
             let item = item?;
@@ -24,11 +93,11 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
             let mut kvs = Vec::new();
             let mut key_parts = Vec::new();
 
-            for property in properties {
-                match item.get_property(property) {
+            for key in keys {
+                match key.value(&item) {
                     Some(val) => {
                         key_parts.push(val.inner_stringify());
-                        kvs.push((property.to_string(), val.clone()));
+                        kvs.push((key.name.to_string(), val));
                     }
                     None => {
                         key_parts.push("null".to_string());
@@ -37,11 +106,22 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
             }
             let key = key_parts.join("_");
 
-            let group = groups.entry(key).or_default();
+            let (group, accumulators) = groups
+                .entry(key)
+                .or_insert_with(|| (GroupByItem::default(), GroupAccumulators::new(aggregations)));
             group.values.extend(kvs);
             group.count += 1;
+            accumulators.update(aggregations, &item)?;
         }
 
+        let groups = groups
+            .into_iter()
+            .map(|(key, (mut group, accumulators))| {
+                group.aggregates = accumulators.finish(aggregations);
+                (key, group)
+            })
+            .collect();
+
         if should_count {
             Ok(GroupBy::Count(groups))
         } else {
@@ -1,6 +1,9 @@
 pub mod aggregate;
+pub mod centrality;
 pub mod count;
+pub mod dag;
 pub mod dedup;
+pub mod degree;
 pub mod drop;
 pub mod exist;
 pub mod filter_mut;
@@ -8,6 +11,8 @@ pub mod filter_ref;
 pub mod group_by;
 pub mod map;
 pub mod order;
+pub mod page;
 pub mod paths;
 pub mod range;
+pub mod subgraph;
 pub mod update;
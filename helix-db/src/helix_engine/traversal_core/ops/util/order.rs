@@ -1,42 +1,216 @@
-use std::cmp::Ordering;
+use std::{borrow::Cow, cmp::Ordering, collections::BinaryHeap};
 
-use itertools::Itertools;
-
-use crate::helix_engine::{
-    traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
-    types::GraphError,
+use crate::{
+    helix_engine::{
+        traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
+        types::GraphError,
+    },
+    protocol::value::Value,
 };
 
-pub struct OrderByAsc<I> {
-    iter: I,
+/// A property to order by, with its direction and where items missing the property go.
+///
+/// Items missing the property are placed last unless [`OrderKey::nulls_first`] is used.
+/// [`OrderKey::by`] orders on a value computed from each item instead of the property.
+#[derive(Clone, Copy, Debug)]
+pub struct OrderKey<'a> {
+    pub property: &'a str,
+    pub descending: bool,
+    pub nulls_first: bool,
+    pub computed: Option<fn(&TraversalValue) -> Value>,
 }
 
-impl<'arena, I> Iterator for OrderByAsc<I>
-where
-    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
-{
-    type Item = I::Item;
+impl<'a> OrderKey<'a> {
+    pub const fn asc(property: &'a str) -> Self {
+        Self {
+            property,
+            descending: false,
+            nulls_first: false,
+            computed: None,
+        }
+    }
+
+    pub const fn desc(property: &'a str) -> Self {
+        Self {
+            property,
+            descending: true,
+            nulls_first: false,
+            computed: None,
+        }
+    }
+
+    pub const fn nulls_first(self) -> Self {
+        Self {
+            nulls_first: true,
+            ..self
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+    pub const fn nulls_last(self) -> Self {
+        Self {
+            nulls_first: false,
+            ..self
+        }
+    }
+
+    /// Orders on the value `computed` returns for each item, e.g. a date truncated to the day.
+    /// An empty result counts as missing.
+    pub const fn by(self, computed: fn(&TraversalValue) -> Value) -> Self {
+        Self {
+            computed: Some(computed),
+            ..self
+        }
+    }
+
+    fn value<'v>(&self, item: &'v TraversalValue) -> Option<Cow<'v, Value>> {
+        match self.computed {
+            Some(computed) => match computed(item) {
+                Value::Empty => None,
+                value => Some(Cow::Owned(value)),
+            },
+            None => sort_value(item, self.property).map(Cow::Borrowed),
+        }
+    }
+
+    fn compare(&self, a: &TraversalValue, b: &TraversalValue) -> Ordering {
+        match (self.value(a), self.value(b)) {
+            (Some(a), Some(b)) => {
+                let ordering = compare_values(&a, &b);
+                if self.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            }
+            (Some(_), None) if self.nulls_first => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) if self.nulls_first => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
     }
 }
 
-pub struct OrderByDesc<I> {
-    iter: I,
+/// The value an item is ordered on, treating [`Value::Empty`] as missing.
+fn sort_value<'a>(item: &'a TraversalValue, property: &str) -> Option<&'a Value> {
+    let value = match item {
+        TraversalValue::Value(value) => Some(value),
+        _ => item.get_property(property),
+    };
+    value.filter(|value| !matches!(value, Value::Empty))
 }
 
-impl<'arena, I> Iterator for OrderByDesc<I>
-where
-    I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
-{
-    type Item = I::Item;
+/// Orders values of the same kind by [`Value`]'s ordering, which compares integers and floats
+/// numerically, and values of different kinds by kind so the order is total.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    kind_rank(a).cmp(&kind_rank(b)).then_with(|| a.cmp(b))
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+fn kind_rank(value: &Value) -> u8 {
+    match value {
+        Value::Empty => 0,
+        Value::Boolean(_) => 1,
+        Value::I8(_)
+        | Value::I16(_)
+        | Value::I32(_)
+        | Value::I64(_)
+        | Value::U8(_)
+        | Value::U16(_)
+        | Value::U32(_)
+        | Value::U64(_)
+        | Value::U128(_)
+        | Value::F32(_)
+        | Value::F64(_) => 2,
+        Value::Date(_) => 3,
+        Value::String(_) => 4,
+        Value::Id(_) => 5,
+        Value::Array(_) => 6,
+        Value::Object(_) => 7,
     }
 }
 
+fn compare_items(keys: &[OrderKey], a: &TraversalValue, b: &TraversalValue) -> Ordering {
+    keys.iter()
+        .map(|key| key.compare(a, b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// An item in the top-k heap. Ties are broken by arrival order so the result matches a stable
+/// sort.
+struct Ranked<'k, 'arena> {
+    keys: &'k [OrderKey<'k>],
+    position: usize,
+    item: TraversalValue<'arena>,
+}
+
+impl Ord for Ranked<'_, '_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_items(self.keys, &self.item, &other.item)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for Ranked<'_, '_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked<'_, '_> {}
+
+fn sort_items<'arena>(
+    iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    keys: &[OrderKey],
+) -> Vec<Result<TraversalValue<'arena>, GraphError>> {
+    match iter.collect::<Result<Vec<_>, _>>() {
+        Ok(mut items) => {
+            items.sort_by(|a, b| compare_items(keys, a, b));
+            items.into_iter().map(Ok).collect()
+        }
+        Err(e) => vec![Err(e)],
+    }
+}
+
+/// Keeps the first `k` items in order using a heap of at most `k` items, rather than sorting
+/// all of them.
+fn top_k_items<'arena>(
+    iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    keys: &[OrderKey],
+    k: usize,
+) -> Vec<Result<TraversalValue<'arena>, GraphError>> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut heap = BinaryHeap::with_capacity(k);
+    for (position, item) in iter.enumerate() {
+        let ranked = match item {
+            Ok(item) => Ranked {
+                keys,
+                position,
+                item,
+            },
+            Err(e) => return vec![Err(e)],
+        };
+        if heap.len() < k {
+            heap.push(ranked);
+        } else if heap.peek().is_some_and(|worst| ranked < *worst) {
+            heap.pop();
+            heap.push(ranked);
+        }
+    }
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|ranked| Ok(ranked.item))
+        .collect()
+}
+
 pub trait OrderByAdapter<'db, 'arena, 'txn>: Iterator {
     fn order_by_asc(
         self,
@@ -57,6 +231,34 @@ pub trait OrderByAdapter<'db, 'arena, 'txn>: Iterator {
         'txn,
         impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
     >;
+
+    /// Orders the items by each key in turn, using later keys to break ties in earlier ones.
+    ///
+    /// The sort is stable, and if the traversal yields an error it is returned on its own.
+    fn order_by(
+        self,
+        keys: &[OrderKey],
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >;
+
+    /// Returns the first `k` items of [`OrderByAdapter::order_by`] without sorting the rest.
+    fn order_by_top_k<K>(
+        self,
+        keys: &[OrderKey],
+        k: K,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: TryInto<usize>,
+        K::Error: std::fmt::Debug;
 }
 
 impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
@@ -75,42 +277,7 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
             arena: self.arena,
             storage: self.storage,
             txn: self.txn,
-            inner: OrderByAsc {
-                iter: self.inner.sorted_by(|a, b| match (a, b) {
-                    (Ok(a), Ok(b)) => match (a, b) {
-                        (TraversalValue::Node(a), TraversalValue::Node(b)) => {
-                            match (a.get_property(property), b.get_property(property)) {
-                                (Some(val_a), Some(val_b)) => val_a.cmp(val_b),
-                                (Some(_), None) => Ordering::Less,
-                                (None, Some(_)) => Ordering::Greater,
-                                (None, None) => Ordering::Equal,
-                            }
-                        }
-                        (TraversalValue::Edge(a), TraversalValue::Edge(b)) => {
-                            match (a.get_property(property), b.get_property(property)) {
-                                (Some(val_a), Some(val_b)) => val_a.cmp(val_b),
-                                (Some(_), None) => Ordering::Less,
-                                (None, Some(_)) => Ordering::Greater,
-                                (None, None) => Ordering::Equal,
-                            }
-                        }
-                        (TraversalValue::Vector(a), TraversalValue::Vector(b)) => {
-                            match (a.get_property(property), b.get_property(property)) {
-                                (Some(val_a), Some(val_b)) => val_a.cmp(val_b),
-                                (Some(_), None) => Ordering::Less,
-                                (None, Some(_)) => Ordering::Greater,
-                                (None, None) => Ordering::Equal,
-                            }
-                        }
-                        (TraversalValue::Value(val_a), TraversalValue::Value(val_b)) => {
-                            val_a.cmp(val_b)
-                        }
-                        _ => Ordering::Equal,
-                    },
-                    (Err(_), _) => Ordering::Equal,
-                    (_, Err(_)) => Ordering::Equal,
-                }),
-            },
+            inner: sort_items(self.inner, &[OrderKey::asc(property)]).into_iter(),
         }
     }
 
@@ -127,42 +294,49 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
             arena: self.arena,
             storage: self.storage,
             txn: self.txn,
-            inner: OrderByAsc {
-                iter: self.inner.sorted_by(|a, b| match (a, b) {
-                    (Ok(a), Ok(b)) => match (a, b) {
-                        (TraversalValue::Node(a), TraversalValue::Node(b)) => {
-                            match (a.get_property(property), b.get_property(property)) {
-                                (Some(val_a), Some(val_b)) => val_b.cmp(val_a),
-                                (Some(_), None) => Ordering::Less,
-                                (None, Some(_)) => Ordering::Greater,
-                                (None, None) => Ordering::Equal,
-                            }
-                        }
-                        (TraversalValue::Edge(a), TraversalValue::Edge(b)) => {
-                            match (a.get_property(property), b.get_property(property)) {
-                                (Some(val_a), Some(val_b)) => val_b.cmp(val_a),
-                                (Some(_), None) => Ordering::Less,
-                                (None, Some(_)) => Ordering::Greater,
-                                (None, None) => Ordering::Equal,
-                            }
-                        }
-                        (TraversalValue::Vector(a), TraversalValue::Vector(b)) => {
-                            match (a.get_property(property), b.get_property(property)) {
-                                (Some(val_a), Some(val_b)) => val_b.cmp(val_a),
-                                (Some(_), None) => Ordering::Less,
-                                (None, Some(_)) => Ordering::Greater,
-                                (None, None) => Ordering::Equal,
-                            }
-                        }
-                        (TraversalValue::Value(val_a), TraversalValue::Value(val_b)) => {
-                            val_b.cmp(val_a)
-                        }
-                        _ => Ordering::Equal,
-                    },
-                    (Err(_), _) => Ordering::Equal,
-                    (_, Err(_)) => Ordering::Equal,
-                }),
-            },
+            inner: sort_items(self.inner, &[OrderKey::desc(property)]).into_iter(),
+        }
+    }
+
+    fn order_by(
+        self,
+        keys: &[OrderKey],
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    > {
+        RoTraversalIterator {
+            arena: self.arena,
+            storage: self.storage,
+            txn: self.txn,
+            inner: sort_items(self.inner, keys).into_iter(),
+        }
+    }
+
+    fn order_by_top_k<K>(
+        self,
+        keys: &[OrderKey],
+        k: K,
+    ) -> RoTraversalIterator<
+        'db,
+        'arena,
+        'txn,
+        impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    >
+    where
+        K: TryInto<usize>,
+        K::Error: std::fmt::Debug,
+    {
+        let k = k
+            .try_into()
+            .expect("Top k must be non-negative and fit in usize");
+        RoTraversalIterator {
+            arena: self.arena,
+            storage: self.storage,
+            txn: self.txn,
+            inner: top_k_items(self.inner, keys, k).into_iter(),
         }
     }
 }
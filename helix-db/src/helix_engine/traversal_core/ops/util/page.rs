@@ -0,0 +1,126 @@
+use crate::{
+    helix_engine::{traversal_core::traversal_value::TraversalValue, types::GraphError},
+    utils::label_hash::hash_label,
+};
+
+/// The source iterator a [`Cursor`] resumes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CursorSource {
+    /// `n_from_type_after`
+    Nodes = 1,
+    /// `n_from_index_after`
+    Index = 2,
+    /// `e_from_type_after`
+    Edges = 3,
+    /// `out_e_after`
+    OutEdges = 4,
+    /// `in_e_after`
+    InEdges = 5,
+}
+
+impl CursorSource {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            1 => Some(CursorSource::Nodes),
+            2 => Some(CursorSource::Index),
+            3 => Some(CursorSource::Edges),
+            4 => Some(CursorSource::OutEdges),
+            5 => Some(CursorSource::InEdges),
+            _ => None,
+        }
+    }
+}
+
+/// Opaque continuation cursor of a page of results.
+///
+/// Holds the LMDB key of the last item of a page in its source, so the next page of nodes or
+/// edges of a type seeks to it instead of skipping the items before it. The edges of a node and
+/// the IDs under an index key are the values of a single LMDB key, which can only be walked, so
+/// their next page walks the IDs before the cursor, without reading their items. Clients get it
+/// as a hex string and pass it back unchanged, with an empty string for the first page.
+///
+/// The cursor also records its source and the hash of the label it iterates, so a cursor from
+/// another query is rejected instead of resuming at an unrelated position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    source: CursorSource,
+    label: [u8; 4],
+    after: u128,
+}
+
+/// `source(1)` | `label-hash(4)` | `key(16)`
+const CURSOR_LENGTH: usize = 21;
+
+impl Cursor {
+    /// The key to resume `source` after, or `None` for the first page
+    pub fn resume(
+        cursor: &str,
+        source: CursorSource,
+        label: &str,
+    ) -> Result<Option<u128>, GraphError> {
+        if cursor.is_empty() {
+            return Ok(None);
+        }
+        let decoded = Cursor::decode(cursor)?;
+        if decoded.source != source || decoded.label != hash_label(label, None) {
+            return Err(GraphError::InvalidCursor(
+                "the cursor was returned by another traversal".to_string(),
+            ));
+        }
+        Ok(Some(decoded.after))
+    }
+
+    /// The cursor of the page after `page`, or `None` if `page` is the last page.
+    ///
+    /// A page with fewer than `size` items is the last one. A full page may be followed by an
+    /// empty page. Fails if `size` is negative.
+    pub fn next_page<N>(
+        page: &[TraversalValue<'_>],
+        size: N,
+        source: CursorSource,
+        label: &str,
+    ) -> Result<Option<String>, GraphError>
+    where
+        N: TryInto<usize>,
+    {
+        let size: usize = size.try_into().map_err(|_| {
+            GraphError::DecodeError("Page size must be non-negative and fit in usize".to_string())
+        })?;
+        Ok(match page.last() {
+            Some(last) if page.len() >= size => Some(
+                Cursor {
+                    source,
+                    label: hash_label(label, None),
+                    after: last.id(),
+                }
+                .encode(),
+            ),
+            _ => None,
+        })
+    }
+
+    fn encode(&self) -> String {
+        let mut bytes = [0u8; CURSOR_LENGTH];
+        bytes[0] = self.source as u8;
+        bytes[1..5].copy_from_slice(&self.label);
+        bytes[5..21].copy_from_slice(&self.after.to_be_bytes());
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn decode(cursor: &str) -> Result<Cursor, GraphError> {
+        let invalid = || GraphError::InvalidCursor(format!("`{cursor}` is not a cursor"));
+        if cursor.len() != CURSOR_LENGTH * 2 || !cursor.is_ascii() {
+            return Err(invalid());
+        }
+        let mut bytes = [0u8; CURSOR_LENGTH];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&cursor[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(Cursor {
+            source: CursorSource::from_byte(bytes[0]).ok_or_else(invalid)?,
+            label: bytes[1..5].try_into().unwrap(),
+            after: u128::from_be_bytes(bytes[5..21].try_into().unwrap()),
+        })
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     helix_engine::{
+        deadline::check_deadline,
         storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
         traversal_core::{traversal_iter::RoTraversalIterator, traversal_value::TraversalValue},
         types::GraphError,
@@ -247,6 +248,9 @@ where
         }
 
         while let Some(current_id) = queue.pop_front() {
+            if let Err(e) = check_deadline() {
+                return Some(Err(e));
+            }
             let out_prefix = self.edge_label.map_or_else(
                 || current_id.to_be_bytes().to_vec(),
                 |label| {
@@ -305,6 +309,9 @@ where
             distance: current_dist,
         }) = heap.pop()
         {
+            if let Err(e) = check_deadline() {
+                return Some(Err(e));
+            }
             // Already found a better path
             if let Some(&best_dist) = distances.get(&current_id)
                 && current_dist > best_dist
@@ -423,6 +430,9 @@ where
             ..
         }) = heap.pop()
         {
+            if let Err(e) = check_deadline() {
+                return Some(Err(e));
+            }
             // Found the target
             if current_id == to {
                 return Some(self.reconstruct_path(&parent, &from, &to, self.arena));
@@ -0,0 +1,169 @@
+use crate::{
+    helix_engine::{
+        deadline::check_deadline,
+        storage_core::{HelixGraphStorage, storage_methods::StorageMethods},
+        traversal_core::{
+            traversal_iter::{RoTraversalIterator, RwTraversalIterator},
+            traversal_value::TraversalValue,
+        },
+        types::GraphError,
+    },
+    utils::{label_hash::hash_label, subgraph::Subgraph},
+};
+use heed3::{RoTxn, types::Bytes};
+use std::collections::{HashSet, VecDeque};
+
+pub trait SubgraphAdapter<'arena>: Iterator {
+    /// Returns the neighbourhood of the nodes in the traversal as a deduplicated set of nodes and
+    /// the edges between them.
+    ///
+    /// Edges are followed in both directions for up to `hops` hops from the seed nodes. Only
+    /// `edge_labels` edges are followed and returned, unless it is empty in which case every
+    /// label is. Once `max_nodes` nodes have been reached no more are added, with the nodes
+    /// closest to the seeds kept first.
+    fn ego_graph(
+        self,
+        edge_labels: &[&str],
+        hops: usize,
+        max_nodes: Option<usize>,
+    ) -> Result<Subgraph<'arena>, GraphError>;
+}
+
+fn ego_graph<'db, 'arena>(
+    storage: &'db HelixGraphStorage,
+    txn: &RoTxn<'db>,
+    arena: &'arena bumpalo::Bump,
+    iter: impl Iterator<Item = Result<TraversalValue<'arena>, GraphError>>,
+    edge_labels: &[&str],
+    hops: usize,
+    max_nodes: Option<usize>,
+) -> Result<Subgraph<'arena>, GraphError> {
+    let mut label_hashes = edge_labels
+        .iter()
+        .map(|label| hash_label(label, None))
+        .collect::<Vec<_>>();
+    label_hashes.sort_unstable();
+    label_hashes.dedup();
+    let max_nodes = max_nodes.unwrap_or(usize::MAX);
+
+    let mut subgraph = Subgraph::default();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    for item in iter {
+        if visited.len() >= max_nodes {
+            break;
+        }
+        let node = match item? {
+            TraversalValue::Node(node) => node,
+            TraversalValue::NodeWithScore { node, .. } => node,
+            _ => continue,
+        };
+        if visited.insert(node.id) {
+            queue.push_back((node.id, 0));
+            subgraph.nodes.push(node);
+        }
+    }
+
+    'bfs: while let Some((node_id, depth)) = queue.pop_front() {
+        check_deadline()?;
+        if depth >= hops {
+            continue;
+        }
+        for db in [&storage.out_edges_db, &storage.in_edges_db] {
+            for (_, other) in adjacent(db, txn, &node_id, &label_hashes)? {
+                if visited.len() >= max_nodes {
+                    break 'bfs;
+                }
+                if visited.insert(other) {
+                    subgraph.nodes.push(storage.get_node(txn, &other, arena)?);
+                    queue.push_back((other, depth + 1));
+                }
+            }
+        }
+    }
+
+    // every edge is in the out list of its source node, so scanning those finds each edge once
+    let mut edge_ids = HashSet::new();
+    for node in &subgraph.nodes {
+        for (edge_id, to_node) in adjacent(&storage.out_edges_db, txn, &node.id, &label_hashes)? {
+            if visited.contains(&to_node) && edge_ids.insert(edge_id) {
+                subgraph.edges.push(storage.get_edge(txn, &edge_id, arena)?);
+            }
+        }
+    }
+
+    Ok(subgraph)
+}
+
+/// (edge id, other node id) pairs from one of the adjacency databases of `node_id`.
+///
+/// Every label is read when `label_hashes` is empty.
+fn adjacent(
+    db: &heed3::Database<Bytes, Bytes>,
+    txn: &RoTxn,
+    node_id: &u128,
+    label_hashes: &[[u8; 4]],
+) -> Result<Vec<(u128, u128)>, GraphError> {
+    let mut pairs = Vec::new();
+    if label_hashes.is_empty() {
+        for item in db.prefix_iter(txn, &node_id.to_be_bytes())? {
+            let (_, value) = item?;
+            pairs.push(HelixGraphStorage::unpack_adj_edge_data(value)?);
+        }
+        return Ok(pairs);
+    }
+    for label_hash in label_hashes {
+        // out and in adjacency keys share the same layout
+        let key = HelixGraphStorage::out_edge_key(node_id, label_hash);
+        if let Some(iter) = db.get_duplicates(txn, &key)? {
+            for item in iter {
+                let (_, value) = item?;
+                pairs.push(HelixGraphStorage::unpack_adj_edge_data(value)?);
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+impl<'db, 'arena: 'txn, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    SubgraphAdapter<'arena> for RoTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn ego_graph(
+        self,
+        edge_labels: &[&str],
+        hops: usize,
+        max_nodes: Option<usize>,
+    ) -> Result<Subgraph<'arena>, GraphError> {
+        ego_graph(
+            self.storage,
+            self.txn,
+            self.arena,
+            self.inner,
+            edge_labels,
+            hops,
+            max_nodes,
+        )
+    }
+}
+
+impl<'db, 'arena: 'txn, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphError>>>
+    SubgraphAdapter<'arena> for RwTraversalIterator<'db, 'arena, 'txn, I>
+{
+    fn ego_graph(
+        self,
+        edge_labels: &[&str],
+        hops: usize,
+        max_nodes: Option<usize>,
+    ) -> Result<Subgraph<'arena>, GraphError> {
+        ego_graph(
+            self.storage,
+            self.txn,
+            self.arena,
+            self.inner,
+            edge_labels,
+            hops,
+            max_nodes,
+        )
+    }
+}
@@ -3,6 +3,7 @@ use itertools::Itertools;
 
 use crate::{
     helix_engine::{
+        storage_core::change_log::{ChangeOp, EdgeItem, ItemKind},
         traversal_core::{traversal_iter::RwTraversalIterator, traversal_value::TraversalValue},
         types::GraphError,
     },
@@ -65,7 +66,7 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                         continue;
                                     };
 
-                                    match bincode::serialize(v) {
+                                    match self.storage.index_key(k, v) {
                                         Ok(v_serialized) => {
                                             if let Err(e) = db.put_with_flags(
                                                 self.txn,
@@ -76,7 +77,7 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                                 results.push(Err(GraphError::from(e)));
                                             }
                                         }
-                                        Err(e) => results.push(Err(GraphError::from(e))),
+                                        Err(e) => results.push(Err(e)),
                                     }
                                 }
 
@@ -100,7 +101,7 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                         continue;
                                     };
 
-                                    match bincode::serialize(old_value) {
+                                    match self.storage.index_key(k, old_value) {
                                         Ok(old_serialized) => {
                                             if let Err(e) = db.delete_one_duplicate(
                                                 self.txn,
@@ -112,13 +113,13 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                             }
                                         }
                                         Err(e) => {
-                                            results.push(Err(GraphError::from(e)));
+                                            results.push(Err(e));
                                             continue;
                                         }
                                     }
 
                                     // create new secondary indexes for the props changed
-                                    match bincode::serialize(v) {
+                                    match self.storage.index_key(k, v) {
                                         Ok(v_serialized) => {
                                             if let Err(e) = db.put_with_flags(
                                                 self.txn,
@@ -129,7 +130,7 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                                 results.push(Err(GraphError::from(e)));
                                             }
                                         }
-                                        Err(e) => results.push(Err(GraphError::from(e))),
+                                        Err(e) => results.push(Err(e)),
                                     }
                                 }
 
@@ -171,7 +172,18 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                     &node.id,
                                     &serialized_node,
                                 ) {
-                                    Ok(_) => results.push(Ok(TraversalValue::Node(node))),
+                                    Ok(_) => results.push(
+                                        self.storage
+                                            .record_write(
+                                                self.txn,
+                                                ChangeOp::Update,
+                                                ItemKind::Node,
+                                                node.id,
+                                                node.label,
+                                                &node,
+                                            )
+                                            .map(|_| TraversalValue::Node(node)),
+                                    ),
                                     Err(e) => results.push(Err(GraphError::from(e))),
                                 }
                             }
@@ -229,7 +241,18 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
                                     &edge.id,
                                     &serialized_edge,
                                 ) {
-                                    Ok(_) => results.push(Ok(TraversalValue::Edge(edge))),
+                                    Ok(_) => results.push(
+                                        self.storage
+                                            .record_write(
+                                                self.txn,
+                                                ChangeOp::Update,
+                                                ItemKind::Edge,
+                                                edge.id,
+                                                edge.label,
+                                                &EdgeItem::from(&edge),
+                                            )
+                                            .map(|_| TraversalValue::Edge(edge)),
+                                    ),
                                     Err(e) => results.push(Err(GraphError::from(e))),
                                 }
                             }
@@ -1,5 +1,6 @@
 use crate::{
     helix_engine::{
+        storage_core::change_log::{ChangeOp, ItemKind},
         traversal_core::{traversal_iter::RwTraversalIterator, traversal_value::TraversalValue},
         types::GraphError,
         vector_core::{hnsw::HNSW, vector::HVector},
@@ -49,7 +50,17 @@ impl<'db, 'arena, 'txn, I: Iterator<Item = Result<TraversalValue<'arena>, GraphE
             .insert::<F>(self.txn, label, query, properties, self.arena);
 
         let result = match vector {
-            Ok(vector) => Ok(TraversalValue::Vector(vector)),
+            Ok(vector) => self
+                .storage
+                .record_write(
+                    self.txn,
+                    ChangeOp::Create,
+                    ItemKind::Vector,
+                    vector.id,
+                    vector.label,
+                    &vector,
+                )
+                .map(|_| TraversalValue::Vector(vector)),
             Err(e) => Err(GraphError::from(e)),
         };
 
@@ -39,6 +39,7 @@ impl<'arena> TraversalValue<'arena> {
             TraversalValue::Edge(edge) => edge.id,
             TraversalValue::Vector(vector) => vector.id,
             TraversalValue::VectorNodeWithoutVectorData(vector) => vector.id,
+            TraversalValue::NodeWithScore { node, .. } => node.id,
             TraversalValue::Empty => 0,
             _ => 0,
         }
@@ -50,6 +51,7 @@ impl<'arena> TraversalValue<'arena> {
             TraversalValue::Edge(edge) => edge.label,
             TraversalValue::Vector(vector) => vector.label,
             TraversalValue::VectorNodeWithoutVectorData(vector) => vector.label,
+            TraversalValue::NodeWithScore { node, .. } => node.label,
             TraversalValue::Empty => "",
             _ => "",
         }
@@ -79,6 +81,9 @@ impl<'arena> TraversalValue<'arena> {
     pub fn score(&self) -> f64 {
         match self {
             TraversalValue::Vector(vector) => vector.score(),
+            TraversalValue::NodeWithScore { score, .. } => *score,
+            // nodes that have not been scored by a search or centrality step
+            TraversalValue::Node(_) => 0.0,
             _ => unimplemented!(),
         }
     }
@@ -89,6 +94,7 @@ impl<'arena> TraversalValue<'arena> {
             TraversalValue::Edge(edge) => edge.label,
             TraversalValue::Vector(vector) => vector.label,
             TraversalValue::VectorNodeWithoutVectorData(vector) => vector.label,
+            TraversalValue::NodeWithScore { node, .. } => node.label,
             TraversalValue::Empty => "",
             _ => "",
         }
@@ -100,6 +106,7 @@ impl<'arena> TraversalValue<'arena> {
             TraversalValue::Edge(edge) => edge.get_property(property),
             TraversalValue::Vector(vector) => vector.get_property(property),
             TraversalValue::VectorNodeWithoutVectorData(vector) => vector.get_property(property),
+            TraversalValue::NodeWithScore { node, .. } => node.get_property(property),
             TraversalValue::Empty => None,
             _ => None,
         }
@@ -113,6 +120,7 @@ impl Hash for TraversalValue<'_> {
             TraversalValue::Edge(edge) => edge.id.hash(state),
             TraversalValue::Vector(vector) => vector.id.hash(state),
             TraversalValue::VectorNodeWithoutVectorData(vector) => vector.id.hash(state),
+            TraversalValue::NodeWithScore { node, .. } => node.id.hash(state),
             TraversalValue::Empty => state.write_u8(0),
             _ => state.write_u8(0),
         }
@@ -140,6 +148,10 @@ impl PartialEq for TraversalValue<'_> {
                 TraversalValue::VectorNodeWithoutVectorData(vector1),
                 TraversalValue::Vector(vector2),
             ) => vector1.id() == vector2.id(),
+            (
+                TraversalValue::NodeWithScore { node: node1, .. },
+                TraversalValue::NodeWithScore { node: node2, .. },
+            ) => node1.id == node2.id,
             (TraversalValue::Empty, TraversalValue::Empty) => true,
             _ => false,
         }
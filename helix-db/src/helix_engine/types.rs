@@ -30,6 +30,10 @@ pub enum GraphError {
     ParamNotFound(&'static str),
     IoNeeded(IoContFn),
     RerankerError(String),
+    CycleDetected(String),
+    CardinalityViolation(String),
+    QueryTimeout,
+    InvalidCursor(String),
 }
 
 impl std::error::Error for GraphError {}
@@ -67,6 +71,10 @@ impl fmt::Display for GraphError {
                 write!(f, "Asyncronous IO is needed to complete the DB operation")
             }
             GraphError::RerankerError(msg) => write!(f, "Reranker error: {msg}"),
+            GraphError::CycleDetected(msg) => write!(f, "Cycle detected: {msg}"),
+            GraphError::CardinalityViolation(msg) => write!(f, "Cardinality violation: {msg}"),
+            GraphError::QueryTimeout => write!(f, "Query exceeded its deadline and was cancelled"),
+            GraphError::InvalidCursor(msg) => write!(f, "Invalid cursor: {msg}"),
         }
     }
 }
@@ -139,7 +147,10 @@ impl From<uuid::Error> for GraphError {
 
 impl From<VectorError> for GraphError {
     fn from(error: VectorError) -> Self {
-        GraphError::VectorError(format!("VectorError: {error}"))
+        match error {
+            VectorError::QueryTimeout => GraphError::QueryTimeout,
+            error => GraphError::VectorError(format!("VectorError: {error}")),
+        }
     }
 }
 
@@ -153,6 +164,7 @@ pub enum VectorError {
     ConversionError(String),
     VectorCoreError(String),
     VectorAlreadyDeleted(String),
+    QueryTimeout,
 }
 
 impl std::error::Error for VectorError {}
@@ -168,6 +180,7 @@ impl fmt::Display for VectorError {
             VectorError::ConversionError(msg) => write!(f, "Conversion error: {msg}"),
             VectorError::VectorCoreError(msg) => write!(f, "Vector core error: {msg}"),
             VectorError::VectorAlreadyDeleted(id) => write!(f, "Vector already deleted: {id}"),
+            VectorError::QueryTimeout => write!(f, "Query exceeded its deadline and was cancelled"),
         }
     }
 }
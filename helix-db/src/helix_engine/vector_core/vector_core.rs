@@ -2,6 +2,7 @@ use super::binary_heap::BinaryHeap;
 use crate::{
     debug_println,
     helix_engine::{
+        deadline::deadline_exceeded,
         types::VectorError,
         vector_core::{
             hnsw::HNSW,
@@ -341,6 +342,10 @@ impl VectorCore {
         visited.insert(entry_point.id);
 
         while let Some(curr_cand) = candidates.pop() {
+            if deadline_exceeded() {
+                return Err(VectorError::QueryTimeout);
+            }
+
             if results.len() >= ef
                 && results
                     .get_max()
@@ -0,0 +1,286 @@
+//! API key authentication for the gateway.
+//!
+//! Keys are read at startup from two places:
+//! - `HELIX_API_KEYS`: comma separated plaintext keys, each optionally followed by
+//!   `:read` or `:write` and a `|` separated list of the queries it may run,
+//!   e.g. `k1,k2:read,k3:write:get_user|add_user`
+//! - `HELIX_API_KEYS_FILE`: path to a JSON array of
+//!   `{ "key_hash": "<sha256 hex>", "access": "read", "queries": ["get_user"] }`
+//!
+//! Only SHA-256 hashes of the keys are kept. When no keys are configured every
+//! request is let through.
+//!
+//! A `/batch` request only needs a valid key to reach its handler, which then checks the
+//! query of every step against the key's policy before any of them runs, see [`BatchAuth`].
+//! The routes that aren't queries, e.g. `/changes`, `/stats`, `/metrics` and `/introspect`,
+//! read across the whole graph, so they need an unrestricted key: `write` with no query list.
+
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+};
+
+use axum::{
+    extract::{Request, State},
+    http::Method,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    helix_engine::types::GraphError,
+    helix_gateway::batch::{BATCH_ROUTE, batch_queries},
+    protocol::{self, HelixError},
+};
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+pub const API_KEYS_ENV: &str = "HELIX_API_KEYS";
+pub const API_KEYS_FILE_ENV: &str = "HELIX_API_KEYS_FILE";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAccess {
+    /// May only run queries that don't write to the graph
+    Read,
+    /// May run any query
+    Write,
+}
+
+impl FromStr for KeyAccess {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(KeyAccess::Read),
+            "write" => Ok(KeyAccess::Write),
+            _ => Err(GraphError::New(format!(
+                "invalid API key access `{s}`, expected `read` or `write`"
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyPolicy {
+    pub access: KeyAccess,
+    /// The queries this key may run, `None` allows all of them
+    pub queries: Option<HashSet<String>>,
+}
+
+impl KeyPolicy {
+    /// Checks this key may run the query `name`, which writes to the graph if `is_mut`
+    pub fn allows(&self, name: &str, is_mut: bool) -> Result<(), HelixError> {
+        let forbidden = || HelixError::Forbidden {
+            name: name.to_string(),
+        };
+        if is_mut && self.access == KeyAccess::Read {
+            return Err(forbidden());
+        }
+        match &self.queries {
+            Some(queries) if !queries.contains(name) => Err(forbidden()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl KeyPolicy {
+    /// Whether this key may run every query, which the routes that aren't queries need
+    pub fn is_unrestricted(&self) -> bool {
+        self.access == KeyAccess::Write && self.queries.is_none()
+    }
+}
+
+impl Default for KeyPolicy {
+    fn default() -> Self {
+        Self {
+            access: KeyAccess::Write,
+            queries: None,
+        }
+    }
+}
+
+/// What a request asks to run, see [`ApiKeys::authorize`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route<'a> {
+    /// A query or MCP tool, given as its name and whether it writes to the graph
+    Query(&'a str, bool),
+    /// A `/batch`, whose steps are checked by its handler, see [`BatchAuth`]
+    Batch,
+    /// Any other route, given as its path
+    Other(&'a str),
+}
+
+#[derive(Debug, Deserialize)]
+struct HashedKey {
+    key_hash: String,
+    access: KeyAccess,
+    queries: Option<Vec<String>>,
+}
+
+/// SHA-256 of the key as lowercase hex
+pub fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ApiKeys {
+    /// Key hash => Policy
+    keys: HashMap<String, KeyPolicy>,
+}
+
+impl ApiKeys {
+    /// Loads the keys named by `HELIX_API_KEYS` and `HELIX_API_KEYS_FILE`
+    pub fn from_env() -> Result<Self, GraphError> {
+        let mut keys = match std::env::var(API_KEYS_ENV) {
+            Ok(value) => Self::parse(&value)?,
+            Err(_) => Self::default(),
+        };
+        if let Ok(path) = std::env::var(API_KEYS_FILE_ENV) {
+            let contents = std::fs::read_to_string(path)?;
+            keys.extend_from_json(&contents)?;
+        }
+        Ok(keys)
+    }
+
+    /// Parses the `HELIX_API_KEYS` format
+    pub fn parse(value: &str) -> Result<Self, GraphError> {
+        let mut keys = Self::default();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let mut parts = entry.splitn(3, ':');
+            let key = parts.next().unwrap_or_default();
+            let access = match parts.next() {
+                Some(access) => access.parse()?,
+                None => KeyAccess::Write,
+            };
+            let queries = parts
+                .next()
+                .map(|qs| qs.split('|').map(|q| q.trim().to_string()).collect());
+            keys.insert_key(key, KeyPolicy { access, queries });
+        }
+        Ok(keys)
+    }
+
+    /// Adds the hashed keys from a JSON keys file
+    pub fn extend_from_json(&mut self, json: &str) -> Result<(), GraphError> {
+        for entry in sonic_rs::from_str::<Vec<HashedKey>>(json)? {
+            let policy = KeyPolicy {
+                access: entry.access,
+                queries: entry.queries.map(|qs| qs.into_iter().collect()),
+            };
+            self.insert_hash(&entry.key_hash, policy);
+        }
+        Ok(())
+    }
+
+    pub fn insert_key(&mut self, key: &str, policy: KeyPolicy) {
+        self.keys.insert(hash_key(key), policy);
+    }
+
+    pub fn insert_hash(&mut self, key_hash: &str, policy: KeyPolicy) {
+        self.keys.insert(key_hash.trim().to_ascii_lowercase(), policy);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Checks `key` may run `route` and returns the policy of the key
+    pub fn authorize(&self, key: Option<&str>, route: Route) -> Result<&KeyPolicy, HelixError> {
+        let policy = key
+            .and_then(|k| self.keys.get(&hash_key(k)))
+            .ok_or(HelixError::Unauthorized)?;
+
+        match route {
+            Route::Query(name, is_mut) => policy.allows(name, is_mut)?,
+            Route::Batch => {}
+            Route::Other(path) if !policy.is_unrestricted() => {
+                return Err(HelixError::Forbidden {
+                    name: path.to_string(),
+                });
+            }
+            Route::Other(_) => {}
+        }
+        Ok(policy)
+    }
+}
+
+/// State for [`require_api_key`]
+pub struct ApiKeyAuth {
+    pub keys: ApiKeys,
+    /// Names of the query and MCP routes that write to the graph
+    pub write_routes: HashSet<String>,
+}
+
+/// The policy of the key a `/batch` request was made with. [`require_api_key`] adds it to the
+/// request so the batch handler can check every step before the batch runs.
+#[derive(Clone)]
+pub struct BatchAuth {
+    pub policy: KeyPolicy,
+    auth: Arc<ApiKeyAuth>,
+}
+
+impl BatchAuth {
+    /// Checks the key may run the query of every step of the batch `request`
+    pub fn authorize_steps(&self, request: &protocol::Request) -> Result<(), HelixError> {
+        for (step, query) in batch_queries(request)?.into_iter().enumerate() {
+            let is_mut = self.auth.write_routes.contains(&query);
+            if let Err(e) = self.policy.allows(&query, is_mut) {
+                return Err(HelixError::BatchStep {
+                    step,
+                    query,
+                    source: Box::new(e),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects requests without a key that is allowed to run the requested route
+pub async fn require_api_key(
+    State(auth): State<Arc<ApiKeyAuth>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let key = req
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok());
+
+    let path = req.uri().path();
+    let is_batch = req.method() == Method::POST && path.strip_prefix('/') == Some(BATCH_ROUTE);
+    // queries and MCP tools are the POST routes, see `protocol::request::Request`
+    let route = match req.method() {
+        _ if is_batch => Route::Batch,
+        &Method::POST => {
+            let name = path
+                .strip_prefix("/mcp/")
+                .or_else(|| path.strip_prefix('/'))
+                .unwrap_or(path);
+            Route::Query(name, auth.write_routes.contains(name))
+        }
+        _ => Route::Other(path),
+    };
+
+    let batch_policy = match auth.keys.authorize(key, route) {
+        Ok(policy) => is_batch.then(|| policy.clone()),
+        Err(e) => return e.into_response(),
+    };
+    if let Some(policy) = batch_policy {
+        req.extensions_mut().insert(BatchAuth {
+            policy,
+            auth: Arc::clone(&auth),
+        });
+    }
+    next.run(req).await
+}
@@ -0,0 +1,180 @@
+//! Runs several queries atomically through `POST /batch`.
+//!
+//! The body is an ordered list of steps, `[{ "query": "add_user", "params": {...} }, ...]`.
+//! Every step runs inside the same write transaction, which is committed once all of them
+//! succeed and rolled back as soon as one fails. The response is the list of step outputs.
+//!
+//! A parameter can reference the output of an earlier step with
+//! `{ "$ref": "<step index>.<path>" }`, e.g. `{ "$ref": "0.user.id" }` is the `id` of the
+//! `user` returned by the first step. Array elements are addressed by their index.
+
+use std::{collections::HashMap, sync::Arc};
+
+use heed3::RwTxn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    helix_engine::{deadline::check_deadline, storage_core::HelixGraphStorage, types::GraphError},
+    protocol::{HelixError, Request, Response, request::RequestType},
+};
+
+/// The route batches are posted to, which shadows any query with the same name
+pub const BATCH_ROUTE: &str = "batch";
+
+/// Key of the object that references the output of an earlier step
+pub const REF_KEY: &str = "$ref";
+
+pub struct BatchInput<'a, 'db> {
+    pub db: &'db HelixGraphStorage,
+    /// The transaction shared by every step of the batch
+    pub txn: &'a mut RwTxn<'db>,
+    pub params: &'a Value,
+}
+
+// basic type for function pointer
+pub type BasicBatchHandlerFn = for<'a, 'db> fn(BatchInput<'a, 'db>) -> Result<Value, GraphError>;
+
+// thread safe type for multi threaded use
+pub type BatchHandlerFn =
+    Arc<dyn for<'a, 'db> Fn(BatchInput<'a, 'db>) -> Result<Value, GraphError> + Send + Sync>;
+
+#[derive(Clone, Debug)]
+pub struct BatchHandlerSubmission(pub BatchHandler);
+
+#[derive(Clone, Debug)]
+pub struct BatchHandler {
+    /// Name of the query this runs
+    pub name: &'static str,
+    pub func: BasicBatchHandlerFn,
+}
+
+impl BatchHandler {
+    pub const fn new(name: &'static str, func: BasicBatchHandlerFn) -> Self {
+        Self { name, func }
+    }
+}
+
+inventory::collect!(BatchHandlerSubmission);
+
+#[derive(Debug, Deserialize)]
+pub struct BatchStep {
+    pub query: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// The queries run by the steps of a batch request, in order
+pub fn batch_queries(request: &Request) -> Result<Vec<String>, HelixError> {
+    #[derive(Deserialize)]
+    struct StepQuery {
+        query: String,
+    }
+    let steps: Vec<StepQuery> = request.in_fmt.deserialize_owned(&request.body)?;
+    Ok(steps.into_iter().map(|step| step.query).collect())
+}
+
+/// Deserializes the parameters of a step into the input struct of its query
+pub fn batch_params<'a, T: Deserialize<'a>>(params: &'a Value) -> Result<T, GraphError> {
+    T::deserialize(params).map_err(|e| GraphError::DecodeError(e.to_string()))
+}
+
+/// Converts the response of a step into the value later steps can reference
+pub fn batch_output<T: Serialize>(response: &T) -> Result<Value, GraphError> {
+    serde_json::to_value(response).map_err(|e| GraphError::ConversionError(e.to_string()))
+}
+
+/// Replaces every `{ "$ref": ... }` in `params` with the value it points to in `outputs`
+pub fn resolve_refs(params: &mut Value, outputs: &[Value]) -> Result<(), GraphError> {
+    match params {
+        Value::Object(map) if map.len() == 1 && map.contains_key(REF_KEY) => {
+            let path = map[REF_KEY].as_str().ok_or_else(|| {
+                GraphError::DecodeError(format!("{REF_KEY} must be a string"))
+            })?;
+            *params = lookup_ref(path, outputs)?.clone();
+        }
+        Value::Object(map) => {
+            for value in map.values_mut() {
+                resolve_refs(value, outputs)?;
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                resolve_refs(value, outputs)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn lookup_ref<'a>(path: &str, outputs: &'a [Value]) -> Result<&'a Value, GraphError> {
+    let invalid =
+        |reason: &str| GraphError::DecodeError(format!("invalid {REF_KEY} `{path}`: {reason}"));
+
+    let mut segments = path.split('.');
+    let mut value = segments
+        .next()
+        .and_then(|step| step.parse::<usize>().ok())
+        .and_then(|step| outputs.get(step))
+        .ok_or_else(|| invalid("must start with the index of an earlier step"))?;
+
+    for segment in segments {
+        value = match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(values) => segment.parse::<usize>().ok().and_then(|i| values.get(i)),
+            _ => None,
+        }
+        .ok_or_else(|| invalid(&format!("`{segment}` not found")))?;
+    }
+    Ok(value)
+}
+
+/// Runs the steps of a batch request in one transaction, see the module docs
+pub fn run_batch(
+    request: &Request,
+    storage: &HelixGraphStorage,
+    routes: &HashMap<String, BatchHandlerFn>,
+) -> Result<Response, HelixError> {
+    let steps: Vec<BatchStep> = request.in_fmt.deserialize_owned(&request.body)?;
+
+    let mut txn = storage.graph_env.write_txn().map_err(GraphError::from)?;
+    let mut outputs = Vec::with_capacity(steps.len());
+    for (step, BatchStep { query, params }) in steps.into_iter().enumerate() {
+        // returning early drops the transaction, which aborts every earlier step
+        match run_step(storage, &mut txn, routes, &query, params, &outputs) {
+            Ok(output) => outputs.push(output),
+            Err(e) => {
+                return Err(HelixError::BatchStep {
+                    step,
+                    query,
+                    source: Box::new(e),
+                });
+            }
+        }
+    }
+    txn.commit().map_err(GraphError::from)?;
+
+    Ok(request.out_fmt.create_response(&outputs))
+}
+
+fn run_step<'db>(
+    storage: &'db HelixGraphStorage,
+    txn: &mut RwTxn<'db>,
+    routes: &HashMap<String, BatchHandlerFn>,
+    query: &str,
+    mut params: Value,
+    outputs: &[Value],
+) -> Result<Value, HelixError> {
+    check_deadline()?;
+    let handler = routes.get(query).ok_or_else(|| HelixError::NotFound {
+        ty: RequestType::Query,
+        name: query.to_string(),
+    })?;
+    resolve_refs(&mut params, outputs)?;
+    Ok(handler(BatchInput {
+        db: storage,
+        txn,
+        params: &params,
+    })?)
+}
@@ -13,8 +13,8 @@ use crate::helix_engine::vector_core::hnsw::HNSW;
 use crate::helix_gateway::gateway::AppState;
 use crate::helix_gateway::router::router::{Handler, HandlerInput, HandlerSubmission};
 use crate::protocol::{self, request::RequestType};
-use crate::utils::id::ID;
 use crate::utils::items::{Edge, Node};
+use crate::utils::subgraph::{graph_edge_json, graph_node_json};
 use heed3::RoTxn;
 
 // get top nodes by cardinality (with limit, max 300):
@@ -45,6 +45,7 @@ pub async fn nodes_edges_handler(
         body: axum::body::Bytes::new(),
         in_fmt: protocol::Format::default(),
         out_fmt: protocol::Format::default(),
+        stream: false,
     };
 
     if let Ok(params_json) = sonic_rs::to_vec(&json!({
@@ -121,6 +122,7 @@ pub fn nodes_edges_inner(input: HandlerInput) -> Result<protocol::Response, Grap
     Ok(protocol::Response {
         body: combined.into_bytes(),
         fmt: Default::default(),
+        stream: None,
     })
 }
 
@@ -136,12 +138,7 @@ fn get_all_nodes_edges_json(
     let node_iter = db.nodes_db.iter(txn)?;
     for result in node_iter {
         let (id, value) = result?;
-        let id_str = ID::from(id).stringify();
-
-        let mut json_node = json!({
-            "id": id_str.clone(),
-            "title": id_str.clone()
-        });
+        let mut json_node = graph_node_json(id);
 
         if let Some(prop) = &node_label {
             let node = Node::decode_node(value, id)?;
@@ -162,14 +159,7 @@ fn get_all_nodes_edges_json(
     for result in edge_iter {
         let (id, value) = result?;
         let edge = Edge::decode_edge(value, id)?;
-        let id_str = ID::from(id).stringify();
-
-        edges.push(json!({
-            "from": ID::from(edge.from_node).stringify(),
-            "to": ID::from(edge.to_node).stringify(),
-            "title": id_str.clone(),
-            "id": id_str
-        }));
+        edges.push(graph_edge_json(id, edge.from_node, edge.to_node));
     }
 
     let result = json!({
@@ -232,6 +222,7 @@ mod tests {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -279,6 +270,7 @@ mod tests {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -327,6 +319,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -362,6 +355,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -383,6 +377,7 @@ mod tests {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -32,6 +32,7 @@ pub async fn node_details_handler(
         body: axum::body::Bytes::new(),
         in_fmt: protocol::Format::default(),
         out_fmt: protocol::Format::default(),
+        stream: false,
     };
 
     if let Ok(params_json) = sonic_rs::to_vec(&json!({
@@ -114,6 +115,7 @@ pub fn node_details_inner(input: HandlerInput) -> Result<protocol::Response, Gra
     Ok(protocol::Response {
         body: sonic_rs::to_vec(&result).map_err(|e| GraphError::New(e.to_string()))?,
         fmt: Default::default(),
+        stream: None,
     })
 }
 
@@ -179,6 +181,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -208,6 +211,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -236,6 +240,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -258,6 +263,7 @@ mod tests {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -293,6 +299,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
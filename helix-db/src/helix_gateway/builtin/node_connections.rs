@@ -35,6 +35,7 @@ pub async fn node_connections_handler(
         body: axum::body::Bytes::new(),
         in_fmt: protocol::Format::default(),
         out_fmt: protocol::Format::default(),
+        stream: false,
     };
 
     if let Ok(params_json) = sonic_rs::to_vec(&json!({
@@ -196,6 +197,7 @@ pub fn node_connections_inner(input: HandlerInput) -> Result<protocol::Response,
     Ok(protocol::Response {
         body: sonic_rs::to_vec(&result).map_err(|e| GraphError::New(e.to_string()))?,
         fmt: Default::default(),
+        stream: None,
     })
 }
 
@@ -272,6 +274,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -317,6 +320,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -353,6 +357,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -383,6 +388,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -405,6 +411,7 @@ mod tests {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -33,6 +33,7 @@ pub async fn nodes_by_label_handler(
         body: axum::body::Bytes::new(),
         in_fmt: protocol::Format::default(),
         out_fmt: protocol::Format::default(),
+        stream: false,
     };
 
     if let Ok(params_json) = sonic_rs::to_vec(&json!({
@@ -124,6 +125,7 @@ pub fn nodes_by_label_inner(input: HandlerInput) -> Result<protocol::Response, G
     Ok(protocol::Response {
         body: sonic_rs::to_vec(&result).map_err(|e| GraphError::New(e.to_string()))?,
         fmt: Default::default(),
+        stream: None,
     })
 }
 
@@ -190,6 +192,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -227,6 +230,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -255,6 +259,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -281,6 +286,7 @@ mod tests {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
@@ -316,6 +322,7 @@ mod tests {
             body: Bytes::from(params_json),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let input = HandlerInput {
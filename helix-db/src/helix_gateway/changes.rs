@@ -0,0 +1,208 @@
+//! Streams the change log over server-sent events through `GET /changes`.
+//!
+//! Each change is sent as a `change` event whose id is its sequence number, so a client that
+//! reconnects with the standard `Last-Event-ID` header (or `?since=<seq>`) picks up right after
+//! the last change it saw. `?labels=User,Follows` only streams changes to items with one of
+//! those labels. Without either, the stream starts from the oldest change still kept. Resuming
+//! from a change that was already pruned fails with `410 Gone`, the client then has to resync
+//! and subscribe again without a position.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    convert::Infallible,
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures_util::{Stream, stream};
+use serde::Deserialize;
+
+use crate::{
+    helix_engine::{
+        storage_core::{HelixGraphStorage, change_log::ChangeEvent},
+        types::GraphError,
+    },
+    protocol::HelixError,
+};
+
+/// The route changes are streamed from
+pub const CHANGES_ROUTE: &str = "/changes";
+
+/// Header SSE clients send on reconnect with the id of the last event they received
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// How long a subscription waits before checking for new changes once it is caught up
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Most changes read from the log at once
+const READ_LIMIT: usize = 256;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ChangesParams {
+    /// Sequence number of the last change already seen, the oldest change kept is streamed
+    /// first without it
+    pub since: Option<u64>,
+    /// Comma separated labels to stream the changes of
+    pub labels: Option<String>,
+}
+
+/// Reads up to `limit` changes after `since`, failing if some of them were already pruned.
+///
+/// Without `since` the changes are read from the oldest one kept, which never fails.
+pub fn read_changes(
+    storage: &HelixGraphStorage,
+    since: Option<u64>,
+    limit: usize,
+) -> Result<Vec<ChangeEvent>, HelixError> {
+    let change_log = storage
+        .change_log
+        .as_ref()
+        .ok_or(HelixError::ChangeLogDisabled)?;
+    let txn = storage.graph_env.read_txn().map_err(GraphError::from)?;
+
+    let oldest = match change_log.first_seq(&txn)? {
+        Some(seq) => seq,
+        None => change_log.last_seq(&txn)? + 1,
+    };
+    let since = match since {
+        Some(since) if oldest > since.saturating_add(1) => {
+            return Err(HelixError::ChangesExpired { since, oldest });
+        }
+        Some(since) => since,
+        None => oldest - 1,
+    };
+    Ok(change_log.read(&txn, since, limit)?)
+}
+
+struct Subscription {
+    storage: Arc<HelixGraphStorage>,
+    /// Sequence number of the last change read, including the ones filtered out, none until
+    /// a change is read by a subscription started without a position
+    since: Option<u64>,
+    labels: Option<HashSet<String>>,
+    pending: VecDeque<ChangeEvent>,
+    closed: bool,
+}
+
+impl Subscription {
+    fn push(&mut self, changes: Vec<ChangeEvent>) {
+        if let Some(last) = changes.last() {
+            self.since = Some(last.seq);
+        }
+        let labels = &self.labels;
+        self.pending.extend(
+            changes
+                .into_iter()
+                .filter(|c| labels.as_ref().is_none_or(|l| l.contains(&c.label))),
+        );
+    }
+
+    async fn read(&self) -> Result<Vec<ChangeEvent>, HelixError> {
+        let storage = Arc::clone(&self.storage);
+        let since = self.since;
+        tokio::task::spawn_blocking(move || read_changes(&storage, since, READ_LIMIT))
+            .await
+            .map_err(|e| GraphError::New(e.to_string()))?
+    }
+
+    /// Waits for the next change to send, or the error closing the stream
+    async fn next(mut self) -> Option<(Event, Self)> {
+        loop {
+            if let Some(change) = self.pending.pop_front() {
+                return Some((change_event(&change), self));
+            }
+            if self.closed {
+                return None;
+            }
+            match self.read().await {
+                Ok(changes) if changes.is_empty() => tokio::time::sleep(POLL_INTERVAL).await,
+                Ok(changes) => self.push(changes),
+                Err(e) => {
+                    self.closed = true;
+                    return Some((error_event(&e), self));
+                }
+            }
+        }
+    }
+
+    fn into_stream(self) -> impl Stream<Item = Result<Event, Infallible>> {
+        stream::unfold(self, |sub| async move {
+            sub.next().await.map(|(event, sub)| (Ok(event), sub))
+        })
+    }
+}
+
+fn change_event(change: &ChangeEvent) -> Event {
+    Event::default()
+        .id(change.seq.to_string())
+        .event("change")
+        .json_data(change)
+        .unwrap_or_else(|e| error_event(&HelixError::from(GraphError::New(e.to_string()))))
+}
+
+fn error_event(err: &HelixError) -> Event {
+    Event::default()
+        .event("error")
+        .json_data(err.to_body())
+        .unwrap_or_else(|_| Event::default().event("error").data(err.to_string()))
+}
+
+/// Where a subscription starts, `Last-Event-ID` wins over `?since` as it is sent on reconnect
+fn start_seq(headers: &HeaderMap, params: &ChangesParams) -> Result<Option<u64>, HelixError> {
+    match headers.get(LAST_EVENT_ID_HEADER) {
+        Some(v) => v
+            .to_str()
+            .ok()
+            .and_then(|seq| seq.trim().parse().ok())
+            .map(Some)
+            .ok_or_else(|| {
+                GraphError::DecodeError(format!("{LAST_EVENT_ID_HEADER} must be a number"))
+                    .into()
+            }),
+        None => Ok(params.since),
+    }
+}
+
+pub async fn changes_handler(
+    State(storage): State<Arc<HelixGraphStorage>>,
+    headers: HeaderMap,
+    Query(params): Query<ChangesParams>,
+) -> Response {
+    let since = match start_seq(&headers, &params) {
+        Ok(since) => since,
+        Err(e) => return e.into_response(),
+    };
+    let labels = params.labels.as_deref().map(|labels| {
+        labels
+            .split(',')
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect()
+    });
+
+    let mut subscription = Subscription {
+        storage,
+        since,
+        labels,
+        pending: VecDeque::new(),
+        closed: false,
+    };
+    // a disabled log or an expired position is reported as a plain error response
+    match subscription.read().await {
+        Ok(changes) => subscription.push(changes),
+        Err(e) => return e.into_response(),
+    }
+
+    Sse::new(subscription.into_stream())
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
@@ -1,14 +1,25 @@
 use std::sync::atomic::{self, AtomicUsize};
-use std::time::Instant;
-use std::{collections::HashMap, sync::Arc};
+use std::time::{Duration, Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use axum::body::Body;
-use axum::extract::State;
+use axum::extract::{Extension, State};
+use axum::http::HeaderMap;
 use axum::response::IntoResponse;
 use axum::routing::{get, post};
 use core_affinity::CoreId;
 use tracing::{info, trace, warn};
 
+use super::auth::{ApiKeyAuth, ApiKeys, BatchAuth, require_api_key};
+use super::batch::{BATCH_ROUTE, BatchHandlerFn};
+use super::changes::{CHANGES_ROUTE, changes_handler};
+use super::health::{
+    HEALTH_ROUTE, READY_ROUTE, STATS_ROUTE, health_handler, ready_handler, stats_handler,
+};
+use super::metrics::{METRICS_ROUTE, QueryMetrics, metrics_handler};
 use super::router::router::{HandlerFn, HelixRouter};
 #[cfg(feature = "dev-instance")]
 use crate::helix_gateway::builtin::all_nodes_and_edges::nodes_edges_handler;
@@ -20,7 +31,8 @@ use crate::helix_gateway::builtin::node_connections::node_connections_handler;
 use crate::helix_gateway::builtin::nodes_by_label::nodes_by_label_handler;
 use crate::helix_gateway::introspect_schema::introspect_schema_handler;
 use crate::helix_gateway::worker_pool::WorkerPool;
-use crate::protocol;
+use crate::helix_engine::{storage_core::HelixGraphStorage, types::GraphError};
+use crate::protocol::{self, HelixError, request::RequestType};
 use crate::{
     helix_engine::traversal_core::{HelixGraphEngine, HelixGraphEngineOpts},
     helix_gateway::mcp::mcp::MCPHandlerFn,
@@ -32,6 +44,9 @@ impl GatewayOpts {
     pub const DEFAULT_WORKERS_PER_CORE: usize = 8;
 }
 
+/// Header overriding the default query timeout of a request, in milliseconds
+pub const QUERY_TIMEOUT_HEADER: &str = "x-helix-timeout-ms";
+
 pub struct HelixGateway {
     pub(crate) address: String,
     pub(crate) workers_per_core: usize,
@@ -39,6 +54,8 @@ pub struct HelixGateway {
     pub(crate) router: Arc<HelixRouter>,
     pub(crate) opts: Option<HelixGraphEngineOpts>,
     pub(crate) cluster_id: Option<String>,
+    pub(crate) write_routes: HashSet<String>,
+    pub(crate) query_timeout: Option<Duration>,
 }
 
 impl HelixGateway {
@@ -52,6 +69,10 @@ impl HelixGateway {
     ) -> HelixGateway {
         let router = Arc::new(HelixRouter::new(routes, mcp_routes));
         let cluster_id = std::env::var("CLUSTER_ID").ok();
+        let query_timeout = std::env::var("HELIX_QUERY_TIMEOUT_MS")
+            .ok()
+            .and_then(|ms| ms.parse().ok())
+            .map(Duration::from_millis);
         HelixGateway {
             address: address.to_string(),
             graph_access,
@@ -59,9 +80,31 @@ impl HelixGateway {
             workers_per_core,
             opts,
             cluster_id,
+            write_routes: HashSet::new(),
+            query_timeout,
         }
     }
 
+    /// Default deadline for every query, requests can override it with [`QUERY_TIMEOUT_HEADER`]
+    pub fn with_query_timeout(mut self, query_timeout: Option<Duration>) -> Self {
+        self.query_timeout = query_timeout;
+        self
+    }
+
+    /// Functions running the queries that can be part of a `/batch` request
+    pub fn with_batch_routes(mut self, batch_routes: HashMap<String, BatchHandlerFn>) -> Self {
+        let router = Arc::get_mut(&mut self.router)
+            .expect("the router is only shared once the gateway runs");
+        router.batch_routes = batch_routes;
+        self
+    }
+
+    /// Names of the routes that write to the graph, which read-only API keys can't call
+    pub fn with_write_routes(mut self, write_routes: HashSet<String>) -> Self {
+        self.write_routes = write_routes;
+        self
+    }
+
     pub fn run(self) -> Result<(), Box<dyn std::error::Error>> {
         trace!("Starting Helix Gateway");
 
@@ -92,7 +135,15 @@ impl HelixGateway {
 
         axum_app = axum_app
             .route("/{*path}", post(post_handler))
-            .route("/introspect", get(introspect_schema_handler));
+            .route(&format!("/{BATCH_ROUTE}"), post(batch_handler))
+            .route("/introspect", get(introspect_schema_handler))
+            .route(METRICS_ROUTE, get(metrics_handler))
+            .route(STATS_ROUTE, get(stats_handler))
+            .merge(
+                axum::Router::new()
+                    .route(CHANGES_ROUTE, get(changes_handler))
+                    .with_state(Arc::clone(&self.graph_access.storage)),
+            );
 
         #[cfg(feature = "dev-instance")]
         {
@@ -103,10 +154,30 @@ impl HelixGateway {
                 .route("/node-details", get(node_details_handler));
         }
 
+        let api_keys = ApiKeys::from_env()?;
+        if api_keys.is_empty() {
+            warn!("No API keys configured, the gateway accepts unauthenticated requests");
+        } else {
+            info!("API key authentication enabled with {} keys", api_keys.len());
+            let auth = Arc::new(ApiKeyAuth {
+                keys: api_keys,
+                write_routes: self.write_routes,
+            });
+            axum_app = axum_app.layer(axum::middleware::from_fn_with_state(auth, require_api_key));
+        }
+
+        // added after the auth layer so probes can reach them without an API key
+        axum_app = axum_app
+            .route(HEALTH_ROUTE, get(health_handler))
+            .route(READY_ROUTE, get(ready_handler));
+
         let axum_app = axum_app.with_state(Arc::new(AppState {
             worker_pool,
+            storage: Arc::clone(&self.graph_access.storage),
+            query_metrics: QueryMetrics::default(),
             schema_json: self.opts.and_then(|o| o.config.schema),
             cluster_id: self.cluster_id,
+            query_timeout: self.query_timeout,
         }));
 
         rt.block_on(async move {
@@ -167,14 +238,54 @@ async fn sigterm() {
     term.recv().await;
 }
 
+/// When a request received at `start` must finish by
+pub(crate) fn request_deadline(
+    headers: &HeaderMap,
+    default_timeout: Option<Duration>,
+    start: Instant,
+) -> Result<Option<Instant>, HelixError> {
+    let timeout = match headers.get(QUERY_TIMEOUT_HEADER) {
+        Some(v) => match v.to_str().ok().and_then(|ms| ms.trim().parse().ok()) {
+            Some(ms) => Some(Duration::from_millis(ms)),
+            None => {
+                return Err(GraphError::DecodeError(format!(
+                    "{QUERY_TIMEOUT_HEADER} must be a number of milliseconds"
+                ))
+                .into());
+            }
+        },
+        None => default_timeout,
+    };
+    Ok(timeout.map(|t| start + t))
+}
+
 async fn post_handler(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     req: protocol::request::Request,
 ) -> axum::http::Response<Body> {
     let start_time = Instant::now();
+    let deadline = match request_deadline(&headers, state.query_timeout, start_time) {
+        Ok(deadline) => deadline,
+        Err(e) => return e.into_response(),
+    };
     let body = req.body.to_vec();
     let query_name = req.name.clone();
-    let res = state.worker_pool.process(req).await;
+    let res = state
+        .worker_pool
+        .process_with_deadline(req, deadline)
+        .await;
+
+    let metrics_name = match &res {
+        // unknown names are grouped so clients can't grow the metrics without bound
+        Err(HelixError::NotFound { .. }) => "unknown",
+        _ => &query_name,
+    };
+    state.query_metrics.record(
+        metrics_name,
+        start_time.elapsed(),
+        res.as_ref().err().map(HelixError::code),
+    );
 
     match res {
         Ok(r) => {
@@ -196,7 +307,7 @@ async fn post_handler(
                     cluster_id: state.cluster_id.clone(),
                     query_name,
                     input_json: sonic_rs::to_string(&body).ok(),
-                    output_json: Some(format!(r#"{{"error":"{e}"}}"#)),
+                    output_json: sonic_rs::to_string(&e.to_body()).ok(),
                     time_taken_usec: start_time.elapsed().as_micros() as u32,
                 },
             );
@@ -205,10 +316,32 @@ async fn post_handler(
     }
 }
 
+/// Runs every step of the request in one transaction, see [`super::batch`]
+async fn batch_handler(
+    state: State<Arc<AppState>>,
+    headers: HeaderMap,
+    auth: Option<Extension<BatchAuth>>,
+    mut req: protocol::request::Request,
+) -> axum::http::Response<Body> {
+    req.req_type = RequestType::Batch;
+    // only set when API keys are configured
+    if let Some(Extension(auth)) = auth
+        && let Err(e) = auth.authorize_steps(&req)
+    {
+        return e.into_response();
+    }
+    post_handler(state, headers, req).await
+}
+
 pub struct AppState {
     pub worker_pool: WorkerPool,
+    pub storage: Arc<HelixGraphStorage>,
+    /// Served from `/metrics` along with storage stats, see [`super::metrics`]
+    pub query_metrics: QueryMetrics,
     pub schema_json: Option<String>,
     pub cluster_id: Option<String>,
+    /// Default deadline for queries without a [`QUERY_TIMEOUT_HEADER`]
+    pub query_timeout: Option<Duration>,
 }
 
 pub struct CoreSetter {
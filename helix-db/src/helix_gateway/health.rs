@@ -0,0 +1,91 @@
+//! Endpoints for orchestrators and operators, rather than queries.
+//!
+//! - `GET /health` answers as long as the process is serving requests.
+//! - `GET /ready` checks the storage can be read and its migrations have finished.
+//! - `GET /stats` counts the nodes, edges and vectors by label, see [`StorageStats`].
+//!
+//! `/health` and `/ready` skip API key authentication so probes don't need a key.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::{
+    helix_engine::{
+        storage_core::{
+            HelixGraphStorage,
+            metadata::{NATIVE_VECTOR_ENDIANNESS, StorageMetadata},
+            stats::StorageStats,
+        },
+        types::GraphError,
+    },
+    helix_gateway::gateway::AppState,
+    protocol::HelixError,
+};
+
+pub const HEALTH_ROUTE: &str = "/health";
+pub const READY_ROUTE: &str = "/ready";
+pub const STATS_ROUTE: &str = "/stats";
+
+#[derive(Serialize)]
+struct Status {
+    status: &'static str,
+}
+
+fn json_response<T: Serialize>(body: &T) -> Response {
+    match sonic_rs::to_vec(body) {
+        Ok(body) => Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .expect("should be able to make response from json"),
+        Err(e) => HelixError::from(GraphError::from(e)).into_response(),
+    }
+}
+
+pub async fn health_handler() -> Response {
+    json_response(&Status { status: "ok" })
+}
+
+/// Fails unless the storage is readable and stored in the current format
+pub fn check_ready(storage: &HelixGraphStorage) -> Result<(), HelixError> {
+    let txn = storage
+        .graph_env
+        .read_txn()
+        .map_err(|e| HelixError::NotReady(e.to_string()))?;
+    match StorageMetadata::read(&txn, &storage.metadata_db) {
+        Ok(StorageMetadata::DateIndexKeys {
+            vector_endianness: NATIVE_VECTOR_ENDIANNESS,
+        }) => Ok(()),
+        Ok(_) => Err(HelixError::NotReady(
+            "storage migrations haven't finished".to_string(),
+        )),
+        Err(e) => Err(HelixError::NotReady(e.to_string())),
+    }
+}
+
+pub async fn ready_handler(State(state): State<Arc<AppState>>) -> Response {
+    match check_ready(&state.storage) {
+        Ok(()) => json_response(&Status { status: "ready" }),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn stats_handler(State(state): State<Arc<AppState>>) -> Response {
+    let storage = Arc::clone(&state.storage);
+    let stats: Result<StorageStats, HelixError> =
+        match tokio::task::spawn_blocking(move || storage.stats()).await {
+            Ok(stats) => stats.map_err(Into::into),
+            Err(e) => Err(GraphError::New(e.to_string()).into()),
+        };
+    match stats {
+        Ok(stats) => json_response(&stats),
+        Err(e) => e.into_response(),
+    }
+}
@@ -2,7 +2,12 @@ use crate::{
     helix_engine::{
         storage_core::HelixGraphStorage,
         traversal_core::{
-            ops::util::{aggregate::AggregateAdapter, group_by::GroupByAdapter},
+            ops::util::{
+                aggregate::AggregateAdapter,
+                degree::{DegreeAdapter, Direction},
+                group_by::GroupByAdapter,
+                subgraph::SubgraphAdapter,
+            },
             traversal_value::TraversalValue,
         },
         types::GraphError,
@@ -153,11 +158,25 @@ pub struct MCPHandlerSubmission(pub MCPHandler);
 pub struct MCPHandler {
     pub name: &'static str,
     pub func: BasicMCPHandlerFn,
+    /// Whether the handler opens a write transaction
+    pub is_mut: bool,
 }
 
 impl MCPHandler {
     pub const fn new(name: &'static str, func: BasicMCPHandlerFn) -> Self {
-        Self { name, func }
+        Self {
+            name,
+            func,
+            is_mut: false,
+        }
+    }
+
+    pub const fn new_mut(name: &'static str, func: BasicMCPHandlerFn) -> Self {
+        Self {
+            name,
+            func,
+            is_mut: true,
+        }
     }
 }
 
@@ -434,7 +453,7 @@ pub fn aggregate_by(input: &mut MCPToolInput) -> Result<Response, GraphError> {
 
     let aggregation = stream
         .into_ro()
-        .aggregate_by(&data.properties, true)?
+        .aggregate_by(&data.properties, true, &[])?
         .into_count();
 
     // Update connection state
@@ -476,7 +495,7 @@ pub fn group_by(input: &mut MCPToolInput) -> Result<Response, GraphError> {
 
     let aggregation = stream
         .into_ro()
-        .group_by(&data.properties, true)?
+        .group_by(&data.properties, true, &[])?
         .into_count();
 
     // Update connection state
@@ -494,6 +513,109 @@ pub fn group_by(input: &mut MCPToolInput) -> Result<Response, GraphError> {
     Ok(Format::Json.create_response(&aggregation))
 }
 
+#[derive(Deserialize)]
+pub struct DegreeRequest {
+    pub connection_id: String,
+    pub edge_label: String,
+    pub direction: Direction,
+    pub drop: Option<bool>,
+}
+
+#[mcp_handler]
+pub fn degree(input: &mut MCPToolInput) -> Result<Response, GraphError> {
+    let data: DegreeRequest = match sonic_rs::from_slice(&input.request.body) {
+        Ok(data) => data,
+        Err(err) => return Err(GraphError::from(err)),
+    };
+
+    // Clone necessary data while holding the lock
+    let query_chain = {
+        let connections = input.mcp_connections.lock().unwrap();
+        let connection = connections
+            .get_connection(&data.connection_id)
+            .ok_or_else(|| GraphError::StorageError(format!("Connection not found: {}", data.connection_id)))?;
+        connection.query_chain.clone()
+    };
+
+    // Execute long-running operation without holding the lock
+    let arena = Bump::new();
+    let storage = input.mcp_backend.db.as_ref();
+    let txn = storage.graph_env.read_txn()?;
+    let stream = execute_query_chain(&query_chain, storage, &txn, &arena)?;
+
+    let degree = match data.direction {
+        Direction::Out => stream.into_ro().out_degree_to_val(&data.edge_label),
+        Direction::In => stream.into_ro().in_degree_to_val(&data.edge_label),
+        Direction::Both => stream.into_ro().degree_to_val(&data.edge_label),
+    }?;
+
+    // Update connection state
+    {
+        let mut connections = input.mcp_connections.lock().unwrap();
+        let connection = connections
+            .get_connection_mut(&data.connection_id)
+            .ok_or_else(|| GraphError::StorageError(format!("Connection not found: {}", data.connection_id)))?;
+
+        if data.drop.unwrap_or(true) {
+            connection.clear_chain();
+        }
+    }
+
+    Ok(Format::Json.create_response(&degree))
+}
+
+#[derive(Deserialize)]
+pub struct EgoGraphRequest {
+    pub connection_id: String,
+    #[serde(default)]
+    pub edge_labels: Vec<String>,
+    pub hops: usize,
+    pub max_nodes: Option<usize>,
+    pub drop: Option<bool>,
+}
+
+#[mcp_handler]
+pub fn ego_graph(input: &mut MCPToolInput) -> Result<Response, GraphError> {
+    let data: EgoGraphRequest = match sonic_rs::from_slice(&input.request.body) {
+        Ok(data) => data,
+        Err(err) => return Err(GraphError::from(err)),
+    };
+
+    // Clone necessary data while holding the lock
+    let query_chain = {
+        let connections = input.mcp_connections.lock().unwrap();
+        let connection = connections
+            .get_connection(&data.connection_id)
+            .ok_or_else(|| GraphError::StorageError(format!("Connection not found: {}", data.connection_id)))?;
+        connection.query_chain.clone()
+    };
+
+    // Execute long-running operation without holding the lock
+    let arena = Bump::new();
+    let storage = input.mcp_backend.db.as_ref();
+    let txn = storage.graph_env.read_txn()?;
+    let stream = execute_query_chain(&query_chain, storage, &txn, &arena)?;
+
+    let edge_labels = data.edge_labels.iter().map(String::as_str).collect::<Vec<_>>();
+    let subgraph = stream
+        .into_ro()
+        .ego_graph(&edge_labels, data.hops, data.max_nodes)?;
+
+    // Update connection state
+    {
+        let mut connections = input.mcp_connections.lock().unwrap();
+        let connection = connections
+            .get_connection_mut(&data.connection_id)
+            .ok_or_else(|| GraphError::StorageError(format!("Connection not found: {}", data.connection_id)))?;
+
+        if data.drop.unwrap_or(true) {
+            connection.clear_chain();
+        }
+    }
+
+    Ok(Format::Json.create_response(&subgraph))
+}
+
 #[derive(Deserialize)]
 pub struct ResetRequest {
     pub connection_id: String,
@@ -755,6 +877,58 @@ pub fn order_by(input: &mut MCPToolInput) -> Result<Response, GraphError> {
     execute_tool_step(input, &req.connection_id, tool)
 }
 
+#[derive(Debug, Deserialize)]
+pub struct BetweennessData {
+    pub edge_label: String,
+    pub samples: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BetweennessInput {
+    pub connection_id: String,
+    pub data: BetweennessData,
+}
+
+#[mcp_handler]
+pub fn betweenness(input: &mut MCPToolInput) -> Result<Response, GraphError> {
+    let req: BetweennessInput = match sonic_rs::from_slice(&input.request.body) {
+        Ok(data) => data,
+        Err(err) => return Err(GraphError::from(err)),
+    };
+
+    let tool = ToolArgs::Betweenness {
+        edge_label: req.data.edge_label,
+        samples: req.data.samples,
+    };
+
+    execute_tool_step(input, &req.connection_id, tool)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrianglesData {
+    pub edge_label: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrianglesInput {
+    pub connection_id: String,
+    pub data: TrianglesData,
+}
+
+#[mcp_handler]
+pub fn triangles(input: &mut MCPToolInput) -> Result<Response, GraphError> {
+    let req: TrianglesInput = match sonic_rs::from_slice(&input.request.body) {
+        Ok(data) => data,
+        Err(err) => return Err(GraphError::from(err)),
+    };
+
+    let tool = ToolArgs::Triangles {
+        edge_label: req.data.edge_label,
+    };
+
+    execute_tool_step(input, &req.connection_id, tool)
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SearchKeywordData {
     pub query: String,
@@ -7,7 +7,9 @@ use crate::{
                 in_::{in_::InAdapter, in_e::InEdgesAdapter},
                 out::{out::OutAdapter, out_e::OutEdgesAdapter},
                 source::{e_from_type::EFromTypeAdapter, n_from_type::NFromTypeAdapter},
-                util::{order::OrderByAdapter, range::RangeAdapter},
+                util::{
+                    centrality::CentralityAdapter, order::OrderByAdapter, range::RangeAdapter,
+                },
             },
             traversal_iter::RoTraversalIterator,
             traversal_value::TraversalValue,
@@ -79,6 +81,13 @@ pub enum ToolArgs {
         k: usize,
         min_score: Option<f64>,
     },
+    Betweenness {
+        edge_label: String,
+        samples: Option<usize>,
+    },
+    Triangles {
+        edge_label: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, Deserialize)]
@@ -368,6 +377,18 @@ where
             };
             Ok(ordered_stream)
         }
+        ToolArgs::Betweenness {
+            edge_label,
+            samples,
+        } => {
+            let label = arena.alloc_str(edge_label);
+            let samples = *samples;
+            Ok(stream.map(|iter| iter.betweenness(label, samples)))
+        }
+        ToolArgs::Triangles { edge_label } => {
+            let label = arena.alloc_str(edge_label);
+            Ok(stream.map(|iter| iter.triangles(label)))
+        }
         ToolArgs::SearchKeyword { .. } => {
             // SearchKeyword requires special BM25 indexing and connection state
             // It should be called via the dedicated search_keyword MCP handler
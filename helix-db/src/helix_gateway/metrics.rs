@@ -0,0 +1,241 @@
+//! Serves `GET /metrics` in the Prometheus text format.
+//!
+//! Unlike `helix_metrics`, which ships events to the hosted metrics service, everything here is
+//! kept in the process so it can be scraped locally: latency histograms and error counts per
+//! query, the worker pool queue depth, LMDB map usage and readers, and HNSW and BM25 sizes.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Write},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{
+    extract::State,
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use heed3::{Env, RoTxn};
+
+use crate::{
+    helix_engine::{storage_core::HelixGraphStorage, types::GraphError},
+    helix_gateway::gateway::AppState,
+    protocol::HelixError,
+};
+
+/// The route metrics are scraped from
+pub const METRICS_ROUTE: &str = "/metrics";
+
+/// Content type of the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Upper bounds of the query latency histogram buckets, in seconds
+const LATENCY_BUCKETS: [f64; 14] = [
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Debug, Default, Clone)]
+pub struct QueryStats {
+    /// Requests per latency bucket, not cumulative, the last one counting the slower ones
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    count: u64,
+    sum_secs: f64,
+    /// Failed requests by error code
+    errors: BTreeMap<&'static str, u64>,
+}
+
+/// Latency and errors of the queries the gateway ran, by query name
+#[derive(Debug, Default)]
+pub struct QueryMetrics {
+    queries: Mutex<BTreeMap<String, QueryStats>>,
+}
+
+impl QueryMetrics {
+    /// Records a request to `query`, with the code of the error it failed with if it did
+    pub fn record(&self, query: &str, elapsed: Duration, error: Option<&'static str>) {
+        let secs = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|&le| secs <= le)
+            .unwrap_or(LATENCY_BUCKETS.len());
+
+        let mut queries = self.queries.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = match queries.get_mut(query) {
+            Some(stats) => stats,
+            None => queries.entry(query.to_string()).or_default(),
+        };
+        stats.buckets[bucket] += 1;
+        stats.count += 1;
+        stats.sum_secs += secs;
+        if let Some(code) = error {
+            *stats.errors.entry(code).or_insert(0) += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> BTreeMap<String, QueryStats> {
+        self.queries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+/// Writes metrics in the Prometheus text format
+struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {name} {help}");
+        let _ = writeln!(self.out, "# TYPE {name} {kind}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (key, label)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{key}=\"{}\"", escape_label(label));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    fn gauge(&mut self, name: &str, help: &str, value: impl Display) {
+        self.header(name, "gauge", help);
+        self.sample(name, &[], value);
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_query_metrics(exp: &mut Exposition, queries: &BTreeMap<String, QueryStats>) {
+    let name = "helix_query_duration_seconds";
+    exp.header(name, "histogram", "Time taken to run queries");
+    for (query, stats) in queries {
+        let mut cumulative = 0;
+        for (le, count) in LATENCY_BUCKETS.iter().zip(stats.buckets) {
+            cumulative += count;
+            let le = le.to_string();
+            exp.sample(&format!("{name}_bucket"), &[("query", query), ("le", &le)], cumulative);
+        }
+        exp.sample(&format!("{name}_bucket"), &[("query", query), ("le", "+Inf")], stats.count);
+        exp.sample(&format!("{name}_sum"), &[("query", query)], stats.sum_secs);
+        exp.sample(&format!("{name}_count"), &[("query", query)], stats.count);
+    }
+
+    let name = "helix_query_errors_total";
+    exp.header(name, "counter", "Queries that failed, by error code");
+    for (query, stats) in queries {
+        for (code, count) in &stats.errors {
+            exp.sample(name, &[("query", query), ("code", code)], count);
+        }
+    }
+}
+
+/// Must run without a transaction open on this thread, as measuring the used pages opens one
+fn write_lmdb_metrics(exp: &mut Exposition, env: &Env) -> Result<(), GraphError> {
+    let info = env.info();
+    exp.gauge("helix_lmdb_map_size_bytes", "Size of the LMDB memory map", info.map_size);
+    exp.gauge(
+        "helix_lmdb_used_bytes",
+        "Bytes of the LMDB map used by pages holding data",
+        env.non_free_pages_size()?,
+    );
+    exp.gauge(
+        "helix_lmdb_last_page_number",
+        "ID of the last LMDB page used",
+        info.last_page_number,
+    );
+    exp.gauge("helix_lmdb_readers", "LMDB reader slots in use", info.number_of_readers);
+    exp.gauge(
+        "helix_lmdb_max_readers",
+        "LMDB reader slots available",
+        info.maximum_number_of_readers,
+    );
+    Ok(())
+}
+
+fn write_storage_metrics(
+    exp: &mut Exposition,
+    storage: &HelixGraphStorage,
+    txn: &RoTxn,
+) -> Result<(), GraphError> {
+    exp.gauge("helix_nodes", "Nodes stored", storage.nodes_db.len(txn)?);
+    exp.gauge("helix_edges", "Edges stored", storage.edges_db.len(txn)?);
+
+    let vectors = &storage.vectors;
+    exp.gauge(
+        "helix_hnsw_vectors",
+        "Vectors stored in the HNSW index",
+        vectors.vector_properties_db.len(txn)?,
+    );
+    exp.gauge(
+        "helix_hnsw_entries",
+        "Vector entries across every level of the HNSW index",
+        vectors.vectors_db.len(txn)?,
+    );
+    exp.gauge("helix_hnsw_edges", "Edges between HNSW neighbours", vectors.edges_db.len(txn)?);
+
+    if let Some(bm25) = &storage.bm25 {
+        let (docs, avgdl) = bm25
+            .metadata(txn)?
+            .map_or((0, 0.0), |m| (m.total_docs, m.avgdl));
+        exp.gauge("helix_bm25_documents", "Documents in the BM25 index", docs);
+        exp.gauge(
+            "helix_bm25_terms",
+            "Distinct terms in the BM25 index",
+            bm25.term_frequencies_db.len(txn)?,
+        );
+        exp.gauge(
+            "helix_bm25_avg_document_length",
+            "Average length of the documents in the BM25 index",
+            avgdl,
+        );
+    }
+    Ok(())
+}
+
+/// Renders every metric of the gateway in the Prometheus text format
+pub fn render_metrics(state: &AppState) -> Result<String, GraphError> {
+    let mut exp = Exposition { out: String::new() };
+
+    write_query_metrics(&mut exp, &state.query_metrics.snapshot());
+
+    let name = "helix_worker_queue_depth";
+    exp.header(name, "gauge", "Messages waiting for a free worker");
+    exp.sample(name, &[("queue", "requests")], state.worker_pool.pending_requests());
+    exp.sample(
+        name,
+        &[("queue", "continuations")],
+        state.worker_pool.pending_continuations(),
+    );
+
+    write_lmdb_metrics(&mut exp, &state.storage.graph_env)?;
+    let txn = state.storage.graph_env.read_txn()?;
+    write_storage_metrics(&mut exp, &state.storage, &txn)?;
+    Ok(exp.out)
+}
+
+pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> Response {
+    match render_metrics(&state) {
+        Ok(body) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+            body,
+        )
+            .into_response(),
+        Err(e) => HelixError::from(e).into_response(),
+    }
+}
@@ -1,9 +1,14 @@
 #[cfg(feature = "dev-instance")]
 pub mod builtin;
+pub mod auth;
+pub mod batch;
+pub mod changes;
 pub mod embedding_providers;
 pub mod gateway;
+pub mod health;
 pub mod introspect_schema;
 pub mod mcp;
+pub mod metrics;
 pub mod router;
 pub mod worker_pool;
 
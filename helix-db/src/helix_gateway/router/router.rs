@@ -9,11 +9,13 @@
 
 use crate::{
     helix_engine::{traversal_core::HelixGraphEngine, types::GraphError},
-    helix_gateway::mcp::mcp::MCPHandlerFn,
-    protocol::request::RetChan,
+    helix_gateway::{batch::BatchHandlerFn, mcp::mcp::MCPHandlerFn},
+    protocol::{request::RetChan, stream::STREAM_SEND_TIMEOUT},
 };
 use core::fmt;
-use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin, sync::Arc};
+use std::{
+    collections::HashMap, fmt::Debug, future::Future, pin::Pin, sync::Arc, time::Duration,
+};
 
 use crate::protocol::{Request, Response};
 
@@ -62,11 +64,25 @@ pub struct HandlerSubmission(pub Handler);
 pub struct Handler {
     pub name: &'static str,
     pub func: BasicHandlerFn,
+    /// Whether the handler opens a write transaction
+    pub is_mut: bool,
 }
 
 impl Handler {
     pub const fn new(name: &'static str, func: BasicHandlerFn) -> Self {
-        Self { name, func }
+        Self {
+            name,
+            func,
+            is_mut: false,
+        }
+    }
+
+    pub const fn new_mut(name: &'static str, func: BasicHandlerFn) -> Self {
+        Self {
+            name,
+            func,
+            is_mut: true,
+        }
     }
 }
 
@@ -79,6 +95,10 @@ pub struct HelixRouter {
     /// Name => Function
     pub routes: HashMap<String, HandlerFn>,
     pub mcp_routes: HashMap<String, MCPHandlerFn>,
+    /// Query name => Function running it inside a batch
+    pub batch_routes: HashMap<String, BatchHandlerFn>,
+    /// How long a streamed response waits for the client to take a chunk
+    pub stream_send_timeout: Duration,
 }
 
 impl HelixRouter {
@@ -92,9 +112,23 @@ impl HelixRouter {
         Self {
             routes: rts,
             mcp_routes: mcp_rts,
+            batch_routes: HashMap::new(),
+            stream_send_timeout: STREAM_SEND_TIMEOUT,
         }
     }
 
+    /// Set the queries that can run inside a batch
+    pub fn with_batch_routes(mut self, batch_routes: HashMap<String, BatchHandlerFn>) -> Self {
+        self.batch_routes = batch_routes;
+        self
+    }
+
+    /// Set how long a streamed response waits for the client before it fails
+    pub fn with_stream_send_timeout(mut self, stream_send_timeout: Duration) -> Self {
+        self.stream_send_timeout = stream_send_timeout;
+        self
+    }
+
     /// Add a route to the router
     pub fn add_route(&mut self, name: &str, handler: BasicHandlerFn) {
         self.routes.insert(name.to_string(), Arc::new(handler));
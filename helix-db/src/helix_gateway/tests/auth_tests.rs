@@ -0,0 +1,293 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{
+    extract::Extension,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use reqwest::StatusCode;
+use sonic_rs::JsonValueTrait;
+
+use crate::{
+    helix_gateway::auth::{
+        API_KEY_HEADER, ApiKeyAuth, ApiKeys, BatchAuth, KeyAccess, KeyPolicy, Route, hash_key,
+        require_api_key,
+    },
+    protocol::{HelixError, Request},
+};
+
+fn policy(access: KeyAccess, queries: Option<&[&str]>) -> KeyPolicy {
+    KeyPolicy {
+        access,
+        queries: queries.map(|qs| qs.iter().map(|q| q.to_string()).collect()),
+    }
+}
+
+// ============================================================================
+// Key Loading Tests
+// ============================================================================
+
+#[test]
+fn test_hash_key_is_sha256_hex() {
+    assert_eq!(
+        hash_key("abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn test_parse_env_keys() {
+    let keys = ApiKeys::parse("admin, reader:read ,scoped:write:get_user|add_user").unwrap();
+    assert_eq!(keys.len(), 3);
+
+    assert!(keys.authorize(Some("admin"), Route::Query("add_user", true)).is_ok());
+    assert!(keys.authorize(Some("reader"), Route::Query("get_user", false)).is_ok());
+    assert!(keys.authorize(Some("scoped"), Route::Query("add_user", true)).is_ok());
+}
+
+#[test]
+fn test_parse_env_keys_empty() {
+    assert!(ApiKeys::parse("").unwrap().is_empty());
+    assert!(ApiKeys::parse(" , ").unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_env_keys_invalid_access() {
+    assert!(ApiKeys::parse("key:admin").is_err());
+}
+
+#[test]
+fn test_keys_file_uses_hashes() {
+    let json = format!(
+        r#"[
+            {{ "key_hash": "{}", "access": "read", "queries": ["get_user"] }},
+            {{ "key_hash": "{}", "access": "write" }}
+        ]"#,
+        hash_key("reader").to_uppercase(),
+        hash_key("writer"),
+    );
+    let mut keys = ApiKeys::default();
+    keys.extend_from_json(&json).unwrap();
+
+    assert_eq!(keys.len(), 2);
+    assert!(keys.authorize(Some("reader"), Route::Query("get_user", false)).is_ok());
+    assert!(keys.authorize(Some("writer"), Route::Query("add_user", true)).is_ok());
+    // the hash itself is not a valid key
+    assert!(keys.authorize(Some(&hash_key("writer")), Route::Batch).is_err());
+}
+
+#[test]
+fn test_keys_file_invalid_json() {
+    let mut keys = ApiKeys::default();
+    assert!(keys.extend_from_json(r#"[{ "key_hash": "abc" }]"#).is_err());
+}
+
+// ============================================================================
+// Authorization Tests
+// ============================================================================
+
+#[test]
+fn test_authorize_missing_or_unknown_key() {
+    let mut keys = ApiKeys::default();
+    keys.insert_key("secret", KeyPolicy::default());
+
+    assert!(matches!(keys.authorize(None, Route::Batch), Err(HelixError::Unauthorized)));
+    assert!(matches!(
+        keys.authorize(Some("wrong"), Route::Query("get_user", false)),
+        Err(HelixError::Unauthorized)
+    ));
+    assert!(keys.authorize(Some("secret"), Route::Other("/stats")).is_ok());
+}
+
+#[test]
+fn test_authorize_read_only_key() {
+    let mut keys = ApiKeys::default();
+    keys.insert_key("reader", policy(KeyAccess::Read, None));
+
+    assert!(keys.authorize(Some("reader"), Route::Query("get_user", false)).is_ok());
+    assert!(matches!(
+        keys.authorize(Some("reader"), Route::Query("add_user", true)),
+        Err(HelixError::Forbidden { name }) if name == "add_user"
+    ));
+}
+
+#[test]
+fn test_authorize_query_allow_list() {
+    let mut keys = ApiKeys::default();
+    keys.insert_key("scoped", policy(KeyAccess::Write, Some(&["get_user", "add_user"])));
+
+    assert!(keys.authorize(Some("scoped"), Route::Query("get_user", false)).is_ok());
+    assert!(keys.authorize(Some("scoped"), Route::Query("add_user", true)).is_ok());
+    assert!(matches!(
+        keys.authorize(Some("scoped"), Route::Query("delete_user", true)),
+        Err(HelixError::Forbidden { .. })
+    ));
+    // a batch only needs a valid key, its handler checks the steps
+    assert!(keys.authorize(Some("scoped"), Route::Batch).is_ok());
+}
+
+#[test]
+fn test_authorize_other_routes_need_unrestricted_key() {
+    let mut keys = ApiKeys::default();
+    keys.insert_key("writer", KeyPolicy::default());
+    keys.insert_key("reader", policy(KeyAccess::Read, None));
+    keys.insert_key("scoped", policy(KeyAccess::Write, Some(&["get_user"])));
+
+    assert!(keys.authorize(Some("writer"), Route::Other("/changes")).is_ok());
+    for key in ["reader", "scoped"] {
+        assert!(matches!(
+            keys.authorize(Some(key), Route::Other("/changes")),
+            Err(HelixError::Forbidden { name }) if name == "/changes"
+        ));
+    }
+}
+
+// ============================================================================
+// Middleware Tests
+// ============================================================================
+
+async fn spawn_app(keys: ApiKeys, write_routes: &[&str]) -> String {
+    let auth = Arc::new(ApiKeyAuth {
+        keys,
+        write_routes: write_routes.iter().map(|r| r.to_string()).collect::<HashSet<_>>(),
+    });
+    let app = axum::Router::new()
+        .route("/{*path}", post(|| async { "ok" }))
+        .route("/introspect", get(|| async { "schema" }))
+        .route("/changes", get(|| async { "changes" }))
+        .route("/stats", get(|| async { "stats" }))
+        // checks the steps like the gateway's batch handler
+        .route(
+            "/batch",
+            post(
+                |auth: Option<Extension<BatchAuth>>, req: Request| async move {
+                    match auth.map(|Extension(auth)| auth.authorize_steps(&req)) {
+                        Some(Err(e)) => e.into_response(),
+                        _ => "ok".into_response(),
+                    }
+                },
+            ),
+        )
+        .layer(axum::middleware::from_fn_with_state(auth, require_api_key));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn test_middleware_status_codes() {
+    let mut keys = ApiKeys::default();
+    keys.insert_key("writer", KeyPolicy::default());
+    keys.insert_key("reader", policy(KeyAccess::Read, None));
+    keys.insert_key("scoped", policy(KeyAccess::Write, Some(&["get_user"])));
+    let base = spawn_app(keys, &["add_user", "add_userMcp"]).await;
+    let client = reqwest::Client::new();
+
+    let status = |path: &'static str, key: Option<&'static str>| {
+        let mut req = client.post(format!("{base}{path}"));
+        if let Some(key) = key {
+            req = req.header(API_KEY_HEADER, key);
+        }
+        async move { req.send().await.unwrap().status() }
+    };
+
+    assert_eq!(status("/get_user", None).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(status("/get_user", Some("nope")).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(status("/get_user", Some("reader")).await, StatusCode::OK);
+    assert_eq!(status("/add_user", Some("reader")).await, StatusCode::FORBIDDEN);
+    assert_eq!(status("/mcp/add_userMcp", Some("reader")).await, StatusCode::FORBIDDEN);
+    assert_eq!(status("/add_user", Some("writer")).await, StatusCode::OK);
+    assert_eq!(status("/add_user", Some("scoped")).await, StatusCode::FORBIDDEN);
+    assert_eq!(status("/get_user", Some("scoped")).await, StatusCode::OK);
+
+    let get_status = |path: &'static str, key: Option<&'static str>| {
+        let mut req = client.get(format!("{base}{path}"));
+        if let Some(key) = key {
+            req = req.header(API_KEY_HEADER, key);
+        }
+        async move { req.send().await.unwrap().status() }
+    };
+
+    assert_eq!(get_status("/introspect", None).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(get_status("/introspect", Some("writer")).await, StatusCode::OK);
+    assert_eq!(get_status("/introspect", Some("scoped")).await, StatusCode::FORBIDDEN);
+    // restricted keys can't read the graph around their queries
+    for path in ["/changes", "/stats"] {
+        assert_eq!(get_status(path, Some("writer")).await, StatusCode::OK);
+        assert_eq!(get_status(path, Some("reader")).await, StatusCode::FORBIDDEN);
+        assert_eq!(get_status(path, Some("scoped")).await, StatusCode::FORBIDDEN);
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_batch_checks_every_step() {
+    let mut keys = ApiKeys::default();
+    keys.insert_key("writer", KeyPolicy::default());
+    keys.insert_key("reader", policy(KeyAccess::Read, None));
+    keys.insert_key("scoped", policy(KeyAccess::Write, Some(&["get_user"])));
+    keys.insert_key("batcher", policy(KeyAccess::Write, Some(&["get_user", "batch"])));
+    let base = spawn_app(keys, &["add_user"]).await;
+    let client = reqwest::Client::new();
+
+    let status = |key: Option<&'static str>, queries: &[&str]| {
+        let steps = queries
+            .iter()
+            .map(|q| format!(r#"{{"query": "{q}", "params": {{}}}}"#))
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut req = client.post(format!("{base}/batch")).body(format!("[{steps}]"));
+        if let Some(key) = key {
+            req = req.header(API_KEY_HEADER, key);
+        }
+        async move { req.send().await.unwrap().status() }
+    };
+
+    assert_eq!(status(None, &["get_user"]).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(status(Some("writer"), &["get_user", "add_user"]).await, StatusCode::OK);
+    assert_eq!(status(Some("reader"), &["get_user"]).await, StatusCode::OK);
+    assert_eq!(status(Some("reader"), &["get_user", "add_user"]).await, StatusCode::FORBIDDEN);
+    assert_eq!(status(Some("scoped"), &["get_user"]).await, StatusCode::OK);
+    assert_eq!(status(Some("scoped"), &["add_user"]).await, StatusCode::FORBIDDEN);
+    // listing `batch` doesn't allow other queries through it
+    assert_eq!(status(Some("batcher"), &["delete_user"]).await, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_middleware_batch_forbidden_step_envelope() {
+    let mut keys = ApiKeys::default();
+    keys.insert_key("scoped", policy(KeyAccess::Write, Some(&["get_user"])));
+    let base = spawn_app(keys, &["add_user"]).await;
+
+    let response = reqwest::Client::new()
+        .post(format!("{base}/batch"))
+        .header(API_KEY_HEADER, "scoped")
+        .body(r#"[{"query": "get_user"}, {"query": "add_user", "params": {"name": "a"}}]"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let body: sonic_rs::Value = sonic_rs::from_slice(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!(body["code"].as_str(), Some("FORBIDDEN"));
+    assert_eq!(body["details"]["step"].as_u64(), Some(1));
+    assert_eq!(body["details"]["query"].as_str(), Some("add_user"));
+}
+
+#[tokio::test]
+async fn test_middleware_error_envelope() {
+    let mut keys = ApiKeys::default();
+    keys.insert_key("reader", policy(KeyAccess::Read, None));
+    let base = spawn_app(keys, &["add_user"]).await;
+
+    let response = reqwest::Client::new()
+        .post(format!("{base}/add_user"))
+        .header(API_KEY_HEADER, "reader")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let body: sonic_rs::Value = sonic_rs::from_slice(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!(body["code"].as_str(), Some("FORBIDDEN"));
+    assert_eq!(body["details"]["name"].as_str(), Some("add_user"));
+}
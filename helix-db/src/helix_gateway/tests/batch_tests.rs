@@ -0,0 +1,295 @@
+use crate::{
+    helix_engine::{
+        traversal_core::{
+            HelixGraphEngine, HelixGraphEngineOpts,
+            config::Config,
+            ops::{
+                g::G,
+                source::{add_e::AddEAdapter, add_n::AddNAdapter, n_from_type::NFromTypeAdapter},
+            },
+        },
+        types::GraphError,
+    },
+    helix_gateway::{
+        batch::{BatchHandlerFn, BatchInput, batch_output, resolve_refs, run_batch},
+        gateway::CoreSetter,
+        router::router::HelixRouter,
+        worker_pool::WorkerPool,
+    },
+    protocol::{Format, HelixError, Request, request::RequestType},
+};
+use axum::body::Bytes;
+use bumpalo::Bump;
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+use sonic_rs::JsonValueTrait;
+use std::{collections::HashMap, sync::Arc};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+fn create_test_graph() -> (Arc<HelixGraphEngine>, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        db_max_size_gb: Some(0),
+        ..Default::default()
+    };
+    let opts = HelixGraphEngineOpts {
+        path: temp_dir.path().to_str().unwrap().to_string(),
+        config,
+        version_info: Default::default(),
+    };
+    let graph = Arc::new(HelixGraphEngine::new(opts).unwrap());
+    (graph, temp_dir)
+}
+
+/// Mirrors a generated batch function adding a node with the label given in the params
+fn add_node_batch(input: BatchInput<'_, '_>) -> Result<Value, GraphError> {
+    let arena = Bump::new();
+    let label = input.params["label"]
+        .as_str()
+        .ok_or(GraphError::ParamNotFound("label"))?;
+    let label = arena.alloc_str(label);
+    let node = G::new_mut(input.db, &arena, input.txn)
+        .add_n(label, None, None)
+        .collect_to_obj()?;
+    batch_output(&json!({ "node": { "id": Uuid::from_u128(node.id()).to_string() } }))
+}
+
+fn add_edge_batch(input: BatchInput<'_, '_>) -> Result<Value, GraphError> {
+    let arena = Bump::new();
+    let id = |param: &'static str| -> Result<u128, GraphError> {
+        let id = input.params[param]
+            .as_str()
+            .ok_or(GraphError::ParamNotFound(param))?;
+        Ok(Uuid::parse_str(id)?.as_u128())
+    };
+    let (from, to) = (id("from")?, id("to")?);
+    G::new_mut(input.db, &arena, input.txn)
+        .add_edge("member_of", None, from, to, true)
+        .collect_to_obj()?;
+    batch_output(&())
+}
+
+fn count_nodes_batch(input: BatchInput<'_, '_>) -> Result<Value, GraphError> {
+    let arena = Bump::new();
+    let count = G::new(input.db, input.txn, &arena)
+        .n_from_type("user")
+        .count();
+    batch_output(&json!({ "count": count }))
+}
+
+fn failing_batch(_input: BatchInput<'_, '_>) -> Result<Value, GraphError> {
+    Err(GraphError::NodeNotFound)
+}
+
+fn batch_routes() -> HashMap<String, BatchHandlerFn> {
+    let mut routes: HashMap<String, BatchHandlerFn> = HashMap::new();
+    routes.insert("add_node".to_string(), Arc::new(add_node_batch));
+    routes.insert("add_edge".to_string(), Arc::new(add_edge_batch));
+    routes.insert("count_users".to_string(), Arc::new(count_nodes_batch));
+    routes.insert("fail".to_string(), Arc::new(failing_batch));
+    routes
+}
+
+fn batch_request(steps: Value) -> Request {
+    Request {
+        name: "batch".to_string(),
+        req_type: RequestType::Batch,
+        body: Bytes::from(serde_json::to_vec(&steps).unwrap()),
+        in_fmt: Format::Json,
+        out_fmt: Format::Json,
+        stream: false,
+    }
+}
+
+fn count_label(graph: &HelixGraphEngine, label: &str) -> usize {
+    let arena = Bump::new();
+    let txn = graph.storage.graph_env.read_txn().unwrap();
+    G::new(&graph.storage, &txn, &arena).n_from_type(label).count()
+}
+
+// ============================================================================
+// $ref Resolution Tests
+// ============================================================================
+
+#[test]
+fn test_resolve_refs_nested_paths() {
+    let outputs = vec![
+        json!({ "user": { "id": "u1" } }),
+        json!({ "orgs": [{ "id": "o1" }, { "id": "o2" }] }),
+    ];
+    let mut params = json!({
+        "user_id": { "$ref": "0.user.id" },
+        "nested": { "ids": [{ "$ref": "1.orgs.1.id" }, "literal"] },
+        "whole": { "$ref": "0" },
+    });
+
+    resolve_refs(&mut params, &outputs).unwrap();
+
+    assert_eq!(
+        params,
+        json!({
+            "user_id": "u1",
+            "nested": { "ids": ["o2", "literal"] },
+            "whole": { "user": { "id": "u1" } },
+        })
+    );
+}
+
+#[test]
+fn test_resolve_refs_leaves_other_objects_alone() {
+    // an object with more keys than `$ref` is a plain parameter
+    let mut params = json!({ "obj": { "$ref": "0", "other": 1 } });
+    let expected = params.clone();
+
+    resolve_refs(&mut params, &[json!(1)]).unwrap();
+
+    assert_eq!(params, expected);
+}
+
+#[test]
+fn test_resolve_refs_invalid() {
+    let outputs = vec![json!({ "user": { "id": "u1" } })];
+    for invalid in [
+        json!({ "$ref": "1.user.id" }),
+        json!({ "$ref": "first.user" }),
+        json!({ "$ref": "0.org.id" }),
+        json!({ "$ref": "0.user.id.inner" }),
+        json!({ "$ref": 0 }),
+    ] {
+        let mut params = invalid.clone();
+        let err = resolve_refs(&mut params, &outputs).unwrap_err();
+        assert!(matches!(err, GraphError::DecodeError(_)), "{invalid}: {err:?}");
+    }
+}
+
+// ============================================================================
+// Batch Execution Tests
+// ============================================================================
+
+#[test]
+fn test_run_batch_commits_all_steps() {
+    let (graph, _temp_dir) = create_test_graph();
+    let request = batch_request(json!([
+        { "query": "add_node", "params": { "label": "user" } },
+        { "query": "add_node", "params": { "label": "org" } },
+        {
+            "query": "add_edge",
+            "params": { "from": { "$ref": "0.node.id" }, "to": { "$ref": "1.node.id" } }
+        },
+    ]));
+
+    let response = run_batch(&request, &graph.storage, &batch_routes()).unwrap();
+
+    let outputs: Vec<Value> = serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(outputs.len(), 3);
+    assert!(outputs[0]["node"]["id"].is_string());
+    assert!(outputs[2].is_null());
+    assert_eq!(count_label(&graph, "user"), 1);
+    assert_eq!(count_label(&graph, "org"), 1);
+}
+
+#[test]
+fn test_run_batch_steps_see_earlier_writes() {
+    let (graph, _temp_dir) = create_test_graph();
+    let request = batch_request(json!([
+        { "query": "add_node", "params": { "label": "user" } },
+        { "query": "add_node", "params": { "label": "user" } },
+        { "query": "count_users" },
+    ]));
+
+    let response = run_batch(&request, &graph.storage, &batch_routes()).unwrap();
+
+    let outputs: Vec<Value> = serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(outputs[2], json!({ "count": 2 }));
+}
+
+#[test]
+fn test_run_batch_rolls_back_on_failure() {
+    let (graph, _temp_dir) = create_test_graph();
+    let request = batch_request(json!([
+        { "query": "add_node", "params": { "label": "user" } },
+        { "query": "fail" },
+        { "query": "add_node", "params": { "label": "user" } },
+    ]));
+
+    let err = run_batch(&request, &graph.storage, &batch_routes()).unwrap_err();
+
+    assert!(matches!(err, HelixError::BatchStep { step: 1, .. }));
+    assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    assert_eq!(err.code(), "NODE_NOT_FOUND");
+    let details = err.details();
+    assert_eq!(details["step"].as_u64(), Some(1));
+    assert_eq!(details["query"].as_str(), Some("fail"));
+    assert_eq!(count_label(&graph, "user"), 0);
+}
+
+#[test]
+fn test_run_batch_unknown_query() {
+    let (graph, _temp_dir) = create_test_graph();
+    let request = batch_request(json!([
+        { "query": "add_node", "params": { "label": "user" } },
+        { "query": "missing" },
+    ]));
+
+    let err = run_batch(&request, &graph.storage, &batch_routes()).unwrap_err();
+
+    assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    assert_eq!(err.code(), "QUERY_NOT_FOUND");
+    assert_eq!(count_label(&graph, "user"), 0);
+}
+
+#[test]
+fn test_run_batch_invalid_ref_rolls_back() {
+    let (graph, _temp_dir) = create_test_graph();
+    let request = batch_request(json!([
+        { "query": "add_node", "params": { "label": "user" } },
+        {
+            "query": "add_edge",
+            "params": { "from": { "$ref": "0.node.id" }, "to": { "$ref": "2" } }
+        },
+    ]));
+
+    let err = run_batch(&request, &graph.storage, &batch_routes()).unwrap_err();
+
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(err.code(), "DECODE_ERROR");
+    assert_eq!(count_label(&graph, "user"), 0);
+}
+
+#[test]
+fn test_run_batch_rejects_malformed_body() {
+    let (graph, _temp_dir) = create_test_graph();
+    let request = batch_request(json!({ "query": "add_node" }));
+
+    let err = run_batch(&request, &graph.storage, &batch_routes()).unwrap_err();
+
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_worker_pool_processes_batch_requests() {
+    let (graph, _temp_dir) = create_test_graph();
+    let router = Arc::new(HelixRouter::new(None, None).with_batch_routes(batch_routes()));
+    let rt = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap(),
+    );
+    let core_setter = Arc::new(CoreSetter::new(vec![core_affinity::CoreId { id: 0 }], 2));
+    let pool = WorkerPool::new(core_setter, Arc::clone(&graph), router, rt);
+
+    let response = pool
+        .process(batch_request(json!([
+            { "query": "add_node", "params": { "label": "user" } },
+            { "query": "count_users" },
+        ])))
+        .await
+        .unwrap();
+
+    let outputs: Vec<Value> = serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(outputs[1], json!({ "count": 1 }));
+    assert_eq!(count_label(&graph, "user"), 1);
+}
@@ -0,0 +1,225 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::routing::get;
+use bumpalo::Bump;
+use reqwest::StatusCode;
+use serde_json::Value;
+use tempfile::TempDir;
+
+use crate::{
+    helix_engine::{
+        storage_core::HelixGraphStorage,
+        traversal_core::{
+            config::{ChangeLogConfig, Config},
+            ops::{g::G, source::add_n::AddNAdapter},
+        },
+    },
+    helix_gateway::changes::{
+        CHANGES_ROUTE, LAST_EVENT_ID_HEADER, changes_handler, read_changes,
+    },
+    protocol::HelixError,
+};
+
+fn create_test_storage(change_log: Option<ChangeLogConfig>) -> (Arc<HelixGraphStorage>, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        change_log,
+        ..Default::default()
+    };
+    let storage =
+        HelixGraphStorage::new(temp_dir.path().to_str().unwrap(), config, Default::default())
+            .unwrap();
+    (Arc::new(storage), temp_dir)
+}
+
+fn add_node(storage: &HelixGraphStorage, label: &'static str) {
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    G::new_mut(storage, &arena, &mut txn)
+        .add_n(label, None, None)
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+}
+
+async fn spawn_app(storage: Arc<HelixGraphStorage>) -> String {
+    let app = axum::Router::new()
+        .route(CHANGES_ROUTE, get(changes_handler))
+        .with_state(storage);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}{CHANGES_ROUTE}")
+}
+
+struct SseEvent {
+    id: Option<String>,
+    event: String,
+    data: Value,
+}
+
+/// Reads the next `count` events of the stream, skipping keep alive comments
+async fn next_events(response: &mut reqwest::Response, count: usize) -> Vec<SseEvent> {
+    let mut buffer = String::new();
+    let mut events = Vec::new();
+    while events.len() < count {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .expect("timed out waiting for changes")
+            .unwrap()
+            .expect("stream closed");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+
+        while let Some(end) = buffer.find("\n\n") {
+            let block: String = buffer.drain(..end + 2).collect();
+            let field = |name: &str| {
+                block
+                    .lines()
+                    .find_map(|line| line.strip_prefix(name))
+                    .map(|v| v.trim_start().to_string())
+            };
+            if let Some(event) = field("event:") {
+                events.push(SseEvent {
+                    id: field("id:"),
+                    event,
+                    data: serde_json::from_str(&field("data:").unwrap()).unwrap(),
+                });
+            }
+        }
+    }
+    events
+}
+
+#[test]
+fn test_read_changes_expired() {
+    let config = ChangeLogConfig {
+        max_events: Some(2),
+        retention_secs: None,
+    };
+    let (storage, _temp_dir) = create_test_storage(Some(config));
+    for _ in 0..4 {
+        add_node(&storage, "user");
+    }
+
+    assert!(matches!(
+        read_changes(&storage, Some(1), 10),
+        Err(HelixError::ChangesExpired { since: 1, oldest: 3 })
+    ));
+    assert_eq!(read_changes(&storage, Some(2), 10).unwrap().len(), 2);
+    assert!(read_changes(&storage, Some(4), 10).unwrap().is_empty());
+
+    // without a position the changes start from the oldest kept
+    let seqs: Vec<_> = read_changes(&storage, None, 10)
+        .unwrap()
+        .iter()
+        .map(|c| c.seq)
+        .collect();
+    assert_eq!(seqs, vec![3, 4]);
+}
+
+#[test]
+fn test_read_changes_disabled() {
+    let (storage, _temp_dir) = create_test_storage(None);
+
+    let err = read_changes(&storage, None, 10).unwrap_err();
+
+    assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    assert_eq!(err.code(), "CHANGE_LOG_DISABLED");
+}
+
+#[tokio::test]
+async fn test_streams_existing_and_new_changes() {
+    let (storage, _temp_dir) = create_test_storage(Some(ChangeLogConfig::default()));
+    add_node(&storage, "user");
+    let url = spawn_app(Arc::clone(&storage)).await;
+
+    let mut response = reqwest::get(&url).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let events = next_events(&mut response, 1).await;
+    assert_eq!(events[0].id.as_deref(), Some("1"));
+    assert_eq!(events[0].event, "change");
+    assert_eq!(events[0].data["op"], "create");
+    assert_eq!(events[0].data["kind"], "node");
+
+    let writer = Arc::clone(&storage);
+    tokio::task::spawn_blocking(move || add_node(&writer, "org"))
+        .await
+        .unwrap();
+    let events = next_events(&mut response, 1).await;
+    assert_eq!(events[0].id.as_deref(), Some("2"));
+    assert_eq!(events[0].data["label"], "org");
+}
+
+#[tokio::test]
+async fn test_filters_by_label_and_resumes() {
+    let (storage, _temp_dir) = create_test_storage(Some(ChangeLogConfig::default()));
+    for label in ["user", "org", "post", "org", "user"] {
+        add_node(&storage, label);
+    }
+    let url = spawn_app(storage).await;
+    let client = reqwest::Client::new();
+
+    let mut response = client
+        .get(format!("{url}?labels=user,post"))
+        .send()
+        .await
+        .unwrap();
+    let ids: Vec<_> = next_events(&mut response, 3)
+        .await
+        .into_iter()
+        .map(|e| e.id.unwrap())
+        .collect();
+    assert_eq!(ids, vec!["1", "3", "5"]);
+
+    let mut response = client.get(format!("{url}?since=3")).send().await.unwrap();
+    let events = next_events(&mut response, 1).await;
+    assert_eq!(events[0].id.as_deref(), Some("4"));
+
+    // Last-Event-ID is sent on reconnect, so it wins over the original `since`
+    let mut response = client
+        .get(format!("{url}?since=0"))
+        .header(LAST_EVENT_ID_HEADER, "4")
+        .send()
+        .await
+        .unwrap();
+    let events = next_events(&mut response, 1).await;
+    assert_eq!(events[0].id.as_deref(), Some("5"));
+}
+
+#[tokio::test]
+async fn test_subscription_errors() {
+    let config = ChangeLogConfig {
+        max_events: Some(1),
+        retention_secs: None,
+    };
+    let (storage, _temp_dir) = create_test_storage(Some(config));
+    add_node(&storage, "user");
+    add_node(&storage, "user");
+    let url = spawn_app(storage).await;
+    let client = reqwest::Client::new();
+
+    let response = client.get(format!("{url}?since=0")).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::GONE);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["code"], "CHANGES_EXPIRED");
+    assert_eq!(body["details"]["oldest"], 2);
+
+    // only an explicit position can be expired
+    let mut response = client.get(&url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let events = next_events(&mut response, 1).await;
+    assert_eq!(events[0].id.as_deref(), Some("2"));
+
+    let response = client
+        .get(&url)
+        .header(LAST_EVENT_ID_HEADER, "latest")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let (disabled, _temp_dir) = create_test_storage(None);
+    let response = reqwest::get(spawn_app(disabled).await).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
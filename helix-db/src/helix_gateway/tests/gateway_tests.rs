@@ -1,9 +1,13 @@
 use crate::helix_engine::traversal_core::{HelixGraphEngine, HelixGraphEngineOpts};
-use crate::helix_gateway::gateway::{AppState, CoreSetter, GatewayOpts, HelixGateway};
+use crate::helix_gateway::gateway::{
+    AppState, CoreSetter, GatewayOpts, HelixGateway, QUERY_TIMEOUT_HEADER, request_deadline,
+};
 use crate::helix_gateway::router::router::HelixRouter;
 use crate::helix_gateway::worker_pool::WorkerPool;
 use core_affinity::CoreId;
+use axum::http::HeaderMap;
 use std::sync::atomic;
+use std::time::{Duration, Instant};
 use std::{collections::HashMap, sync::Arc};
 
 use crate::helix_engine::traversal_core::config::Config;
@@ -140,12 +144,16 @@ fn test_app_state_creation() {
 
     let cores = core_affinity::get_core_ids().unwrap_or_default();
     let core_setter = Arc::new(CoreSetter::new(cores, 2));
+    let storage = Arc::clone(&graph.storage);
     let worker_pool = WorkerPool::new(core_setter, graph, router, rt);
 
     let state = AppState {
         worker_pool,
+        storage,
+        query_metrics: Default::default(),
         schema_json: None,
         cluster_id: None,
+        query_timeout: None,
     };
 
     assert!(state.schema_json.is_none());
@@ -166,12 +174,16 @@ fn test_app_state_with_schema() {
 
     let cores = core_affinity::get_core_ids().unwrap_or_default();
     let core_setter = Arc::new(CoreSetter::new(cores, 2));
+    let storage = Arc::clone(&graph.storage);
     let worker_pool = WorkerPool::new(core_setter, graph, router, rt);
 
     let state = AppState {
         worker_pool,
+        storage,
+        query_metrics: Default::default(),
         schema_json: Some("{\"schema\": \"test\"}".to_string()),
         cluster_id: None,
+        query_timeout: None,
     };
 
     assert!(state.schema_json.is_some());
@@ -192,12 +204,16 @@ fn test_app_state_with_cluster_id() {
 
     let cores = core_affinity::get_core_ids().unwrap_or_default();
     let core_setter = Arc::new(CoreSetter::new(cores, 2));
+    let storage = Arc::clone(&graph.storage);
     let worker_pool = WorkerPool::new(core_setter, graph, router, rt);
 
     let state = AppState {
         worker_pool,
+        storage,
+        query_metrics: Default::default(),
         schema_json: None,
         cluster_id: Some("cluster-456".to_string()),
+        query_timeout: None,
     };
 
     assert!(state.cluster_id.is_some());
@@ -329,3 +345,52 @@ fn test_core_setter_index_initial_value() {
 fn test_gateway_opts_default_workers_per_core() {
     assert_eq!(GatewayOpts::DEFAULT_WORKERS_PER_CORE, 8);
 }
+
+// ============================================================================
+// Query Timeout Tests
+// ============================================================================
+
+#[test]
+fn test_gateway_with_query_timeout() {
+    let (graph, _temp_dir) = create_test_graph();
+    let gateway = HelixGateway::new("127.0.0.1:8080", graph, 8, None, None, None)
+        .with_query_timeout(Some(Duration::from_secs(5)));
+    assert_eq!(gateway.query_timeout, Some(Duration::from_secs(5)));
+}
+
+#[test]
+fn test_request_deadline_default() {
+    let start = Instant::now();
+    let headers = HeaderMap::new();
+
+    assert_eq!(request_deadline(&headers, None, start).unwrap(), None);
+    assert_eq!(
+        request_deadline(&headers, Some(Duration::from_secs(2)), start).unwrap(),
+        Some(start + Duration::from_secs(2))
+    );
+}
+
+#[test]
+fn test_request_deadline_header_overrides_default() {
+    let start = Instant::now();
+    let mut headers = HeaderMap::new();
+    headers.insert(QUERY_TIMEOUT_HEADER, "250".parse().unwrap());
+
+    assert_eq!(
+        request_deadline(&headers, Some(Duration::from_secs(2)), start).unwrap(),
+        Some(start + Duration::from_millis(250))
+    );
+    assert_eq!(
+        request_deadline(&headers, None, start).unwrap(),
+        Some(start + Duration::from_millis(250))
+    );
+}
+
+#[test]
+fn test_request_deadline_invalid_header() {
+    let mut headers = HeaderMap::new();
+    headers.insert(QUERY_TIMEOUT_HEADER, "soon".parse().unwrap());
+
+    let err = request_deadline(&headers, None, Instant::now()).unwrap_err();
+    assert_eq!(err.status(), 400);
+}
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::Response};
+use bumpalo::Bump;
+use heed3::RoTxn;
+use reqwest::StatusCode;
+use serde_json::Value;
+use tempfile::TempDir;
+
+use crate::{
+    helix_engine::{
+        storage_core::{metadata::STORAGE_VERSION_KEY, storage_methods::StorageMethods},
+        traversal_core::{
+            HelixGraphEngine, HelixGraphEngineOpts,
+            config::Config,
+            ops::{
+                g::G,
+                source::{add_e::AddEAdapter, add_n::AddNAdapter},
+                vectors::insert::InsertVAdapter,
+            },
+            traversal_value::TraversalValue,
+        },
+        vector_core::vector::HVector,
+    },
+    helix_gateway::{
+        gateway::{AppState, CoreSetter},
+        health::{health_handler, ready_handler, stats_handler},
+        router::router::HelixRouter,
+        worker_pool::WorkerPool,
+    },
+    protocol::value::Value as PropValue,
+    utils::properties::ImmutablePropertiesMap,
+};
+
+type Filter = fn(&HVector, &RoTxn) -> bool;
+
+fn create_test_app_state() -> (Arc<AppState>, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let opts = HelixGraphEngineOpts {
+        path: temp_dir.path().to_str().unwrap().to_string(),
+        config: Config {
+            db_max_size_gb: Some(1),
+            ..Default::default()
+        },
+        version_info: Default::default(),
+    };
+    let graph = Arc::new(HelixGraphEngine::new(opts).unwrap());
+    let router = Arc::new(HelixRouter::new(None, None));
+    let rt = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap(),
+    );
+    let core_setter = Arc::new(CoreSetter::new(vec![core_affinity::CoreId { id: 0 }], 2));
+    let storage = Arc::clone(&graph.storage);
+    let worker_pool = WorkerPool::new(core_setter, graph, router, rt);
+
+    let state = AppState {
+        worker_pool,
+        storage,
+        query_metrics: Default::default(),
+        schema_json: None,
+        cluster_id: None,
+        query_timeout: None,
+    };
+    (Arc::new(state), temp_dir)
+}
+
+async fn json_body(response: Response) -> Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_health() {
+    let response = health_handler().await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["status"], "ok");
+}
+
+#[tokio::test]
+async fn test_ready_after_migrations() {
+    let (state, _temp_dir) = create_test_app_state();
+
+    let response = ready_handler(State(state)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(json_body(response).await["status"], "ready");
+}
+
+#[tokio::test]
+async fn test_not_ready_before_migrations() {
+    let (state, _temp_dir) = create_test_app_state();
+    let mut txn = state.storage.graph_env.write_txn().unwrap();
+    state
+        .storage
+        .metadata_db
+        .delete(&mut txn, STORAGE_VERSION_KEY)
+        .unwrap();
+    txn.commit().unwrap();
+
+    let response = ready_handler(State(state)).await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(json_body(response).await["code"], "NOT_READY");
+}
+
+#[tokio::test]
+async fn test_stats_counts_by_label() {
+    let (state, _temp_dir) = create_test_app_state();
+    let storage = &state.storage;
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    let mut add_node = |label| {
+        G::new_mut(storage, &arena, &mut txn)
+            .add_n(label, None, None)
+            .collect_to_obj()
+            .unwrap()
+            .id()
+    };
+    let user = add_node("user");
+    let org = add_node("org");
+    add_node("user");
+    G::new_mut(storage, &arena, &mut txn)
+        .add_edge("member_of", None, user, org, false)
+        .collect_to_obj()
+        .unwrap();
+    let props = ImmutablePropertiesMap::new(
+        1,
+        [("text", PropValue::from("helix graph vector database"))].into_iter(),
+        &arena,
+    );
+    G::new_mut(storage, &arena, &mut txn)
+        .add_n("doc", Some(props), None)
+        .collect_to_obj()
+        .unwrap();
+    let mut insert_vector = || match G::new_mut(storage, &arena, &mut txn)
+        .insert_v::<Filter>(&[1.0, 1.0, 1.0], "embedding", None)
+        .collect_to_obj()
+        .unwrap()
+    {
+        TraversalValue::Vector(vector) => vector.id,
+        other => panic!("unexpected value: {other:?}"),
+    };
+    insert_vector();
+    let deleted = insert_vector();
+    storage.drop_vector(&mut txn, &deleted).unwrap();
+    txn.commit().unwrap();
+
+    let response = stats_handler(State(Arc::clone(&state))).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let stats = json_body(response).await;
+    assert_eq!(stats["nodes"]["total"], 4);
+    assert_eq!(stats["nodes"]["by_label"]["user"], 2);
+    assert_eq!(stats["nodes"]["by_label"]["org"], 1);
+    assert_eq!(stats["edges"]["by_label"]["member_of"], 1);
+    assert_eq!(stats["vectors"]["total"], 1);
+    assert_eq!(stats["vectors"]["by_label"]["embedding"], 1);
+    assert!(stats["num_inserted_vectors"].as_u64().unwrap() >= 2);
+    assert_eq!(stats["bm25"]["total_docs"], 1);
+    assert!(stats["bm25"]["avgdl"].as_f64().unwrap() > 0.0);
+    assert!(stats["disk_size_bytes"].as_u64().unwrap() > 0);
+    assert_eq!(stats["max_size_bytes"], 1024 * 1024 * 1024);
+}
+
+#[tokio::test]
+async fn test_stats_are_reused_within_ttl() {
+    let (state, _temp_dir) = create_test_app_state();
+    let storage = &state.storage;
+    assert_eq!(storage.stats().unwrap().nodes.total, 0);
+
+    let arena = Bump::new();
+    let mut txn = storage.graph_env.write_txn().unwrap();
+    G::new_mut(storage, &arena, &mut txn)
+        .add_n("user", None, None)
+        .collect_to_obj()
+        .unwrap();
+    txn.commit().unwrap();
+
+    // the node is only counted once the cached stats expire
+    let response = stats_handler(State(Arc::clone(&state))).await;
+    assert_eq!(json_body(response).await["nodes"]["total"], 0);
+}
@@ -34,12 +34,16 @@ fn create_test_app_state(schema_json: Option<String>) -> Arc<AppState> {
 
     let cores = core_affinity::get_core_ids().unwrap_or_default();
     let core_setter = Arc::new(CoreSetter::new(cores, 2));
+    let storage = Arc::clone(&graph.storage);
     let worker_pool = WorkerPool::new(core_setter, graph, router, rt);
 
     Arc::new(AppState {
         worker_pool,
+        storage,
+        query_metrics: Default::default(),
         schema_json,
         cluster_id: None,
+        query_timeout: None,
     })
 }
 
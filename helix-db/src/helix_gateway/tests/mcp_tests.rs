@@ -171,6 +171,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -222,6 +223,10 @@ mod mcp_tests {
         assert!(handler_names.contains(&"filter_items"));
         assert!(handler_names.contains(&"order_by"));
         assert!(handler_names.contains(&"search_keyword"));
+        assert!(handler_names.contains(&"degree"));
+        assert!(handler_names.contains(&"betweenness"));
+        assert!(handler_names.contains(&"triangles"));
+        assert!(handler_names.contains(&"ego_graph"));
     }
 
     #[test]
@@ -318,6 +323,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -374,6 +380,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -428,6 +435,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -483,6 +491,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -527,6 +536,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -578,6 +588,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -646,6 +657,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -713,6 +725,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1200,6 +1213,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1237,6 +1251,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1278,6 +1293,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1348,6 +1364,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1383,6 +1400,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1425,6 +1443,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1461,6 +1480,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1497,6 +1517,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1539,6 +1560,7 @@ mod mcp_tests {
             body: request_body,
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let mut input = MCPToolInput {
@@ -1551,4 +1573,182 @@ mod mcp_tests {
         let response = search_vector_text(&mut input);
         assert!(response.is_err());
     }
+
+    // ============================================================================
+    // Degree and Centrality Tests
+    // ============================================================================
+
+    #[test]
+    fn test_degree_handler_http() {
+        use crate::helix_gateway::mcp::mcp::degree;
+
+        let (engine, _temp_dir) = setup_engine();
+        let mut txn = engine.storage.graph_env.write_txn().unwrap();
+        let arena = Bump::new();
+
+        let person1 = G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+            .add_n("person", None, None)
+            .collect_to_obj().unwrap();
+        for _ in 0..3 {
+            let person = G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+                .add_n("person", None, None)
+                .collect_to_obj().unwrap();
+            G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+                .add_edge("knows", None, person1.id(), person.id(), false)
+                .collect_to_obj().unwrap();
+        }
+        txn.commit().unwrap();
+
+        let backend = Arc::new(McpBackend::new(Arc::clone(&engine.storage)));
+        let connections = Arc::new(Mutex::new(McpConnections::new()));
+
+        let mut connection = MCPConnection::new("conn_degree".to_string());
+        connection.add_query_step(ToolArgs::NFromType {
+            node_type: "person".to_string(),
+        });
+        connections.lock().unwrap().add_connection(connection);
+
+        let request_body = Bytes::from(
+            r#"{"connection_id":"conn_degree","edge_label":"knows","direction":"both"}"#
+                .to_string(),
+        );
+
+        let request = Request {
+            name: "degree".to_string(),
+            req_type: RequestType::MCP,
+            body: request_body,
+            in_fmt: Format::Json,
+            out_fmt: Format::Json,
+            stream: false,
+        };
+
+        let mut input = MCPToolInput {
+            request,
+            mcp_backend: backend,
+            mcp_connections: Arc::clone(&connections),
+            schema: None,
+        };
+
+        let response = degree(&mut input).unwrap();
+        let body = String::from_utf8(response.body.clone()).unwrap();
+        // every edge is counted once from each end
+        assert_eq!(body, "6");
+    }
+
+    #[test]
+    fn test_betweenness_tool_step() {
+        let (engine, _temp_dir) = setup_engine();
+        let mut txn = engine.storage.graph_env.write_txn().unwrap();
+        let arena = Bump::new();
+
+        let ids = (0..3)
+            .map(|_| {
+                G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+                    .add_n("person", None, None)
+                    .collect_to_obj()
+                    .unwrap()
+                    .id()
+            })
+            .collect::<Vec<_>>();
+        G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+            .add_edge("knows", None, ids[0], ids[1], false)
+            .collect_to_obj().unwrap();
+        G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+            .add_edge("knows", None, ids[1], ids[2], false)
+            .collect_to_obj().unwrap();
+        txn.commit().unwrap();
+
+        let storage = engine.storage.as_ref();
+        let arena = Bump::new();
+        let txn = storage.graph_env.read_txn().unwrap();
+
+        let steps = vec![
+            ToolArgs::NFromType {
+                node_type: "person".to_string(),
+            },
+            ToolArgs::Betweenness {
+                edge_label: "knows".to_string(),
+                samples: None,
+            },
+        ];
+
+        let results =
+            crate::helix_gateway::mcp::tools::execute_query_chain(&steps, storage, &txn, &arena)
+                .unwrap()
+                .collect()
+                .unwrap();
+
+        assert_eq!(results.len(), 3);
+        let TraversalValue::NodeWithScore { node, score } = &results[0] else {
+            panic!("expected scored node result");
+        };
+        assert_eq!(node.id, ids[1]);
+        assert_eq!(*score, 1.0);
+    }
+
+    #[test]
+    fn test_ego_graph_handler_http() {
+        use crate::helix_gateway::mcp::mcp::ego_graph;
+        use sonic_rs::{JsonContainerTrait, JsonValueTrait};
+
+        let (engine, _temp_dir) = setup_engine();
+        let mut txn = engine.storage.graph_env.write_txn().unwrap();
+        let arena = Bump::new();
+
+        // a -> b -> c
+        let ids = (0..3)
+            .map(|_| {
+                G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+                    .add_n("person", None, None)
+                    .collect_to_obj()
+                    .unwrap()
+                    .id()
+            })
+            .collect::<Vec<_>>();
+        G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+            .add_edge("knows", None, ids[0], ids[1], false)
+            .collect_to_obj().unwrap();
+        G::new_mut(engine.storage.as_ref(), &arena, &mut txn)
+            .add_edge("knows", None, ids[1], ids[2], false)
+            .collect_to_obj().unwrap();
+        txn.commit().unwrap();
+
+        let backend = Arc::new(McpBackend::new(Arc::clone(&engine.storage)));
+        let connections = Arc::new(Mutex::new(McpConnections::new()));
+
+        let mut connection = MCPConnection::new("conn_ego".to_string());
+        connection.add_query_step(ToolArgs::NFromType {
+            node_type: "person".to_string(),
+        });
+        connections.lock().unwrap().add_connection(connection);
+
+        let request_body = Bytes::from(
+            r#"{"connection_id":"conn_ego","edge_labels":["knows"],"hops":1}"#
+                .to_string(),
+        );
+
+        let request = Request {
+            name: "ego_graph".to_string(),
+            req_type: RequestType::MCP,
+            body: request_body,
+            in_fmt: Format::Json,
+            out_fmt: Format::Json,
+            stream: false,
+        };
+
+        let mut input = MCPToolInput {
+            request,
+            mcp_backend: backend,
+            mcp_connections: Arc::clone(&connections),
+            schema: None,
+        };
+
+        let response = ego_graph(&mut input).unwrap();
+        let body: sonic_rs::Value = sonic_rs::from_slice(&response.body).unwrap();
+        let nodes = body["nodes"].as_array().unwrap();
+        let edges = body["edges"].as_array().unwrap();
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(edges.len(), 2);
+        assert!(edges[0]["from"].as_str().is_some());
+    }
 }
@@ -0,0 +1,146 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::extract::State;
+use bumpalo::Bump;
+use reqwest::StatusCode;
+use tempfile::TempDir;
+
+use crate::{
+    helix_engine::traversal_core::{
+        HelixGraphEngine, HelixGraphEngineOpts,
+        config::Config,
+        ops::{g::G, source::add_n::AddNAdapter},
+    },
+    helix_gateway::{
+        gateway::{AppState, CoreSetter},
+        metrics::{PROMETHEUS_CONTENT_TYPE, metrics_handler, render_metrics},
+        router::router::HelixRouter,
+        worker_pool::WorkerPool,
+    },
+};
+
+fn create_test_app_state() -> (Arc<AppState>, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let opts = HelixGraphEngineOpts {
+        path: temp_dir.path().to_str().unwrap().to_string(),
+        config: Config::default(),
+        version_info: Default::default(),
+    };
+    let graph = Arc::new(HelixGraphEngine::new(opts).unwrap());
+    let router = Arc::new(HelixRouter::new(None, None));
+    let rt = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap(),
+    );
+    let core_setter = Arc::new(CoreSetter::new(vec![core_affinity::CoreId { id: 0 }], 2));
+    let storage = Arc::clone(&graph.storage);
+    let worker_pool = WorkerPool::new(core_setter, graph, router, rt);
+
+    let state = AppState {
+        worker_pool,
+        storage,
+        query_metrics: Default::default(),
+        schema_json: None,
+        cluster_id: None,
+        query_timeout: None,
+    };
+    (Arc::new(state), temp_dir)
+}
+
+/// Value of the sample with exactly this name and labels
+fn sample(metrics: &str, series: &str) -> Option<f64> {
+    metrics.lines().find_map(|line| {
+        let (name, value) = line.rsplit_once(' ')?;
+        (name == series).then(|| value.parse().unwrap())
+    })
+}
+
+#[test]
+fn test_query_latency_histogram() {
+    let (state, _temp_dir) = create_test_app_state();
+    let metrics = &state.query_metrics;
+    metrics.record("get_user", Duration::from_micros(300), None);
+    metrics.record("get_user", Duration::from_millis(20), None);
+    metrics.record("get_user", Duration::from_secs(30), Some("REQUEST_TIMEOUT"));
+
+    let out = render_metrics(&state).unwrap();
+
+    let bucket = |le: &str| {
+        let series = format!(r#"helix_query_duration_seconds_bucket{{query="get_user",le="{le}"}}"#);
+        sample(&out, &series)
+    };
+    assert_eq!(bucket("0.0005"), Some(1.0));
+    assert_eq!(bucket("0.01"), Some(1.0));
+    assert_eq!(bucket("0.025"), Some(2.0));
+    assert_eq!(bucket("10"), Some(2.0));
+    assert_eq!(bucket("+Inf"), Some(3.0));
+    assert_eq!(
+        sample(&out, r#"helix_query_duration_seconds_count{query="get_user"}"#),
+        Some(3.0)
+    );
+    assert_eq!(
+        sample(&out, r#"helix_query_errors_total{query="get_user",code="REQUEST_TIMEOUT"}"#),
+        Some(1.0)
+    );
+    assert!(out.contains("# TYPE helix_query_duration_seconds histogram"));
+}
+
+#[test]
+fn test_label_values_are_escaped() {
+    let (state, _temp_dir) = create_test_app_state();
+    state
+        .query_metrics
+        .record("a\"b\\c\nd", Duration::from_millis(1), None);
+
+    let out = render_metrics(&state).unwrap();
+
+    assert_eq!(
+        sample(&out, r#"helix_query_duration_seconds_count{query="a\"b\\c\nd"}"#),
+        Some(1.0)
+    );
+}
+
+#[test]
+fn test_storage_and_worker_metrics() {
+    let (state, _temp_dir) = create_test_app_state();
+    let arena = Bump::new();
+    let mut txn = state.storage.graph_env.write_txn().unwrap();
+    for _ in 0..3 {
+        G::new_mut(&state.storage, &arena, &mut txn)
+            .add_n("user", None, None)
+            .collect_to_obj()
+            .unwrap();
+    }
+    txn.commit().unwrap();
+
+    let out = render_metrics(&state).unwrap();
+
+    assert_eq!(sample(&out, "helix_nodes"), Some(3.0));
+    assert_eq!(sample(&out, "helix_edges"), Some(0.0));
+    assert_eq!(sample(&out, "helix_hnsw_vectors"), Some(0.0));
+    assert!(sample(&out, "helix_lmdb_map_size_bytes").unwrap() > 0.0);
+    assert!(sample(&out, "helix_lmdb_used_bytes").unwrap() > 0.0);
+    assert!(sample(&out, "helix_lmdb_readers").is_some());
+    assert!(sample(&out, "helix_bm25_documents").is_some());
+    assert_eq!(sample(&out, r#"helix_worker_queue_depth{queue="requests"}"#), Some(0.0));
+    assert_eq!(
+        sample(&out, r#"helix_worker_queue_depth{queue="continuations"}"#),
+        Some(0.0)
+    );
+}
+
+#[tokio::test]
+async fn test_metrics_handler_content_type() {
+    let (state, _temp_dir) = create_test_app_state();
+
+    let response = metrics_handler(State(state)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        PROMETHEUS_CONTENT_TYPE
+    );
+}
@@ -1,7 +1,13 @@
+pub mod auth_tests;
+pub mod batch_tests;
+pub mod changes_tests;
 pub mod embedding_providers;
 pub mod gateway_tests;
+pub mod health_tests;
 pub mod introspect_schema_tests;
 pub mod mcp_tests;
+pub mod metrics_tests;
 pub mod router_tests;
+pub mod stream_tests;
 pub mod worker_pool_tests;
 pub mod worker_pool_concurrency_tests;
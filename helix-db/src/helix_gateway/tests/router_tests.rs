@@ -27,6 +27,7 @@ fn test_handler(_input: HandlerInput) -> Result<Response, GraphError> {
     Ok(Response {
         body: b"test response".to_vec(),
         fmt: Format::Json,
+        stream: None,
     })
 }
 
@@ -38,6 +39,7 @@ fn echo_handler(input: HandlerInput) -> Result<Response, GraphError> {
     Ok(Response {
         body: input.request.name.as_bytes().to_vec(),
         fmt: Format::Json,
+        stream: None,
     })
 }
 
@@ -144,6 +146,7 @@ fn test_handler_invocation_success() {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         },
         graph: graph.clone(),
     };
@@ -168,6 +171,7 @@ fn test_handler_invocation_error() {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         },
         graph: graph.clone(),
     };
@@ -191,6 +195,7 @@ fn test_handler_invocation_echo() {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         },
         graph: graph.clone(),
     };
@@ -221,6 +226,7 @@ fn test_handler_input_creation() {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         },
         graph: graph.clone(),
     };
@@ -240,6 +246,7 @@ fn test_handler_input_with_body() {
             body: Bytes::from(body_data.clone()),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         },
         graph: graph.clone(),
     };
@@ -302,3 +309,87 @@ fn test_router_new_with_mcp_routes() {
     assert!(router.routes.is_empty());
     assert!(router.mcp_routes.is_empty());
 }
+
+// ============================================================================
+// Wire Format Round-Trip Tests
+// ============================================================================
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct GreetInput {
+    name: String,
+    scores: Vec<f64>,
+}
+
+// Mirrors the shape of a generated handler: decode with the request format,
+// reply with the negotiated response format.
+fn greet_handler(input: HandlerInput) -> Result<Response, GraphError> {
+    let data = input
+        .request
+        .in_fmt
+        .deserialize::<GreetInput>(&input.request.body)?;
+    let response = sonic_rs::json!({
+        "greeting": format!("hello {}", data.name),
+        "total": data.scores.iter().sum::<f64>(),
+    });
+    Ok(input.request.out_fmt.create_response(&response))
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq)]
+struct GreetOutput {
+    greeting: String,
+    total: f64,
+}
+
+fn greet_request(in_fmt: Format, out_fmt: Format, graph: Arc<HelixGraphEngine>) -> HandlerInput {
+    let data = GreetInput {
+        name: "helix".to_string(),
+        scores: vec![1.5, 2.5],
+    };
+    let body = in_fmt.serialize(&data).into_owned();
+    HandlerInput {
+        request: Request {
+            name: "greet".to_string(),
+            req_type: RequestType::Query,
+            body: Bytes::from(body),
+            in_fmt,
+            out_fmt,
+            stream: false,
+        },
+        graph,
+    }
+}
+
+#[test]
+fn test_handler_round_trip_all_formats() {
+    let (graph, _temp_dir) = create_test_graph();
+    for fmt in [Format::Json, Format::MessagePack, Format::Cbor] {
+        let response = greet_handler(greet_request(fmt, fmt, graph.clone())).unwrap();
+        assert_eq!(response.fmt, fmt);
+        let output: GreetOutput = fmt.deserialize_owned(&response.body).unwrap();
+        assert_eq!(
+            output,
+            GreetOutput {
+                greeting: "hello helix".to_string(),
+                total: 4.0,
+            }
+        );
+    }
+}
+
+#[test]
+fn test_handler_round_trip_mixed_formats() {
+    let (graph, _temp_dir) = create_test_graph();
+    let response =
+        greet_handler(greet_request(Format::MessagePack, Format::Cbor, graph)).unwrap();
+    assert_eq!(response.fmt, Format::Cbor);
+    let output: GreetOutput = Format::Cbor.deserialize_owned(&response.body).unwrap();
+    assert_eq!(output.greeting, "hello helix");
+}
+
+#[test]
+fn test_handler_rejects_mismatched_format() {
+    let (graph, _temp_dir) = create_test_graph();
+    let mut input = greet_request(Format::Cbor, Format::Json, graph);
+    input.request.in_fmt = Format::MessagePack;
+    assert!(greet_handler(input).is_err());
+}
@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{body::Bytes, response::IntoResponse};
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use tempfile::TempDir;
+
+use crate::{
+    helix_engine::{
+        traversal_core::{HelixGraphEngine, HelixGraphEngineOpts, config::Config},
+        types::GraphError,
+    },
+    helix_gateway::{
+        gateway::CoreSetter,
+        router::router::{HandlerInput, HelixRouter},
+        worker_pool::WorkerPool,
+    },
+    protocol::{
+        Format, HelixError, Request, request::RequestType, response::Response,
+        stream::{ResponseStream, STREAM_SEND_TIMEOUT},
+    },
+};
+
+/// Enough items to fill several chunks
+const ITEMS: u64 = 20_000;
+
+#[derive(Serialize)]
+struct Item {
+    i: u64,
+}
+
+/// Streams `count` items when the client accepts it, failing afterwards if `fail` is set
+fn export(input: &HandlerInput, count: u64, fail: bool) -> Result<Response, GraphError> {
+    let items = (0..count).map(|i| Item { i });
+    match ResponseStream::requested(&input.request) {
+        Some(mut stream) => {
+            for item in items {
+                stream.write(&item)?;
+            }
+            if fail {
+                return Err(GraphError::New("export failed".to_string()));
+            }
+            stream.finish()
+        }
+        None => Ok(input
+            .request
+            .out_fmt
+            .create_response(&items.collect::<Vec<_>>())),
+    }
+}
+
+fn export_handler(input: HandlerInput) -> Result<Response, GraphError> {
+    export(&input, ITEMS, false)
+}
+
+fn fail_late_handler(input: HandlerInput) -> Result<Response, GraphError> {
+    export(&input, ITEMS, true)
+}
+
+fn fail_early_handler(input: HandlerInput) -> Result<Response, GraphError> {
+    export(&input, 1, true)
+}
+
+fn endless_handler(input: HandlerInput) -> Result<Response, GraphError> {
+    export(&input, u64::MAX, false)
+}
+
+fn create_pool() -> (WorkerPool, TempDir) {
+    create_pool_with_send_timeout(STREAM_SEND_TIMEOUT)
+}
+
+fn create_pool_with_send_timeout(send_timeout: Duration) -> (WorkerPool, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let opts = HelixGraphEngineOpts {
+        path: temp_dir.path().to_str().unwrap().to_string(),
+        config: Config::default(),
+        version_info: Default::default(),
+    };
+    let graph = Arc::new(HelixGraphEngine::new(opts).unwrap());
+
+    let mut routes = HashMap::new();
+    routes.insert("export".to_string(), Arc::new(export_handler) as Arc<_>);
+    routes.insert("fail_late".to_string(), Arc::new(fail_late_handler) as Arc<_>);
+    routes.insert("fail_early".to_string(), Arc::new(fail_early_handler) as Arc<_>);
+    routes.insert("endless".to_string(), Arc::new(endless_handler) as Arc<_>);
+    let router =
+        Arc::new(HelixRouter::new(Some(routes), None).with_stream_send_timeout(send_timeout));
+
+    let rt = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap(),
+    );
+    let core_setter = Arc::new(CoreSetter::new(vec![core_affinity::CoreId { id: 0 }], 2));
+    (WorkerPool::new(core_setter, graph, router, rt), temp_dir)
+}
+
+fn request(name: &str, out_fmt: Format, stream: bool) -> Request {
+    Request {
+        name: name.to_string(),
+        req_type: RequestType::Query,
+        body: Bytes::new(),
+        in_fmt: Format::Json,
+        out_fmt,
+        stream,
+    }
+}
+
+async fn body_bytes(response: Response) -> Result<Bytes, axum::Error> {
+    axum::body::to_bytes(response.into_response().into_body(), usize::MAX).await
+}
+
+#[tokio::test]
+async fn test_streamed_ndjson() {
+    let (pool, _temp_dir) = create_pool();
+
+    let response = pool.process(request("export", Format::Json, true)).await.unwrap();
+
+    assert!(response.stream.is_some());
+    let http = response.into_response();
+    assert_eq!(http.headers()[CONTENT_TYPE], "application/x-ndjson");
+    let body = axum::body::to_bytes(http.into_body(), usize::MAX).await.unwrap();
+    let lines: Vec<_> = std::str::from_utf8(&body).unwrap().lines().collect();
+    assert_eq!(lines.len() as u64, ITEMS);
+    for (i, line) in lines.iter().enumerate() {
+        let item: serde_json::Value = serde_json::from_str(line).unwrap();
+        assert_eq!(item["i"], i as u64);
+    }
+}
+
+#[tokio::test]
+async fn test_streamed_msgpack_is_length_prefixed() {
+    let (pool, _temp_dir) = create_pool();
+
+    let response = pool
+        .process(request("export", Format::MessagePack, true))
+        .await
+        .unwrap();
+
+    let body = body_bytes(response).await.unwrap();
+    let mut rest = &body[..];
+    let mut count = 0;
+    while !rest.is_empty() {
+        let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+        let item: serde_json::Value = rmp_serde::from_slice(&rest[4..4 + len]).unwrap();
+        assert_eq!(item["i"], count);
+        rest = &rest[4 + len..];
+        count += 1;
+    }
+    assert_eq!(count, ITEMS);
+}
+
+#[tokio::test]
+async fn test_not_streamed_without_opt_in() {
+    let (pool, _temp_dir) = create_pool();
+
+    let response = pool.process(request("export", Format::Json, false)).await.unwrap();
+
+    assert!(response.stream.is_none());
+    let items: Vec<serde_json::Value> = serde_json::from_slice(&response.body).unwrap();
+    assert_eq!(items.len() as u64, ITEMS);
+}
+
+#[tokio::test]
+async fn test_error_before_first_chunk_is_returned() {
+    let (pool, _temp_dir) = create_pool();
+
+    let result = pool.process(request("fail_early", Format::Json, true)).await;
+
+    match result {
+        Err(HelixError::Graph(GraphError::New(msg))) => assert_eq!(msg, "export failed"),
+        other => panic!("expected the handler's error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_error_after_first_chunk_aborts_body() {
+    let (pool, _temp_dir) = create_pool();
+
+    let response = pool.process(request("fail_late", Format::Json, true)).await.unwrap();
+
+    assert!(body_bytes(response).await.is_err());
+}
+
+#[tokio::test]
+async fn test_endless_stream_hits_deadline() {
+    let (pool, _temp_dir) = create_pool();
+
+    let deadline = Instant::now() + Duration::from_millis(300);
+    let response = pool
+        .process_with_deadline(request("endless", Format::Json, true), Some(deadline))
+        .await
+        .unwrap();
+
+    // the handler is blocked on the full channel when its deadline passes
+    tokio::time::sleep(Duration::from_millis(600)).await;
+    assert!(body_bytes(response).await.is_err());
+}
+
+#[tokio::test]
+async fn test_stream_fails_when_client_stops_reading() {
+    let (pool, _temp_dir) = create_pool_with_send_timeout(Duration::from_millis(300));
+
+    // no deadline, so only the send timeout stops the handler
+    let response = pool.process(request("endless", Format::Json, true)).await.unwrap();
+
+    // reading the body would let the handler go on, so wait for it to give up first
+    let chunks = response.stream.clone().unwrap();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !chunks.is_disconnected() {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("the handler should give up on the stream");
+    assert!(body_bytes(response).await.is_err());
+}
@@ -31,6 +31,7 @@ fn test_handler(_input: HandlerInput) -> Result<Response, GraphError> {
     Ok(Response {
         body: b"test response".to_vec(),
         fmt: Format::Json,
+        stream: None,
     })
 }
 
@@ -45,6 +46,7 @@ fn create_test_request(name: &str, req_type: RequestType) -> Request {
         body: Bytes::new(),
         in_fmt: Format::Json,
         out_fmt: Format::Json,
+        stream: false,
     }
 }
 
@@ -587,6 +589,7 @@ async fn test_request_with_body_data() {
         body: Bytes::from(vec![1, 2, 3, 4]),
         in_fmt: Format::Json,
         out_fmt: Format::Json,
+        stream: false,
     };
 
     let result = pool.process(request).await;
@@ -646,6 +649,7 @@ async fn test_request_format_json() {
         body: Bytes::new(),
         in_fmt: Format::Json,
         out_fmt: Format::Json,
+        stream: false,
     };
 
     let result = pool.process(request).await;
@@ -791,6 +795,7 @@ async fn test_handler_receives_correct_request() {
         Ok(Response {
             body: input.request.name.as_bytes().to_vec(),
             fmt: Format::Json,
+            stream: None,
         })
     }
 
@@ -832,6 +837,7 @@ async fn test_handler_receives_graph_access() {
         Ok(Response {
             body: b"graph_accessed".to_vec(),
             fmt: Format::Json,
+            stream: None,
         })
     }
 
@@ -1140,6 +1146,7 @@ async fn test_request_with_large_body() {
         body: Bytes::from(large_body),
         in_fmt: Format::Json,
         out_fmt: Format::Json,
+        stream: false,
     };
 
     let result = pool.process(request).await;
@@ -1242,6 +1249,7 @@ async fn test_request_type_query_explicit() {
         body: Bytes::new(),
         in_fmt: Format::Json,
         out_fmt: Format::Json,
+        stream: false,
     };
 
     let result = pool.process(request).await;
@@ -1314,6 +1322,7 @@ async fn test_response_with_custom_body() {
         Ok(Response {
             body: b"custom response data".to_vec(),
             fmt: Format::Json,
+            stream: None,
         })
     }
 
@@ -1643,3 +1652,79 @@ async fn test_rapid_fire_requests() {
 
     assert!(all_ok);
 }
+
+// ============================================================================
+// Deadline Tests
+// ============================================================================
+
+// Spins until the worker's deadline passes, like a long traversal would
+fn spinning_handler(_input: HandlerInput) -> Result<Response, GraphError> {
+    loop {
+        crate::helix_engine::deadline::check_deadline()?;
+        std::thread::sleep(std::time::Duration::from_millis(1));
+    }
+}
+
+fn create_deadline_pool() -> (WorkerPool, TempDir) {
+    let (graph, temp_dir) = create_test_graph();
+    let mut routes = std::collections::HashMap::new();
+    routes.insert("test_query".to_string(), Arc::new(test_handler) as Arc<_>);
+    routes.insert("spin".to_string(), Arc::new(spinning_handler) as Arc<_>);
+    let router = Arc::new(HelixRouter::new(Some(routes), None));
+
+    let rt = Arc::new(
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .unwrap(),
+    );
+
+    let cores = vec![core_affinity::CoreId { id: 0 }];
+    let core_setter = Arc::new(CoreSetter::new(cores, 2));
+    (WorkerPool::new(core_setter, graph, router, rt), temp_dir)
+}
+
+#[tokio::test]
+async fn test_process_with_deadline_cancels_running_query() {
+    let (pool, _temp_dir) = create_deadline_pool();
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(500);
+    let request = create_test_request("spin", RequestType::Query);
+    let result = pool.process_with_deadline(request, Some(deadline)).await;
+
+    match result {
+        Err(e @ HelixError::Graph(GraphError::QueryTimeout)) => {
+            assert_eq!(e.status(), 504);
+        }
+        other => panic!("expected a query timeout, got {other:?}"),
+    }
+
+    // the worker is free again and the deadline doesn't leak into the next request
+    let request = create_test_request("test_query", RequestType::Query);
+    assert!(pool.process(request).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_process_with_expired_deadline_is_not_started() {
+    let (pool, _temp_dir) = create_deadline_pool();
+
+    let deadline = std::time::Instant::now() - std::time::Duration::from_millis(1);
+    let request = create_test_request("test_query", RequestType::Query);
+    let result = pool.process_with_deadline(request, Some(deadline)).await;
+
+    match result {
+        Err(e @ HelixError::RequestTimeout { .. }) => assert_eq!(e.status(), 408),
+        other => panic!("expected a request timeout, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_process_with_future_deadline_succeeds() {
+    let (pool, _temp_dir) = create_deadline_pool();
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(60);
+    let request = create_test_request("test_query", RequestType::Query);
+    let result = pool.process_with_deadline(request, Some(deadline)).await;
+    assert_eq!(result.unwrap().body, b"test response");
+}
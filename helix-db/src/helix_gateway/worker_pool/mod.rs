@@ -1,5 +1,8 @@
-use crate::helix_engine::{traversal_core::HelixGraphEngine, types::GraphError};
+use crate::helix_engine::{
+    deadline::set_deadline, traversal_core::HelixGraphEngine, types::GraphError,
+};
 use crate::helix_gateway::{
+    batch::run_batch,
     gateway::CoreSetter,
     mcp::mcp::MCPToolInput,
     router::router::{ContChan, ContMsg, HandlerInput, HelixRouter},
@@ -8,11 +11,13 @@ use crate::protocol::{
     HelixError, Request,
     request::{ReqMsg, RequestType, RetChan},
     response::Response,
+    stream::run_streaming,
 };
 use flume::{Receiver, Sender};
 use std::iter;
 use std::sync::Arc;
 use std::thread::JoinHandle;
+use std::time::Instant;
 use tokio::runtime::Runtime;
 use tokio::sync::oneshot;
 use tracing::{error, trace};
@@ -20,6 +25,7 @@ use tracing::{error, trace};
 /// A Thread Pool of workers to execute Database operations
 pub struct WorkerPool {
     tx: Sender<ReqMsg>,
+    cont_tx: ContChan,
     _workers: Vec<Worker>,
 }
 
@@ -59,17 +65,37 @@ impl WorkerPool {
 
         WorkerPool {
             tx: req_tx,
+            cont_tx,
             _workers: workers,
         }
     }
 
+    /// Number of requests waiting for a free worker
+    pub fn pending_requests(&self) -> usize {
+        self.tx.len()
+    }
+
+    /// Number of continuations (e.g. MCP tool calls) waiting for a free worker
+    pub fn pending_continuations(&self) -> usize {
+        self.cont_tx.len()
+    }
+
     /// Process a request on the Worker Pool
     pub async fn process(&self, req: Request) -> Result<Response, HelixError> {
+        self.process_with_deadline(req, None).await
+    }
+
+    /// Process a request on the Worker Pool, cancelling it once `deadline` has passed
+    pub async fn process_with_deadline(
+        &self,
+        req: Request,
+        deadline: Option<Instant>,
+    ) -> Result<Response, HelixError> {
         let (ret_tx, ret_rx) = oneshot::channel();
 
         // this read by Worker in start()
         self.tx
-            .send_async((req, ret_tx))
+            .send_async((req, deadline, ret_tx))
             .await
             .expect("WorkerPool channel should be open");
 
@@ -125,8 +151,9 @@ impl Worker {
                         }
 
                         match rx.recv() {
-                            Ok((req, ret_chan)) => request_mapper(
+                            Ok((req, deadline, ret_chan)) => request_mapper(
                                 req,
+                                deadline,
                                 ret_chan,
                                 graph_access.clone(),
                                 &router,
@@ -144,8 +171,9 @@ impl Worker {
                         // rx.try_recv() then cont_rx.recv()
 
                         match rx.try_recv() {
-                            Ok((req, ret_chan)) => request_mapper(
+                            Ok((req, deadline, ret_chan)) => request_mapper(
                                 req,
+                                deadline,
                                 ret_chan,
                                 graph_access.clone(),
                                 &router,
@@ -176,6 +204,7 @@ impl Worker {
 
 fn request_mapper(
     request: Request,
+    deadline: Option<Instant>,
     ret_chan: RetChan,
     graph_access: Arc<HelixGraphEngine>,
     router: &HelixRouter,
@@ -185,24 +214,44 @@ fn request_mapper(
     let req_name = request.name.clone();
     let req_type = request.req_type;
 
-    let res = match request.req_type {
+    // the request waited in the queue past its deadline, don't start it
+    if deadline.is_some_and(|d| Instant::now() >= d) {
+        ret_chan
+            .send(Err(HelixError::RequestTimeout { name: req_name }))
+            .expect("Should always be able to send, as only one worker processes a request");
+        return;
+    }
+    let _deadline = set_deadline(deadline);
+
+    let (res, ret_chan) = match request.req_type {
         RequestType::Query => {
             if let Some(handler) = router.routes.get(&request.name) {
+                let stream = request.stream;
                 let input = HandlerInput {
                     request,
                     graph: graph_access,
                 };
 
-                match handler(input) {
+                let (res, ret_chan) = match stream {
+                    true => match run_streaming(ret_chan, router.stream_send_timeout, || {
+                        handler(input)
+                    }) {
+                        Some(unstreamed) => unstreamed,
+                        // the handler sent the response itself
+                        None => return,
+                    },
+                    false => (handler(input), ret_chan),
+                };
+                match res {
                     Err(GraphError::IoNeeded(cont_closure)) => {
                         let fut = cont_closure.0(cont_tx.clone(), ret_chan);
                         io_rt.spawn(fut);
                         return;
                     }
-                    res => Some(res.map_err(Into::into)),
+                    res => (Some(res.map_err(Into::into)), ret_chan),
                 }
             } else {
-                None
+                (None, ret_chan)
             }
         }
         RequestType::MCP => {
@@ -223,11 +272,19 @@ fn request_mapper(
                     ),
                     schema: graph_access.storage.storage_config.schema.clone(),
                 };
-                Some(mcp_handler(&mut mcp_input).map_err(Into::into))
+                (Some(mcp_handler(&mut mcp_input).map_err(Into::into)), ret_chan)
             } else {
-                None
+                (None, ret_chan)
             }
         }
+        RequestType::Batch => (
+            Some(run_batch(
+                &request,
+                &graph_access.storage,
+                &router.batch_routes,
+            )),
+            ret_chan,
+        ),
     };
 
     let res = res.unwrap_or(Err(HelixError::NotFound {
@@ -20,6 +20,8 @@ pub enum ErrorCode {
     E107,
     /// `E108` – `invalid schema version`
     E108,
+    /// `E109` – `invalid union edge endpoint`
+    E109,
 
     // TYPE ERRORS
     /// `E201` – `item type not in schema`
@@ -42,6 +44,12 @@ pub enum ErrorCode {
     E209,
     /// `E210` – `identifier was expected to be of type ID, but got {}`
     E210,
+    /// `E211` – `aggregation function cannot be applied to the type of the field`
+    E211,
+    /// `E212` – `required field cannot be set to NONE`
+    E212,
+    /// `E213` – `value is not a variant of the enum`
+    E213,
     // QUERY ERRORS
     /// `E301` – `variable not in scope`
     E301,
@@ -55,6 +63,16 @@ pub enum ErrorCode {
     E305,
     /// `E306` – `expression is not a boolean`
     E306,
+    /// `E307` – `unknown function`
+    E307,
+    /// `E308` – `wrong number of function arguments`
+    E308,
+    /// `E309` – `recursive function call`
+    E309,
+    /// `E310` – `function argument type mismatch`
+    E310,
+    /// `E311` – `function with a start node used as a step`
+    E311,
 
     // MCP ERRORS
     /// `E401` – `MCP query must return a single value`
@@ -98,6 +116,14 @@ pub enum ErrorCode {
     E632,
     /// `E633` - `index of range must be an integer`
     E633,
+    /// `E634` - `page size must be an integer`
+    E634,
+    /// `E635` - `page cursor must be a string`
+    E635,
+    /// `E636` - `traversal cannot be paged`
+    E636,
+    /// `E637` - `page is only valid as the last step of an assigned traversal`
+    E637,
 
     /// `E641` - `closure is only valid as the last step in a traversal`
     E641,
@@ -119,6 +145,25 @@ pub enum ErrorCode {
     /// `E653` - `inner type of in variable is not an object`
     E653,
 
+    /// `E661` - `function argument has the wrong type`
+    E661,
+    /// `E662` - `property read outside of an object remapping`
+    E662,
+    /// `E663` - `invalid regular expression`
+    E663,
+    /// `E664` - `unknown date unit`
+    E664,
+    /// `E665` - `unknown time zone`
+    E665,
+    /// `E666` - `invalid date literal`
+    E666,
+    /// `E667` - `sort or grouping key reads a parameter or variable`
+    E667,
+    /// `E668` - `operation cannot be applied to a function result`
+    E668,
+    /// `E669` - `function result compared with a value of another type`
+    E669,
+
 
     /// `W101` - `query has no return`
     W101,
@@ -134,6 +179,7 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::E106 => write!(f, "E106"),
             ErrorCode::E107 => write!(f, "E107"),
             ErrorCode::E108 => write!(f, "E108"),
+            ErrorCode::E109 => write!(f, "E109"),
             ErrorCode::E201 => write!(f, "E201"),
             ErrorCode::E202 => write!(f, "E202"),
             ErrorCode::E203 => write!(f, "E203"),
@@ -144,12 +190,20 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::E208 => write!(f, "E208"),
             ErrorCode::E209 => write!(f, "E209"),
             ErrorCode::E210 => write!(f, "E210"),
+            ErrorCode::E211 => write!(f, "E211"),
+            ErrorCode::E212 => write!(f, "E212"),
+            ErrorCode::E213 => write!(f, "E213"),
             ErrorCode::E301 => write!(f, "E301"),
             ErrorCode::E302 => write!(f, "E302"),
             ErrorCode::E303 => write!(f, "E303"),
             ErrorCode::E304 => write!(f, "E304"),
             ErrorCode::E305 => write!(f, "E305"),
             ErrorCode::E306 => write!(f, "E306"),
+            ErrorCode::E307 => write!(f, "E307"),
+            ErrorCode::E308 => write!(f, "E308"),
+            ErrorCode::E309 => write!(f, "E309"),
+            ErrorCode::E310 => write!(f, "E310"),
+            ErrorCode::E311 => write!(f, "E311"),
             ErrorCode::E401 => write!(f, "E401"),
             ErrorCode::E501 => write!(f, "E501"),
             ErrorCode::E601 => write!(f, "E601"),
@@ -167,6 +221,10 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::E631 => write!(f, "E631"),
             ErrorCode::E632 => write!(f, "E632"),
             ErrorCode::E633 => write!(f, "E633"),
+            ErrorCode::E634 => write!(f, "E634"),
+            ErrorCode::E635 => write!(f, "E635"),
+            ErrorCode::E636 => write!(f, "E636"),
+            ErrorCode::E637 => write!(f, "E637"),
             ErrorCode::E641 => write!(f, "E641"),
             ErrorCode::E642 => write!(f, "E642"),
             ErrorCode::E643 => write!(f, "E643"),
@@ -176,6 +234,15 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::E651 => write!(f, "E651"),
             ErrorCode::E652 => write!(f, "E652"),
             ErrorCode::E653 => write!(f, "E653"),
+            ErrorCode::E661 => write!(f, "E661"),
+            ErrorCode::E662 => write!(f, "E662"),
+            ErrorCode::E663 => write!(f, "E663"),
+            ErrorCode::E664 => write!(f, "E664"),
+            ErrorCode::E665 => write!(f, "E665"),
+            ErrorCode::E666 => write!(f, "E666"),
+            ErrorCode::E667 => write!(f, "E667"),
+            ErrorCode::E668 => write!(f, "E668"),
+            ErrorCode::E669 => write!(f, "E669"),
             ErrorCode::W101 => write!(f, "W101"),
         }
     }
@@ -225,6 +292,9 @@ implement_error_code!(E207, "edge type `{}` exists but it is not a valid edge ty
 implement_error_code!(E208, "field `{}` has not been indexed for node type `{}`" => { field_name, node_type }, "use a field that has been indexed with `INDEX` in the schema for node type `{}`" => { node_type });
 implement_error_code!(E209, "unknown type `{}` for parameter `{}`" => { parameter_type, parameter_name }, "declare or use a matching schema object or use a primitive type" => {});
 implement_error_code!(E210, "identifier `{}` was expected to be of type ID, but got {}" => { identifier, value_type_name }, "ensure the identifier is of type ID" => {});
+implement_error_code!(E211, "`{}` cannot be applied to field `{}` of type `{}`" => { function, field_name, field_type }, "use a numeric field" => {});
+implement_error_code!(E212, "field `{}` of `{}` is required and cannot be set to `NONE`" => { field_name, item_type_name }, "declare the field as `Optional<...>` in the schema to allow `NONE`" => {});
+implement_error_code!(E213, "`{}` is not a variant of enum `{}`" => { value, enum_name }, "use one of {}" => { variants });
 
 // Query errors
 implement_error_code!(E301, "variable `{}` not in scope" => { variable }, "check the variable" => {});
@@ -232,6 +302,11 @@ implement_error_code!(E302, "variable `{}` previously declared" => { variable },
 implement_error_code!(E304, "missing {} type" => { item_type }, "add an {} type" => { item_type });
 implement_error_code!(E305, "missing parameter `{}` for method `{}`" => { parameter_name, method_name }, "add the parameter `{}`" => { parameter_name });
 implement_error_code!(E306, "expression should result in a boolean, instead got `{}`" => { expression_type }, "ensure the expression is a boolean" => {});
+implement_error_code!(E307, "function `{}` is not defined" => { function_name }, "define it with `FN {}(...) => ...`" => { function_name });
+implement_error_code!(E308, "function `{}` takes {} arguments, but {} were given" => { function_name, expected, given }, "pass one argument for each parameter of `{}`" => { function_name });
+implement_error_code!(E309, "function `{}` calls itself" => { function_name }, "functions are inlined, so they cannot call themselves directly or through other functions" => {});
+implement_error_code!(E310, "argument `{}` is of type `{}`, but parameter `{}` of function `{}` is of type `{}`" => { argument, argument_type, parameter, function_name, parameter_type }, "pass an argument of type `{}`" => { parameter_type });
+implement_error_code!(E311, "function `{}` starts its own traversal, so it cannot be used as a step" => { function_name }, "call `{}` at the start of a traversal, or start its body with `_`" => { function_name });
 
 // MCP errors
 implement_error_code!(E401, "MCP query must return a single value, but got `{}`" => { number_of_values }, "return a single value" => {});
@@ -261,6 +336,10 @@ implement_error_code!(E626, "edge type `{}` does not have a vector type as its `
 implement_error_code!(E631, "range must have a start and end, missing the `{}` value" => { start_or_end }, "add a `{}` value to the range" => { start_or_end });
 implement_error_code!(E632, "range start must be less than range end, got `{}` which is larger than `{}`" => { start, end }, "change the range start to be less than the range end" => {});
 implement_error_code!(E633, "index of range must be an integer, got `{}` which is of type `{}`" => { index, index_type }, "change {} to be an integer" => { index_type });
+implement_error_code!(E634, "page size must be an integer, got `{}` which is of type `{}`" => { size, size_type }, "change {} to be an integer" => { size_type });
+implement_error_code!(E635, "page cursor `{}` must be a `String` parameter, but it is {}" => { cursor, found }, "pass the cursor returned with the previous page as a `String` parameter" => {});
+implement_error_code!(E636, "`PAGE` can only follow `N<Type>`, `N<Type>({{index: value}})`, `E<Type>` or the `OutE` or `InE` of a single node, filtered only by `WHERE`" => {}, "use `RANGE` to take a slice of other traversals" => {});
+implement_error_code!(E637, "`PAGE` is only valid as the last step of a traversal assigned to a variable" => {}, "assign the paged traversal to a variable, which returns its cursor as `<variable>_cursor`" => {});
 
 // Object remapping errors
 implement_error_code!(E641, "closure is only valid as the last step in a traversal" => {}, "move the closure to the end of the traversal" => {});
@@ -275,6 +354,17 @@ implement_error_code!(E651, "`IN` variable `{}` is not iterable" => { in_variabl
 implement_error_code!(E652, "variable `{}` is not a field of the inner object of the `IN` variable `{}`" => { variable, in_variable }, "ensure `{}` is a field of `{}`" => { variable, in_variable });
 implement_error_code!(E653, "inner object of `IN` variable `{}` is not an object" => { in_variable }, "ensure the inner type of `{}` is an object" => { in_variable });
 
+// String and date function errors
+implement_error_code!(E661, "argument {} of `{}` must be {}, but got `{}`" => { position, function_name, expected, found }, "pass {} as argument {} of `{}`" => { expected, position, function_name });
+implement_error_code!(E662, "property `{}` cannot be read by a function here" => { property }, "read properties with `_::{{{}}}` inside an object remapping, a `WHERE`, an `ORDER` or a `GROUP_BY`, or pass the value as a parameter" => { property });
+implement_error_code!(E663, "invalid regular expression `{}`: {}" => { pattern, reason }, "fix the pattern passed to `REGEX`" => {});
+implement_error_code!(E664, "unknown date unit `{}`" => { unit }, "use one of \"year\", \"month\", \"week\", \"day\", \"hour\", \"minute\" or \"second\"" => {});
+implement_error_code!(E665, "unknown time zone `{}`" => { zone }, "use \"UTC\", an offset such as \"+05:30\", or an IANA zone such as \"Europe/London\"" => {});
+implement_error_code!(E666, "invalid date `{}`: {}" => { date, reason }, "write dates as RFC 3339 (\"2024-01-31T09:00:00Z\") or as \"2024-01-31\"" => {});
+implement_error_code!(E667, "`{}` cannot be used as a sort or grouping key because it reads `{}`" => { function_name, variable }, "use literal arguments in sort and grouping keys" => {});
+implement_error_code!(E668, "`{}` cannot be applied to the result of `{}`" => { operation, function_name }, "compare function results with `GT`, `GTE`, `LT`, `LTE`, `EQ`, `NEQ` or a string predicate" => {});
+implement_error_code!(E669, "the result of `{}` is `{}` and cannot be compared with `{}`" => { function_name, result_type, found }, "compare with a value of type `{}`" => { result_type });
+
 #[macro_export]
 macro_rules! generate_error {
     ($ctx:ident, $original_query:ident, $loc:expr, $error_code:ident, [$($message_args:expr),*], [$($hint_args:expr),*]) => {
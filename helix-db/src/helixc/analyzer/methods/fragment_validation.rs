@@ -0,0 +1,940 @@
+//! Inlining and validation of `FN` fragments.
+//!
+//! Fragment calls are expanded into the calling traversal before the queries are analyzed, so
+//! the rest of the analyzer and the generator never see them. Calls that cannot be expanded are
+//! left in place and reported by [`validate_fragment_call`] when the traversal is validated.
+
+use crate::helixc::analyzer::error_codes::ErrorCode;
+use crate::{
+    generate_error,
+    helixc::{
+        analyzer::{
+            Ctx,
+            errors::{push_query_err, push_schema_err},
+            methods::query_validation::validate_query,
+        },
+        parser::{location::Loc, types::*},
+    },
+};
+use paste::paste;
+use std::collections::{HashMap, HashSet};
+
+/// Why a fragment call cannot be inlined.
+pub(crate) enum CallError<'a> {
+    Unknown,
+    Arity(&'a Fragment),
+    ArgumentType {
+        argument: &'a str,
+        argument_type: &'a FieldType,
+        parameter: &'a Parameter,
+    },
+    StartsTraversal,
+}
+
+/// Looks up the fragment a call refers to and checks the call against its parameters.
+///
+/// `params` are the parameters of the query or fragment the call is written in, used to check
+/// the types of arguments that are parameters themselves.
+pub(crate) fn check_fragment_call<'a>(
+    fragments: &HashMap<&str, &'a Fragment>,
+    call: &'a FragmentCall,
+    params: &'a [Parameter],
+    as_step: bool,
+) -> Result<&'a Fragment, CallError<'a>> {
+    let fragment = *fragments
+        .get(call.name.as_str())
+        .ok_or(CallError::Unknown)?;
+    if fragment.parameters.len() != call.args.len() {
+        return Err(CallError::Arity(fragment));
+    }
+    for ((_, argument), parameter) in call.args.iter().zip(&fragment.parameters) {
+        if let Some(caller_param) = params.iter().find(|p| p.name.1 == *argument)
+            && caller_param.param_type.1 != parameter.param_type.1
+        {
+            return Err(CallError::ArgumentType {
+                argument,
+                argument_type: &caller_param.param_type.1,
+                parameter,
+            });
+        }
+    }
+    if as_step && !matches!(fragment.body.start, StartNode::Anonymous) {
+        return Err(CallError::StartsTraversal);
+    }
+    Ok(fragment)
+}
+
+/// Reports a fragment call that was left in place by [`inline_fragments`].
+///
+/// A call that passes [`check_fragment_call`] can only have been left in place because it is
+/// recursive.
+pub(crate) fn validate_fragment_call<'a>(
+    ctx: &mut Ctx<'a>,
+    call: &'a FragmentCall,
+    original_query: &'a Query,
+    as_step: bool,
+) {
+    let fragments = ctx.fragments.clone();
+    let name = call.name.as_str();
+    match check_fragment_call(&fragments, call, &original_query.parameters, as_step) {
+        Err(CallError::Unknown) => {
+            generate_error!(ctx, original_query, call.loc.clone(), E307, [name], [name]);
+        }
+        Err(CallError::Arity(fragment)) => {
+            generate_error!(
+                ctx,
+                original_query,
+                call.loc.clone(),
+                E308,
+                [
+                    name,
+                    &fragment.parameters.len().to_string(),
+                    &call.args.len().to_string()
+                ],
+                [name]
+            );
+        }
+        Err(CallError::ArgumentType {
+            argument,
+            argument_type,
+            parameter,
+        }) => {
+            let parameter_type = parameter.param_type.1.to_string();
+            generate_error!(
+                ctx,
+                original_query,
+                call.loc.clone(),
+                E310,
+                [
+                    argument,
+                    &argument_type.to_string(),
+                    &parameter.name.1,
+                    name,
+                    &parameter_type
+                ],
+                [&parameter_type]
+            );
+        }
+        Err(CallError::StartsTraversal) => {
+            generate_error!(ctx, original_query, call.loc.clone(), E311, [name], [name]);
+        }
+        Ok(_) => {
+            generate_error!(ctx, original_query, call.loc.clone(), E309, name);
+        }
+    }
+}
+
+/// Expands every fragment call in the queries and fragment bodies of `src`.
+///
+/// Also returns the names of the fragments called, directly or through other fragments, from a
+/// query.
+pub(crate) fn inline_fragments(src: &Source) -> (Source, HashSet<String>) {
+    let mut fragments = HashMap::new();
+    for fragment in &src.fragments {
+        fragments.entry(fragment.name.1.as_str()).or_insert(fragment);
+    }
+
+    let mut out = src.clone();
+    let mut called = HashSet::new();
+    for (query, original) in out.queries.iter_mut().zip(&src.queries) {
+        let mut inliner = Inliner {
+            fragments: &fragments,
+            params: &original.parameters,
+            stack: Vec::new(),
+            called: &mut called,
+        };
+        for statement in &mut query.statements {
+            walk_statement(&mut inliner, statement);
+        }
+        for ret in &mut query.return_values {
+            walk_return(&mut inliner, ret);
+        }
+    }
+    // calls between fragments only count once a query reaches them
+    let mut unused = HashSet::new();
+    for (fragment, original) in out.fragments.iter_mut().zip(&src.fragments) {
+        let mut inliner = Inliner {
+            fragments: &fragments,
+            params: &original.parameters,
+            stack: vec![fragment.name.1.clone()],
+            called: &mut unused,
+        };
+        inliner.visit_traversal(&mut fragment.body);
+    }
+    (out, called)
+}
+
+/// Builds a query returning the body of each fragment, so fragments can be validated on their
+/// own with the same machinery as queries.
+pub(crate) fn fragment_queries(src: &Source) -> Vec<Query> {
+    src.fragments
+        .iter()
+        .map(|fragment| Query {
+            original_query: fragment.original_fragment.clone(),
+            built_in_macro: None,
+            name: fragment.name.1.clone(),
+            parameters: fragment.parameters.clone(),
+            statements: Vec::new(),
+            return_values: vec![ReturnType::Expression(Expression {
+                loc: fragment.body.loc.clone(),
+                expr: ExpressionType::Traversal(Box::new(fragment.body.clone())),
+            })],
+            loc: fragment.loc.clone(),
+        })
+        .collect()
+}
+
+/// Checks the fragment definitions.
+///
+/// Every fragment may only refer to its own parameters. Fragments with a start node that no
+/// query calls are validated like a query returning their body, so their errors are not lost;
+/// the generated code is discarded.
+pub(crate) fn check_fragments<'a>(
+    ctx: &mut Ctx<'a>,
+    queries: &'a [Query],
+    called: &HashSet<String>,
+) {
+    let src = ctx.src;
+    let mut seen = HashSet::new();
+    for (fragment, query) in src.fragments.iter().zip(queries) {
+        if !seen.insert(fragment.name.1.as_str()) {
+            push_schema_err(
+                ctx,
+                fragment.name.0.clone(),
+                ErrorCode::E107,
+                ErrorCode::E107_message("FN", &fragment.name.1),
+                Some(ErrorCode::E107_hint("FN")),
+            );
+            continue;
+        }
+
+        let mut free = FreeNames::default();
+        let mut body = fragment.body.clone();
+        free.visit_traversal(&mut body);
+        let mut reported = HashSet::new();
+        for (name, loc) in free.names {
+            if !fragment.parameters.iter().any(|p| p.name.1 == name) && reported.insert(name.clone())
+            {
+                generate_error!(ctx, query, loc, E301, &name);
+            }
+        }
+        if !reported.is_empty() {
+            continue;
+        }
+
+        if !called.contains(&fragment.name.1)
+            && !matches!(fragment.body.start, StartNode::Anonymous)
+        {
+            let generated = ctx.output.queries.len();
+            validate_query(ctx, query);
+            ctx.output.queries.truncate(generated);
+        }
+    }
+}
+
+/// Expands fragment calls in place.
+struct Inliner<'s, 'a> {
+    fragments: &'s HashMap<&'a str, &'a Fragment>,
+    params: &'s [Parameter],
+    /// Fragments currently being expanded, to stop at recursive calls.
+    stack: Vec<String>,
+    called: &'s mut HashSet<String>,
+}
+
+impl Inliner<'_, '_> {
+    /// The fully expanded body of the called fragment, with its parameters renamed to the
+    /// arguments and every location moved to the call.
+    fn inline(&mut self, call: &FragmentCall, as_step: bool) -> Option<Traversal> {
+        let fragment = check_fragment_call(self.fragments, call, self.params, as_step).ok()?;
+        if self.stack.contains(&call.name) {
+            return None;
+        }
+        let renames = fragment
+            .parameters
+            .iter()
+            .map(|p| p.name.1.as_str())
+            .zip(call.args.iter().map(|(_, arg)| arg.as_str()))
+            .collect();
+        let mut body = fragment.body.clone();
+        Relocate {
+            renames: &renames,
+            loc: &call.loc,
+            bound: Vec::new(),
+        }
+        .visit_traversal(&mut body);
+
+        self.called.insert(call.name.clone());
+        self.stack.push(call.name.clone());
+        self.visit_traversal(&mut body);
+        self.stack.pop();
+        Some(body)
+    }
+}
+
+impl VisitMut for Inliner<'_, '_> {
+    fn visit_traversal(&mut self, tr: &mut Traversal) {
+        let mut steps = Vec::with_capacity(tr.steps.len());
+        let inlined = match &tr.start {
+            StartNode::FragmentCall(call) => self.inline(call, false),
+            _ => None,
+        };
+        match inlined {
+            Some(body) => {
+                tr.start = body.start;
+                steps = body.steps;
+            }
+            None => walk_start(self, &mut tr.start, &tr.loc),
+        }
+        for mut step in std::mem::take(&mut tr.steps) {
+            if let StepType::FragmentCall(call) = &step.step
+                && let Some(body) = self.inline(call, true)
+            {
+                steps.extend(body.steps);
+                continue;
+            }
+            walk_step(self, &mut step);
+            steps.push(step);
+        }
+        tr.steps = steps;
+    }
+}
+
+/// Renames parameters to arguments and moves every location to the call site.
+///
+/// Closure parameters shadow fragment parameters of the same name, so they aren't renamed to
+/// arguments. A closure parameter with the same name as an argument would capture it instead,
+/// so it is renamed to a name starting with `_`, which no identifier written in HQL can have.
+struct Relocate<'s> {
+    renames: &'s HashMap<&'s str, &'s str>,
+    loc: &'s Loc,
+    /// The closure parameters in scope and the names they are renamed to.
+    bound: Vec<(String, String)>,
+}
+
+impl VisitMut for Relocate<'_> {
+    fn visit_name(&mut self, name: &mut String, _: &Loc) {
+        if let Some((_, fresh)) = self.bound.iter().rev().find(|(param, _)| param == name) {
+            *name = fresh.clone();
+        } else if let Some(arg) = self.renames.get(name.as_str()) {
+            *name = arg.to_string();
+        }
+    }
+
+    fn visit_loc(&mut self, loc: &mut Loc) {
+        *loc = self.loc.clone();
+    }
+
+    fn visit_closure(&mut self, closure: &mut Closure) {
+        let param = std::mem::take(&mut closure.identifier);
+        let mut fresh = param.clone();
+        let mut n = 0;
+        while self.renames.values().any(|arg| *arg == fresh) {
+            fresh = format!("_{param}{n}");
+            n += 1;
+        }
+        closure.identifier = fresh.clone();
+        self.bound.push((param, fresh));
+        walk_closure(self, closure);
+        self.bound.pop();
+    }
+}
+
+/// Collects the variables a traversal refers to, other than closure parameters.
+#[derive(Default)]
+struct FreeNames {
+    names: Vec<(String, Loc)>,
+    bound: Vec<String>,
+}
+
+impl VisitMut for FreeNames {
+    fn visit_name(&mut self, name: &mut String, loc: &Loc) {
+        if !self.bound.contains(name) {
+            self.names.push((name.clone(), loc.clone()));
+        }
+    }
+
+    fn visit_closure(&mut self, closure: &mut Closure) {
+        self.bound.push(closure.identifier.clone());
+        walk_closure(self, closure);
+        self.bound.pop();
+    }
+}
+
+/// A mutable walk over the parts of the AST that can appear in a fragment call or body.
+///
+/// `visit_name` is called for every reference to a variable, with the closest location.
+trait VisitMut {
+    fn visit_name(&mut self, _name: &mut String, _loc: &Loc) {}
+
+    fn visit_loc(&mut self, _loc: &mut Loc) {}
+
+    fn visit_traversal(&mut self, tr: &mut Traversal) {
+        walk_traversal(self, tr);
+    }
+
+    fn visit_closure(&mut self, closure: &mut Closure) {
+        walk_closure(self, closure);
+    }
+}
+
+fn walk_statement<V: VisitMut + ?Sized>(v: &mut V, statement: &mut Statement) {
+    v.visit_loc(&mut statement.loc);
+    match &mut statement.statement {
+        StatementType::Assignment(assignment) => {
+            v.visit_loc(&mut assignment.loc);
+            walk_expression(v, &mut assignment.value);
+        }
+        StatementType::Expression(expr) | StatementType::Drop(expr) => walk_expression(v, expr),
+        StatementType::ForLoop(for_loop) => {
+            v.visit_loc(&mut for_loop.loc);
+            let (loc, name) = &mut for_loop.in_variable;
+            v.visit_name(name, loc);
+            v.visit_loc(loc);
+            for statement in &mut for_loop.statements {
+                walk_statement(v, statement);
+            }
+        }
+        StatementType::IfElse(if_else) => {
+            v.visit_loc(&mut if_else.loc);
+            walk_expression(v, &mut if_else.condition);
+            for statement in if_else
+                .then_statements
+                .iter_mut()
+                .chain(&mut if_else.else_statements)
+            {
+                walk_statement(v, statement);
+            }
+        }
+    }
+}
+
+fn walk_return<V: VisitMut + ?Sized>(v: &mut V, ret: &mut ReturnType) {
+    match ret {
+        ReturnType::Array(values) => values.iter_mut().for_each(|r| walk_return(v, r)),
+        ReturnType::Object(values) => values.values_mut().for_each(|r| walk_return(v, r)),
+        ReturnType::Expression(expr) => walk_expression(v, expr),
+        ReturnType::Empty => {}
+    }
+}
+
+fn walk_traversal<V: VisitMut + ?Sized>(v: &mut V, tr: &mut Traversal) {
+    v.visit_loc(&mut tr.loc);
+    walk_start(v, &mut tr.start, &tr.loc);
+    for step in &mut tr.steps {
+        walk_step(v, step);
+    }
+}
+
+fn walk_start<V: VisitMut + ?Sized>(v: &mut V, start: &mut StartNode, loc: &Loc) {
+    match start {
+        StartNode::Node { ids, .. } | StartNode::Edge { ids, .. } | StartNode::Vector { ids, .. } => {
+            ids.iter_mut().flatten().for_each(|id| walk_id(v, id));
+        }
+        StartNode::SearchVector(sv) => walk_search_vector(v, sv),
+        StartNode::Identifier(name) => v.visit_name(name, loc),
+        StartNode::FragmentCall(call) => walk_call(v, call),
+        StartNode::Anonymous => {}
+    }
+}
+
+fn walk_call<V: VisitMut + ?Sized>(v: &mut V, call: &mut FragmentCall) {
+    v.visit_loc(&mut call.loc);
+    for (loc, name) in &mut call.args {
+        v.visit_name(name, loc);
+        v.visit_loc(loc);
+    }
+}
+
+fn walk_step<V: VisitMut + ?Sized>(v: &mut V, step: &mut Step) {
+    v.visit_loc(&mut step.loc);
+    match &mut step.step {
+        StepType::Node(gs) | StepType::Edge(gs) => walk_graph_step(v, gs),
+        StepType::Where(expr) => walk_expression(v, expr),
+        StepType::BooleanOperation(op) => walk_boolean_op(v, op),
+        StepType::Count | StepType::First => {}
+        StepType::Update(update) => {
+            v.visit_loc(&mut update.loc);
+            update.fields.iter_mut().for_each(|f| walk_field(v, f));
+        }
+        StepType::Object(object) => walk_object(v, object),
+        StepType::Exclude(exclude) => {
+            v.visit_loc(&mut exclude.loc);
+            exclude.fields.iter_mut().for_each(|(loc, _)| v.visit_loc(loc));
+        }
+        StepType::Closure(closure) => v.visit_closure(closure),
+        StepType::Range((start, end)) => {
+            walk_expression(v, start);
+            walk_expression(v, end);
+        }
+        StepType::Page(page) => {
+            v.visit_loc(&mut page.loc);
+            walk_expression(v, &mut page.size);
+            walk_expression(v, &mut page.cursor);
+        }
+        StepType::OrderBy(order_by) => {
+            v.visit_loc(&mut order_by.loc);
+            for key in &mut order_by.keys {
+                v.visit_loc(&mut key.loc);
+                walk_expression(v, &mut key.expression);
+            }
+        }
+        StepType::Aggregate(Aggregate {
+            loc, aggregations, ..
+        }) => {
+            v.visit_loc(loc);
+            aggregations.iter_mut().for_each(|a| v.visit_loc(&mut a.loc));
+        }
+        StepType::GroupBy(GroupBy {
+            loc,
+            computed,
+            aggregations,
+            ..
+        }) => {
+            v.visit_loc(loc);
+            for (_, call) in computed {
+                v.visit_loc(&mut call.loc);
+                call.args.iter_mut().for_each(|e| walk_expression(v, e));
+            }
+            aggregations.iter_mut().for_each(|a| v.visit_loc(&mut a.loc));
+        }
+        StepType::AddEdge(add_edge) => walk_add_edge(v, add_edge),
+        StepType::RerankRRF(rrf) => {
+            v.visit_loc(&mut rrf.loc);
+            rrf.k.iter_mut().for_each(|k| walk_expression(v, k));
+        }
+        StepType::RerankMMR(mmr) => {
+            v.visit_loc(&mut mmr.loc);
+            walk_expression(v, &mut mmr.lambda);
+            if let Some(MMRDistance::Identifier(name)) = &mut mmr.distance {
+                v.visit_name(name, &mmr.loc);
+            }
+        }
+        StepType::Centrality(centrality) => {
+            v.visit_loc(&mut centrality.loc);
+            if let CentralityKind::Betweenness(Some(expr)) = &mut centrality.kind {
+                walk_expression(v, expr);
+            }
+        }
+        StepType::EgoGraph(ego) => {
+            v.visit_loc(&mut ego.loc);
+            walk_expression(v, &mut ego.hops);
+            ego.max_nodes.iter_mut().for_each(|e| walk_expression(v, e));
+        }
+        StepType::Dag(dag) => v.visit_loc(&mut dag.loc),
+        StepType::FragmentCall(call) => walk_call(v, call),
+    }
+}
+
+fn walk_graph_step<V: VisitMut + ?Sized>(v: &mut V, gs: &mut GraphStep) {
+    v.visit_loc(&mut gs.loc);
+    match &mut gs.step {
+        GraphStepType::ShortestPath(ShortestPath { loc, from, to, .. })
+        | GraphStepType::ShortestPathBFS(ShortestPathBFS { loc, from, to, .. }) => {
+            v.visit_loc(loc);
+            from.iter_mut().chain(to).for_each(|id| walk_id(v, id));
+        }
+        GraphStepType::ShortestPathDijkstras(ShortestPathDijkstras {
+            loc,
+            from,
+            to,
+            inner_traversal,
+            weight_expr,
+            ..
+        })
+        | GraphStepType::ShortestPathAStar(ShortestPathAStar {
+            loc,
+            from,
+            to,
+            inner_traversal,
+            weight_expr,
+            ..
+        }) => {
+            v.visit_loc(loc);
+            from.iter_mut().chain(to).for_each(|id| walk_id(v, id));
+            inner_traversal.iter_mut().for_each(|t| v.visit_traversal(t));
+            if let Some(WeightExpression::Expression(expr)) = weight_expr {
+                walk_expression(v, expr);
+            }
+        }
+        GraphStepType::SearchVector(sv) => walk_search_vector(v, sv),
+        GraphStepType::Out(_)
+        | GraphStepType::In(_)
+        | GraphStepType::FromN
+        | GraphStepType::ToN
+        | GraphStepType::FromV
+        | GraphStepType::ToV
+        | GraphStepType::OutE(_)
+        | GraphStepType::InE(_) => {}
+    }
+}
+
+fn walk_expression<V: VisitMut + ?Sized>(v: &mut V, expr: &mut Expression) {
+    v.visit_loc(&mut expr.loc);
+    match &mut expr.expr {
+        ExpressionType::Traversal(tr) => v.visit_traversal(tr),
+        ExpressionType::Identifier(name) => v.visit_name(name, &expr.loc),
+        ExpressionType::ArrayLiteral(exprs)
+        | ExpressionType::And(exprs)
+        | ExpressionType::Or(exprs) => exprs.iter_mut().for_each(|e| walk_expression(v, e)),
+        ExpressionType::Exists(exists) => {
+            v.visit_loc(&mut exists.loc);
+            walk_expression(v, &mut exists.expr);
+        }
+        ExpressionType::Not(inner) => walk_expression(v, inner),
+        ExpressionType::AddVector(add_vector) => {
+            v.visit_loc(&mut add_vector.loc);
+            if let Some(data) = &mut add_vector.data {
+                walk_vector_data(v, data, &add_vector.loc);
+            }
+            walk_value_fields(v, &mut add_vector.fields);
+        }
+        ExpressionType::AddNode(add_node) => walk_add_node(v, add_node),
+        ExpressionType::AddEdge(add_edge) => walk_add_edge(v, add_edge),
+        ExpressionType::UpsertNode(upsert) => {
+            v.visit_loc(&mut upsert.loc);
+            v.visit_loc(&mut upsert.key.0);
+            walk_add_node(v, &mut upsert.node);
+        }
+        ExpressionType::UpsertEdge(upsert) => {
+            v.visit_loc(&mut upsert.loc);
+            walk_add_edge(v, &mut upsert.edge);
+        }
+        ExpressionType::SearchVector(sv) => walk_search_vector(v, sv),
+        ExpressionType::BM25Search(bm25) => {
+            v.visit_loc(&mut bm25.loc);
+            bm25.data.iter_mut().for_each(|d| walk_value(v, d));
+            bm25.k.iter_mut().for_each(|k| walk_number(v, k));
+        }
+        ExpressionType::MathFunctionCall(call) => {
+            v.visit_loc(&mut call.loc);
+            call.args.iter_mut().for_each(|e| walk_expression(v, e));
+        }
+        ExpressionType::StringFunctionCall(call) => {
+            v.visit_loc(&mut call.loc);
+            call.args.iter_mut().for_each(|e| walk_expression(v, e));
+        }
+        ExpressionType::DateFunctionCall(call) => {
+            v.visit_loc(&mut call.loc);
+            call.args.iter_mut().for_each(|e| walk_expression(v, e));
+        }
+        ExpressionType::ComputedPredicate(predicate) => {
+            walk_expression(v, &mut predicate.value);
+            walk_boolean_op(v, &mut predicate.op);
+        }
+        ExpressionType::Now | ExpressionType::Duration(_) => {}
+        ExpressionType::StringLiteral(_)
+        | ExpressionType::IntegerLiteral(_)
+        | ExpressionType::FloatLiteral(_)
+        | ExpressionType::BooleanLiteral(_)
+        | ExpressionType::Empty => {}
+    }
+}
+
+fn walk_boolean_op<V: VisitMut + ?Sized>(v: &mut V, op: &mut BooleanOp) {
+    v.visit_loc(&mut op.loc);
+    match &mut op.op {
+        BooleanOpType::And(exprs) | BooleanOpType::Or(exprs) => {
+            exprs.iter_mut().for_each(|e| walk_expression(v, e));
+        }
+        BooleanOpType::GreaterThan(expr)
+        | BooleanOpType::GreaterThanOrEqual(expr)
+        | BooleanOpType::LessThan(expr)
+        | BooleanOpType::LessThanOrEqual(expr)
+        | BooleanOpType::Equal(expr)
+        | BooleanOpType::NotEqual(expr)
+        | BooleanOpType::Contains(expr)
+        | BooleanOpType::IsIn(expr)
+        | BooleanOpType::StartsWith(expr)
+        | BooleanOpType::EndsWith(expr)
+        | BooleanOpType::EqualIgnoreCase(expr)
+        | BooleanOpType::Regex(expr) => walk_expression(v, expr),
+        BooleanOpType::IsNull | BooleanOpType::IsNotNull => {}
+    }
+}
+
+fn walk_search_vector<V: VisitMut + ?Sized>(v: &mut V, sv: &mut SearchVector) {
+    v.visit_loc(&mut sv.loc);
+    if let Some(data) = &mut sv.data {
+        walk_vector_data(v, data, &sv.loc);
+    }
+    sv.k.iter_mut().for_each(|k| walk_number(v, k));
+    sv.pre_filter.iter_mut().for_each(|e| walk_expression(v, e));
+}
+
+fn walk_vector_data<V: VisitMut + ?Sized>(v: &mut V, data: &mut VectorData, loc: &Loc) {
+    match data {
+        VectorData::Identifier(name) => v.visit_name(name, loc),
+        VectorData::Embed(embed) => {
+            if let EvaluatesToString::Identifier(name) = &mut embed.value {
+                v.visit_name(name, &embed.loc);
+            }
+            v.visit_loc(&mut embed.loc);
+        }
+        VectorData::Vector(_) => {}
+    }
+}
+
+fn walk_number<V: VisitMut + ?Sized>(v: &mut V, number: &mut EvaluatesToNumber) {
+    if let EvaluatesToNumberType::Identifier(name) = &mut number.value {
+        v.visit_name(name, &number.loc);
+    }
+    v.visit_loc(&mut number.loc);
+}
+
+fn walk_id<V: VisitMut + ?Sized>(v: &mut V, id: &mut IdType) {
+    match id {
+        IdType::Literal { loc, .. } => v.visit_loc(loc),
+        IdType::Identifier { value, loc } => {
+            v.visit_name(value, loc);
+            v.visit_loc(loc);
+        }
+        // the index is the name of the indexed field, not a variable
+        IdType::ByIndex { index, value, loc } => {
+            if let IdType::Identifier { loc, .. } | IdType::Literal { loc, .. } = index.as_mut() {
+                v.visit_loc(loc);
+            }
+            walk_value(v, value);
+            v.visit_loc(loc);
+        }
+    }
+}
+
+fn walk_value<V: VisitMut + ?Sized>(v: &mut V, value: &mut ValueType) {
+    match value {
+        ValueType::Literal { loc, .. } => v.visit_loc(loc),
+        ValueType::Identifier { value, loc } => {
+            v.visit_name(value, loc);
+            v.visit_loc(loc);
+        }
+        ValueType::Object { fields, loc } => {
+            fields.values_mut().for_each(|f| walk_value(v, f));
+            v.visit_loc(loc);
+        }
+    }
+}
+
+fn walk_value_fields<V: VisitMut + ?Sized>(
+    v: &mut V,
+    fields: &mut Option<HashMap<String, ValueType>>,
+) {
+    fields
+        .iter_mut()
+        .flat_map(|fields| fields.values_mut())
+        .for_each(|f| walk_value(v, f));
+}
+
+fn walk_add_node<V: VisitMut + ?Sized>(v: &mut V, add_node: &mut AddNode) {
+    v.visit_loc(&mut add_node.loc);
+    walk_value_fields(v, &mut add_node.fields);
+}
+
+fn walk_add_edge<V: VisitMut + ?Sized>(v: &mut V, add_edge: &mut AddEdge) {
+    v.visit_loc(&mut add_edge.loc);
+    walk_value_fields(v, &mut add_edge.fields);
+    v.visit_loc(&mut add_edge.connection.loc);
+    let EdgeConnection { from_id, to_id, .. } = &mut add_edge.connection;
+    from_id.iter_mut().chain(to_id).for_each(|id| walk_id(v, id));
+}
+
+fn walk_object<V: VisitMut + ?Sized>(v: &mut V, object: &mut Object) {
+    v.visit_loc(&mut object.loc);
+    object.fields.iter_mut().for_each(|f| walk_field(v, f));
+}
+
+fn walk_closure<V: VisitMut + ?Sized>(v: &mut V, closure: &mut Closure) {
+    v.visit_loc(&mut closure.loc);
+    walk_object(v, &mut closure.object);
+}
+
+// an identifier field value names a property of the item, not a variable
+fn walk_field<V: VisitMut + ?Sized>(v: &mut V, field: &mut FieldAddition) {
+    v.visit_loc(&mut field.loc);
+    v.visit_loc(&mut field.value.loc);
+    match &mut field.value.value {
+        FieldValueType::Traversal(tr) => v.visit_traversal(tr),
+        FieldValueType::Expression(expr) => walk_expression(v, expr),
+        FieldValueType::Fields(fields) => fields.iter_mut().for_each(|f| walk_field(v, f)),
+        FieldValueType::Literal(_) | FieldValueType::Identifier(_) | FieldValueType::Empty => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helixc::analyzer::error_codes::ErrorCode;
+    use crate::helixc::parser::{HelixParser, write_to_temp_file};
+
+    const SCHEMA: &str = r#"
+        N::User { name: String }
+        N::Team { name: String }
+        N::Doc { title: String }
+        E::Member { From: User, To: Team }
+        E::Owns { From: Team, To: Doc }
+    "#;
+
+    fn analyze(queries: &str) -> (Vec<ErrorCode>, Vec<String>) {
+        let content = write_to_temp_file(vec![SCHEMA, queries]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let (diagnostics, generated) = crate::helixc::analyzer::analyze(&parsed).unwrap();
+        (
+            diagnostics.into_iter().map(|d| d.error_code).collect(),
+            generated.queries.iter().map(|q| q.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_fragment_call_is_inlined() {
+        let (errors, queries) = analyze(
+            r#"
+            FN visible_docs(u: ID) => N<User>(u)::Out<Member>::Out<Owns>
+
+            QUERY getDocs(user_id: ID) =>
+                docs <- visible_docs(user_id)::RANGE(0, 10)
+                RETURN docs
+
+            QUERY inlined(user_id: ID) =>
+                docs <- N<User>(user_id)::Out<Member>::Out<Owns>::RANGE(0, 10)
+                RETURN docs
+        "#,
+        );
+
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(queries.len(), 2);
+        let traversal = |q: &str| {
+            let start = q.find("let docs").unwrap();
+            q[start..start + q[start..].find(";\n").unwrap()].to_string()
+        };
+        assert_eq!(traversal(&queries[0]), traversal(&queries[1]));
+    }
+
+    #[test]
+    fn test_fragment_step_is_inlined() {
+        let (errors, queries) = analyze(
+            r#"
+            FN docs_of(u: ID) => N<User>(u)::Out<Member>::docs()
+            FN docs() => _::Out<Owns>
+            FN titled(t: String) => _::WHERE(_::{title}::EQ(t))
+
+            QUERY getDocs(user_id: ID, title: String) =>
+                docs <- docs_of(user_id)::titled(title)
+                RETURN docs
+        "#,
+        );
+
+        assert!(errors.is_empty(), "{errors:?}");
+        assert!(queries[0].contains("out_node(\"Owns\")"));
+        assert!(queries[0].contains("data.title"));
+        assert!(!queries[0].contains("docs_of"));
+    }
+
+    #[test]
+    fn test_closure_shadows_fragment_parameter() {
+        let (errors, queries) = analyze(
+            r#"
+            FN user_teams(u: ID) => N<User>(u)::|u|{ teams: u::Out<Member> }
+
+            QUERY getTeams(user_id: ID) =>
+                RETURN user_teams(user_id)
+
+            QUERY inlined(user_id: ID) =>
+                RETURN N<User>(user_id)::|u|{ teams: u::Out<Member> }
+        "#,
+        );
+
+        assert!(errors.is_empty(), "{errors:?}");
+        // the closure's `u` is the user, not the renamed parameter
+        let body = |q: &str| {
+            q[q.find("let response").unwrap()..]
+                .replace("GetTeams", "Inlined")
+                .replace("getTeams", "inlined")
+        };
+        assert_eq!(body(&queries[0]), body(&queries[1]));
+        assert!(!queries[0].contains("user_id.iter()"));
+    }
+
+    #[test]
+    fn test_closure_does_not_capture_argument() {
+        let (errors, queries) = analyze(
+            r#"
+            FN with_friend(x: ID) => N<User>(x)::|u|{ friend: N<User>(x) }
+
+            QUERY getFriend(u: ID) =>
+                RETURN with_friend(u)
+
+            QUERY inlined(u: ID) =>
+                RETURN N<User>(u)::|v|{ friend: N<User>(u) }
+        "#,
+        );
+
+        assert!(errors.is_empty(), "{errors:?}");
+        // `friend` is the user passed in, not the closure's user
+        let body = |q: &str| {
+            q[q.find("let response").unwrap()..]
+                .replace("GetFriend", "Inlined")
+                .replace("getFriend", "inlined")
+                .replace("_u0", "v")
+        };
+        assert_eq!(body(&queries[0]), body(&queries[1]));
+    }
+
+    #[test]
+    fn test_recursive_fragments_are_rejected() {
+        let (errors, _) = analyze(
+            r#"
+            FN ping(u: ID) => pong(u)::Out<Member>
+            FN pong(u: ID) => ping(u)::Out<Member>
+
+            QUERY getTeams(user_id: ID) =>
+                teams <- ping(user_id)
+                RETURN teams
+        "#,
+        );
+
+        assert!(errors.contains(&ErrorCode::E309));
+    }
+
+    #[test]
+    fn test_fragment_call_errors() {
+        let (errors, _) = analyze(
+            r#"
+            FN teams_of(u: ID) => N<User>(u)::Out<Member>
+
+            QUERY unknown(user_id: ID) =>
+                teams <- team_of(user_id)
+                RETURN teams
+
+            QUERY arity(user_id: ID) =>
+                teams <- teams_of(user_id, user_id)
+                RETURN teams
+
+            QUERY argType(name: String) =>
+                teams <- teams_of(name)
+                RETURN teams
+
+            QUERY asStep(user_id: ID) =>
+                teams <- N<User>::teams_of(user_id)
+                RETURN teams
+        "#,
+        );
+
+        for code in [ErrorCode::E307, ErrorCode::E308, ErrorCode::E310, ErrorCode::E311] {
+            assert!(errors.contains(&code), "missing {code}: {errors:?}");
+        }
+    }
+
+    #[test]
+    fn test_fragment_definitions_are_checked() {
+        let (errors, queries) = analyze(
+            r#"
+            FN unused(u: ID) => N<User>(u)::Out<Owns>
+            FN free() => N<User>(user_id)
+            FN dup() => _::Out<Member>
+            FN dup() => _::Out<Owns>
+        "#,
+        );
+
+        assert!(queries.is_empty());
+        assert!(errors.contains(&ErrorCode::E301));
+        assert!(errors.contains(&ErrorCode::E107));
+        assert!(errors.len() >= 3, "{errors:?}");
+    }
+}
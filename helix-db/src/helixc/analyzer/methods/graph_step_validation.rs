@@ -70,6 +70,7 @@ pub(crate) fn apply_graph_step<'a>(
                 .steps
                 .push(Separator::Period(GeneratedStep::OutE(GeneratedOutE {
                     label: GenRef::Literal(label.clone()),
+                    resume: None,
                 })));
             traversal.should_collect = ShouldCollect::ToVec;
             let edge = match ctx.edge_map.get(label.as_str()) {
@@ -79,7 +80,7 @@ pub(crate) fn apply_graph_step<'a>(
                     return None;
                 }
             };
-            match edge.from.1 == node_label.clone() {
+            match edge.is_from(node_label) {
                 true => Some(Type::Edges(Some(label.to_string()))),
                 false => {
                     generate_error!(
@@ -106,6 +107,7 @@ pub(crate) fn apply_graph_step<'a>(
                 .steps
                 .push(Separator::Period(GeneratedStep::InE(GeneratedInE {
                     label: GenRef::Literal(label.clone()),
+                    resume: None,
                 })));
             traversal.should_collect = ShouldCollect::ToVec;
             let edge = match ctx.edge_map.get(label.as_str()) {
@@ -116,7 +118,7 @@ pub(crate) fn apply_graph_step<'a>(
                 }
             };
 
-            match edge.to.1 == node_label.clone() {
+            match edge.is_to(node_label) {
                 true => Some(Type::Edges(Some(label.to_string()))),
                 false => {
                     generate_error!(ctx, original_query, gs.loc.clone(), E102, label.as_str());
@@ -164,7 +166,7 @@ pub(crate) fn apply_graph_step<'a>(
                     return None;
                 }
             };
-            match edge.from.1 == node_label.clone() {
+            match edge.is_from(node_label) {
                 true => {
                     if EdgeType::Node == edge_type {
                         Some(Type::Nodes(Some(edge.to.1.clone())))
@@ -229,7 +231,7 @@ pub(crate) fn apply_graph_step<'a>(
                 }
             };
 
-            match edge.to.1 == node_label.clone() {
+            match edge.is_to(node_label) {
                 true => {
                     if EdgeType::Node == edge_type {
                         Some(Type::Nodes(Some(edge.from.1.clone())))
@@ -948,6 +950,88 @@ mod tests {
         assert!(diagnostics.is_empty());
     }
 
+    // ============================================================================
+    // Union Endpoint Tests
+    // ============================================================================
+
+    #[test]
+    fn test_union_endpoint_traversals() {
+        let source = r#"
+            N::Post { title: String, body: String }
+            N::Comment { body: String }
+            N::Tag { name: String }
+            E::Tagged { From: Post | Comment, To: Tag }
+            E::Mentions { From: Comment, To: Post | Comment }
+
+            QUERY test(post_id: ID, tag_id: ID) =>
+                tags <- N<Post>(post_id)::Out<Tagged>
+                items <- N<Tag>(tag_id)::In<Tagged>::WHERE(_::{title}::IS_NOT_NULL)
+                sources <- N<Tag>(tag_id)::InE<Tagged>::FromN
+                mentioned_tags <- N<Comment>(post_id)::Out<Mentions>::Out<Tagged>
+                RETURN tags, items, sources, mentioned_tags
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_union_endpoint_return_fields() {
+        let source = r#"
+            N::Post { title: String, body: String }
+            N::Comment { body: String }
+            N::Tag { name: String }
+            E::Tagged { From: Post | Comment, To: Tag }
+
+            QUERY test(tag_id: ID) =>
+                items <- N<Tag>(tag_id)::In<Tagged>::{body, title}
+                RETURN items
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+        // items carry their label, and only the fields of their own type
+        let code = generated.to_string();
+        let skip = "    #[serde(skip_serializing_if = \"Option::is_none\")]\n";
+        assert!(code.contains("    pub label: &'a str,\n"));
+        assert!(code.contains(&format!("{skip}    pub title: Option<&'a Value>,\n")));
+        assert!(!code.contains(&format!("{skip}    pub body: Option<&'a Value>,\n")));
+    }
+
+    #[test]
+    fn test_union_endpoint_requires_every_member() {
+        let source = r#"
+            N::Post { title: String }
+            N::Comment { body: String }
+            N::Tag { name: String }
+            E::Tagged { From: Post | Comment, To: Tag }
+            E::Mentions { From: Comment, To: Post }
+
+            QUERY test(tag_id: ID) =>
+                items <- N<Tag>(tag_id)::In<Tagged>::Out<Mentions>
+                RETURN items
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E207
+            && d.message.contains("Comment | Post")));
+    }
+
     // ============================================================================
     // Edge Type Validation Tests
     // ============================================================================
@@ -11,16 +11,25 @@ use crate::{
             methods::traversal_validation::validate_traversal,
             types::Type,
             utils::{
-                gen_id_access_or_param, gen_identifier_or_param, is_valid_identifier, type_in_scope,
+                FieldLookup, check_enum_literal, field_enum, gen_field_value,
+                gen_id_access_or_param, gen_identifier_or_param, gen_literal_field_value,
+                get_field_type_from_item_fields, is_valid_identifier, type_in_scope,
+                validate_field_name_existence_for_item_type,
             },
         },
         generator::{
-            bool_ops::BoExp,
+            bool_ops::{
+                BoExp, BoolOp, EndsWith, Eq, EqIgnoreCase, Gt, Gte, Lt, Lte, Neq, Regex,
+                StartsWith,
+            },
             queries::Query as GeneratedQuery,
             source_steps::{
                 AddE, AddN, AddV, SearchBM25, SearchVector as GeneratedSearchVector, SourceStep,
+                UpsertN,
             },
             statements::Statement as GeneratedStatement,
+            date_functions::{DateExpr, DateFunctionCallGen},
+            string_functions::{StringExpr, StringFunctionCallGen},
             traversal_steps::{
                 ShouldCollect, Step as GeneratedStep, Traversal as GeneratedTraversal,
                 TraversalType, Where, WhereRef,
@@ -29,7 +38,7 @@ use crate::{
         },
         parser::types::*,
     },
-    protocol::date::Date,
+    protocol::date::{Date, DateUnit, DateZone},
 };
 use paste::paste;
 use std::collections::HashMap;
@@ -84,11 +93,11 @@ pub(crate) fn infer_expr_type<'a>(
 
         IntegerLiteral(i) => (
             Type::Scalar(FieldType::I32),
-            Some(GeneratedStatement::Literal(GenRef::Literal(i.to_string()))),
+            Some(GeneratedStatement::Literal(GenRef::Std(i.to_string()))),
         ),
         FloatLiteral(f) => (
             Type::Scalar(FieldType::F64),
-            Some(GeneratedStatement::Literal(GenRef::Literal(f.to_string()))),
+            Some(GeneratedStatement::Literal(GenRef::Std(format!("{f:?}")))),
         ),
         StringLiteral(s) => (
             Type::Scalar(FieldType::String),
@@ -96,7 +105,7 @@ pub(crate) fn infer_expr_type<'a>(
         ),
         BooleanLiteral(b) => (
             Type::Boolean,
-            Some(GeneratedStatement::Literal(GenRef::Literal(b.to_string()))),
+            Some(GeneratedStatement::Literal(GenRef::Std(b.to_string()))),
         ),
         // Gets expression type for each element in the array
         // Checks if all elements are of the same type
@@ -156,34 +165,79 @@ pub(crate) fn infer_expr_type<'a>(
             }
         }
 
-        AddNode(add) => {
-            if let Some(ref ty) = add.node_type {
-                if !ctx.node_set.contains(ty.as_str()) {
-                    generate_error!(ctx, original_query, add.loc.clone(), E101, ty.as_str());
-                }
-                let label = GenRef::Literal(ty.clone());
-
-                let node_in_schema = match ctx.output.nodes.iter().find(|n| n.name == ty.as_str()) {
-                    Some(node) => node.clone(),
-                    None => {
-                        generate_error!(ctx, original_query, add.loc.clone(), E101, ty.as_str());
-                        return (Type::Node(None), None);
+        AddNode(add) => infer_add_node(ctx, add, scope, original_query, gen_query),
+        AddEdge(add) => infer_add_edge(ctx, add, scope, original_query, gen_query),
+        UpsertNode(upsert) => {
+            let (ty, mut stmt) =
+                infer_add_node(ctx, &upsert.node, scope, original_query, gen_query);
+            let (key_loc, key) = &upsert.key;
+            if let Some(node_type) = upsert.node.node_type.as_deref()
+                && let Some(field) = ctx
+                    .node_fields
+                    .get(node_type)
+                    .and_then(|fields| fields.get(key.as_str()))
+                && !field.is_indexed()
+            {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    key_loc.clone(),
+                    E208,
+                    [key, node_type],
+                    [node_type]
+                );
+            }
+            if let Some(GeneratedStatement::Traversal(tr)) = &mut stmt
+                && let SourceStep::AddN(add_n) = tr.source_step.inner()
+            {
+                let mut key_value = GeneratedValue::Unknown;
+                let mut properties = Vec::new();
+                let mut defaults = Vec::new();
+                for (name, value) in add_n.properties.iter().flatten() {
+                    if name == key {
+                        key_value = value.clone();
+                    } else if upsert
+                        .node
+                        .fields
+                        .as_ref()
+                        .is_some_and(|fields| fields.contains_key(name))
+                    {
+                        properties.push((name.clone(), value.clone()));
+                    } else {
+                        defaults.push((name.clone(), value.clone()));
                     }
-                };
-
-                let default_properties = node_in_schema
-                    .properties
-                    .iter()
-                    .filter_map(|p| p.default_value.clone().map(|v| (p.name.clone(), v)))
-                    .collect::<Vec<(String, GeneratedValue)>>();
-
-                // Validate fields of add node by traversing the fields
-                // checking they exist in the schema, then checking their types
-                let (properties, secondary_indices) = match &add.fields {
-                    Some(fields_to_add) => {
-                        let field_set_from_schema = ctx.node_fields.get(ty.as_str()).cloned();
-                        if let Some(field_set) = field_set_from_schema {
-                            for (field_name, field_value) in fields_to_add {
+                }
+                *tr.source_step.inner_mut() = SourceStep::UpsertN(UpsertN {
+                    label: add_n.label.clone(),
+                    key: (key.clone(), key_value),
+                    properties: Some(properties),
+                    defaults: Some(defaults),
+                    secondary_indices: add_n.secondary_indices.clone(),
+                });
+            }
+            (ty, stmt)
+        }
+        UpsertEdge(upsert) => {
+            let (ty, mut stmt) =
+                infer_add_edge(ctx, &upsert.edge, scope, original_query, gen_query);
+            if let Some(GeneratedStatement::Traversal(tr)) = &mut stmt
+                && let SourceStep::AddE(add_e) = tr.source_step.inner_mut()
+            {
+                add_e.upsert = true;
+            }
+            (ty, stmt)
+        }
+        AddVector(add) => {
+            if let Some(ref ty) = add.vector_type {
+                if !ctx.vector_set.contains(ty.as_str()) {
+                    generate_error!(ctx, original_query, add.loc.clone(), E103, ty.as_str());
+                }
+                // Validate vector fields
+                let (label, properties) = match &add.fields {
+                    Some(fields) => {
+                        let field_set = ctx.vector_fields.get(ty.as_str()).cloned();
+                        if let Some(field_set) = field_set {
+                            for (field_name, value) in fields {
                                 if !field_set.contains_key(field_name.as_str()) {
                                     generate_error!(
                                         ctx,
@@ -191,11 +245,11 @@ pub(crate) fn infer_expr_type<'a>(
                                         add.loc.clone(),
                                         E202,
                                         field_name.as_str(),
-                                        "node",
+                                        "vector",
                                         ty.as_str()
                                     );
                                 }
-                                match field_value {
+                                match value {
                                     ValueType::Identifier { value, loc } => {
                                         if is_valid_identifier(
                                             ctx,
@@ -212,9 +266,8 @@ pub(crate) fn infer_expr_type<'a>(
                                                 value.as_str()
                                             );
                                         } else {
-                                            
                                             let variable_type =
-                                            &scope.get(value.as_str()).unwrap().ty;
+                                                &scope.get(value.as_str()).unwrap().ty;
                                             if variable_type
                                                 != &Type::from(
                                                     field_set
@@ -236,31 +289,40 @@ pub(crate) fn infer_expr_type<'a>(
                                                         .unwrap()
                                                         .field_type
                                                         .to_string(),
-                                                    "node",
+                                                    "vector",
                                                     ty.as_str()
                                                 );
                                             }
                                         }
                                     }
                                     ValueType::Literal { value, loc } => {
+                                        // check against type
                                         let field_type = ctx
-                                            .node_fields
+                                            .vector_fields
                                             .get(ty.as_str())
                                             .unwrap()
                                             .get(field_name.as_str())
                                             .unwrap()
                                             .field_type
                                             .clone();
-                                        if field_type != *value {
+                                        if let Some(enum_schema) = field_enum(ctx, &field_type) {
+                                            check_enum_literal(
+                                                ctx,
+                                                original_query,
+                                                loc.clone(),
+                                                enum_schema,
+                                                value,
+                                            );
+                                        } else if field_type != *value {
                                             generate_error!(
                                                 ctx,
                                                 original_query,
                                                 loc.clone(),
                                                 E205,
-                                                &value.inner_stringify(),
-                                                value.to_variant_string(),
+                                                value.as_str(),
+                                                &value.to_variant_string(),
                                                 &field_type.to_string(),
-                                                "node",
+                                                "vector",
                                                 ty.as_str()
                                             );
                                         }
@@ -269,22 +331,23 @@ pub(crate) fn infer_expr_type<'a>(
                                 }
                             }
                         }
-                        let mut properties = fields_to_add
+                        let label = GenRef::Literal(ty.clone());
+                        let properties = fields
                             .iter()
                             .map(|(field_name, value)| {
                                 (
                                     field_name.clone(),
                                     match value {
                                         ValueType::Literal { value, loc } => {
-                                            match ctx
-                                                .node_fields
+                                            let field_type = ctx
+                                                .vector_fields
                                                 .get(ty.as_str())
                                                 .unwrap()
                                                 .get(field_name.as_str())
                                                 .unwrap()
                                                 .field_type
-                                                == FieldType::Date
-                                            {
+                                                .clone();
+                                            match field_type == FieldType::Date {
                                                 true => match Date::new(value) {
                                                     Ok(date) => GeneratedValue::Literal(
                                                         GenRef::Literal(date.to_rfc3339()),
@@ -300,17 +363,25 @@ pub(crate) fn infer_expr_type<'a>(
                                                         GeneratedValue::Unknown
                                                     }
                                                 },
-                                                false => GeneratedValue::Literal(GenRef::from(
-                                                    value.clone(),
-                                                )),
+                                                false => {
+                                                    gen_literal_field_value(ctx, &field_type, value)
+                                                }
                                             }
                                         }
-                                        ValueType::Identifier { value, .. } => {
-                                            gen_identifier_or_param(
+                                        ValueType::Identifier { value, loc } => {
+                                            is_valid_identifier(
+                                                ctx,
                                                 original_query,
-                                                value,
-                                                true,
+                                                loc.clone(),
+                                                value.as_str(),
+                                            );
+                                            gen_field_value(
+                                                original_query,
+                                                value.as_str(),
+                                                Type::Vector(Some(ty.clone()))
+                                                    .is_optional_field(ctx, field_name),
                                                 false,
+                                                true,
                                             )
                                         }
                                         v => {
@@ -326,655 +397,510 @@ pub(crate) fn infer_expr_type<'a>(
                                     },
                                 )
                             })
-                            .collect::<HashMap<String, GeneratedValue>>();
-
-                        for (field_name, default_value) in default_properties {
-                            if !properties.contains_key(field_name.as_str()) {
-                                properties.insert(field_name, default_value);
-                            }
-                        }
-
-                        let secondary_indices = {
-                            let secondary_indices = node_in_schema
-                                .properties
-                                .iter()
-                                .filter_map(|p| {
-                                    matches!(p.is_index, FieldPrefix::Index)
-                                        .then_some(p.name.clone())
-                                })
-                                .collect::<Vec<_>>();
-                            match secondary_indices.is_empty() {
-                                true => None,
-                                false => Some(secondary_indices),
-                            }
-                        };
-
-                        (properties, secondary_indices)
+                            .collect();
+                        (label, Some(properties))
                     }
-                    None => (
-                        default_properties.into_iter().fold(
-                            HashMap::new(),
-                            |mut acc, (field_name, default_value)| {
-                                acc.insert(field_name, default_value);
-                                acc
-                            },
-                        ),
-                        None,
-                    ),
-                };
-
-                let add_n = AddN {
-                    label,
-                    properties: Some(properties.into_iter().collect()),
-                    secondary_indices,
+                    None => (GenRef::Literal(ty.clone()), None),
                 };
+                if let Some(vec_data) = &add.data {
+                    let vec = match vec_data {
+                        VectorData::Vector(v) => {
+                            VecData::Standard(GeneratedValue::Literal(GenRef::Ref(format!(
+                                "[{}]",
+                                v.iter()
+                                    .map(|f| f.to_string())
+                                    .collect::<Vec<String>>()
+                                    .join(",")
+                            ))))
+                        }
+                        VectorData::Identifier(i) => {
+                            is_valid_identifier(ctx, original_query, add.loc.clone(), i.as_str());
+                            let id =
+                                gen_identifier_or_param(original_query, i.as_str(), true, false);
+                            VecData::Standard(id)
+                        }
+                        VectorData::Embed(e) => {
+                            let embed_data = match &e.value {
+                                EvaluatesToString::Identifier(i) => EmbedData {
+                                    data: gen_identifier_or_param(
+                                        original_query,
+                                        i.as_str(),
+                                        true,
+                                        false,
+                                    ),
+                                    model_name: gen_query.embedding_model_to_use.clone(),
+                                },
+                                EvaluatesToString::StringLiteral(s) => EmbedData {
+                                    data: GeneratedValue::Literal(GenRef::Ref(s.clone())),
+                                    model_name: gen_query.embedding_model_to_use.clone(),
+                                },
+                            };
 
-                let stmt = GeneratedStatement::Traversal(GeneratedTraversal {
-                    source_step: Separator::Period(SourceStep::AddN(add_n)),
-                    steps: vec![],
-                    traversal_type: TraversalType::Mut,
-                    should_collect: ShouldCollect::ToObj,
-                    ..Default::default()
-                });
-                gen_query.is_mut = true;
-                return (Type::Node(Some(ty.to_string())), Some(stmt));
+                            VecData::Hoisted(gen_query.add_hoisted_embed(embed_data))
+                        }
+                    };
+                    let add_v = AddV {
+                        vec,
+                        label,
+                        properties,
+                    };
+                    let stmt = GeneratedStatement::Traversal(GeneratedTraversal {
+                        source_step: Separator::Period(SourceStep::AddV(add_v)),
+                        steps: vec![],
+                        traversal_type: TraversalType::Mut,
+                        should_collect: ShouldCollect::ToObj,
+                        ..Default::default()
+                    });
+                    gen_query.is_mut = true;
+                    return (Type::Vector(Some(ty.to_string())), Some(stmt));
+                }
             }
             generate_error!(
                 ctx,
                 original_query,
                 add.loc.clone(),
                 E304,
-                ["node"],
-                ["node"]
+                ["vector"],
+                ["vector"]
             );
-            (Type::Node(None), None)
+            (Type::Vector(None), None)
         }
-        AddEdge(add) => {
-            if let Some(ref ty) = add.edge_type {
-                if !ctx.edge_map.contains_key(ty.as_str()) {
-                    generate_error!(ctx, original_query, add.loc.clone(), E102, ty.as_str());
+        // BatchAddVector(add) => {
+        //     if let Some(ref ty) = add.vector_type {
+        //         if !ctx.vector_set.contains(ty.as_str()) {
+        //             push_query_err(ctx,
+        //                 original_query,
+        //                 add.loc.clone(),
+        //                 format!("vector type `{}` has not been declared", ty),
+        //                 format!("add a `V::{}` schema first", ty),
+        //             );
+        //         }
+        //     }
+        //     Type::Vector(add.vector_type.as_deref())
+        // }
+        SearchVector(sv) => {
+            if let Some(ref ty) = sv.vector_type
+                && !ctx.vector_set.contains(ty.as_str())
+            {
+                generate_error!(ctx, original_query, sv.loc.clone(), E103, ty.as_str());
+            }
+            let vec: VecData = match &sv.data {
+                Some(VectorData::Vector(v)) => {
+                    VecData::Standard(GeneratedValue::Literal(GenRef::Ref(format!(
+                        "[{}]",
+                        v.iter()
+                            .map(|f| f.to_string())
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    ))))
                 }
-                let label = GenRef::Literal(ty.clone());
-                // Validate fields if both type and fields are present
-                let properties = match &add.fields {
-                    Some(fields) => {
-                        // Get the field set before validation
-                        let field_set = ctx.edge_fields.get(ty.as_str()).cloned();
-                        if let Some(field_set) = field_set {
-                            for (field_name, value) in fields {
-                                if !field_set.contains_key(field_name.as_str()) {
-                                    generate_error!(
-                                        ctx,
-                                        original_query,
-                                        add.loc.clone(),
-                                        E202,
-                                        field_name.as_str(),
-                                        "edge",
-                                        ty.as_str()
-                                    );
-                                }
+                Some(VectorData::Identifier(i)) => {
+                    is_valid_identifier(ctx, original_query, sv.loc.clone(), i.as_str());
+                    // if is in params then use data.
+                    let _ = type_in_scope(ctx, original_query, sv.loc.clone(), scope, i.as_str());
+                    VecData::Standard(gen_identifier_or_param(
+                        original_query,
+                        i.as_str(),
+                        true,
+                        false,
+                    ))
+                }
+                Some(VectorData::Embed(e)) => {
+                    let embed_data = match &e.value {
+                        EvaluatesToString::Identifier(i) => EmbedData {
+                            data: gen_identifier_or_param(original_query, i.as_str(), true, false),
+                            model_name: gen_query.embedding_model_to_use.clone(),
+                        },
+                        EvaluatesToString::StringLiteral(s) => EmbedData {
+                            data: GeneratedValue::Literal(GenRef::Ref(s.clone())),
+                            model_name: gen_query.embedding_model_to_use.clone(),
+                        },
+                    };
 
-                                match value {
-                                    ValueType::Identifier { value, loc } => {
-                                        if is_valid_identifier(
-                                            ctx,
-                                            original_query,
-                                            loc.clone(),
-                                            value.as_str(),
-                                        ) && !scope.contains_key(value.as_str())
-                                        {
-                                            generate_error!(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                E301,
-                                                value.as_str()
-                                            );
-                                        } else {
-                                            let variable_type =
-                                                &scope.get(value.as_str()).unwrap().ty;
-                                            if variable_type
-                                                != &Type::from(
-                                                    field_set
-                                                        .get(field_name.as_str())
-                                                        .unwrap()
-                                                        .field_type
-                                                        .clone(),
-                                                )
-                                            {
-                                                generate_error!(
-                                                    ctx,
-                                                    original_query,
-                                                    loc.clone(),
-                                                    E205,
-                                                    value.as_str(),
-                                                    &variable_type.to_string(),
-                                                    &field_set
-                                                        .get(field_name.as_str())
-                                                        .unwrap()
-                                                        .field_type
-                                                        .to_string(),
-                                                    "edge",
-                                                    ty.as_str()
-                                                );
-                                            }
-                                        }
-                                    }
-                                    ValueType::Literal { value, loc } => {
-                                        // check against type
-                                        let field_type = ctx
-                                            .edge_fields
-                                            .get(ty.as_str())
-                                            .unwrap()
-                                            .get(field_name.as_str())
-                                            .unwrap()
-                                            .field_type
-                                            .clone();
-                                        if field_type != *value {
-                                            generate_error!(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                E205,
-                                                &value.inner_stringify(),
-                                                value.to_variant_string(),
-                                                &field_type.to_string(),
-                                                "edge",
-                                                ty.as_str()
-                                            );
-                                        }
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-                        Some(
-                            fields
-                                .iter()
-                                .map(|(field_name, value)| {
-                                    (
-                                        field_name.clone(),
-                                        match value {
-                                            ValueType::Literal { value, loc } => {
-                                                match ctx
-                                                    .edge_fields
-                                                    .get(ty.as_str())
-                                                    .unwrap()
-                                                    .get(field_name.as_str())
-                                                    .unwrap()
-                                                    .field_type
-                                                    == FieldType::Date
-                                                {
-                                                    true => match Date::new(value) {
-                                                        Ok(date) => GeneratedValue::Literal(
-                                                            GenRef::Literal(date.to_rfc3339()),
-                                                        ),
-                                                        Err(_) => {
-                                                            generate_error!(
-                                                                ctx,
-                                                                original_query,
-                                                                loc.clone(),
-                                                                E501,
-                                                                value.as_str()
-                                                            );
-                                                            GeneratedValue::Unknown
-                                                        }
-                                                    },
-                                                    false => GeneratedValue::Literal(GenRef::from(
-                                                        value.clone(),
-                                                    )),
-                                                }
-                                            }
-                                            ValueType::Identifier { value, loc } => {
-                                                is_valid_identifier(
-                                                    ctx,
-                                                    original_query,
-                                                    loc.clone(),
-                                                    value.as_str(),
-                                                );
-                                                gen_identifier_or_param(
-                                                    original_query,
-                                                    value.as_str(),
-                                                    false,
-                                                    true,
-                                                )
-                                            }
-                                            v => {
-                                                generate_error!(
-                                                    ctx,
-                                                    original_query,
-                                                    add.loc.clone(),
-                                                    E206,
-                                                    &v.to_string()
-                                                );
-                                                GeneratedValue::Unknown
-                                            }
-                                        },
-                                    )
-                                })
-                                .collect(),
-                        )
+                    VecData::Hoisted(gen_query.add_hoisted_embed(embed_data))
+                }
+                _ => {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        sv.loc.clone(),
+                        E305,
+                        ["vector_data", "SearchV"],
+                        ["vector_data"]
+                    );
+                    VecData::Unknown
+                }
+            };
+            let k = match &sv.k {
+                Some(k) => match &k.value {
+                    EvaluatesToNumberType::I8(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    }
+                    EvaluatesToNumberType::I16(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    }
+                    EvaluatesToNumberType::I32(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    }
+                    EvaluatesToNumberType::I64(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                     }
-                    None => None,
-                };
 
-                let (to, to_is_plural) = match &add.connection.to_id {
-                    Some(id) => match id {
-                        IdType::Identifier { value, loc } => {
-                            is_valid_identifier(ctx, original_query, loc.clone(), value.as_str());
-                            // Check if this variable is plural
-                            let is_plural = scope
-                                .get(value.as_str())
-                                .map(|var_info| !var_info.is_single)
-                                .unwrap_or(false);
-                            let gen_value = if is_plural {
-                                // For plural variables, just use the variable name without .id()
-                                if let Some(param) = is_param(original_query, value.as_str()) {
-                                    GeneratedValue::Parameter(match param.is_optional {
-                                        true => GenRef::DeRef(format!(
-                                            "data.{}.as_ref().ok_or_else(|| GraphError::ParamNotFound(\"{}\"))?",
-                                            value, value
-                                        )),
-                                        false => GenRef::DeRef(format!("data.{}", value)),
-                                    })
-                                } else {
-                                    GeneratedValue::Identifier(GenRef::Std(value.clone()))
-                                }
-                            } else {
-                                gen_id_access_or_param(original_query, value.as_str())
-                            };
-                            (gen_value, is_plural)
-                        }
-                        IdType::Literal { value, loc: _ } => (
-                            GeneratedValue::Literal(GenRef::Literal(value.clone())),
-                            false,
-                        ),
-                        _ => unreachable!(),
-                    },
+                    EvaluatesToNumberType::U8(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    }
+                    EvaluatesToNumberType::U16(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    }
+                    EvaluatesToNumberType::U32(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    }
+                    EvaluatesToNumberType::U64(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    }
+                    EvaluatesToNumberType::U128(i) => {
+                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    }
+                    EvaluatesToNumberType::Identifier(i) => {
+                        is_valid_identifier(ctx, original_query, sv.loc.clone(), i.as_str());
+                        gen_identifier_or_param(original_query, i, false, false)
+                    }
                     _ => {
-                        generate_error!(ctx, original_query, add.loc.clone(), E611);
-                        (GeneratedValue::Unknown, false)
+                        generate_error!(
+                            ctx,
+                            original_query,
+                            sv.loc.clone(),
+                            E305,
+                            ["k", "SearchV"],
+                            ["k"]
+                        );
+                        GeneratedValue::Unknown
                     }
-                };
-                let (from, from_is_plural) = match &add.connection.from_id {
-                    Some(id) => match id {
-                        IdType::Identifier { value, loc } => {
-                            is_valid_identifier(ctx, original_query, loc.clone(), value.as_str());
-                            // Check if this variable is plural
-                            let is_plural = scope
-                                .get(value.as_str())
-                                .map(|var_info| !var_info.is_single)
-                                .unwrap_or(false);
-                            let gen_value = if is_plural {
-                                // For plural variables, just use the variable name without .id()
-                                if let Some(param) = is_param(original_query, value.as_str()) {
-                                    GeneratedValue::Parameter(match param.is_optional {
-                                        true => GenRef::DeRef(format!(
-                                            "data.{}.as_ref().ok_or_else(|| GraphError::ParamNotFound(\"{}\"))?",
-                                            value, value
-                                        )),
-                                        false => GenRef::DeRef(format!("data.{}", value)),
-                                    })
-                                } else {
-                                    GeneratedValue::Identifier(GenRef::Std(value.clone()))
-                                }
-                            } else {
-                                gen_id_access_or_param(original_query, value.as_str())
-                            };
-                            (gen_value, is_plural)
+                },
+                None => {
+                    generate_error!(ctx, original_query, sv.loc.clone(), E601, &sv.loc.span);
+                    GeneratedValue::Unknown
+                }
+            };
+
+            let pre_filter: Option<Vec<BoExp>> = match &sv.pre_filter {
+                Some(expr) => {
+                    let (_, stmt) = infer_expr_type(
+                        ctx,
+                        expr,
+                        scope,
+                        original_query,
+                        Some(Type::Vector(sv.vector_type.clone())),
+                        gen_query,
+                    );
+                    // Where/boolean ops don't change the element type,
+                    // so `cur_ty` stays the same.
+                    if stmt.is_none() {
+                        return (Type::Vector(sv.vector_type.clone()), None);
+                    }
+                    let stmt = stmt.unwrap();
+                    let mut gen_traversal = GeneratedTraversal {
+                        traversal_type: TraversalType::FromIter(GenRef::Std("v".to_string())),
+                        steps: vec![],
+                        should_collect: ShouldCollect::ToVec,
+                        source_step: Separator::Empty(SourceStep::Anonymous),
+                        ..Default::default()
+                    };
+                    match stmt {
+                        GeneratedStatement::Traversal(tr) => {
+                            gen_traversal
+                                .steps
+                                .push(Separator::Period(GeneratedStep::Where(Where::Ref(
+                                    WhereRef {
+                                        expr: BoExp::Expr(tr),
+                                    },
+                                ))));
+                        }
+                        GeneratedStatement::BoExp(expr) => {
+                            gen_traversal
+                                .steps
+                                .push(Separator::Period(GeneratedStep::Where(match expr {
+                                    BoExp::Exists(mut traversal) => {
+                                        traversal.should_collect = ShouldCollect::No;
+                                        Where::Ref(WhereRef {
+                                            expr: BoExp::Exists(traversal),
+                                        })
+                                    }
+                                    _ => Where::Ref(WhereRef { expr }),
+                                })));
                         }
-                        IdType::Literal { value, loc: _ } => (
-                            GeneratedValue::Literal(GenRef::Literal(value.clone())),
-                            false,
-                        ),
                         _ => unreachable!(),
-                    },
-                    _ => {
-                        generate_error!(ctx, original_query, add.loc.clone(), E612);
-                        (GeneratedValue::Unknown, false)
                     }
-                };
-                let add_e = AddE {
-                    to,
-                    from,
-                    label,
-                    properties,
-                    from_is_plural,
-                    to_is_plural,
-                };
-                // If either from or to is plural, use Standalone (no G::new_mut wrapper),
-                // Empty separator (no period before it), and No collection (already done in iteration)
-                let (final_traversal_result_type, traversal_type, separator, should_collect) =
-                    if from_is_plural || to_is_plural {
-                        (
-                            Type::Edges(Some(ty.to_string())),
-                            TraversalType::Standalone,
-                            Separator::Empty(SourceStep::AddE(add_e)),
-                            ShouldCollect::No,
-                        )
-                    } else {
-                        (
-                            Type::Edge(Some(ty.to_string())),
-                            TraversalType::Mut,
-                            Separator::Period(SourceStep::AddE(add_e)),
-                            ShouldCollect::ToObj,
-                        )
-                    };
-                let stmt = GeneratedStatement::Traversal(GeneratedTraversal {
-                    source_step: separator,
+                    Some(vec![BoExp::Expr(gen_traversal)])
+                }
+                None => None,
+            };
+
+            // Search returns nodes that contain the vectors
+            (
+                Type::Vectors(sv.vector_type.clone()),
+                Some(GeneratedStatement::Traversal(GeneratedTraversal {
+                    traversal_type: TraversalType::Ref,
                     steps: vec![],
-                    traversal_type,
-                    should_collect,
+                    should_collect: ShouldCollect::ToVec,
+                    source_step: Separator::Period(SourceStep::SearchVector(
+                        GeneratedSearchVector {
+                            label: GenRef::Literal(sv.vector_type.clone().unwrap()),
+                            vec,
+                            k,
+                            pre_filter,
+                        },
+                    )),
                     ..Default::default()
-                });
-                gen_query.is_mut = true;
-                return (final_traversal_result_type, Some(stmt));
-            }
-            generate_error!(
-                ctx,
-                original_query,
-                add.loc.clone(),
-                E304,
-                ["edge"],
-                ["edge"]
-            );
-            (Type::Edge(None), None)
+                })),
+            )
         }
-        AddVector(add) => {
-            if let Some(ref ty) = add.vector_type {
-                if !ctx.vector_set.contains(ty.as_str()) {
-                    generate_error!(ctx, original_query, add.loc.clone(), E103, ty.as_str());
-                }
-                // Validate vector fields
-                let (label, properties) = match &add.fields {
-                    Some(fields) => {
-                        let field_set = ctx.vector_fields.get(ty.as_str()).cloned();
-                        if let Some(field_set) = field_set {
-                            for (field_name, value) in fields {
-                                if !field_set.contains_key(field_name.as_str()) {
-                                    generate_error!(
-                                        ctx,
-                                        original_query,
-                                        add.loc.clone(),
-                                        E202,
-                                        field_name.as_str(),
-                                        "vector",
-                                        ty.as_str()
-                                    );
-                                }
-                                match value {
-                                    ValueType::Identifier { value, loc } => {
-                                        if is_valid_identifier(
-                                            ctx,
-                                            original_query,
-                                            loc.clone(),
-                                            value.as_str(),
-                                        ) && !scope.contains_key(value.as_str())
-                                        {
-                                            generate_error!(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                E301,
-                                                value.as_str()
-                                            );
-                                        } else {
-                                            let variable_type =
-                                                &scope.get(value.as_str()).unwrap().ty;
-                                            if variable_type
-                                                != &Type::from(
-                                                    field_set
-                                                        .get(field_name.as_str())
-                                                        .unwrap()
-                                                        .field_type
-                                                        .clone(),
-                                                )
-                                            {
-                                                generate_error!(
-                                                    ctx,
-                                                    original_query,
-                                                    loc.clone(),
-                                                    E205,
-                                                    value.as_str(),
-                                                    &variable_type.to_string(),
-                                                    &field_set
-                                                        .get(field_name.as_str())
-                                                        .unwrap()
-                                                        .field_type
-                                                        .to_string(),
-                                                    "vector",
-                                                    ty.as_str()
-                                                );
-                                            }
-                                        }
-                                    }
-                                    ValueType::Literal { value, loc } => {
-                                        // check against type
-                                        let field_type = ctx
-                                            .vector_fields
-                                            .get(ty.as_str())
-                                            .unwrap()
-                                            .get(field_name.as_str())
-                                            .unwrap()
-                                            .field_type
-                                            .clone();
-                                        if field_type != *value {
-                                            generate_error!(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                E205,
-                                                value.as_str(),
-                                                &value.to_variant_string(),
-                                                &field_type.to_string(),
-                                                "vector",
-                                                ty.as_str()
-                                            );
-                                        }
-                                    }
-                                    _ => {}
+        And(exprs) => {
+            let exprs = exprs
+                .iter()
+                .map(|expr| {
+                    let (ty, stmt) = infer_expr_type(
+                        ctx,
+                        expr,
+                        scope,
+                        original_query,
+                        parent_ty.clone(),
+                        gen_query,
+                    );
+
+                    match stmt.unwrap() {
+                        GeneratedStatement::BoExp(expr) => match expr {
+                            BoExp::Exists(mut traversal) => {
+                                traversal.should_collect = ShouldCollect::No;
+                                BoExp::Exists(traversal)
+                            }
+                            BoExp::Not(inner_expr) => {
+                                if let BoExp::Exists(mut traversal) = *inner_expr {
+                                    traversal.should_collect = ShouldCollect::No;
+                                    BoExp::Exists(traversal)
+                                } else {
+                                    BoExp::Not(inner_expr)
                                 }
                             }
+                            _ => expr,
+                        },
+                        GeneratedStatement::Traversal(tr) => BoExp::Expr(tr),
+                        _ => {
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                expr.loc.clone(),
+                                E306,
+                                ty.kind_str()
+                            );
+                            BoExp::Empty
                         }
-                        let label = GenRef::Literal(ty.clone());
-                        let properties = fields
-                            .iter()
-                            .map(|(field_name, value)| {
-                                (
-                                    field_name.clone(),
-                                    match value {
-                                        ValueType::Literal { value, loc } => {
-                                            match ctx
-                                                .vector_fields
-                                                .get(ty.as_str())
-                                                .unwrap()
-                                                .get(field_name.as_str())
-                                                .unwrap()
-                                                .field_type
-                                                == FieldType::Date
-                                            {
-                                                true => match Date::new(value) {
-                                                    Ok(date) => GeneratedValue::Literal(
-                                                        GenRef::Literal(date.to_rfc3339()),
-                                                    ),
-                                                    Err(_) => {
-                                                        generate_error!(
-                                                            ctx,
-                                                            original_query,
-                                                            loc.clone(),
-                                                            E501,
-                                                            value.as_str()
-                                                        );
-                                                        GeneratedValue::Unknown
-                                                    }
-                                                },
-                                                false => GeneratedValue::Literal(GenRef::from(
-                                                    value.clone(),
-                                                )),
-                                            }
-                                        }
-                                        ValueType::Identifier { value, loc } => {
-                                            is_valid_identifier(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                value.as_str(),
-                                            );
-                                            gen_identifier_or_param(
-                                                original_query,
-                                                value.as_str(),
-                                                false,
-                                                true,
-                                            )
-                                        }
-                                        v => {
-                                            generate_error!(
-                                                ctx,
-                                                original_query,
-                                                add.loc.clone(),
-                                                E206,
-                                                &v.to_string()
-                                            );
-                                            GeneratedValue::Unknown
-                                        }
-                                    },
-                                )
-                            })
-                            .collect();
-                        (label, Some(properties))
                     }
-                    None => (GenRef::Literal(ty.clone()), None),
-                };
-                if let Some(vec_data) = &add.data {
-                    let vec = match vec_data {
-                        VectorData::Vector(v) => {
-                            VecData::Standard(GeneratedValue::Literal(GenRef::Ref(format!(
-                                "[{}]",
-                                v.iter()
-                                    .map(|f| f.to_string())
-                                    .collect::<Vec<String>>()
-                                    .join(",")
-                            ))))
-                        }
-                        VectorData::Identifier(i) => {
-                            is_valid_identifier(ctx, original_query, add.loc.clone(), i.as_str());
-                            let id =
-                                gen_identifier_or_param(original_query, i.as_str(), true, false);
-                            VecData::Standard(id)
-                        }
-                        VectorData::Embed(e) => {
-                            let embed_data = match &e.value {
-                                EvaluatesToString::Identifier(i) => EmbedData {
-                                    data: gen_identifier_or_param(
-                                        original_query,
-                                        i.as_str(),
-                                        true,
-                                        false,
-                                    ),
-                                    model_name: gen_query.embedding_model_to_use.clone(),
-                                },
-                                EvaluatesToString::StringLiteral(s) => EmbedData {
-                                    data: GeneratedValue::Literal(GenRef::Ref(s.clone())),
-                                    model_name: gen_query.embedding_model_to_use.clone(),
-                                },
-                            };
+                })
+                .collect::<Vec<_>>();
+            (
+                Type::Boolean,
+                Some(GeneratedStatement::BoExp(BoExp::And(exprs))),
+            )
+        }
+        Or(exprs) => {
+            let exprs = exprs
+                .iter()
+                .map(|expr| {
+                    let (ty, stmt) = infer_expr_type(
+                        ctx,
+                        expr,
+                        scope,
+                        original_query,
+                        parent_ty.clone(),
+                        gen_query,
+                    );
 
-                            VecData::Hoisted(gen_query.add_hoisted_embed(embed_data))
+                    match stmt.unwrap() {
+                        GeneratedStatement::BoExp(expr) => match expr {
+                            BoExp::Exists(mut traversal) => {
+                                traversal.should_collect = ShouldCollect::No;
+                                BoExp::Exists(traversal)
+                            }
+                            BoExp::Not(inner_expr) => {
+                                if let BoExp::Exists(mut traversal) = *inner_expr {
+                                    traversal.should_collect = ShouldCollect::No;
+                                    BoExp::Exists(traversal)
+                                } else {
+                                    BoExp::Not(inner_expr)
+                                }
+                            }
+                            _ => expr,
+                        },
+                        GeneratedStatement::Traversal(tr) => BoExp::Expr(tr),
+                        _ => {
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                expr.loc.clone(),
+                                E306,
+                                ty.kind_str()
+                            );
+                            BoExp::Empty
                         }
+                    }
+                })
+                .collect::<Vec<_>>();
+            (
+                Type::Boolean,
+                Some(GeneratedStatement::BoExp(BoExp::Or(exprs))),
+            )
+        }
+        Not(expr) => {
+            let (ty, stmt) =
+                infer_expr_type(ctx, expr, scope, original_query, parent_ty, gen_query);
+
+            match stmt.unwrap() {
+                GeneratedStatement::BoExp(expr) => (
+                    Type::Boolean,
+                    Some(GeneratedStatement::BoExp(BoExp::Not(Box::new(expr)))),
+                ),
+                _ => {
+                    generate_error!(ctx, original_query, expr.loc.clone(), E306, ty.kind_str());
+                    (Type::Unknown, None)
+                }
+            }
+        }
+        Exists(expr) => {
+            let (_, stmt) =
+                infer_expr_type(ctx, &expr.expr, scope, original_query, parent_ty, gen_query);
+            if stmt.is_none() {
+                return (Type::Boolean, None);
+            }
+            assert!(matches!(stmt, Some(GeneratedStatement::Traversal(_))));
+            let traversal = match stmt.unwrap() {
+                GeneratedStatement::Traversal(mut tr) => {
+                    // Traversals starting from the graph (e.g. `EXISTS(N<User>)`) keep their
+                    // own source; only variable and anonymous traversals are re-rooted.
+                    let source_variable = match tr.source_step.inner() {
+                        SourceStep::Identifier(id) => Some(id.inner().clone()),
+                        SourceStep::Anonymous | SourceStep::Empty => Some(DEFAULT_VAR_NAME.to_string()),
+                        _ => None,
                     };
-                    let add_v = AddV {
-                        vec,
-                        label,
-                        properties,
-                    };
-                    let stmt = GeneratedStatement::Traversal(GeneratedTraversal {
-                        source_step: Separator::Period(SourceStep::AddV(add_v)),
-                        steps: vec![],
-                        traversal_type: TraversalType::Mut,
-                        should_collect: ShouldCollect::ToObj,
-                        ..Default::default()
-                    });
-                    gen_query.is_mut = true;
-                    return (Type::Vector(Some(ty.to_string())), Some(stmt));
+                    if let Some(source_variable) = source_variable {
+                        // Check if the variable is single or plural to determine traversal type
+                        let is_single = scope
+                            .get(source_variable.as_str())
+                            .map(|var_info| var_info.is_single)
+                            .unwrap_or(false);
+
+                        tr.traversal_type = if is_single {
+                            TraversalType::FromSingle(GenRef::Std(source_variable))
+                        } else {
+                            TraversalType::FromIter(GenRef::Std(source_variable))
+                        };
+                    }
+                    tr.should_collect = ShouldCollect::No;
+                    tr
                 }
+                _ => unreachable!(),
+            };
+            (
+                Type::Boolean,
+                Some(GeneratedStatement::BoExp(BoExp::Exists(traversal))),
+            )
+        }
+        MathFunctionCall(_math_call) => {
+            // Math function calls always return f64
+            // TODO: Add proper type inference and validation for math function arguments
+            (
+                Type::Scalar(FieldType::F64),
+                None, // Will be handled by generator
+            )
+        }
+        StringFunctionCall(call) => {
+            match gen_string_function_call(ctx, call, scope, original_query, None) {
+                Some(generated) => (
+                    Type::Scalar(string_function_type(call.function)),
+                    Some(GeneratedStatement::Literal(GenRef::Std(generated.render("val")))),
+                ),
+                None => (Type::Unknown, None),
             }
-            generate_error!(
+        }
+        DateFunctionCall(call) => {
+            match gen_date_function_call(ctx, call, scope, original_query, None) {
+                Some(generated) => (
+                    Type::Scalar(date_function_type(call.function)),
+                    Some(GeneratedStatement::Literal(GenRef::Std(generated.render("val")))),
+                ),
+                None => (Type::Unknown, None),
+            }
+        }
+        Now => (
+            Type::Scalar(FieldType::Date),
+            Some(GeneratedStatement::Literal(GenRef::Std(
+                DateExpr::Now.render("val"),
+            ))),
+        ),
+        Duration(millis) => (
+            Type::Scalar(FieldType::I64),
+            Some(GeneratedStatement::Literal(GenRef::Std(millis.to_string()))),
+        ),
+        ComputedPredicate(predicate) => {
+            let expr = gen_computed_predicate(
                 ctx,
+                predicate,
+                scope,
                 original_query,
-                add.loc.clone(),
-                E304,
-                ["vector"],
-                ["vector"]
+                parent_ty.as_ref(),
+                gen_query,
             );
-            (Type::Vector(None), None)
+            (
+                Type::Boolean,
+                Some(GeneratedStatement::BoExp(expr.unwrap_or(BoExp::Empty))),
+            )
         }
-        // BatchAddVector(add) => {
-        //     if let Some(ref ty) = add.vector_type {
-        //         if !ctx.vector_set.contains(ty.as_str()) {
-        //             push_query_err(ctx,
-        //                 original_query,
-        //                 add.loc.clone(),
-        //                 format!("vector type `{}` has not been declared", ty),
-        //                 format!("add a `V::{}` schema first", ty),
-        //             );
-        //         }
-        //     }
-        //     Type::Vector(add.vector_type.as_deref())
-        // }
-        SearchVector(sv) => {
-            if let Some(ref ty) = sv.vector_type
-                && !ctx.vector_set.contains(ty.as_str())
+        Empty => (Type::Unknown, Some(GeneratedStatement::Empty)),
+        BM25Search(bm25_search) => {
+            if let Some(ref ty) = bm25_search.type_arg
+                && !ctx.node_set.contains(ty.as_str())
             {
-                generate_error!(ctx, original_query, sv.loc.clone(), E103, ty.as_str());
+                generate_error!(
+                    ctx,
+                    original_query,
+                    bm25_search.loc.clone(),
+                    E101,
+                    ty.as_str()
+                );
             }
-            let vec: VecData = match &sv.data {
-                Some(VectorData::Vector(v)) => {
-                    VecData::Standard(GeneratedValue::Literal(GenRef::Ref(format!(
-                        "[{}]",
-                        v.iter()
-                            .map(|f| f.to_string())
-                            .collect::<Vec<String>>()
-                            .join(",")
-                    ))))
-                }
-                Some(VectorData::Identifier(i)) => {
-                    is_valid_identifier(ctx, original_query, sv.loc.clone(), i.as_str());
-                    // if is in params then use data.
-                    let _ = type_in_scope(ctx, original_query, sv.loc.clone(), scope, i.as_str());
-                    VecData::Standard(gen_identifier_or_param(
-                        original_query,
-                        i.as_str(),
-                        true,
-                        false,
-                    ))
+            let vec = match &bm25_search.data {
+                Some(ValueType::Literal { value, loc: _ }) => {
+                    GeneratedValue::Literal(GenRef::Std(value.inner_stringify()))
                 }
-                Some(VectorData::Embed(e)) => {
-                    let embed_data = match &e.value {
-                        EvaluatesToString::Identifier(i) => EmbedData {
-                            data: gen_identifier_or_param(original_query, i.as_str(), true, false),
-                            model_name: gen_query.embedding_model_to_use.clone(),
-                        },
-                        EvaluatesToString::StringLiteral(s) => EmbedData {
-                            data: GeneratedValue::Literal(GenRef::Ref(s.clone())),
-                            model_name: gen_query.embedding_model_to_use.clone(),
-                        },
-                    };
+                Some(ValueType::Identifier { value: i, loc: _ }) => {
+                    is_valid_identifier(ctx, original_query, bm25_search.loc.clone(), i.as_str());
 
-                    VecData::Hoisted(gen_query.add_hoisted_embed(embed_data))
+                    if is_in_scope(scope, i.as_str()) {
+                        gen_identifier_or_param(original_query, i, true, false)
+                    } else {
+                        generate_error!(
+                            ctx,
+                            original_query,
+                            bm25_search.loc.clone(),
+                            E301,
+                            i.as_str()
+                        );
+                        GeneratedValue::Unknown
+                    }
                 }
                 _ => {
                     generate_error!(
                         ctx,
                         original_query,
-                        sv.loc.clone(),
+                        bm25_search.loc.clone(),
                         E305,
                         ["vector_data", "SearchV"],
                         ["vector_data"]
                     );
-                    VecData::Unknown
+                    GeneratedValue::Unknown
                 }
             };
-            let k = match &sv.k {
+            let k = match &bm25_search.k {
                 Some(k) => match &k.value {
                     EvaluatesToNumberType::I8(i) => {
                         GeneratedValue::Primitive(GenRef::Std(i.to_string()))
@@ -1005,376 +931,1101 @@ pub(crate) fn infer_expr_type<'a>(
                         GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                     }
                     EvaluatesToNumberType::Identifier(i) => {
-                        is_valid_identifier(ctx, original_query, sv.loc.clone(), i.as_str());
+                        is_valid_identifier(
+                            ctx,
+                            original_query,
+                            bm25_search.loc.clone(),
+                            i.as_str(),
+                        );
                         gen_identifier_or_param(original_query, i, false, false)
                     }
                     _ => {
                         generate_error!(
                             ctx,
                             original_query,
-                            sv.loc.clone(),
+                            bm25_search.loc.clone(),
                             E305,
-                            ["k", "SearchV"],
+                            ["k", "SearchBM25"],
                             ["k"]
                         );
                         GeneratedValue::Unknown
                     }
                 },
                 None => {
-                    generate_error!(ctx, original_query, sv.loc.clone(), E601, &sv.loc.span);
-                    GeneratedValue::Unknown
-                }
-            };
-
-            let pre_filter: Option<Vec<BoExp>> = match &sv.pre_filter {
-                Some(expr) => {
-                    let (_, stmt) = infer_expr_type(
+                    generate_error!(
                         ctx,
-                        expr,
-                        scope,
                         original_query,
-                        Some(Type::Vector(sv.vector_type.clone())),
-                        gen_query,
+                        bm25_search.loc.clone(),
+                        E601,
+                        &bm25_search.loc.span
                     );
-                    // Where/boolean ops don't change the element type,
-                    // so `cur_ty` stays the same.
-                    if stmt.is_none() {
-                        return (Type::Vector(sv.vector_type.clone()), None);
-                    }
-                    let stmt = stmt.unwrap();
-                    let mut gen_traversal = GeneratedTraversal {
-                        traversal_type: TraversalType::FromIter(GenRef::Std("v".to_string())),
-                        steps: vec![],
-                        should_collect: ShouldCollect::ToVec,
-                        source_step: Separator::Empty(SourceStep::Anonymous),
-                        ..Default::default()
-                    };
-                    match stmt {
-                        GeneratedStatement::Traversal(tr) => {
-                            gen_traversal
-                                .steps
-                                .push(Separator::Period(GeneratedStep::Where(Where::Ref(
-                                    WhereRef {
-                                        expr: BoExp::Expr(tr),
-                                    },
-                                ))));
-                        }
-                        GeneratedStatement::BoExp(expr) => {
-                            gen_traversal
-                                .steps
-                                .push(Separator::Period(GeneratedStep::Where(match expr {
-                                    BoExp::Exists(mut traversal) => {
-                                        traversal.should_collect = ShouldCollect::No;
-                                        Where::Ref(WhereRef {
-                                            expr: BoExp::Exists(traversal),
-                                        })
-                                    }
-                                    _ => Where::Ref(WhereRef { expr }),
-                                })));
-                        }
-                        _ => unreachable!(),
-                    }
-                    Some(vec![BoExp::Expr(gen_traversal)])
+                    GeneratedValue::Unknown
                 }
-                None => None,
             };
 
-            // Search returns nodes that contain the vectors
+            let search_bm25 = SearchBM25 {
+                type_arg: GenRef::Literal(bm25_search.type_arg.clone().unwrap()),
+                query: vec,
+                k,
+            };
             (
-                Type::Vectors(sv.vector_type.clone()),
-                Some(GeneratedStatement::Traversal(GeneratedTraversal {
-                    traversal_type: TraversalType::Ref,
-                    steps: vec![],
-                    should_collect: ShouldCollect::ToVec,
-                    source_step: Separator::Period(SourceStep::SearchVector(
-                        GeneratedSearchVector {
-                            label: GenRef::Literal(sv.vector_type.clone().unwrap()),
-                            vec,
-                            k,
-                            pre_filter,
-                        },
-                    )),
+                Type::Nodes(bm25_search.type_arg.clone()),
+                Some(GeneratedStatement::Traversal(GeneratedTraversal {
+                    traversal_type: TraversalType::Ref,
+                    steps: vec![],
+                    should_collect: ShouldCollect::ToVec,
+                    source_step: Separator::Period(SourceStep::SearchBM25(search_bm25)),
+                    is_scored: true,
                     ..Default::default()
                 })),
             )
         }
-        And(exprs) => {
-            let exprs = exprs
-                .iter()
-                .map(|expr| {
-                    let (ty, stmt) = infer_expr_type(
-                        ctx,
-                        expr,
-                        scope,
-                        original_query,
-                        parent_ty.clone(),
-                        gen_query,
-                    );
+    }
+}
 
-                    match stmt.unwrap() {
-                        GeneratedStatement::BoExp(expr) => match expr {
-                            BoExp::Exists(mut traversal) => {
-                                traversal.should_collect = ShouldCollect::No;
-                                BoExp::Exists(traversal)
+/// Validates an `AddN` and generates the traversal adding the node
+fn infer_add_node<'a>(
+    ctx: &mut Ctx<'a>,
+    add: &'a AddNode,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    gen_query: &mut GeneratedQuery,
+) -> (Type, Option<GeneratedStatement>) {
+        if let Some(ref ty) = add.node_type {
+            if !ctx.node_set.contains(ty.as_str()) {
+                generate_error!(ctx, original_query, add.loc.clone(), E101, ty.as_str());
+            }
+            let label = GenRef::Literal(ty.clone());
+
+            let node_in_schema = match ctx.output.nodes.iter().find(|n| n.name == ty.as_str()) {
+                Some(node) => node.clone(),
+                None => {
+                    generate_error!(ctx, original_query, add.loc.clone(), E101, ty.as_str());
+                    return (Type::Node(None), None);
+                }
+            };
+
+            let default_properties = node_in_schema
+                .properties
+                .iter()
+                .filter_map(|p| p.default_value.clone().map(|v| (p.name.clone(), v)))
+                .collect::<Vec<(String, GeneratedValue)>>();
+
+            // Validate fields of add node by traversing the fields
+            // checking they exist in the schema, then checking their types
+            let (properties, secondary_indices) = match &add.fields {
+                Some(fields_to_add) => {
+                    let field_set_from_schema = ctx.node_fields.get(ty.as_str()).cloned();
+                    if let Some(field_set) = field_set_from_schema {
+                        for (field_name, field_value) in fields_to_add {
+                            if !field_set.contains_key(field_name.as_str()) {
+                                generate_error!(
+                                    ctx,
+                                    original_query,
+                                    add.loc.clone(),
+                                    E202,
+                                    field_name.as_str(),
+                                    "node",
+                                    ty.as_str()
+                                );
                             }
-                            BoExp::Not(inner_expr) => {
-                                if let BoExp::Exists(mut traversal) = *inner_expr {
-                                    traversal.should_collect = ShouldCollect::No;
-                                    BoExp::Exists(traversal)
-                                } else {
-                                    BoExp::Not(inner_expr)
+                            match field_value {
+                                ValueType::Identifier { value, loc } => {
+                                    if is_valid_identifier(
+                                        ctx,
+                                        original_query,
+                                        loc.clone(),
+                                        value.as_str(),
+                                    ) && !scope.contains_key(value.as_str())
+                                    {
+                                        generate_error!(
+                                            ctx,
+                                            original_query,
+                                            loc.clone(),
+                                            E301,
+                                            value.as_str()
+                                        );
+                                    } else {
+                                        
+                                        let variable_type =
+                                        &scope.get(value.as_str()).unwrap().ty;
+                                        if variable_type
+                                            != &Type::from(
+                                                field_set
+                                                    .get(field_name.as_str())
+                                                    .unwrap()
+                                                    .field_type
+                                                    .clone(),
+                                            )
+                                        {
+                                            generate_error!(
+                                                ctx,
+                                                original_query,
+                                                loc.clone(),
+                                                E205,
+                                                value.as_str(),
+                                                &variable_type.to_string(),
+                                                &field_set
+                                                    .get(field_name.as_str())
+                                                    .unwrap()
+                                                    .field_type
+                                                    .to_string(),
+                                                "node",
+                                                ty.as_str()
+                                            );
+                                        }
+                                    }
+                                }
+                                ValueType::Literal { value, loc } => {
+                                    let field_type = ctx
+                                        .node_fields
+                                        .get(ty.as_str())
+                                        .unwrap()
+                                        .get(field_name.as_str())
+                                        .unwrap()
+                                        .field_type
+                                        .clone();
+                                    if let Some(enum_schema) = field_enum(ctx, &field_type) {
+                                        check_enum_literal(
+                                            ctx,
+                                            original_query,
+                                            loc.clone(),
+                                            enum_schema,
+                                            value,
+                                        );
+                                    } else if field_type != *value {
+                                        generate_error!(
+                                            ctx,
+                                            original_query,
+                                            loc.clone(),
+                                            E205,
+                                            &value.inner_stringify(),
+                                            value.to_variant_string(),
+                                            &field_type.to_string(),
+                                            "node",
+                                            ty.as_str()
+                                        );
+                                    }
                                 }
+                                _ => {}
                             }
-                            _ => expr,
-                        },
-                        GeneratedStatement::Traversal(tr) => BoExp::Expr(tr),
-                        _ => {
-                            generate_error!(
-                                ctx,
-                                original_query,
-                                expr.loc.clone(),
-                                E306,
-                                ty.kind_str()
-                            );
-                            BoExp::Empty
                         }
                     }
-                })
-                .collect::<Vec<_>>();
-            (
-                Type::Boolean,
-                Some(GeneratedStatement::BoExp(BoExp::And(exprs))),
-            )
+                    let mut properties = fields_to_add
+                        .iter()
+                        .map(|(field_name, value)| {
+                            (
+                                field_name.clone(),
+                                match value {
+                                    ValueType::Literal { value, loc } => {
+                                        let field_type = ctx
+                                            .node_fields
+                                            .get(ty.as_str())
+                                            .unwrap()
+                                            .get(field_name.as_str())
+                                            .unwrap()
+                                            .field_type
+                                            .clone();
+                                        match field_type == FieldType::Date {
+                                            true => match Date::new(value) {
+                                                Ok(date) => GeneratedValue::Literal(
+                                                    GenRef::Literal(date.to_rfc3339()),
+                                                ),
+                                                Err(_) => {
+                                                    generate_error!(
+                                                        ctx,
+                                                        original_query,
+                                                        loc.clone(),
+                                                        E501,
+                                                        value.as_str()
+                                                    );
+                                                    GeneratedValue::Unknown
+                                                }
+                                            },
+                                            false => {
+                                                gen_literal_field_value(ctx, &field_type, value)
+                                            }
+                                        }
+                                    }
+                                    ValueType::Identifier { value, .. } => {
+                                        gen_field_value(
+                                            original_query,
+                                            value,
+                                            Type::Node(Some(ty.clone()))
+                                                .is_optional_field(ctx, field_name),
+                                            true,
+                                            false,
+                                        )
+                                    }
+                                    v => {
+                                        generate_error!(
+                                            ctx,
+                                            original_query,
+                                            add.loc.clone(),
+                                            E206,
+                                            &v.to_string()
+                                        );
+                                        GeneratedValue::Unknown
+                                    }
+                                },
+                            )
+                        })
+                        .collect::<HashMap<String, GeneratedValue>>();
+
+                    for (field_name, default_value) in default_properties {
+                        if !properties.contains_key(field_name.as_str()) {
+                            properties.insert(field_name, default_value);
+                        }
+                    }
+
+                    let secondary_indices = {
+                        let secondary_indices = node_in_schema
+                            .properties
+                            .iter()
+                            .filter_map(|p| {
+                                matches!(p.is_index, FieldPrefix::Index)
+                                    .then_some(p.name.clone())
+                            })
+                            .collect::<Vec<_>>();
+                        match secondary_indices.is_empty() {
+                            true => None,
+                            false => Some(secondary_indices),
+                        }
+                    };
+
+                    (properties, secondary_indices)
+                }
+                None => (
+                    default_properties.into_iter().fold(
+                        HashMap::new(),
+                        |mut acc, (field_name, default_value)| {
+                            acc.insert(field_name, default_value);
+                            acc
+                        },
+                    ),
+                    None,
+                ),
+            };
+
+            let add_n = AddN {
+                label,
+                properties: Some(properties.into_iter().collect()),
+                secondary_indices,
+            };
+
+            let stmt = GeneratedStatement::Traversal(GeneratedTraversal {
+                source_step: Separator::Period(SourceStep::AddN(add_n)),
+                steps: vec![],
+                traversal_type: TraversalType::Mut,
+                should_collect: ShouldCollect::ToObj,
+                ..Default::default()
+            });
+            gen_query.is_mut = true;
+            return (Type::Node(Some(ty.to_string())), Some(stmt));
         }
-        Or(exprs) => {
-            let exprs = exprs
-                .iter()
-                .map(|expr| {
-                    let (ty, stmt) = infer_expr_type(
-                        ctx,
-                        expr,
-                        scope,
-                        original_query,
-                        parent_ty.clone(),
-                        gen_query,
-                    );
+        generate_error!(
+            ctx,
+            original_query,
+            add.loc.clone(),
+            E304,
+            ["node"],
+            ["node"]
+        );
+        (Type::Node(None), None)
+}
 
-                    match stmt.unwrap() {
-                        GeneratedStatement::BoExp(expr) => match expr {
-                            BoExp::Exists(mut traversal) => {
-                                traversal.should_collect = ShouldCollect::No;
-                                BoExp::Exists(traversal)
+/// Validates an `AddE` and generates the traversal adding the edge
+fn infer_add_edge<'a>(
+    ctx: &mut Ctx<'a>,
+    add: &'a AddEdge,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    gen_query: &mut GeneratedQuery,
+) -> (Type, Option<GeneratedStatement>) {
+        if let Some(ref ty) = add.edge_type {
+            if !ctx.edge_map.contains_key(ty.as_str()) {
+                generate_error!(ctx, original_query, add.loc.clone(), E102, ty.as_str());
+            }
+            let label = GenRef::Literal(ty.clone());
+            // Validate fields if both type and fields are present
+            let properties = match &add.fields {
+                Some(fields) => {
+                    // Get the field set before validation
+                    let field_set = ctx.edge_fields.get(ty.as_str()).cloned();
+                    if let Some(field_set) = field_set {
+                        for (field_name, value) in fields {
+                            if !field_set.contains_key(field_name.as_str()) {
+                                generate_error!(
+                                    ctx,
+                                    original_query,
+                                    add.loc.clone(),
+                                    E202,
+                                    field_name.as_str(),
+                                    "edge",
+                                    ty.as_str()
+                                );
                             }
-                            BoExp::Not(inner_expr) => {
-                                if let BoExp::Exists(mut traversal) = *inner_expr {
-                                    traversal.should_collect = ShouldCollect::No;
-                                    BoExp::Exists(traversal)
-                                } else {
-                                    BoExp::Not(inner_expr)
+
+                            match value {
+                                ValueType::Identifier { value, loc } => {
+                                    if is_valid_identifier(
+                                        ctx,
+                                        original_query,
+                                        loc.clone(),
+                                        value.as_str(),
+                                    ) && !scope.contains_key(value.as_str())
+                                    {
+                                        generate_error!(
+                                            ctx,
+                                            original_query,
+                                            loc.clone(),
+                                            E301,
+                                            value.as_str()
+                                        );
+                                    } else {
+                                        let variable_type =
+                                            &scope.get(value.as_str()).unwrap().ty;
+                                        if variable_type
+                                            != &Type::from(
+                                                field_set
+                                                    .get(field_name.as_str())
+                                                    .unwrap()
+                                                    .field_type
+                                                    .clone(),
+                                            )
+                                        {
+                                            generate_error!(
+                                                ctx,
+                                                original_query,
+                                                loc.clone(),
+                                                E205,
+                                                value.as_str(),
+                                                &variable_type.to_string(),
+                                                &field_set
+                                                    .get(field_name.as_str())
+                                                    .unwrap()
+                                                    .field_type
+                                                    .to_string(),
+                                                "edge",
+                                                ty.as_str()
+                                            );
+                                        }
+                                    }
+                                }
+                                ValueType::Literal { value, loc } => {
+                                    // check against type
+                                    let field_type = ctx
+                                        .edge_fields
+                                        .get(ty.as_str())
+                                        .unwrap()
+                                        .get(field_name.as_str())
+                                        .unwrap()
+                                        .field_type
+                                        .clone();
+                                    if let Some(enum_schema) = field_enum(ctx, &field_type) {
+                                        check_enum_literal(
+                                            ctx,
+                                            original_query,
+                                            loc.clone(),
+                                            enum_schema,
+                                            value,
+                                        );
+                                    } else if field_type != *value {
+                                        generate_error!(
+                                            ctx,
+                                            original_query,
+                                            loc.clone(),
+                                            E205,
+                                            &value.inner_stringify(),
+                                            value.to_variant_string(),
+                                            &field_type.to_string(),
+                                            "edge",
+                                            ty.as_str()
+                                        );
+                                    }
                                 }
+                                _ => {}
+                            }
+                        }
+                    }
+                    Some(
+                        fields
+                            .iter()
+                            .map(|(field_name, value)| {
+                                (
+                                    field_name.clone(),
+                                    match value {
+                                        ValueType::Literal { value, loc } => {
+                                            let field_type = ctx
+                                                .edge_fields
+                                                .get(ty.as_str())
+                                                .unwrap()
+                                                .get(field_name.as_str())
+                                                .unwrap()
+                                                .field_type
+                                                .clone();
+                                            match field_type == FieldType::Date {
+                                                true => match Date::new(value) {
+                                                    Ok(date) => GeneratedValue::Literal(
+                                                        GenRef::Literal(date.to_rfc3339()),
+                                                    ),
+                                                    Err(_) => {
+                                                        generate_error!(
+                                                            ctx,
+                                                            original_query,
+                                                            loc.clone(),
+                                                            E501,
+                                                            value.as_str()
+                                                        );
+                                                        GeneratedValue::Unknown
+                                                    }
+                                                },
+                                                false => {
+                                                    gen_literal_field_value(ctx, &field_type, value)
+                                                }
+                                            }
+                                        }
+                                        ValueType::Identifier { value, loc } => {
+                                            is_valid_identifier(
+                                                ctx,
+                                                original_query,
+                                                loc.clone(),
+                                                value.as_str(),
+                                            );
+                                            gen_field_value(
+                                                original_query,
+                                                value.as_str(),
+                                                Type::Edge(Some(ty.clone()))
+                                                    .is_optional_field(ctx, field_name),
+                                                false,
+                                                true,
+                                            )
+                                        }
+                                        v => {
+                                            generate_error!(
+                                                ctx,
+                                                original_query,
+                                                add.loc.clone(),
+                                                E206,
+                                                &v.to_string()
+                                            );
+                                            GeneratedValue::Unknown
+                                        }
+                                    },
+                                )
+                            })
+                            .collect(),
+                    )
+                }
+                None => None,
+            };
+
+            let (to, to_is_plural) = match &add.connection.to_id {
+                Some(id) => match id {
+                    IdType::Identifier { value, loc } => {
+                        is_valid_identifier(ctx, original_query, loc.clone(), value.as_str());
+                        // Check if this variable is plural
+                        let is_plural = scope
+                            .get(value.as_str())
+                            .map(|var_info| !var_info.is_single)
+                            .unwrap_or(false);
+                        let gen_value = if is_plural {
+                            // For plural variables, just use the variable name without .id()
+                            if let Some(param) = is_param(original_query, value.as_str()) {
+                                GeneratedValue::Parameter(match param.is_optional {
+                                    true => GenRef::DeRef(format!(
+                                        "data.{}.as_ref().ok_or_else(|| GraphError::ParamNotFound(\"{}\"))?",
+                                        value, value
+                                    )),
+                                    false => GenRef::DeRef(format!("data.{}", value)),
+                                })
+                            } else {
+                                GeneratedValue::Identifier(GenRef::Std(value.clone()))
                             }
-                            _ => expr,
-                        },
-                        GeneratedStatement::Traversal(tr) => BoExp::Expr(tr),
-                        _ => {
-                            generate_error!(
-                                ctx,
-                                original_query,
-                                expr.loc.clone(),
-                                E306,
-                                ty.kind_str()
-                            );
-                            BoExp::Empty
-                        }
+                        } else {
+                            gen_id_access_or_param(original_query, value.as_str())
+                        };
+                        (gen_value, is_plural)
                     }
-                })
-                .collect::<Vec<_>>();
-            (
-                Type::Boolean,
-                Some(GeneratedStatement::BoExp(BoExp::Or(exprs))),
-            )
-        }
-        Not(expr) => {
-            let (ty, stmt) =
-                infer_expr_type(ctx, expr, scope, original_query, parent_ty, gen_query);
-
-            match stmt.unwrap() {
-                GeneratedStatement::BoExp(expr) => (
-                    Type::Boolean,
-                    Some(GeneratedStatement::BoExp(BoExp::Not(Box::new(expr)))),
-                ),
+                    IdType::Literal { value, loc: _ } => (
+                        GeneratedValue::Literal(GenRef::Literal(value.clone())),
+                        false,
+                    ),
+                    _ => unreachable!(),
+                },
                 _ => {
-                    generate_error!(ctx, original_query, expr.loc.clone(), E306, ty.kind_str());
-                    (Type::Unknown, None)
-                }
-            }
-        }
-        Exists(expr) => {
-            let (_, stmt) =
-                infer_expr_type(ctx, &expr.expr, scope, original_query, parent_ty, gen_query);
-            if stmt.is_none() {
-                return (Type::Boolean, None);
-            }
-            assert!(matches!(stmt, Some(GeneratedStatement::Traversal(_))));
-            let traversal = match stmt.unwrap() {
-                GeneratedStatement::Traversal(mut tr) => {
-                    let source_variable = match tr.source_step.inner() {
-                        SourceStep::Identifier(id) => id.inner().clone(),
-                        _ => DEFAULT_VAR_NAME.to_string(),
-                    };
-                    // Check if the variable is single or plural to determine traversal type
-                    let is_single = scope
-                        .get(source_variable.as_str())
-                        .map(|var_info| var_info.is_single)
-                        .unwrap_or(false);
-
-                    tr.traversal_type = if is_single {
-                        TraversalType::FromSingle(GenRef::Std(source_variable))
-                    } else {
-                        TraversalType::FromIter(GenRef::Std(source_variable))
-                    };
-                    tr.should_collect = ShouldCollect::No;
-                    tr
+                    generate_error!(ctx, original_query, add.loc.clone(), E611);
+                    (GeneratedValue::Unknown, false)
                 }
-                _ => unreachable!(),
             };
-            (
-                Type::Boolean,
-                Some(GeneratedStatement::BoExp(BoExp::Exists(traversal))),
-            )
-        }
-        MathFunctionCall(_math_call) => {
-            // Math function calls always return f64
-            // TODO: Add proper type inference and validation for math function arguments
-            (
-                Type::Scalar(FieldType::F64),
-                None, // Will be handled by generator
-            )
-        }
-        Empty => (Type::Unknown, Some(GeneratedStatement::Empty)),
-        BM25Search(bm25_search) => {
-            if let Some(ref ty) = bm25_search.type_arg
-                && !ctx.node_set.contains(ty.as_str())
-            {
-                generate_error!(
-                    ctx,
-                    original_query,
-                    bm25_search.loc.clone(),
-                    E101,
-                    ty.as_str()
-                );
-            }
-            let vec = match &bm25_search.data {
-                Some(ValueType::Literal { value, loc: _ }) => {
-                    GeneratedValue::Literal(GenRef::Std(value.inner_stringify()))
-                }
-                Some(ValueType::Identifier { value: i, loc: _ }) => {
-                    is_valid_identifier(ctx, original_query, bm25_search.loc.clone(), i.as_str());
-
-                    if is_in_scope(scope, i.as_str()) {
-                        gen_identifier_or_param(original_query, i, true, false)
-                    } else {
-                        generate_error!(
-                            ctx,
-                            original_query,
-                            bm25_search.loc.clone(),
-                            E301,
-                            i.as_str()
-                        );
-                        GeneratedValue::Unknown
+            let (from, from_is_plural) = match &add.connection.from_id {
+                Some(id) => match id {
+                    IdType::Identifier { value, loc } => {
+                        is_valid_identifier(ctx, original_query, loc.clone(), value.as_str());
+                        // Check if this variable is plural
+                        let is_plural = scope
+                            .get(value.as_str())
+                            .map(|var_info| !var_info.is_single)
+                            .unwrap_or(false);
+                        let gen_value = if is_plural {
+                            // For plural variables, just use the variable name without .id()
+                            if let Some(param) = is_param(original_query, value.as_str()) {
+                                GeneratedValue::Parameter(match param.is_optional {
+                                    true => GenRef::DeRef(format!(
+                                        "data.{}.as_ref().ok_or_else(|| GraphError::ParamNotFound(\"{}\"))?",
+                                        value, value
+                                    )),
+                                    false => GenRef::DeRef(format!("data.{}", value)),
+                                })
+                            } else {
+                                GeneratedValue::Identifier(GenRef::Std(value.clone()))
+                            }
+                        } else {
+                            gen_id_access_or_param(original_query, value.as_str())
+                        };
+                        (gen_value, is_plural)
                     }
-                }
+                    IdType::Literal { value, loc: _ } => (
+                        GeneratedValue::Literal(GenRef::Literal(value.clone())),
+                        false,
+                    ),
+                    _ => unreachable!(),
+                },
                 _ => {
-                    generate_error!(
-                        ctx,
-                        original_query,
-                        bm25_search.loc.clone(),
-                        E305,
-                        ["vector_data", "SearchV"],
-                        ["vector_data"]
-                    );
-                    GeneratedValue::Unknown
+                    generate_error!(ctx, original_query, add.loc.clone(), E612);
+                    (GeneratedValue::Unknown, false)
                 }
             };
-            let k = match &bm25_search.k {
-                Some(k) => match &k.value {
-                    EvaluatesToNumberType::I8(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                    }
-                    EvaluatesToNumberType::I16(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                    }
-                    EvaluatesToNumberType::I32(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                    }
-                    EvaluatesToNumberType::I64(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                    }
+            let edge_schema = ctx.edge_map.get(ty.as_str()).copied();
+            let add_e = AddE {
+                to,
+                from,
+                label,
+                properties,
+                from_is_plural,
+                to_is_plural,
+                acyclic: edge_schema.is_some_and(|edge| edge.acyclic),
+                cardinality: edge_schema.map(|edge| edge.cardinality).unwrap_or_default(),
+                replace_existing: edge_schema.is_some_and(|edge| edge.replace_existing),
+                upsert: false,
+            };
+            // If either from or to is plural, use Standalone (no G::new_mut wrapper),
+            // Empty separator (no period before it), and No collection (already done in iteration)
+            let (final_traversal_result_type, traversal_type, separator, should_collect) =
+                if from_is_plural || to_is_plural {
+                    (
+                        Type::Edges(Some(ty.to_string())),
+                        TraversalType::Standalone,
+                        Separator::Empty(SourceStep::AddE(add_e)),
+                        ShouldCollect::No,
+                    )
+                } else {
+                    (
+                        Type::Edge(Some(ty.to_string())),
+                        TraversalType::Mut,
+                        Separator::Period(SourceStep::AddE(add_e)),
+                        ShouldCollect::ToObj,
+                    )
+                };
+            let stmt = GeneratedStatement::Traversal(GeneratedTraversal {
+                source_step: separator,
+                steps: vec![],
+                traversal_type,
+                should_collect,
+                ..Default::default()
+            });
+            gen_query.is_mut = true;
+            return (final_traversal_result_type, Some(stmt));
+        }
+        generate_error!(
+            ctx,
+            original_query,
+            add.loc.clone(),
+            E304,
+            ["edge"],
+            ["edge"]
+        );
+        (Type::Edge(None), None)
+}
 
-                    EvaluatesToNumberType::U8(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                    }
-                    EvaluatesToNumberType::U16(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                    }
-                    EvaluatesToNumberType::U32(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
-                    }
-                    EvaluatesToNumberType::U64(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+/// The type of the value a string function returns
+pub(crate) fn string_function_type(function: StringFunction) -> FieldType {
+    match function {
+        StringFunction::Length => FieldType::I64,
+        _ => FieldType::String,
+    }
+}
+
+/// Validates the arguments of a string function call and builds its generated form
+///
+/// `_::{property}` arguments read from the item being remapped, so they are only accepted
+/// when `item_ty` is given. SUBSTRING's start and length must be integers; every other
+/// argument may be any scalar, which is converted to its string form at runtime.
+pub(crate) fn gen_string_function_call<'a>(
+    ctx: &mut Ctx<'a>,
+    call: &'a StringFunctionCall,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    item_ty: Option<&Type>,
+) -> Option<StringFunctionCallGen> {
+    let mut args = Vec::with_capacity(call.args.len());
+    let mut valid = true;
+    for (i, arg) in call.args.iter().enumerate() {
+        let position = (i + 1).to_string();
+        let expects_integer = call.function == StringFunction::Substring && i > 0;
+        let expected = if expects_integer {
+            "an integer"
+        } else {
+            "a scalar value"
+        };
+        let mismatch = |ctx: &mut Ctx<'a>, found: &str| {
+            generate_error!(
+                ctx,
+                original_query,
+                arg.loc.clone(),
+                E661,
+                [&position, call.function.name(), expected, found],
+                [expected, &position, call.function.name()]
+            );
+        };
+
+        let generated = match &arg.expr {
+            ExpressionType::IntegerLiteral(i) => {
+                Some(StringExpr::Literal(GenRef::Std(i.to_string())))
+            }
+            ExpressionType::StringLiteral(s) if !expects_integer => {
+                Some(StringExpr::Literal(GenRef::Literal(s.clone())))
+            }
+            ExpressionType::StringLiteral(_) => {
+                mismatch(ctx, "String");
+                None
+            }
+            ExpressionType::Identifier(name) => {
+                is_valid_identifier(ctx, original_query, arg.loc.clone(), name.as_str());
+                match type_in_scope(ctx, original_query, arg.loc.clone(), scope, name.as_str()) {
+                    Some(ty) if expects_integer && !ty.is_integer() => {
+                        mismatch(ctx, &ty.get_type_name());
+                        None
                     }
-                    EvaluatesToNumberType::U128(i) => {
-                        GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                    Some(Type::Scalar(_) | Type::Boolean) => Some(StringExpr::Value(
+                        gen_identifier_or_param(original_query, name.as_str(), false, false),
+                    )),
+                    Some(ty) => {
+                        mismatch(ctx, &ty.get_type_name());
+                        None
                     }
-                    EvaluatesToNumberType::Identifier(i) => {
-                        is_valid_identifier(
+                    None => None,
+                }
+            }
+            ExpressionType::StringFunctionCall(inner) => {
+                let inner_ty = string_function_type(inner.function);
+                if expects_integer && inner_ty != FieldType::I64 {
+                    mismatch(ctx, &inner_ty.to_string());
+                    None
+                } else {
+                    gen_string_function_call(ctx, inner, scope, original_query, item_ty)
+                        .map(StringExpr::FunctionCall)
+                }
+            }
+            ExpressionType::Traversal(tr) => match property_access(tr) {
+                Some(property) => match item_ty {
+                    Some(item_ty) => {
+                        validate_field_name_existence_for_item_type(
                             ctx,
                             original_query,
-                            bm25_search.loc.clone(),
-                            i.as_str(),
+                            arg.loc.clone(),
+                            item_ty,
+                            property,
                         );
-                        gen_identifier_or_param(original_query, i, false, false)
+                        Some(StringExpr::Property(property.to_string()))
                     }
-                    _ => {
+                    None => {
                         generate_error!(
                             ctx,
                             original_query,
-                            bm25_search.loc.clone(),
-                            E305,
-                            ["k", "SearchBM25"],
-                            ["k"]
+                            arg.loc.clone(),
+                            E662,
+                            [property],
+                            [property]
                         );
-                        GeneratedValue::Unknown
+                        None
                     }
                 },
                 None => {
+                    mismatch(ctx, "traversal");
+                    None
+                }
+            },
+            _ => {
+                mismatch(ctx, &arg.expr.to_string());
+                None
+            }
+        };
+        match generated {
+            Some(generated) => args.push(generated),
+            None => valid = false,
+        }
+    }
+
+    valid.then_some(StringFunctionCallGen {
+        function: call.function,
+        args,
+    })
+}
+
+/// The type of the value a date function returns
+pub(crate) fn date_function_type(function: DateFunction) -> FieldType {
+    match function.is_extraction() {
+        true => FieldType::I64,
+        false => FieldType::Date,
+    }
+}
+
+/// Validates the arguments of a date function call and builds its generated form
+///
+/// `_::{property}` arguments read the current item, so they are only accepted when `item_ty`
+/// is given. Units, durations, time zones and dates written as literals are checked here, so
+/// a typo fails the build instead of silently matching nothing at runtime.
+pub(crate) fn gen_date_function_call<'a>(
+    ctx: &mut Ctx<'a>,
+    call: &'a DateFunctionCall,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    item_ty: Option<&Type>,
+) -> Option<DateFunctionCallGen> {
+    let name = call.function.name();
+    let mismatch = |ctx: &mut Ctx<'a>, index: usize, expected: &str| {
+        let arg = &call.args[index];
+        let position = (index + 1).to_string();
+        generate_error!(
+            ctx,
+            original_query,
+            arg.loc.clone(),
+            E661,
+            [&position, name, expected, &arg.expr.to_string()],
+            [expected, &position, name]
+        );
+    };
+
+    // DATE_TRUNC takes its unit first, every other function takes the date first
+    let value_index = usize::from(call.function == DateFunction::Trunc);
+    let mut valid = true;
+
+    let unit = match call.function {
+        DateFunction::Trunc => match &call.args[0].expr {
+            ExpressionType::StringLiteral(unit) => {
+                let parsed = DateUnit::parse(unit).map(|unit| match unit {
+                    DateUnit::Year => "Year",
+                    DateUnit::Month => "Month",
+                    DateUnit::Week => "Week",
+                    DateUnit::Day => "Day",
+                    DateUnit::Hour => "Hour",
+                    DateUnit::Minute => "Minute",
+                    DateUnit::Second => "Second",
+                });
+                if parsed.is_none() {
+                    generate_error!(ctx, original_query, call.args[0].loc.clone(), E664, unit);
+                    valid = false;
+                }
+                parsed
+            }
+            _ => {
+                mismatch(ctx, 0, "a unit such as \"day\"");
+                return None;
+            }
+        },
+        _ => None,
+    };
+
+    let millis = match call.function {
+        DateFunction::Add | DateFunction::Sub => match &call.args[1].expr {
+            ExpressionType::Duration(millis) if call.function == DateFunction::Sub => -*millis,
+            ExpressionType::Duration(millis) => *millis,
+            _ => {
+                mismatch(ctx, 1, "a duration such as `7d`");
+                return None;
+            }
+        },
+        _ => 0,
+    };
+
+    // every function but DATE_ADD and DATE_SUB takes an optional trailing time zone
+    let zone_index = value_index + 1;
+    let (zone, literal_zone) = match call.function {
+        DateFunction::Add | DateFunction::Sub => (None, Some(DateZone::default())),
+        _ => match call.args.get(zone_index).map(|arg| &arg.expr) {
+            None => (None, Some(DateZone::default())),
+            Some(ExpressionType::StringLiteral(zone)) => match DateZone::parse(zone) {
+                Some(parsed) => (
+                    Some(GeneratedValue::Literal(GenRef::Literal(zone.clone()))),
+                    Some(parsed),
+                ),
+                None => {
+                    let loc = call.args[zone_index].loc.clone();
+                    generate_error!(ctx, original_query, loc, E665, zone);
+                    return None;
+                }
+            },
+            Some(ExpressionType::Identifier(zone))
+                if is_param(original_query, zone).is_some_and(|param| {
+                    matches!(param.param_type.1, FieldType::String)
+                }) =>
+            {
+                (
+                    Some(gen_identifier_or_param(original_query, zone, false, false)),
+                    None,
+                )
+            }
+            Some(_) => {
+                mismatch(ctx, zone_index, "a time zone string or `String` parameter");
+                return None;
+            }
+        },
+    };
+
+    // DATE parses text, every other function reads a date
+    let expected = match call.function {
+        DateFunction::Date => FieldType::String,
+        _ => FieldType::Date,
+    };
+    let arg = &call.args[value_index];
+    let value = match &arg.expr {
+        ExpressionType::Now if expected == FieldType::Date => DateExpr::Now,
+        ExpressionType::StringLiteral(text) => {
+            // text without an offset is read in the call's zone when DATE parses it
+            let zone = match call.function {
+                DateFunction::Date => literal_zone,
+                _ => Some(DateZone::default()),
+            };
+            match Date::parse_in(text, zone.unwrap_or_default()) {
+                Ok(date) if zone.is_some() => {
+                    DateExpr::Literal(GenRef::Literal(date.to_rfc3339()))
+                }
+                Ok(_) => DateExpr::Literal(GenRef::Literal(text.clone())),
+                Err(err) => {
                     generate_error!(
                         ctx,
                         original_query,
-                        bm25_search.loc.clone(),
-                        E601,
-                        &bm25_search.loc.span
+                        arg.loc.clone(),
+                        E666,
+                        text,
+                        &err.to_string()
                     );
-                    GeneratedValue::Unknown
+                    return None;
                 }
+            }
+        }
+        ExpressionType::Identifier(ident) => {
+            is_valid_identifier(ctx, original_query, arg.loc.clone(), ident.as_str());
+            match type_in_scope(ctx, original_query, arg.loc.clone(), scope, ident.as_str())? {
+                Type::Scalar(ty) if ty == expected => DateExpr::Value(gen_identifier_or_param(
+                    original_query,
+                    ident.as_str(),
+                    false,
+                    true,
+                )),
+                _ => {
+                    mismatch(ctx, value_index, &format!("a `{expected}` value"));
+                    return None;
+                }
+            }
+        }
+        ExpressionType::DateFunctionCall(inner)
+            if expected == FieldType::Date && !inner.function.is_extraction() =>
+        {
+            DateExpr::FunctionCall(gen_date_function_call(
+                ctx,
+                inner,
+                scope,
+                original_query,
+                item_ty,
+            )?)
+        }
+        ExpressionType::Traversal(tr) if property_access(tr).is_some() => {
+            let property = property_access(tr).unwrap_or_default();
+            let Some(item_ty) = item_ty else {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    arg.loc.clone(),
+                    E662,
+                    [property],
+                    [property]
+                );
+                return None;
             };
-
-            let search_bm25 = SearchBM25 {
-                type_arg: GenRef::Literal(bm25_search.type_arg.clone().unwrap()),
-                query: vec,
-                k,
-            };
-            (
-                Type::Nodes(bm25_search.type_arg.clone()),
-                Some(GeneratedStatement::Traversal(GeneratedTraversal {
-                    traversal_type: TraversalType::Ref,
-                    steps: vec![],
-                    should_collect: ShouldCollect::ToVec,
-                    source_step: Separator::Period(SourceStep::SearchBM25(search_bm25)),
-                    ..Default::default()
-                })),
-            )
+            validate_field_name_existence_for_item_type(
+                ctx,
+                original_query,
+                arg.loc.clone(),
+                item_ty,
+                property,
+            );
+            match get_field_type_from_item_fields(ctx, item_ty, property) {
+                Some(ty) if ty == expected || ty == FieldType::String => {
+                    DateExpr::Property(property.to_string())
+                }
+                Some(_) => {
+                    mismatch(ctx, value_index, &format!("a `{expected}` property"));
+                    return None;
+                }
+                None => return None,
+            }
+        }
+        _ => {
+            mismatch(ctx, value_index, &format!("a `{expected}` value"));
+            return None;
+        }
+    };
+
+    valid.then_some(DateFunctionCallGen {
+        function: call.function,
+        value: Box::new(value),
+        millis,
+        unit,
+        zone,
+    })
+}
+
+/// Builds the boolean expression of a function result compared with a value, e.g.
+/// `YEAR(_::{created_at})::EQ(2024)` in a `WHERE`
+///
+/// The function may read properties of the item being filtered, whose type is `item_ty`.
+fn gen_computed_predicate<'a>(
+    ctx: &mut Ctx<'a>,
+    predicate: &'a ComputedPredicate,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    item_ty: Option<&Type>,
+    gen_query: &mut GeneratedQuery,
+) -> Option<BoExp> {
+    let (name, value, value_ty) = match &predicate.value.expr {
+        ExpressionType::DateFunctionCall(call) => (
+            call.function.name(),
+            gen_date_function_call(ctx, call, scope, original_query, item_ty)?.render("val"),
+            date_function_type(call.function),
+        ),
+        ExpressionType::StringFunctionCall(call) => (
+            call.function.name(),
+            gen_string_function_call(ctx, call, scope, original_query, item_ty)?.render("val"),
+            string_function_type(call.function),
+        ),
+        _ => return None,
+    };
+
+    let (operation, expr) = match &predicate.op.op {
+        BooleanOpType::GreaterThan(expr) => ("GT", Some(expr)),
+        BooleanOpType::GreaterThanOrEqual(expr) => ("GTE", Some(expr)),
+        BooleanOpType::LessThan(expr) => ("LT", Some(expr)),
+        BooleanOpType::LessThanOrEqual(expr) => ("LTE", Some(expr)),
+        BooleanOpType::Equal(expr) => ("EQ", Some(expr)),
+        BooleanOpType::NotEqual(expr) => ("NEQ", Some(expr)),
+        BooleanOpType::StartsWith(expr) => ("STARTS_WITH", Some(expr)),
+        BooleanOpType::EndsWith(expr) => ("ENDS_WITH", Some(expr)),
+        BooleanOpType::EqualIgnoreCase(expr) => ("EQ_IGNORE_CASE", Some(expr)),
+        BooleanOpType::Regex(expr) => ("REGEX", Some(expr)),
+        BooleanOpType::Contains(_) => ("CONTAINS", None),
+        BooleanOpType::IsIn(_) => ("IS_IN", None),
+        BooleanOpType::IsNull => ("IS_NULL", None),
+        BooleanOpType::IsNotNull => ("IS_NOT_NULL", None),
+        BooleanOpType::And(_) => ("AND", None),
+        BooleanOpType::Or(_) => ("OR", None),
+    };
+    let is_string_predicate = matches!(
+        &predicate.op.op,
+        BooleanOpType::StartsWith(_)
+            | BooleanOpType::EndsWith(_)
+            | BooleanOpType::EqualIgnoreCase(_)
+            | BooleanOpType::Regex(_)
+    );
+    let expr = match expr {
+        Some(expr) if !is_string_predicate || value_ty == FieldType::String => expr,
+        _ => {
+            generate_error!(
+                ctx,
+                original_query,
+                predicate.op.loc.clone(),
+                E668,
+                operation,
+                name
+            );
+            return None;
+        }
+    };
+
+    let (ty, stmt) = infer_expr_type(ctx, expr, scope, original_query, None, gen_query);
+    match ty {
+        Type::Scalar(ty) if ty == value_ty => {}
+        Type::Unknown => return None,
+        ty => {
+            let result_type = value_ty.to_string();
+            generate_error!(
+                ctx,
+                original_query,
+                expr.loc.clone(),
+                E669,
+                [name, &result_type, &ty.get_type_name()],
+                [&result_type]
+            );
+            return None;
+        }
+    }
+    let right = match (&expr.expr, stmt) {
+        (ExpressionType::StringLiteral(s), _) => GeneratedValue::Primitive(GenRef::Literal(s.clone())),
+        (ExpressionType::Identifier(ident), _) => {
+            gen_identifier_or_param(original_query, ident, is_string_predicate, !is_string_predicate)
+        }
+        (_, Some(GeneratedStatement::Literal(code))) if is_string_predicate => {
+            GeneratedValue::Primitive(GenRef::Std(format!("&{code}.as_text()")))
+        }
+        (_, Some(GeneratedStatement::Literal(code))) => GeneratedValue::Primitive(code),
+        _ => {
+            generate_error!(
+                ctx,
+                original_query,
+                expr.loc.clone(),
+                E669,
+                [name, &value_ty.to_string(), &expr.expr.to_string()],
+                [&value_ty.to_string()]
+            );
+            return None;
         }
+    };
+    if let (BooleanOpType::Regex(_), ExpressionType::StringLiteral(pattern)) =
+        (&predicate.op.op, &expr.expr)
+        && let Err(err) = regex::Regex::new(pattern)
+    {
+        generate_error!(ctx, original_query, expr.loc.clone(), E663, pattern, &err.to_string());
+        return None;
+    }
+
+    let left = GeneratedValue::Primitive(GenRef::Std("*v".to_string()));
+    let op = match &predicate.op.op {
+        BooleanOpType::GreaterThan(_) => BoolOp::Gt(Gt { left, right }),
+        BooleanOpType::GreaterThanOrEqual(_) => BoolOp::Gte(Gte { left, right }),
+        BooleanOpType::LessThan(_) => BoolOp::Lt(Lt { left, right }),
+        BooleanOpType::LessThanOrEqual(_) => BoolOp::Lte(Lte { left, right }),
+        BooleanOpType::Equal(_) => BoolOp::Eq(Eq { left, right }),
+        BooleanOpType::NotEqual(_) => BoolOp::Neq(Neq { left, right }),
+        BooleanOpType::StartsWith(_) => BoolOp::StartsWith(StartsWith { value: right }),
+        BooleanOpType::EndsWith(_) => BoolOp::EndsWith(EndsWith { value: right }),
+        BooleanOpType::EqualIgnoreCase(_) => BoolOp::EqIgnoreCase(EqIgnoreCase { value: right }),
+        BooleanOpType::Regex(_) => BoolOp::Regex(Regex { value: right }),
+        _ => return None,
+    };
+    Some(BoExp::Computed { value, op })
+}
+
+/// Returns the property read by a `_::{property}` traversal
+fn property_access(tr: &Traversal) -> Option<&str> {
+    match (&tr.start, tr.steps.as_slice()) {
+        (StartNode::Anonymous, [step]) => match &step.step {
+            StepType::Object(obj) if obj.fields.len() == 1 && !obj.should_spread => {
+                Some(obj.fields[0].key.as_str())
+            }
+            _ => None,
+        },
+        _ => None,
     }
 }
 
@@ -1451,6 +2102,112 @@ mod tests {
         assert!(diagnostics.is_empty());
     }
 
+    #[test]
+    fn test_add_edge_acyclic_type() {
+        let source = r#"
+            N::Task { name: String }
+            E::DependsOn ACYCLIC { From: Task, To: Task }
+            E::Blocks { From: Task, To: Task }
+
+            QUERY test(id1: ID, id2: ID) =>
+                task1 <- N<Task>(id1)
+                task2 <- N<Task>(id2)
+                dependency <- AddE<DependsOn>::From(task1)::To(task2)
+                blocker <- AddE<Blocks>::From(task1)::To(task2)
+                RETURN dependency, blocker
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains("add_acyclic_edge(\"DependsOn\""));
+        assert!(code.contains("add_edge(\"Blocks\""));
+    }
+
+    #[test]
+    fn test_add_edge_with_cardinality() {
+        let source = r#"
+            N::User { name: String }
+            N::City { name: String }
+            E::LivesIn MANY_TO_ONE REPLACE { From: User, To: City }
+            E::Founded ACYCLIC ONE_TO_ONE { From: User, To: City }
+
+            QUERY test(user: ID, city: ID) =>
+                home <- AddE<LivesIn>::From(user)::To(city)
+                founded <- UpsertE<Founded>::From(user)::To(city)
+                RETURN home, founded
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains(
+            "add_constrained_edge(\"LivesIn\", None, *data.user, *data.city, EdgeConstraints { \
+             acyclic: false, cardinality: EdgeCardinality::ManyToOne, replace: true })"
+        ));
+        assert!(code.contains(
+            "upsert_constrained_e(\"Founded\", &[], *data.user, *data.city, EdgeConstraints { \
+             acyclic: true, cardinality: EdgeCardinality::OneToOne, replace: false })"
+        ));
+    }
+
+    #[test]
+    fn test_write_queries_register_mutating_handlers() {
+        let source = r#"
+            N::User { name: String }
+
+            QUERY create(name: String) =>
+                user <- AddN<User>({ name: name })
+                RETURN user
+
+            QUERY list() =>
+                users <- N<User>
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let (diagnostics, generated) = crate::helixc::analyzer::analyze(&parsed).unwrap();
+        assert!(diagnostics.is_empty());
+        let create = generated.queries.iter().find(|q| q.name == "create").unwrap();
+        let list = generated.queries.iter().find(|q| q.name == "list").unwrap();
+        assert!(create.to_string().contains("#[handler(is_mut)]"));
+        assert!(list.to_string().contains("#[handler]\n"));
+    }
+
+    #[test]
+    fn test_queries_generate_batch_functions() {
+        let source = r#"
+            N::User { name: String }
+
+            QUERY create(name: String) =>
+                user <- AddN<User>({ name: name })
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let (diagnostics, generated) = crate::helixc::analyzer::analyze(&parsed).unwrap();
+        assert!(diagnostics.is_empty());
+        let create = generated.queries[0].to_string();
+        let batch = &create[create.find("#[batch_handler(create)]").unwrap()..];
+        assert!(batch.contains("pub fn createBatch(input: BatchInput<'_, '_>)"));
+        assert!(batch.contains("let data = batch_params::<createInput>(input.params)?;"));
+        assert!(batch.contains("batch_output(&response)"));
+        // the batch commits once every step succeeded
+        assert!(!batch.contains("txn.commit()"));
+    }
+
     #[test]
     fn test_add_edge_undeclared_type() {
         let source = r#"
@@ -1577,4 +2334,364 @@ mod tests {
         let (diagnostics, _) = result.unwrap();
         assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E205));
     }
+
+    // ============================================================================
+    // Upsert Expression Tests
+    // ============================================================================
+
+    #[test]
+    fn test_upsert_node_and_edge() {
+        let source = r#"
+            N::Person { INDEX email: String, name: String, created_at: Date DEFAULT NOW }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(email: String, name: String, other: ID) =>
+                person <- UpsertN<Person>({email: email}, {name: name})
+                friend <- N<Person>(other)
+                UpsertE<Knows>::From(person)::To(friend)
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains(
+            "upsert_n(\"Person\", (\"email\", Value::from(&data.email)), &[(\"name\", Value::from(&data.name))], &[(\"created_at\""
+        ));
+        assert!(code.contains("upsert_e(\"Knows\", &[], person.id(), friend.id())"));
+    }
+
+    #[test]
+    fn test_upsert_node_key_not_indexed() {
+        let source = r#"
+            N::Person { email: String, name: String }
+
+            QUERY test(email: String) =>
+                person <- UpsertN<Person>({email: email})
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E208));
+    }
+
+    // ============================================================================
+    // Literal Expression Tests
+    // ============================================================================
+
+    #[test]
+    fn test_signed_exponent_and_escaped_literals() {
+        let source = r#"
+            N::Reading { title: String, note: String DEFAULT "n/a\t(unset)", value: F64, offset: I32 }
+
+            QUERY test() =>
+                reading <- AddN<Reading>({title: "say \"hi\"\n", value: -1.5e-3, offset: -7})
+                low <- N<Reading>::WHERE(_::{value}::LT(1e-6))
+                negative <- N<Reading>::WHERE(_::{offset}::GT(-5))
+                RETURN reading
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains(r#"("title", Value::from("say \"hi\"\n"))"#));
+        assert!(code.contains(r#"("note", Value::from("n/a\t(unset)"))"#));
+        assert!(code.contains(r#"("value", Value::from(-0.0015))"#));
+        assert!(code.contains(r#"("offset", Value::from(-7))"#));
+        assert!(code.contains("*v < 1e-6"));
+        assert!(code.contains("*v > -5"));
+    }
+
+    // ============================================================================
+    // String Function Tests
+    // ============================================================================
+
+    #[test]
+    fn test_string_functions_in_where_remapping_and_update() {
+        let source = r#"
+            N::User { name: String, email: String }
+
+            QUERY test(id: ID, name: String, domain: String) =>
+                users <- N<User>::WHERE(AND(
+                    _::{email}::ENDS_WITH(domain),
+                    _::{name}::EQ(LOWER(TRIM(name)))
+                ))
+                updated <- N<User>(id)::UPDATE({name: UPPER(name)})
+                RETURN users::{ shout: UPPER(_::{name}), size: LENGTH(_::{email}) }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains("v.ends_with(&data.domain)"));
+        assert!(code.contains("*v == Value::from(data.name.clone()).trim().to_lowercase()"));
+        assert!(code.contains(r#"("name", Value::from(Value::from(data.name.clone()).to_uppercase()))"#));
+        assert!(code.contains(
+            r#"shout: user.get_property("name").cloned().unwrap_or_default().to_uppercase()"#
+        ));
+        assert!(code.contains("pub size: Value"));
+    }
+
+    #[test]
+    fn test_string_function_errors() {
+        let source = r#"
+            N::User { name: String, age: I32 }
+
+            QUERY test(name: String, start: String) =>
+                short <- N<User>::WHERE(_::{name}::EQ(SUBSTRING(name, start)))
+                loud <- N<User>::WHERE(_::{name}::EQ(UPPER(_::{name})))
+                odd <- N<User>::WHERE(_::{name}::REGEX("(unclosed"))
+                RETURN short, loud, odd
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E661));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E662));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E663));
+    }
+
+    // ============================================================================
+    // Date Function Tests
+    // ============================================================================
+
+    #[test]
+    fn test_date_functions_in_where_order_and_group_by() {
+        let source = r#"
+            N::Event { INDEX created_at: Date, updated_at: Date }
+
+            QUERY test(since: Date) =>
+                recent <- N<Event>::WHERE(_::{created_at}::GT(DATE_SUB(NOW, 7d)))
+                between <- N<Event>::WHERE(AND(_::{created_at}::GTE(since), _::{created_at}::LT(NOW)))
+                touched <- N<Event>::WHERE(_::{updated_at}::GT(since))::ORDER<Desc>(DATE_TRUNC("day", _::{updated_at}))
+                this_year <- N<Event>::WHERE(YEAR(_::{created_at}, "Europe/London")::EQ(2024))
+                daily <- N<Event>::GROUP_BY(day: DATE_TRUNC("day", _::{created_at}, "+02:00"))
+                RETURN recent, between, touched, this_year, daily
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains(
+            r#"n_from_index_range("Event", "created_at", Bound::Excluded(Value::from(Value::from(chrono::Utc::now()).date_add(-604800000))), Bound::Unbounded)"#
+        ));
+        assert!(code.contains(
+            r#"n_from_index_range("Event", "created_at", Bound::Included(Value::from(data.since.clone())), Bound::Excluded(Value::from(Value::from(chrono::Utc::now()))))"#
+        ));
+        // updated_at is not indexed, so it stays a filter
+        assert!(code.contains("*v > data.since.clone()"));
+        assert!(code.contains(
+            r#"OrderKey::desc("date_trunc").by(|val| val.get_property("updated_at").cloned().unwrap_or_default().date_trunc(DateUnit::Day, "UTC"))"#
+        ));
+        assert!(code.contains(r#"date_part(DateUnit::Year, "Europe/London").map_value_or(false, |v| *v == 2024)"#));
+        assert!(code.contains(r#"GroupKey::computed("day", |val| "#));
+    }
+
+    #[test]
+    fn test_date_function_errors() {
+        let source = r#"
+            N::Event { created_at: Date }
+
+            QUERY test(zone: String) =>
+                weeks <- N<Event>::WHERE(DATE_TRUNC("fortnight", _::{created_at})::LT(NOW))
+                zoned <- N<Event>::WHERE(DAY(_::{created_at}, "Mars/Olympus")::EQ(1))
+                gap <- N<Event>::WHERE(_::{created_at}::GT(DATE("2024-03-10 02:30", "America/New_York")))
+                keyed <- N<Event>::ORDER<Asc>(DATE_TRUNC("day", _::{created_at}, zone))
+                RETURN weeks, zoned, gap, keyed
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E664));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E665));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E666));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E667));
+    }
+
+    // ============================================================================
+    // Optional Field Tests
+    // ============================================================================
+
+    #[test]
+    fn test_optional_fields_and_null_checks() {
+        let source = r#"
+            N::User { name: String, bio: Optional<String>, age?: U32 }
+
+            QUERY add(name: String, bio?: String) =>
+                user <- AddN<User>({name: name, bio: bio})
+                RETURN user
+
+            QUERY clear(id: ID) =>
+                user <- N<User>(id)::UPDATE({bio: NONE})
+                RETURN user
+
+            QUERY missing() =>
+                users <- N<User>::WHERE(_::{bio}::IS_NULL)
+                aged <- N<User>::WHERE(_::{age}::IS_NOT_NULL)
+                RETURN users, aged
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let schema = generated.nodes[0].to_string();
+        assert!(schema.contains("pub bio: Option<String>,"));
+        assert!(schema.contains("pub name: String,"));
+        let query = |name: &str| {
+            generated
+                .queries
+                .iter()
+                .find(|query| query.name == name)
+                .unwrap()
+                .to_string()
+        };
+        assert!(query("add").contains("data.bio.clone()"));
+        assert!(query("clear").contains(r#"update(&[("bio", Value::from(Value::Empty))])"#));
+        let missing = query("missing");
+        assert!(missing.contains("map_or(true, |v| matches!(v, Value::Empty))"));
+        assert!(missing.contains("map_or(false, |v| !matches!(v, Value::Empty))"));
+    }
+
+    #[test]
+    fn test_required_field_set_to_none() {
+        let source = r#"
+            N::User { name: String, bio: Optional<String> }
+
+            QUERY clear(id: ID) =>
+                user <- N<User>(id)::UPDATE({name: NONE})
+                RETURN user
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E212));
+    }
+
+    // ============================================================================
+    // Enum Tests
+    // ============================================================================
+
+    #[test]
+    fn test_enum_fields_and_parameters() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { email: String, status: Status DEFAULT "Active" }
+
+            QUERY add(email: String, status: Status) =>
+                user <- AddN<User>({email: email, status: status})
+                RETURN user
+
+            QUERY addActive(email: String) =>
+                user <- AddN<User>({email: email})
+                RETURN user
+
+            QUERY suspend(id: ID) =>
+                user <- N<User>(id)::UPDATE({status: "Suspended"})
+                RETURN user
+
+            QUERY active() =>
+                users <- N<User>::WHERE(_::{status}::EQ("Active"))
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.to_string();
+        assert!(code.contains("pub enum Status {"));
+        assert!(code.contains("impl From<Status> for Value {"));
+        assert!(code.contains("pub status: Status"));
+        // literals and defaults are written as the enum, which is stored by variant name
+        assert!(code.contains(r#"update(&[("status", Value::from(Status::Suspended))])"#));
+        assert!(code.contains(r#"("status", Value::from(Status::Active))"#));
+        assert!(code.contains("*v == Status::Active"));
+        assert!(code.contains("    pub status: Option<&'a Value>,"));
+    }
+
+    #[test]
+    fn test_enum_value_errors() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { email: String, status: Status }
+
+            QUERY add(email: String) =>
+                user <- AddN<User>({email: email, status: "actve"})
+                RETURN user
+
+            QUERY addFromString(email: String, status: String) =>
+                user <- AddN<User>({email: email, status: status})
+                RETURN user
+
+            QUERY suspend(id: ID, status: String) =>
+                user <- N<User>(id)::UPDATE({status: status})
+                RETURN user
+
+            QUERY deleted() =>
+                users <- N<User>::WHERE(_::{status}::EQ("Deleted"))
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        let codes_in = |query: &str| {
+            diagnostics
+                .iter()
+                .filter(|d| d.message.contains(&format!("QUERY named `{query}`")))
+                .map(|d| d.error_code.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(codes_in("add"), [ErrorCode::E213]);
+        assert_eq!(codes_in("addFromString"), [ErrorCode::E205]);
+        assert_eq!(codes_in("suspend"), [ErrorCode::E205]);
+        assert_eq!(codes_in("deleted"), [ErrorCode::E213]);
+    }
 }
@@ -1,4 +1,5 @@
 pub(super) mod exclude_validation;
+pub(super) mod fragment_validation;
 pub(super) mod graph_step_validation;
 pub(super) mod infer_expr_type;
 pub(super) mod migration_validation;
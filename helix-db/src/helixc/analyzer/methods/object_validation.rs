@@ -9,6 +9,7 @@ use crate::{
     helixc::{
         analyzer::{
             Ctx,
+            methods::infer_expr_type::{gen_date_function_call, gen_string_function_call},
             types::Type,
             utils::{
                 gen_property_access, is_valid_identifier,
@@ -16,7 +17,7 @@ use crate::{
             },
         },
         generator::{
-            return_values::ReturnValueField,
+            return_values::{ComputedField, ReturnValueField},
             traversal_steps::{ShouldCollect, Traversal as GeneratedTraversal},
             utils::Separator,
         },
@@ -396,6 +397,36 @@ fn validate_property_access<'a>(
                                     .nested_traversals
                                     .insert(field_addition.key.clone(), nested_info);
                                 gen_traversal.object_fields.push(field_addition.key.clone());
+                            } else if let ExpressionType::StringFunctionCall(call) = &expr.expr {
+                                // Computed per item, e.g. { name: UPPER(_::{name}) }
+                                if let Some(generated) = gen_string_function_call(
+                                    ctx,
+                                    call,
+                                    scope,
+                                    original_query,
+                                    Some(cur_ty),
+                                ) {
+                                    gen_traversal.computed_fields.insert(
+                                        field_addition.key.clone(),
+                                        ComputedField::String(generated),
+                                    );
+                                }
+                                gen_traversal.object_fields.push(field_addition.key.clone());
+                            } else if let ExpressionType::DateFunctionCall(call) = &expr.expr {
+                                // Computed per item, e.g. { year: YEAR(_::{created_at}) }
+                                if let Some(generated) = gen_date_function_call(
+                                    ctx,
+                                    call,
+                                    scope,
+                                    original_query,
+                                    Some(cur_ty),
+                                ) {
+                                    gen_traversal.computed_fields.insert(
+                                        field_addition.key.clone(),
+                                        ComputedField::Date(generated),
+                                    );
+                                }
+                                gen_traversal.object_fields.push(field_addition.key.clone());
                             } else {
                                 // Other expression types (identifiers, literals, etc.)
                                 gen_traversal.object_fields.push(field_addition.key.clone());
@@ -34,6 +34,20 @@ fn capitalize_first(s: &str) -> String {
     }
 }
 
+/// The return field of a property of the schema type `label`. Fields of only some members of
+/// a union are left out of items of the others, so each item has the fields of its own type
+/// next to its `label`.
+fn schema_return_field(ctx: &Ctx, label: &str, name: String) -> ReturnFieldInfo {
+    let member_only = label.contains(" | ")
+        && label.split(" | ").any(|member| {
+            ctx.node_fields.get(member).is_some_and(|fields| !fields.contains_key(name.as_str()))
+        });
+    match member_only {
+        true => ReturnFieldInfo::new_union_member(name),
+        false => ReturnFieldInfo::new_schema(name, "Option<&'a Value>".to_string()),
+    }
+}
+
 /// Build unified field list for return types
 /// This handles all cases: simple schema, projections, spread, nested traversals
 fn build_return_fields(
@@ -140,7 +154,8 @@ fn build_return_fields(
                 "&'a str".to_string(),
             ));
         }
-        if should_add_field("label") {
+        // items of a union are told apart by their label, so they always carry it
+        if should_add_field("label") || label.contains(" | ") {
             fields.push(ReturnFieldInfo::new_implicit(
                 "label".to_string(),
                 "&'a str".to_string(),
@@ -174,6 +189,18 @@ fn build_return_fields(
                     "f64".to_string(),
                 ));
             }
+        } else if item_type == "node"
+            && traversal.has_object_step
+            && traversal.is_scored
+            && should_add_field("score")
+            && !ctx.node_fields.get(label).is_some_and(|fields| fields.contains_key("score"))
+        {
+            // nodes only carry a score after a scoring step, so it has to be asked for,
+            // and a node's own `score` field is returned as a schema field instead
+            fields.push(ReturnFieldInfo::new_implicit(
+                "score".to_string(),
+                "f64".to_string(),
+            ));
         }
 
         // Step 2: Add schema fields based on projection mode
@@ -185,6 +212,8 @@ fn build_return_fields(
         };
 
         if let Some(schema_fields) = schema_fields {
+            // vector fields list their built-in score, which is reserved for them
+            let own_score = item_type != "vector" && schema_fields.contains_key("score");
             if traversal.has_object_step {
                 // Projection mode - only include selected fields
                 for field_name in &traversal.object_fields {
@@ -193,28 +222,35 @@ fn build_return_fields(
                         continue;
                     }
 
+                    // Fields computed per item by string or date functions
+                    if let Some(call) = traversal.computed_fields.get(field_name) {
+                        fields.push(ReturnFieldInfo {
+                            name: field_name.clone(),
+                            field_type: ReturnFieldType::Simple("Value".to_string()),
+                            source: ReturnFieldSource::Computed(call.clone()),
+                        });
+                        continue;
+                    }
+
                     // Skip implicit fields (already added)
                     if field_name == "id"
                         || field_name == "label"
                         || field_name == "from_node"
                         || field_name == "to_node"
                         || field_name == "data"
-                        || field_name == "score"
+                        || (field_name == "score" && !own_score)
                     {
                         continue;
                     }
 
-                    if let Some(_field) = schema_fields.get(field_name.as_str()) {
-                        fields.push(ReturnFieldInfo::new_schema(
-                            field_name.clone(),
-                            "Option<&'a Value>".to_string(),
-                        ));
+                    if schema_fields.contains_key(field_name.as_str()) {
+                        fields.push(schema_return_field(ctx, label, field_name.clone()));
                     }
                 }
 
                 // If has_spread, add all remaining schema fields
                 if traversal.has_spread {
-                    for (field_name, _field) in schema_fields.iter() {
+                    for field_name in schema_fields.keys() {
                         // Skip if already added
                         let already_exists = fields.iter().any(|f| f.name == *field_name);
                         if already_exists {
@@ -228,8 +264,8 @@ fn build_return_fields(
                         // Check if this is an implicit field - if so, use the correct type
                         let is_implicit_field = matches!(
                             *field_name,
-                            "id" | "label" | "from_node" | "to_node" | "data" | "score"
-                        );
+                            "id" | "label" | "from_node" | "to_node" | "data"
+                        ) || (*field_name == "score" && !own_score);
 
                         if is_implicit_field {
                             let rust_type = match *field_name {
@@ -242,23 +278,20 @@ fn build_return_fields(
                                 rust_type,
                             ));
                         } else {
-                            fields.push(ReturnFieldInfo::new_schema(
-                                field_name.to_string(),
-                                "Option<&'a Value>".to_string(),
-                            ));
+                            fields.push(schema_return_field(ctx, label, field_name.to_string()));
                         }
                     }
                 }
             } else {
                 // No projection - include all schema fields except excluded ones
-                for (field_name, _field) in schema_fields.iter() {
+                for field_name in schema_fields.keys() {
                     // Skip implicit fields (already added)
                     if *field_name == "id"
                         || *field_name == "label"
                         || *field_name == "from_node"
                         || *field_name == "to_node"
                         || *field_name == "data"
-                        || *field_name == "score"
+                        || (*field_name == "score" && !own_score)
                     {
                         continue;
                     }
@@ -266,10 +299,7 @@ fn build_return_fields(
                     if traversal.excluded_fields.contains(&field_name.to_string()) {
                         continue;
                     }
-                    fields.push(ReturnFieldInfo::new_schema(
-                        field_name.to_string(),
-                        "Option<&'a Value>".to_string(),
-                    ));
+                    fields.push(schema_return_field(ctx, label, field_name.to_string()));
                 }
             }
         }
@@ -550,6 +580,7 @@ pub(crate) fn validate_query<'a>(ctx: &mut Ctx<'a>, original_query: &'a Query) {
             && !ctx.node_set.contains(id.as_str())
             && !ctx.edge_map.contains_key(id.as_str())
             && !ctx.vector_set.contains(id.as_str())
+            && !ctx.enum_map.contains_key(id.as_str())
         {
             generate_error!(
                 ctx,
@@ -632,6 +663,32 @@ pub(crate) fn validate_query<'a>(ctx: &mut Ctx<'a>, original_query: &'a Query) {
         query.mcp_handler = Some(return_name);
     }
 
+    // a returned page is followed by the cursor of the next page
+    let cursors = query
+        .statements
+        .iter()
+        .filter_map(|stmt| match stmt {
+            GeneratedStatement::Assignment(assignment) => match &*assignment.value {
+                GeneratedStatement::Traversal(traversal) if traversal.page.is_some() => {
+                    Some(assignment.variable.inner().clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|variable| query.return_values.iter().any(|(name, _)| name == variable))
+        .collect::<Vec<_>>();
+    for variable in cursors {
+        query.return_values.push((
+            format!("{variable}_cursor"),
+            ReturnValue {
+                name: "Option<String>".to_string(),
+                fields: vec![],
+                literal_value: None,
+            },
+        ));
+    }
+
     ctx.output.queries.push(query);
 }
 
@@ -650,6 +707,13 @@ fn analyze_return_expr<'a>(
             if stmt.is_none() {
                 return;
             }
+            // the cursor of a page is only generated for an assigned traversal
+            if let Some(GeneratedStatement::Traversal(traversal)) = &stmt
+                && traversal.page.is_some()
+            {
+                generate_error!(ctx, original_query, expr.loc.clone(), E637);
+                return;
+            }
 
             match stmt.unwrap() {
                 GeneratedStatement::Traversal(traversal) => {
@@ -1343,4 +1407,49 @@ mod tests {
         let (diagnostics, _) = result.unwrap();
         assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E301));
     }
+
+    #[test]
+    fn test_streamed_collection_is_not_collected_first() {
+        let source = r#"
+            N::User { name: String }
+            E::Knows { From: User, To: User }
+
+            QUERY getUsers() =>
+                users <- N<User>::RANGE(0, 500000)
+                RETURN users
+
+            QUERY getKnown() =>
+                users <- N<User>
+                known <- users::Out<Knows>
+                RETURN users
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = |name: &str| {
+            let query = generated.queries.iter().find(|q| q.name == name).unwrap();
+            query.to_string()
+        };
+        let stream_branch = |code: &str| {
+            let start = code.find("ResponseStream::requested").unwrap();
+            let end = code[start..].find("return stream.finish()").unwrap();
+            code[start..start + end].to_string()
+        };
+
+        // the traversal is mapped and written as it runs, and only collected without a stream
+        let users = code("getUsers");
+        let branch = stream_branch(&users);
+        assert!(branch.contains("for item in G::new(&db, &txn, &arena)"));
+        assert!(!branch.contains(".collect::<Result<Vec<_>, _>>()"));
+        let collected = users.find("let users = G::new(&db, &txn, &arena)").unwrap();
+        assert!(collected > users.find("return stream.finish()").unwrap());
+
+        // a collection read by a later statement is collected before the stream
+        assert!(stream_branch(&code("getKnown")).contains("for item in users.iter()"));
+    }
 }
@@ -2,10 +2,11 @@ use std::{borrow::Cow, collections::HashMap};
 
 use crate::helixc::{
     analyzer::{Ctx, error_codes::ErrorCode, errors::push_schema_err},
+    generator::utils::GeneratedValue,
     parser::{
         errors::ParserError,
         location::Loc,
-        types::{Field, FieldPrefix, FieldType, Source},
+        types::{DefaultValue, EdgeSchema, EnumSchema, Field, FieldPrefix, FieldType, Source},
     },
 };
 
@@ -49,6 +50,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "id".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -59,6 +61,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "label".to_string(),
                                 field_type: FieldType::String,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -86,6 +89,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "id".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -96,6 +100,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "label".to_string(),
                                 field_type: FieldType::String,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -106,6 +111,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "from_node".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -116,6 +122,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "to_node".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -139,6 +146,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "id".to_string(),
                                 field_type: FieldType::Uuid,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -149,6 +157,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "label".to_string(),
                                 field_type: FieldType::String,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -159,6 +168,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "data".to_string(),
                                 field_type: FieldType::Array(Box::new(FieldType::F64)),
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -169,6 +179,7 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
                                 defaults: None,
                                 name: "score".to_string(),
                                 field_type: FieldType::F64,
+                                is_optional: false,
                                 loc: Loc::empty(),
                             }),
                         );
@@ -182,8 +193,101 @@ pub(crate) fn build_field_lookups<'a>(src: &'a Source) -> SchemaVersionMap<'a> {
     )
 }
 
+/// Field look-ups for the union endpoints of `edges`, keyed by the joined union name.
+///
+/// A union exposes the fields of all of its members; a field missing from any member is optional.
+pub(crate) fn build_union_field_lookups<'a>(
+    edges: &'a [EdgeSchema],
+    node_fields: &FieldLookup<'a>,
+) -> FieldLookup<'a> {
+    let mut unions = FieldLookup::new();
+    let endpoints = edges
+        .iter()
+        .flat_map(|e| [(&e.from.1, &e.from_types), (&e.to.1, &e.to_types)]);
+    for (name, members) in endpoints {
+        if members.len() < 2 || unions.contains_key(name.as_str()) {
+            continue;
+        }
+        let member_fields = members
+            .iter()
+            .filter_map(|(_, member)| node_fields.get(member.as_str()))
+            .collect::<Vec<_>>();
+        let mut fields: HashMap<&'a str, Cow<'a, Field>> = HashMap::new();
+        for props in &member_fields {
+            for (field_name, field) in props.iter() {
+                fields.entry(*field_name).or_insert_with(|| field.clone());
+            }
+        }
+        for (field_name, field) in fields.iter_mut() {
+            if !member_fields.iter().all(|props| props.contains_key(field_name)) {
+                field.to_mut().is_optional = true;
+            }
+        }
+        unions.insert(name.as_str(), fields);
+    }
+    unions
+}
+
+/// A union endpoint may only name distinct node types whose shared fields agree on their type.
+fn check_union_endpoint(ctx: &mut Ctx, endpoint: &(Loc, String), members: &[(Loc, String)]) {
+    if members.len() < 2 {
+        return;
+    }
+    // field name -> (first member declaring it, its type)
+    let mut seen: HashMap<String, (String, FieldType)> = HashMap::new();
+    for (i, (loc, member)) in members.iter().enumerate() {
+        if ctx.vector_set.contains(member.as_str()) {
+            push_schema_err(
+                ctx,
+                loc.clone(),
+                ErrorCode::E109,
+                format!("vector type `{member}` cannot be part of the union `{}`", endpoint.1),
+                Some("union endpoints may only name node types".to_string()),
+            );
+        }
+        if members[..i].iter().any(|(_, prev)| prev == member) {
+            push_schema_err(
+                ctx,
+                loc.clone(),
+                ErrorCode::E109,
+                format!("`{member}` appears more than once in the union `{}`", endpoint.1),
+                Some(format!("remove the repeated `{member}`")),
+            );
+        }
+        let Some(fields) = ctx.node_fields.get(member.as_str()) else {
+            continue;
+        };
+        let mut fields = fields
+            .iter()
+            .map(|(name, f)| (name.to_string(), f.field_type.clone()))
+            .collect::<Vec<_>>();
+        fields.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, field_type) in fields {
+            match seen.get(&name) {
+                Some((prev_member, prev_type)) if *prev_type != field_type => {
+                    let msg = format!(
+                        "field `{name}` is `{prev_type}` on `{prev_member}` but `{field_type}` \
+                         on `{member}`"
+                    );
+                    push_schema_err(
+                        ctx,
+                        loc.clone(),
+                        ErrorCode::E109,
+                        msg,
+                        Some("give fields shared by union members the same type".to_string()),
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    seen.insert(name, (member.clone(), field_type));
+                }
+            }
+        }
+    }
+}
+
 fn check_duplicate_schema_definitions(ctx: &mut Ctx) -> Result<(), ParserError> {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
     // Track seen names for each schema type
     let mut seen_nodes: HashMap<String, (crate::helixc::parser::location::Loc, String)> =
@@ -231,6 +335,40 @@ fn check_duplicate_schema_definitions(ctx: &mut Ctx) -> Result<(), ParserError>
         }
     }
 
+    // Check duplicate enums, which share a namespace with the other schema types
+    let mut seen_enums: HashMap<String, Loc> = HashMap::new();
+    for enum_schema in &schema.enum_schemas {
+        let name = &enum_schema.name.1;
+        if seen_enums.contains_key(name)
+            || seen_nodes.contains_key(name)
+            || seen_edges.contains_key(name)
+            || schema.vector_schemas.iter().any(|v| v.name == *name)
+        {
+            push_schema_err(
+                ctx,
+                enum_schema.name.0.clone(),
+                ErrorCode::E107,
+                format!("duplicate enum definition `{name}`"),
+                Some("rename the enum or remove the duplicate definition".to_string()),
+            );
+        } else {
+            seen_enums.insert(name.clone(), enum_schema.name.0.clone());
+        }
+
+        let mut seen_variants = HashSet::new();
+        for (loc, variant) in &enum_schema.variants {
+            if !seen_variants.insert(variant.as_str()) {
+                push_schema_err(
+                    ctx,
+                    loc.clone(),
+                    ErrorCode::E107,
+                    format!("duplicate enum variant definition `{variant}`"),
+                    Some("rename the enum variant or remove the duplicate definition".to_string()),
+                );
+            }
+        }
+    }
+
     // Check duplicate vectors
     for vector in &schema.vector_schemas {
         if let Some((_first_loc, _)) = seen_vectors.get(&vector.name) {
@@ -255,41 +393,28 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
     // Check for duplicate schema definitions
     check_duplicate_schema_definitions(ctx)?;
 
+    for enum_schema in &ctx.src.get_latest_schema()?.enum_schemas {
+        ctx.output.enums.push(enum_schema.clone().into());
+    }
+
     for edge in &ctx.src.get_latest_schema()?.edge_schemas {
-        if !ctx.node_set.contains(edge.from.1.as_str())
-            && !ctx.vector_set.contains(edge.from.1.as_str())
-        {
-            push_schema_err(
-                ctx,
-                edge.from.0.clone(),
-                ErrorCode::E106,
-                format!(
-                    "use of undeclared node or vector type `{}` in schema",
-                    edge.from.1
-                ),
-                Some(format!(
-                    "declare `{}` in the schema before using it in an edge",
-                    edge.from.1
-                )),
-            );
-        }
-        if !ctx.node_set.contains(edge.to.1.as_str())
-            && !ctx.vector_set.contains(edge.to.1.as_str())
-        {
-            push_schema_err(
-                ctx,
-                edge.to.0.clone(),
-                ErrorCode::E106,
-                format!(
-                    "use of undeclared node or vector type `{}` in schema",
-                    edge.to.1
-                ),
-                Some(format!(
-                    "declare `{}` in the schema before using it in an edge",
-                    edge.to.1
-                )),
-            );
+        for (loc, item_type) in edge.from_types.iter().chain(&edge.to_types) {
+            if !ctx.node_set.contains(item_type.as_str())
+                && !ctx.vector_set.contains(item_type.as_str())
+            {
+                push_schema_err(
+                    ctx,
+                    loc.clone(),
+                    ErrorCode::E106,
+                    format!("use of undeclared node or vector type `{item_type}` in schema"),
+                    Some(format!(
+                        "declare `{item_type}` in the schema before using it in an edge"
+                    )),
+                );
+            }
         }
+        check_union_endpoint(ctx, &edge.from, &edge.from_types);
+        check_union_endpoint(ctx, &edge.to, &edge.to_types);
         if let Some(v) = edge.properties.as_ref() {
             v.iter().for_each(|f| {
                 if NODE_RESERVED_FIELD_NAMES.contains(&f.name.to_lowercase().as_str()) {
@@ -301,7 +426,7 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                         Some("rename the field".to_string()),
                     );
                 }
-                if !is_valid_schema_field_type(&f.field_type) {
+                if !is_valid_schema_field_type(&f.field_type, &ctx.enum_map) {
                     push_schema_err(
                         ctx,
                         f.loc.clone(),
@@ -310,6 +435,7 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                         Some("use built-in types only (String, U32, etc.)".to_string()),
                     );
                 }
+                check_enum_default(ctx, f);
             })
         }
         ctx.output.edges.push(edge.clone().into());
@@ -325,7 +451,7 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                     Some("rename the field".to_string()),
                 );
             }
-            if !is_valid_schema_field_type(&f.field_type) {
+            if !is_valid_schema_field_type(&f.field_type, &ctx.enum_map) {
                 push_schema_err(
                     ctx,
                     f.loc.clone(),
@@ -334,6 +460,7 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                     Some("use built-in types only (String, U32, etc.)".to_string()),
                 );
             }
+            check_enum_default(ctx, f);
         });
         ctx.output.nodes.push(node.clone().into());
     }
@@ -348,7 +475,7 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                     Some("rename the field".to_string()),
                 );
             }
-            if !is_valid_schema_field_type(&f.field_type) {
+            if !is_valid_schema_field_type(&f.field_type, &ctx.enum_map) {
                 push_schema_err(
                     ctx,
                     f.loc.clone(),
@@ -357,21 +484,47 @@ pub(crate) fn check_schema(ctx: &mut Ctx) -> Result<(), ParserError> {
                     Some("use built-in types only (String, U32, etc.)".to_string()),
                 );
             }
+            check_enum_default(ctx, f);
         });
         ctx.output.vectors.push(vector.clone().into());
     }
     Ok(())
 }
 
-fn is_valid_schema_field_type(ft: &FieldType) -> bool {
+fn is_valid_schema_field_type(ft: &FieldType, enums: &HashMap<&str, &EnumSchema>) -> bool {
     match ft {
-        FieldType::Identifier(_) => false,
+        FieldType::Identifier(name) => enums.contains_key(name.as_str()),
         FieldType::Object(_) => false,
-        FieldType::Array(inner) => is_valid_schema_field_type(inner),
+        FieldType::Array(inner) => is_valid_schema_field_type(inner, enums),
         _ => true,
     }
 }
 
+/// Checks that the `DEFAULT` of an enum field is one of its variants
+fn check_enum_default(ctx: &mut Ctx, field: &Field) {
+    let FieldType::Identifier(name) = &field.field_type else {
+        return;
+    };
+    let Some(enum_schema) = ctx.enum_map.get(name.as_str()).copied() else {
+        return;
+    };
+    let value = match &field.defaults {
+        Some(DefaultValue::String(value)) => value.clone(),
+        Some(DefaultValue::Empty) | None => return,
+        Some(DefaultValue::Now) => "NOW".to_string(),
+        Some(other) => GeneratedValue::from(other.clone()).to_string(),
+    };
+    if !enum_schema.has_variant(&value) {
+        push_schema_err(
+            ctx,
+            field.loc.clone(),
+            ErrorCode::E213,
+            format!("`{value}` is not a variant of enum `{name}`"),
+            Some(format!("use one of {}", enum_schema.variant_list())),
+        );
+    }
+}
+
 const NODE_RESERVED_FIELD_NAMES: &[&str] = &["id", "label"];
 const EDGE_RESERVED_FIELD_NAMES: &[&str] = &["id", "label", "to_node", "from_node"];
 const VEC_RESERVED_FIELD_NAMES: &[&str] = &["id", "label", "data", "score"];
@@ -461,6 +614,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_duplicate_enum_definition_and_variant() {
+        let source = r#"
+            ENUM Status { Active, Active }
+            ENUM Status { Open }
+            ENUM Person { Admin }
+            N::Person { name: String }
+
+            QUERY test() =>
+                p <- N<Person>
+                RETURN p
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        let duplicates = diagnostics
+            .iter()
+            .filter(|d| d.error_code == ErrorCode::E107)
+            .map(|d| d.message.as_str())
+            .collect::<Vec<_>>();
+        assert!(duplicates.contains(&"duplicate enum definition `Status`"));
+        assert!(duplicates.contains(&"duplicate enum definition `Person`"));
+        assert!(duplicates.contains(&"duplicate enum variant definition `Active`"));
+    }
+
+    #[test]
+    fn test_invalid_union_endpoints() {
+        let source = r#"
+            N::Post { title: String, score: I32 }
+            N::Comment { title: String, score: String }
+            V::Embedding { content: String }
+            E::Tagged { From: Post | Comment | Post, To: Post | Embedding }
+            E::Links { From: Post | Missing, To: Post }
+
+            QUERY test() =>
+                p <- N<Post>
+                RETURN p
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        let messages = diagnostics
+            .iter()
+            .filter(|d| d.error_code == ErrorCode::E109)
+            .map(|d| d.message.as_str())
+            .collect::<Vec<_>>();
+        assert!(messages.contains(
+            &"`Post` appears more than once in the union `Comment | Post | Post`"
+        ));
+        assert!(messages.contains(&"field `score` is `I32` on `Post` but `String` on `Comment`"));
+        assert!(messages.contains(
+            &"vector type `Embedding` cannot be part of the union `Embedding | Post`"
+        ));
+        assert_eq!(messages.len(), 3);
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E106
+            && d.message.contains("`Missing`")));
+    }
+
+    #[test]
+    fn test_enum_field_types_and_defaults() {
+        let source = r#"
+            ENUM Status { Active, Suspended }
+            N::User { status: Status DEFAULT "Actve", role: Role }
+
+            QUERY test() =>
+                u <- N<User>
+                RETURN u
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        // `Role` is not declared, so it is still an invalid field type
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E209));
+        assert!(diagnostics.iter().any(|d| {
+            d.error_code == ErrorCode::E213
+                && d.message == "`Actve` is not a variant of enum `Status`"
+        }));
+    }
+
     // ============================================================================
     // Undeclared Type Reference Tests
     // ============================================================================
@@ -6,7 +6,7 @@ use crate::{
     helixc::{
         analyzer::{
             Ctx, errors::push_query_err, methods::infer_expr_type::infer_expr_type, types::Type,
-            utils::{is_valid_identifier, VariableInfo},
+            utils::{is_param, is_valid_identifier, VariableInfo},
         },
         generator::{
             queries::Query as GeneratedQuery,
@@ -14,6 +14,7 @@ use crate::{
             statements::{
                 Assignment as GeneratedAssignment, Drop as GeneratedDrop,
                 ForEach as GeneratedForEach, ForLoopInVariable, ForVariable,
+                IfElse as GeneratedIfElse,
             },
             traversal_steps::ShouldCollect,
             utils::GenRef,
@@ -70,7 +71,12 @@ pub(crate) fn validate_statements<'a>(
                 matches!(rhs_ty, Type::Node(_) | Type::Edge(_) | Type::Vector(_))
             };
 
-            scope.insert(assign.variable.as_str(), VariableInfo::new(rhs_ty, is_single));
+            let is_scored =
+                matches!(&stmt, Some(GeneratedStatement::Traversal(tr)) if tr.is_scored);
+            scope.insert(
+                assign.variable.as_str(),
+                VariableInfo::new(rhs_ty, is_single).with_scored(is_scored),
+            );
 
             stmt.as_ref()?;
 
@@ -97,6 +103,11 @@ pub(crate) fn validate_statements<'a>(
 
         Expression(expr) => {
             let (_, stmt) = infer_expr_type(ctx, expr, scope, original_query, None, query);
+            if let Some(GeneratedStatement::Traversal(tr)) = &stmt
+                && tr.page.is_some()
+            {
+                generate_error!(ctx, original_query, expr.loc.clone(), E637);
+            }
             stmt
         }
 
@@ -305,7 +316,79 @@ pub(crate) fn validate_statements<'a>(
             });
             Some(stmt)
         }
+
+        IfElse(if_else) => {
+            let (cond_ty, cond) = infer_expr_type(
+                ctx,
+                &if_else.condition,
+                scope,
+                original_query,
+                None,
+                query,
+            );
+            if !matches!(cond_ty, Type::Boolean | Type::Scalar(FieldType::Boolean)) {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    if_else.condition.loc.clone(),
+                    E306,
+                    cond_ty.kind_str()
+                );
+            }
+
+            // boolean parameters are read from the request data
+            let cond = match (&if_else.condition.expr, cond) {
+                (ExpressionType::Identifier(name), Some(GeneratedStatement::Identifier(_)))
+                    if let Some(param) = is_param(original_query, name) =>
+                {
+                    Some(GeneratedStatement::Identifier(GenRef::Std(
+                        match param.is_optional {
+                            true => format!("data.{name}.unwrap_or(false)"),
+                            false => format!("data.{name}"),
+                        },
+                    )))
+                }
+                (_, cond) => cond,
+            };
+
+            // variables assigned in a branch are only visible inside that branch
+            let then_statements =
+                validate_branch(ctx, scope, original_query, query, &if_else.then_statements);
+            let else_statements =
+                validate_branch(ctx, scope, original_query, query, &if_else.else_statements);
+
+            Some(GeneratedStatement::IfElse(GeneratedIfElse {
+                condition: Box::new(cond?),
+                then_statements,
+                else_statements,
+            }))
+        }
+    }
+}
+
+/// Validates the statements of an `IF`/`ELSE` branch in a copy of `scope`, so that variables
+/// assigned in the branch don't leak out of it.
+///
+/// References to outer variables made in the branch are added back to `scope`, so later uses
+/// of those variables still know they've been used before.
+fn validate_branch<'a>(
+    ctx: &mut Ctx<'a>,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    query: &mut GeneratedQuery,
+    statements: &'a [Statement],
+) -> Vec<GeneratedStatement> {
+    let mut branch_scope = scope.clone();
+    let statements = statements
+        .iter()
+        .filter_map(|stmt| validate_statements(ctx, &mut branch_scope, original_query, query, stmt))
+        .collect();
+    for (name, var_info) in scope.iter_mut() {
+        if let Some(branch_info) = branch_scope.get(name) {
+            var_info.reference_count = branch_info.reference_count;
+        }
     }
+    statements
 }
 
 #[cfg(test)]
@@ -611,4 +694,77 @@ mod tests {
         let (diagnostics, _) = result.unwrap();
         assert!(!diagnostics.iter().any(|d| d.error_code == ErrorCode::E301 || d.error_code == ErrorCode::E302));
     }
+
+    // ============================================================================
+    // If/Else Validation Tests
+    // ============================================================================
+
+    #[test]
+    fn test_if_else_generates_branches() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(name: String, force: Boolean) =>
+                IF EXISTS(N<Person>::WHERE(_::{name}::EQ(name))) {
+                    person <- N<Person>::WHERE(_::{name}::EQ(name))
+                } ELSE IF force {
+                    created <- AddN<Person>({name: name})
+                }
+                RETURN "done"
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains("if Exist::exists(&mut G::new("));
+        assert!(code.contains("} else {"));
+        assert!(code.contains("if data.force {"));
+    }
+
+    #[test]
+    fn test_if_branch_variable_not_in_scope_after_block() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test() =>
+                IF true {
+                    person <- N<Person>
+                }
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E301));
+    }
+
+    #[test]
+    fn test_if_condition_must_be_boolean() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test(name: String) =>
+                IF name {
+                    person <- N<Person>
+                }
+                RETURN "done"
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E306));
+    }
 }
@@ -1,10 +1,15 @@
 use crate::helixc::analyzer::error_codes::*;
 use crate::helixc::analyzer::utils::{
-    DEFAULT_VAR_NAME, VariableInfo, check_identifier_is_fieldtype,
+    DEFAULT_VAR_NAME, FieldLookup, VariableInfo, check_identifier_is_fieldtype,
+};
+use crate::helixc::generator::bool_ops::{Contains, EndsWith, EqIgnoreCase, IsIn, Regex, StartsWith};
+use crate::helixc::generator::source_steps::{PageCursor, SearchVector, VFromID, VFromType};
+use crate::helixc::generator::traversal_steps::{
+    AggregateBy, Aggregation as GeneratedAggregation,
+    AggregationKind as GeneratedAggregationKind, Centrality as GeneratedCentrality, CentralityKind as GeneratedCentralityKind,
+    Dag as GeneratedDag, DagKind as GeneratedDagKind, EgoGraph as GeneratedEgoGraph, GroupBy,
+    Page as GeneratedPage,
 };
-use crate::helixc::generator::bool_ops::{Contains, IsIn};
-use crate::helixc::generator::source_steps::{SearchVector, VFromID, VFromType};
-use crate::helixc::generator::traversal_steps::{AggregateBy, GroupBy};
 use crate::helixc::generator::utils::{EmbedData, VecData};
 use crate::{
     generate_error,
@@ -13,22 +18,27 @@ use crate::{
             Ctx,
             errors::push_query_err,
             methods::{
-                exclude_validation::validate_exclude, graph_step_validation::apply_graph_step,
-                infer_expr_type::infer_expr_type, object_validation::validate_object,
+                exclude_validation::validate_exclude,
+                fragment_validation::validate_fragment_call, graph_step_validation::apply_graph_step,
+                infer_expr_type::{gen_date_function_call, gen_string_function_call, infer_expr_type},
+                object_validation::validate_object,
             },
             types::{AggregateInfo, Type},
             utils::{
-                field_exists_on_item_type, gen_identifier_or_param, is_valid_identifier,
+                check_enum_literal, field_enum, field_exists_on_item_type, gen_enum_literal,
+                gen_field_value, gen_identifier_or_param, is_param, is_valid_identifier,
                 type_in_scope,
             },
         },
         generator::{
             bool_ops::{BoExp, BoolOp, Eq, Gt, Gte, Lt, Lte, Neq},
             queries::Query as GeneratedQuery,
-            source_steps::{EFromID, EFromType, NFromID, NFromIndex, NFromType, SourceStep},
+            source_steps::{
+                EFromID, EFromType, NFromID, NFromIndex, NFromIndexRange, NFromType, SourceStep,
+            },
             statements::Statement as GeneratedStatement,
             traversal_steps::{
-                OrderBy, Range, ShouldCollect, Step as GeneratedStep,
+                OrderBy, OrderKey, Range, ShouldCollect, Step as GeneratedStep,
                 Traversal as GeneratedTraversal, TraversalType, Where, WhereRef,
             },
             utils::{GenRef, GeneratedValue, Order, Separator},
@@ -110,6 +120,33 @@ pub(crate) fn validate_traversal<'a>(
     parent_ty: Option<Type>,
     gen_traversal: &mut GeneratedTraversal,
     gen_query: &mut GeneratedQuery,
+) -> Option<Type> {
+    // The score of the items an anonymous traversal starts from belongs to the outer step
+    let outer_scored = ctx.scored_items;
+    if matches!(tr.start, StartNode::Anonymous) {
+        gen_traversal.is_scored = outer_scored;
+    }
+    let ty = validate_traversal_steps(
+        ctx,
+        tr,
+        scope,
+        original_query,
+        parent_ty,
+        gen_traversal,
+        gen_query,
+    );
+    ctx.scored_items = outer_scored;
+    ty
+}
+
+fn validate_traversal_steps<'a>(
+    ctx: &mut Ctx<'a>,
+    tr: &'a Traversal,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    parent_ty: Option<Type>,
+    gen_traversal: &mut GeneratedTraversal,
+    gen_query: &mut GeneratedQuery,
 ) -> Option<Type> {
     let mut previous_step = None;
     let mut cur_ty = match &tr.start {
@@ -155,6 +192,7 @@ pub(crate) fn validate_traversal<'a>(
                                         HashMap::default()
                                     });
 
+                                let mut index_enum = None;
                                 match corresponding_field
                                     .iter()
                                     .find(|(name, _)| name.to_string() == *index.to_string())
@@ -171,19 +209,31 @@ pub(crate) fn validate_traversal<'a>(
                                             );
                                         } else if let ValueType::Literal { ref value, ref loc } =
                                             *value
-                                            && !field.field_type.eq(value)
                                         {
-                                            generate_error!(
-                                                ctx,
-                                                original_query,
-                                                loc.clone(),
-                                                E205,
-                                                &value.inner_stringify(),
-                                                &value.to_variant_string(),
-                                                &field.field_type.to_string(),
-                                                "node",
-                                                node_type
-                                            );
+                                            if let Some(enum_schema) =
+                                                field_enum(ctx, &field.field_type)
+                                            {
+                                                check_enum_literal(
+                                                    ctx,
+                                                    original_query,
+                                                    loc.clone(),
+                                                    enum_schema,
+                                                    value,
+                                                );
+                                                index_enum = Some(enum_schema);
+                                            } else if !field.field_type.eq(value) {
+                                                generate_error!(
+                                                    ctx,
+                                                    original_query,
+                                                    loc.clone(),
+                                                    E205,
+                                                    &value.inner_stringify(),
+                                                    &value.to_variant_string(),
+                                                    &field.field_type.to_string(),
+                                                    "node",
+                                                    node_type
+                                                );
+                                            }
                                         }
                                     }
                                     None => {
@@ -231,14 +281,20 @@ pub(crate) fn validate_traversal<'a>(
                                             }
                                             ValueType::Literal { value, loc: _ } => {
                                                 GeneratedValue::Primitive(GenRef::Ref(
-                                                    match value {
-                                                        Value::String(s) => format!("\"{s}\""),
-                                                        other => other.inner_stringify(),
+                                                    match (value, index_enum) {
+                                                        (Value::String(s), Some(enum_schema)) => {
+                                                            format!("{}::{s}", enum_schema.name.1)
+                                                        }
+                                                        (Value::String(s), None) => {
+                                                            format!("\"{}\"", s.escape_debug())
+                                                        }
+                                                        (other, _) => other.inner_stringify(),
                                                     },
                                                 ))
                                             }
                                             _ => unreachable!(),
                                         },
+                                        resume: None,
                                     }));
                                 gen_traversal.should_collect = ShouldCollect::ToObj;
                                 gen_traversal.traversal_type = TraversalType::Ref;
@@ -303,6 +359,7 @@ pub(crate) fn validate_traversal<'a>(
             } else {
                 gen_traversal.source_step = Separator::Period(SourceStep::NFromType(NFromType {
                     label: GenRef::Literal(node_type.clone()),
+                    resume: None,
                 }));
                 gen_traversal.traversal_type = TraversalType::Ref;
                 Type::Nodes(Some(node_type.to_string()))
@@ -356,6 +413,7 @@ pub(crate) fn validate_traversal<'a>(
             } else {
                 gen_traversal.source_step = Separator::Period(SourceStep::EFromType(EFromType {
                     label: GenRef::Literal(edge_type.clone()),
+                    resume: None,
                 }));
                 gen_traversal.traversal_type = TraversalType::Ref;
                 Type::Edges(Some(edge_type.to_string()))
@@ -417,6 +475,11 @@ pub(crate) fn validate_traversal<'a>(
             }
         }
 
+        StartNode::FragmentCall(call) => {
+            validate_fragment_call(ctx, call, original_query, false);
+            return None;
+        }
+
         StartNode::Identifier(identifier) => {
             match is_valid_identifier(ctx, original_query, tr.loc.clone(), identifier.as_str()) {
                 true => {
@@ -428,6 +491,7 @@ pub(crate) fn validate_traversal<'a>(
                         if var_info.reference_count > 1 {
                             gen_traversal.is_reused_variable = true;
                         }
+                        gen_traversal.is_scored = var_info.is_scored;
 
                         gen_traversal.traversal_type = if var_info.is_single {
                             TraversalType::FromSingle(GenRef::Std(identifier.clone()))
@@ -640,6 +704,25 @@ pub(crate) fn validate_traversal<'a>(
 
     for (i, graph_step) in tr.steps.iter().enumerate() {
         let step = &graph_step.step;
+        // Filtering, projecting and ordering steps keep the score of the items they pass on
+        if !matches!(
+            step,
+            StepType::Where(_)
+                | StepType::BooleanOperation(_)
+                | StepType::Count
+                | StepType::Object(_)
+                | StepType::Exclude(_)
+                | StepType::Closure(_)
+                | StepType::Range(_)
+                | StepType::Page(_)
+                | StepType::OrderBy(_)
+                | StepType::First
+                | StepType::RerankRRF(_)
+                | StepType::RerankMMR(_)
+        ) {
+            gen_traversal.is_scored = false;
+        }
+        ctx.scored_items = gen_traversal.is_scored;
         match step {
             StepType::Node(gs) | StepType::Edge(gs) => {
                 match apply_graph_step(
@@ -722,51 +805,59 @@ pub(crate) fn validate_traversal<'a>(
                     return Some(cur_ty.clone());
                 }
                 let stmt = stmt.unwrap();
-                match stmt {
-                    GeneratedStatement::Traversal(tr) => {
-                        gen_traversal
-                            .steps
-                            .push(Separator::Period(GeneratedStep::Where(Where::Ref(
-                                WhereRef {
-                                    expr: BoExp::Expr(tr),
-                                },
-                            ))));
-                    }
-                    GeneratedStatement::BoExp(expr) => {
-                        // if Not(Exists()) or Exits() need to modify the traversal to not collect
-                        // else return where as normal
-                        let where_expr = match expr {
-                            BoExp::Not(inner_expr) => {
-                                if let BoExp::Exists(mut traversal) = *inner_expr {
+                if let Some(range) = gen_index_range(ctx, gen_traversal, &stmt) {
+                    // the nodes are read from the index, so nothing is left to filter
+                    gen_traversal.source_step =
+                        Separator::Period(SourceStep::NFromIndexRange(range));
+                } else {
+                    match stmt {
+                        GeneratedStatement::Traversal(tr) => {
+                            gen_traversal
+                                .steps
+                                .push(Separator::Period(GeneratedStep::Where(Where::Ref(
+                                    WhereRef {
+                                        expr: BoExp::Expr(tr),
+                                    },
+                                ))));
+                        }
+                        GeneratedStatement::BoExp(expr) => {
+                            // if Not(Exists()) or Exits() need to modify the traversal to not collect
+                            // else return where as normal
+                            let where_expr = match expr {
+                                BoExp::Not(inner_expr) => {
+                                    if let BoExp::Exists(mut traversal) = *inner_expr {
+                                        traversal.should_collect = ShouldCollect::No;
+                                        Where::Ref(WhereRef {
+                                            expr: BoExp::Not(Box::new(BoExp::Exists(traversal))),
+                                        })
+                                    } else {
+                                        Where::Ref(WhereRef {
+                                            // expr gets moved at start of match to allow for box dereference so need to move back
+                                            expr: BoExp::Not(inner_expr),
+                                        })
+                                    }
+                                }
+                                BoExp::Exists(mut traversal) => {
                                     traversal.should_collect = ShouldCollect::No;
                                     Where::Ref(WhereRef {
-                                        expr: BoExp::Not(Box::new(BoExp::Exists(traversal))),
-                                    })
-                                } else {
-                                    Where::Ref(WhereRef {
-                                        // expr gets moved at start of match to allow for box dereference so need to move back
-                                        expr: BoExp::Not(inner_expr),
+                                        expr: BoExp::Exists(traversal),
                                     })
                                 }
-                            }
-                            BoExp::Exists(mut traversal) => {
-                                traversal.should_collect = ShouldCollect::No;
-                                Where::Ref(WhereRef {
-                                    expr: BoExp::Exists(traversal),
-                                })
-                            }
-                            _ => Where::Ref(WhereRef { expr }),
-                        };
+                                _ => Where::Ref(WhereRef { expr }),
+                            };
 
-                        gen_traversal
-                            .steps
-                            .push(Separator::Period(GeneratedStep::Where(where_expr)));
+                            gen_traversal
+                                .steps
+                                .push(Separator::Period(GeneratedStep::Where(where_expr)));
+                        }
+                        _ => unreachable!(),
                     }
-                    _ => unreachable!(),
                 }
             }
             StepType::BooleanOperation(b_op) => {
                 let step = previous_step.unwrap();
+                // string function arguments are generated while their type is inferred
+                let mut arg_stmt = None;
                 let property_type = match &b_op.op {
                     BooleanOpType::LessThanOrEqual(expr)
                     | BooleanOpType::LessThan(expr)
@@ -774,7 +865,11 @@ pub(crate) fn validate_traversal<'a>(
                     | BooleanOpType::GreaterThan(expr)
                     | BooleanOpType::Equal(expr)
                     | BooleanOpType::NotEqual(expr)
-                    | BooleanOpType::Contains(expr) => {
+                    | BooleanOpType::Contains(expr)
+                    | BooleanOpType::StartsWith(expr)
+                    | BooleanOpType::EndsWith(expr)
+                    | BooleanOpType::EqualIgnoreCase(expr)
+                    | BooleanOpType::Regex(expr) => {
                         match infer_expr_type(
                             ctx,
                             expr,
@@ -783,7 +878,10 @@ pub(crate) fn validate_traversal<'a>(
                             Some(cur_ty.clone()),
                             gen_query,
                         ) {
-                            (Type::Scalar(ft), _) => ft.clone(),
+                            (Type::Scalar(ft), stmt) => {
+                                arg_stmt = stmt;
+                                ft.clone()
+                            }
                             (Type::Boolean, _) => FieldType::Boolean,
                             (field_type, _) => {
                                 generate_error!(
@@ -835,9 +933,38 @@ pub(crate) fn validate_traversal<'a>(
                             }
                         }
                     }
+                    // null checks take no argument, so they match whatever the property is
+                    BooleanOpType::IsNull | BooleanOpType::IsNotNull => match &cur_ty {
+                        Type::Scalar(ft) => ft.clone(),
+                        field_type => {
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                b_op.loc.clone(),
+                                E621,
+                                &b_op.loc.span,
+                                field_type.kind_str()
+                            );
+                            return Some(field_type.clone());
+                        }
+                    },
                     _ => return Some(cur_ty.clone()),
                 };
 
+                // string literals compared with an enum property must name one of its variants,
+                // and are then compared as the enum
+                let (property_type, enum_literal) = if let Type::Scalar(ft) = &cur_ty
+                    && let Some(enum_schema) = field_enum(ctx, ft)
+                    && let BooleanOpType::Equal(expr) | BooleanOpType::NotEqual(expr) = &b_op.op
+                    && let ExpressionType::StringLiteral(variant) = &expr.expr
+                {
+                    let value = Value::String(variant.clone());
+                    check_enum_literal(ctx, original_query, expr.loc.clone(), enum_schema, &value);
+                    (ft.clone(), Some(gen_enum_literal(enum_schema, variant)))
+                } else {
+                    (property_type, None)
+                };
+
                 // get type of field name
                 let field_name = match step {
                     StepType::Object(obj) => {
@@ -1068,6 +1195,11 @@ pub(crate) fn validate_traversal<'a>(
                     }
                 }
 
+                let computed_arg = match arg_stmt {
+                    Some(GeneratedStatement::Literal(code)) => GeneratedValue::Primitive(code),
+                    _ => GeneratedValue::Unknown,
+                };
+
                 // ctx.infer_expr_type(expr, scope, q);
                 // Where/boolean ops don't change the element type,
                 // so `cur_ty` stays the same.
@@ -1079,7 +1211,7 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
                             ExpressionType::FloatLiteral(f) => {
-                                GeneratedValue::Primitive(GenRef::Std(f.to_string()))
+                                GeneratedValue::Primitive(GenRef::Std(format!("{f:?}")))
                             }
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
@@ -1090,6 +1222,9 @@ pub(crate) fn validate_traversal<'a>(
                                 );
                                 gen_identifier_or_param(original_query, i.as_str(), false, true)
                             }
+                            ExpressionType::DateFunctionCall(_) | ExpressionType::Now => {
+                                computed_arg
+                            }
                             _ => unreachable!("Cannot reach here"),
                         };
                         BoolOp::Lte(Lte {
@@ -1103,7 +1238,7 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
                             ExpressionType::FloatLiteral(f) => {
-                                GeneratedValue::Primitive(GenRef::Std(f.to_string()))
+                                GeneratedValue::Primitive(GenRef::Std(format!("{f:?}")))
                             }
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
@@ -1114,6 +1249,9 @@ pub(crate) fn validate_traversal<'a>(
                                 );
                                 gen_identifier_or_param(original_query, i.as_str(), false, true)
                             }
+                            ExpressionType::DateFunctionCall(_) | ExpressionType::Now => {
+                                computed_arg
+                            }
                             _ => unreachable!("Cannot reach here"),
                         };
                         BoolOp::Lt(Lt {
@@ -1127,7 +1265,7 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
                             ExpressionType::FloatLiteral(f) => {
-                                GeneratedValue::Primitive(GenRef::Std(f.to_string()))
+                                GeneratedValue::Primitive(GenRef::Std(format!("{f:?}")))
                             }
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
@@ -1138,6 +1276,9 @@ pub(crate) fn validate_traversal<'a>(
                                 );
                                 gen_identifier_or_param(original_query, i.as_str(), false, true)
                             }
+                            ExpressionType::DateFunctionCall(_) | ExpressionType::Now => {
+                                computed_arg
+                            }
                             _ => unreachable!("Cannot reach here"),
                         };
                         BoolOp::Gte(Gte {
@@ -1151,7 +1292,7 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
                             ExpressionType::FloatLiteral(f) => {
-                                GeneratedValue::Primitive(GenRef::Std(f.to_string()))
+                                GeneratedValue::Primitive(GenRef::Std(format!("{f:?}")))
                             }
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
@@ -1162,6 +1303,9 @@ pub(crate) fn validate_traversal<'a>(
                                 );
                                 gen_identifier_or_param(original_query, i.as_str(), false, true)
                             }
+                            ExpressionType::DateFunctionCall(_) | ExpressionType::Now => {
+                                computed_arg
+                            }
                             _ => unreachable!("Cannot reach here"),
                         };
                         BoolOp::Gt(Gt {
@@ -1178,11 +1322,11 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
                             ExpressionType::FloatLiteral(f) => {
-                                GeneratedValue::Primitive(GenRef::Std(f.to_string()))
+                                GeneratedValue::Primitive(GenRef::Std(format!("{f:?}")))
                             }
-                            ExpressionType::StringLiteral(s) => {
+                            ExpressionType::StringLiteral(s) => enum_literal.unwrap_or_else(|| {
                                 GeneratedValue::Primitive(GenRef::Literal(s.to_string()))
-                            }
+                            }),
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
                                     ctx,
@@ -1207,6 +1351,8 @@ pub(crate) fn validate_traversal<'a>(
                                 gen_traversal.should_collect = ShouldCollect::ToValue;
                                 GeneratedValue::Traversal(Box::new(gen_traversal))
                             }
+                            ExpressionType::StringFunctionCall(_)
+                            | ExpressionType::DateFunctionCall(_) => computed_arg,
                             _ => {
                                 unreachable!("Cannot reach here");
                             }
@@ -1225,11 +1371,11 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
                             ExpressionType::FloatLiteral(f) => {
-                                GeneratedValue::Primitive(GenRef::Std(f.to_string()))
+                                GeneratedValue::Primitive(GenRef::Std(format!("{f:?}")))
                             }
-                            ExpressionType::StringLiteral(s) => {
+                            ExpressionType::StringLiteral(s) => enum_literal.unwrap_or_else(|| {
                                 GeneratedValue::Primitive(GenRef::Literal(s.to_string()))
-                            }
+                            }),
                             ExpressionType::Identifier(i) => {
                                 is_valid_identifier(
                                     ctx,
@@ -1254,6 +1400,8 @@ pub(crate) fn validate_traversal<'a>(
                                 gen_traversal.should_collect = ShouldCollect::ToValue;
                                 GeneratedValue::Traversal(Box::new(gen_traversal))
                             }
+                            ExpressionType::StringFunctionCall(_)
+                            | ExpressionType::DateFunctionCall(_) => computed_arg,
                             _ => unreachable!("Cannot reach here"),
                         };
                         BoolOp::Neq(Neq {
@@ -1279,7 +1427,7 @@ pub(crate) fn validate_traversal<'a>(
                                 GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                             }
                             ExpressionType::FloatLiteral(f) => {
-                                GeneratedValue::Primitive(GenRef::Std(f.to_string()))
+                                GeneratedValue::Primitive(GenRef::Std(format!("{f:?}")))
                             }
                             ExpressionType::StringLiteral(s) => {
                                 GeneratedValue::Primitive(GenRef::Literal(s.to_string()))
@@ -1315,7 +1463,7 @@ pub(crate) fn validate_traversal<'a>(
                                             }
                                             ExpressionType::FloatLiteral(f) => {
                                                 GeneratedValue::Primitive(GenRef::Std(
-                                                    f.to_string(),
+                                                    format!("{f:?}"),
                                                 ))
                                             }
                                             ExpressionType::StringLiteral(s) => {
@@ -1334,6 +1482,35 @@ pub(crate) fn validate_traversal<'a>(
                         };
                         BoolOp::IsIn(IsIn { value: v })
                     }
+                    BooleanOpType::StartsWith(expr) => BoolOp::StartsWith(StartsWith {
+                        value: gen_string_predicate_arg(ctx, original_query, expr, computed_arg),
+                    }),
+                    BooleanOpType::EndsWith(expr) => BoolOp::EndsWith(EndsWith {
+                        value: gen_string_predicate_arg(ctx, original_query, expr, computed_arg),
+                    }),
+                    BooleanOpType::EqualIgnoreCase(expr) => BoolOp::EqIgnoreCase(EqIgnoreCase {
+                        value: gen_string_predicate_arg(ctx, original_query, expr, computed_arg),
+                    }),
+                    BooleanOpType::Regex(expr) => {
+                        // literal patterns are compiled now so typos fail the build, not the query
+                        if let ExpressionType::StringLiteral(pattern) = &expr.expr
+                            && let Err(err) = regex::Regex::new(pattern)
+                        {
+                            generate_error!(
+                                ctx,
+                                original_query,
+                                expr.loc.clone(),
+                                E663,
+                                pattern,
+                                &err.to_string()
+                            );
+                        }
+                        BoolOp::Regex(Regex {
+                            value: gen_string_predicate_arg(ctx, original_query, expr, computed_arg),
+                        })
+                    }
+                    BooleanOpType::IsNull => BoolOp::IsNull,
+                    BooleanOpType::IsNotNull => BoolOp::IsNotNull,
                     _ => unreachable!("shouldve been caught earlier"),
                 };
                 gen_traversal
@@ -1349,6 +1526,8 @@ pub(crate) fn validate_traversal<'a>(
                     .collect::<Vec<_>>();
                 let should_count = matches!(previous_step, Some(StepType::Count));
                 let _ = gen_traversal.steps.pop();
+                let aggregations =
+                    gen_aggregations(ctx, original_query, &cur_ty, &aggr.aggregations);
 
                 // Capture aggregate metadata before replacing cur_ty
                 let property_names = aggr.properties.clone();
@@ -1365,6 +1544,7 @@ pub(crate) fn validate_traversal<'a>(
                     .push(Separator::Period(GeneratedStep::AggregateBy(AggregateBy {
                         properties,
                         should_count,
+                        aggregations,
                     })))
             }
             StepType::GroupBy(gb) => {
@@ -1373,11 +1553,25 @@ pub(crate) fn validate_traversal<'a>(
                     .iter()
                     .map(|p| GenRef::Std(format!("\"{}\".to_string()", p.clone())))
                     .collect::<Vec<_>>();
+                let mut computed = Vec::with_capacity(gb.computed.len());
+                for (name, call) in &gb.computed {
+                    let Some(key) = gen_sort_key(ctx, call, scope, original_query, &cur_ty) else {
+                        return Some(cur_ty.clone());
+                    };
+                    computed.push((name.clone(), key));
+                }
                 let should_count = matches!(previous_step, Some(StepType::Count));
                 let _ = gen_traversal.steps.pop();
+                let aggregations =
+                    gen_aggregations(ctx, original_query, &cur_ty, &gb.aggregations);
 
                 // Capture aggregate metadata before replacing cur_ty
-                let property_names = gb.properties.clone();
+                let property_names = gb
+                    .properties
+                    .iter()
+                    .chain(gb.computed.iter().map(|(name, _)| name))
+                    .cloned()
+                    .collect();
                 cur_ty = Type::Aggregate(AggregateInfo {
                     source_type: Box::new(cur_ty.clone()),
                     properties: property_names,
@@ -1390,7 +1584,9 @@ pub(crate) fn validate_traversal<'a>(
                     .steps
                     .push(Separator::Period(GeneratedStep::GroupBy(GroupBy {
                         properties,
+                        computed,
                         should_count,
+                        aggregations,
                     })))
             }
             StepType::Update(update) => {
@@ -1426,11 +1622,16 @@ pub(crate) fn validate_traversal<'a>(
                         return Some(cur_ty.clone());
                     }
                 }
+                let enum_literals =
+                    check_enum_update_fields(ctx, original_query, &cur_ty, update, scope);
                 gen_traversal.traversal_type = TraversalType::Update(Some(
                     update
                         .fields
                         .iter()
                         .map(|field| {
+                            if let Some(value) = enum_literals.get(&field.key) {
+                                return (field.key.clone(), value.clone());
+                            }
                             (
                                 field.key.clone(),
                                 match &field.value.value {
@@ -1441,9 +1642,10 @@ pub(crate) fn validate_traversal<'a>(
                                             field.value.loc.clone(),
                                             i.as_str(),
                                         );
-                                        gen_identifier_or_param(
+                                        gen_field_value(
                                             original_query,
                                             i.as_str(),
+                                            cur_ty.is_optional_field(ctx, &field.key),
                                             true,
                                             true,
                                         )
@@ -1464,30 +1666,71 @@ pub(crate) fn validate_traversal<'a>(
                                                 e.loc.clone(),
                                                 i.as_str(),
                                             );
-                                            gen_identifier_or_param(
+                                            gen_field_value(
                                                 original_query,
                                                 i.as_str(),
+                                                cur_ty.is_optional_field(ctx, &field.key),
                                                 true,
                                                 true,
                                             )
                                         }
                                         ExpressionType::StringLiteral(i) => {
-                                            GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                                            GeneratedValue::Literal(GenRef::Literal(i.to_string()))
                                         }
 
                                         ExpressionType::IntegerLiteral(i) => {
                                             GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                                         }
                                         ExpressionType::FloatLiteral(i) => {
-                                            GeneratedValue::Primitive(GenRef::Std(i.to_string()))
+                                            GeneratedValue::Primitive(GenRef::Std(format!("{i:?}")))
                                         }
                                         ExpressionType::BooleanLiteral(i) => {
                                             GeneratedValue::Primitive(GenRef::Std(i.to_string()))
                                         }
+                                        ExpressionType::StringFunctionCall(call) => {
+                                            gen_string_function_call(
+                                                ctx,
+                                                call,
+                                                scope,
+                                                original_query,
+                                                None,
+                                            )
+                                            .map_or(GeneratedValue::Unknown, |call| {
+                                                GeneratedValue::Primitive(GenRef::Std(
+                                                    call.render("val"),
+                                                ))
+                                            })
+                                        }
+                                        ExpressionType::DateFunctionCall(call) => {
+                                            gen_date_function_call(
+                                                ctx,
+                                                call,
+                                                scope,
+                                                original_query,
+                                                None,
+                                            )
+                                            .map_or(GeneratedValue::Unknown, |call| {
+                                                GeneratedValue::Primitive(GenRef::Std(
+                                                    call.render("val"),
+                                                ))
+                                            })
+                                        }
+                                        ExpressionType::Empty => gen_none_field_value(
+                                            ctx,
+                                            original_query,
+                                            &cur_ty,
+                                            field,
+                                        ),
                                         _ => {
                                             panic!("expr be primitive or value")
                                         }
                                     },
+                                    FieldValueType::Empty => gen_none_field_value(
+                                        ctx,
+                                        original_query,
+                                        &cur_ty,
+                                        field,
+                                    ),
                                     _ => {
                                         panic!("Should be primitive or value")
                                     }
@@ -1636,6 +1879,12 @@ pub(crate) fn validate_traversal<'a>(
                     }
                     _ => unreachable!("shouldve been caught eariler"),
                 };
+                // an order followed by a range only needs to keep the first `end` items
+                if let Some(Separator::Period(GeneratedStep::OrderBy(order_by))) =
+                    gen_traversal.steps.last_mut()
+                {
+                    order_by.limit = Some(end.clone());
+                }
                 gen_traversal
                     .steps
                     .push(Separator::Period(GeneratedStep::Range(Range {
@@ -1643,42 +1892,81 @@ pub(crate) fn validate_traversal<'a>(
                         end,
                     })));
             }
-            StepType::OrderBy(order_by) => {
-                // verify property access
-                let (_, stmt) = infer_expr_type(
-                    ctx,
-                    &order_by.expression,
-                    scope,
-                    original_query,
-                    Some(cur_ty.clone()),
-                    gen_query,
-                );
-
-                if stmt.is_none() {
+            StepType::Page(page) => {
+                if i != number_of_steps {
+                    generate_error!(ctx, original_query, page.loc.clone(), E637);
                     return Some(cur_ty.clone());
                 }
-                match stmt.unwrap() {
-                    GeneratedStatement::Traversal(traversal) => {
-                        let property = match &traversal.steps.last() {
-                            Some(step) => match &step.inner() {
-                                GeneratedStep::PropertyFetch(property) => property.clone(),
-                                _ => unreachable!("Cannot reach here"),
-                            },
-                            None => unreachable!("Cannot reach here"),
+                if apply_page(ctx, original_query, scope, gen_traversal, page).is_none() {
+                    return Some(cur_ty.clone());
+                }
+                // a page of an index lookup holds every node with the value, not just one
+                if let Type::Node(label) = cur_ty {
+                    cur_ty = Type::Nodes(label);
+                }
+            }
+            StepType::OrderBy(order_by) => {
+                let mut keys = Vec::with_capacity(order_by.keys.len());
+                for key in &order_by.keys {
+                    let order = match key.order_by_type {
+                        OrderByType::Asc => Order::Asc,
+                        OrderByType::Desc => Order::Desc,
+                    };
+                    // ordered on a value computed per item, e.g. DATE_TRUNC("day", _::{created_at})
+                    if let ExpressionType::DateFunctionCall(call) = &key.expression.expr {
+                        let Some(computed) =
+                            gen_sort_key(ctx, call, scope, original_query, &cur_ty)
+                        else {
+                            return Some(cur_ty.clone());
                         };
-                        gen_traversal
-                            .steps
-                            .push(Separator::Period(GeneratedStep::OrderBy(OrderBy {
-                                property,
-                                order: match order_by.order_by_type {
-                                    OrderByType::Asc => Order::Asc,
-                                    OrderByType::Desc => Order::Desc,
+                        keys.push(OrderKey {
+                            property: GenRef::Literal(call.function.name().to_lowercase()),
+                            order,
+                            nulls_first: key.nulls_first,
+                            computed: Some(computed),
+                        });
+                        continue;
+                    }
+
+                    // verify property access
+                    let (_, stmt) = infer_expr_type(
+                        ctx,
+                        &key.expression,
+                        scope,
+                        original_query,
+                        Some(cur_ty.clone()),
+                        gen_query,
+                    );
+
+                    if stmt.is_none() {
+                        return Some(cur_ty.clone());
+                    }
+                    match stmt.unwrap() {
+                        GeneratedStatement::Traversal(traversal) => {
+                            let property = match &traversal.steps.last() {
+                                Some(step) => match &step.inner() {
+                                    GeneratedStep::PropertyFetch(property) => property.clone(),
+                                    _ => unreachable!("Cannot reach here"),
                                 },
-                            })));
-                        gen_traversal.should_collect = ShouldCollect::ToVec;
+                                None => unreachable!("Cannot reach here"),
+                            };
+                            keys.push(OrderKey {
+                                property,
+                                order,
+                                nulls_first: key.nulls_first,
+                                computed: None,
+                            });
+                        }
+                        _ => unreachable!("Cannot reach here"),
                     }
-                    _ => unreachable!("Cannot reach here"),
                 }
+                gen_traversal
+                    .steps
+                    .push(Separator::Period(GeneratedStep::OrderBy(OrderBy {
+                        keys,
+                        limit: None,
+                    })));
+                gen_traversal.should_collect = ShouldCollect::ToVec;
             }
             StepType::Closure(cl) => {
                 if i != number_of_steps {
@@ -1718,7 +2006,8 @@ pub(crate) fn validate_traversal<'a>(
                         closure_param_type.clone(),
                         true,
                         closure_source_var.clone(),
-                    ),
+                    )
+                    .with_scored(gen_traversal.is_scored),
                 );
                 let obj = &cl.object;
                 let mut fields_out = vec![];
@@ -1768,7 +2057,7 @@ pub(crate) fn validate_traversal<'a>(
                         GeneratedValue::Primitive(GenRef::Std(val.to_string()))
                     }
                     ExpressionType::FloatLiteral(val) => {
-                        GeneratedValue::Primitive(GenRef::Std(val.to_string()))
+                        GeneratedValue::Primitive(GenRef::Std(format!("{val:?}")))
                     }
                     _ => {
                         generate_error!(
@@ -1806,7 +2095,7 @@ pub(crate) fn validate_traversal<'a>(
                         ))
                     }
                     ExpressionType::FloatLiteral(val) => {
-                        Some(GeneratedValue::Primitive(GenRef::Std(val.to_string())))
+                        Some(GeneratedValue::Primitive(GenRef::Std(format!("{val:?}"))))
                     }
                     ExpressionType::IntegerLiteral(val) => {
                         Some(GeneratedValue::Primitive(GenRef::Std(val.to_string())))
@@ -1852,6 +2141,190 @@ pub(crate) fn validate_traversal<'a>(
                         crate::helixc::generator::traversal_steps::RerankMMR { lambda, distance },
                     )));
             }
+            StepType::Centrality(centrality) => {
+                let node_label = match cur_ty.base() {
+                    Type::Node(Some(node_label)) | Type::Nodes(Some(node_label)) => {
+                        node_label.clone()
+                    }
+                    _ => {
+                        generate_error!(
+                            ctx,
+                            original_query,
+                            centrality.loc.clone(),
+                            E602,
+                            [&centrality.loc.span, &cur_ty.get_type_name()],
+                            ["degree and centrality steps can only be applied to nodes"]
+                        );
+                        return None;
+                    }
+                };
+                let label = centrality.edge_type.as_str();
+                let Some(edge) = ctx.edge_map.get(label) else {
+                    generate_error!(ctx, original_query, centrality.loc.clone(), E102, label);
+                    return None;
+                };
+                let connects = match centrality.kind {
+                    CentralityKind::OutDegree => edge.is_from(&node_label),
+                    CentralityKind::InDegree => edge.is_to(&node_label),
+                    _ => edge.is_from(&node_label) || edge.is_to(&node_label),
+                };
+                if !connects {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        centrality.loc.clone(),
+                        E207,
+                        label,
+                        "node",
+                        node_label.as_str()
+                    );
+                }
+
+                let kind = match &centrality.kind {
+                    CentralityKind::OutDegree => GeneratedCentralityKind::OutDegree,
+                    CentralityKind::InDegree => GeneratedCentralityKind::InDegree,
+                    CentralityKind::Degree => GeneratedCentralityKind::Degree,
+                    CentralityKind::TriangleCount => GeneratedCentralityKind::TriangleCount,
+                    CentralityKind::Betweenness(samples) => {
+                        GeneratedCentralityKind::Betweenness(samples.as_ref().map(|samples| {
+                            gen_count_arg(ctx, original_query, samples)
+                        }))
+                    }
+                };
+
+                // Betweenness scores the nodes, every other step reduces them to a number
+                if !matches!(kind, GeneratedCentralityKind::Betweenness(_)) {
+                    cur_ty = Type::Scalar(FieldType::I64);
+                    excluded.clear();
+                    gen_traversal.should_collect = ShouldCollect::No;
+                } else {
+                    gen_traversal.should_collect = ShouldCollect::ToVec;
+                    gen_traversal.is_scored = true;
+                }
+                gen_traversal
+                    .steps
+                    .push(Separator::Period(GeneratedStep::Centrality(
+                        GeneratedCentrality {
+                            label: GenRef::Literal(label.to_string()),
+                            kind,
+                        },
+                    )));
+            }
+            StepType::FragmentCall(call) => {
+                validate_fragment_call(ctx, call, original_query, true);
+                return None;
+            }
+            StepType::Dag(dag) => {
+                let node_label = match cur_ty.base() {
+                    Type::Node(Some(node_label)) | Type::Nodes(Some(node_label)) => {
+                        node_label.clone()
+                    }
+                    _ => {
+                        generate_error!(
+                            ctx,
+                            original_query,
+                            dag.loc.clone(),
+                            E602,
+                            [&dag.loc.span, &cur_ty.get_type_name()],
+                            ["TopoSort and HasCycle can only be applied to nodes"]
+                        );
+                        return None;
+                    }
+                };
+                let label = dag.edge_type.as_str();
+                let Some(edge) = ctx.edge_map.get(label) else {
+                    generate_error!(ctx, original_query, dag.loc.clone(), E102, label);
+                    return None;
+                };
+                // only an edge type going from the node type back to itself can form a cycle
+                if edge.from.1 != node_label || edge.to.1 != node_label {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        dag.loc.clone(),
+                        E207,
+                        label,
+                        "node",
+                        node_label.as_str()
+                    );
+                }
+
+                let kind = match dag.kind {
+                    DagStepKind::TopoSort => {
+                        cur_ty = Type::Nodes(Some(node_label));
+                        gen_traversal.should_collect = ShouldCollect::ToVec;
+                        GeneratedDagKind::TopoSort
+                    }
+                    DagStepKind::HasCycle => {
+                        cur_ty = Type::Boolean;
+                        excluded.clear();
+                        gen_traversal.should_collect = ShouldCollect::Try;
+                        GeneratedDagKind::HasCycle
+                    }
+                };
+                gen_traversal
+                    .steps
+                    .push(Separator::Period(GeneratedStep::Dag(GeneratedDag {
+                        label: GenRef::Literal(label.to_string()),
+                        kind,
+                    })));
+            }
+            StepType::EgoGraph(ego_graph) => {
+                if !matches!(cur_ty.base(), Type::Node(_) | Type::Nodes(_)) {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        ego_graph.loc.clone(),
+                        E602,
+                        [&ego_graph.loc.span, &cur_ty.get_type_name()],
+                        ["EgoGraph can only be applied to nodes"]
+                    );
+                    return None;
+                }
+                for label in &ego_graph.edge_types {
+                    if !ctx.edge_map.contains_key(label.as_str()) {
+                        generate_error!(
+                            ctx,
+                            original_query,
+                            ego_graph.loc.clone(),
+                            E102,
+                            label.as_str()
+                        );
+                    }
+                }
+
+                let hops = gen_count_arg(ctx, original_query, &ego_graph.hops);
+                let max_nodes = ego_graph
+                    .max_nodes
+                    .as_ref()
+                    .map(|max_nodes| gen_count_arg(ctx, original_query, max_nodes));
+
+                cur_ty = Type::Scalar(FieldType::Object(HashMap::from([
+                    (
+                        "nodes".to_string(),
+                        FieldType::Array(Box::new(FieldType::Identifier("Node".to_string()))),
+                    ),
+                    (
+                        "edges".to_string(),
+                        FieldType::Array(Box::new(FieldType::Identifier("Edge".to_string()))),
+                    ),
+                ])));
+                excluded.clear();
+                gen_traversal.should_collect = ShouldCollect::Try;
+                gen_traversal
+                    .steps
+                    .push(Separator::Period(GeneratedStep::EgoGraph(
+                        GeneratedEgoGraph {
+                            labels: ego_graph
+                                .edge_types
+                                .iter()
+                                .map(|label| GenRef::Literal(label.clone()))
+                                .collect(),
+                            hops,
+                            max_nodes,
+                        },
+                    )));
+            }
         }
         previous_step = Some(step.clone());
     }
@@ -1864,26 +2337,465 @@ pub(crate) fn validate_traversal<'a>(
     Some(cur_ty)
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::helixc::analyzer::error_codes::ErrorCode;
-    use crate::helixc::parser::{HelixParser, write_to_temp_file};
-
-    // ============================================================================
-    // Start Node Validation Tests
-    // ============================================================================
-
-    #[test]
-    fn test_undeclared_node_type() {
-        let source = r#"
-            N::Person { name: String }
+/// Pages a traversal with `PAGE(size, cursor)`: its source resumes after the key in the cursor
+/// and only the first `size` items are kept.
+///
+/// Only sources that iterate LMDB keys in order can resume, so the source may only be followed
+/// by `WHERE` filters, which do not change which key an item came from.
+fn apply_page<'a>(
+    ctx: &mut Ctx<'a>,
+    original_query: &'a Query,
+    scope: &HashMap<&'a str, VariableInfo>,
+    gen_traversal: &mut GeneratedTraversal,
+    page: &'a Page,
+) -> Option<()> {
+    let size = match &page.size.expr {
+        ExpressionType::IntegerLiteral(size) => {
+            GeneratedValue::Primitive(GenRef::Std(size.to_string()))
+        }
+        ExpressionType::Identifier(name) => {
+            is_valid_identifier(ctx, original_query, page.size.loc.clone(), name);
+            let ty = type_in_scope(ctx, original_query, page.size.loc.clone(), scope, name);
+            if let Some(ty) = ty
+                && !ty.is_integer()
+            {
+                generate_error!(
+                    ctx,
+                    original_query,
+                    page.size.loc.clone(),
+                    E634,
+                    [name, &ty.get_type_name()],
+                    [&ty.get_type_name()]
+                );
+                return None;
+            }
+            gen_identifier_or_param(original_query, name, false, true)
+        }
+        other => {
+            generate_error!(
+                ctx,
+                original_query,
+                page.size.loc.clone(),
+                E634,
+                [&page.size.loc.span, &other.to_string()],
+                [&other.to_string()]
+            );
+            return None;
+        }
+    };
 
-            QUERY test() =>
-                company <- N<Company>
-                RETURN company
-        "#;
+    let ExpressionType::Identifier(name) = &page.cursor.expr else {
+        unreachable!("the grammar only allows an identifier as the cursor")
+    };
+    let cursor = match is_param(original_query, name) {
+        Some(param) if param.param_type.1 == FieldType::String => {
+            GeneratedValue::Parameter(GenRef::Std(match param.is_optional {
+                // the first page is requested without a cursor
+                true => format!("data.{name}.as_deref().unwrap_or_default()"),
+                false => format!("&data.{name}"),
+            }))
+        }
+        param => {
+            let found = match param {
+                Some(param) => format!("of type `{}`", param.param_type.1),
+                None => "not a parameter".to_string(),
+            };
+            generate_error!(
+                ctx,
+                original_query,
+                page.cursor.loc.clone(),
+                E635,
+                [name, &found],
+                []
+            );
+            return None;
+        }
+    };
 
-        let content = write_to_temp_file(vec![source]);
+    let filtered = |steps: &[Separator<GeneratedStep>]| {
+        steps
+            .iter()
+            .all(|step| matches!(step.inner(), GeneratedStep::Where(_)))
+    };
+    let single_node = matches!(gen_traversal.traversal_type, TraversalType::FromSingle(_))
+        || matches!(gen_traversal.source_step.inner(), SourceStep::NFromID(_));
+    let source_filtered = filtered(&gen_traversal.steps);
+    let edges_filtered = single_node && filtered(gen_traversal.steps.get(1..).unwrap_or_default());
+
+    let resumed = match gen_traversal.source_step.inner_mut() {
+        SourceStep::NFromType(step) if source_filtered => {
+            Some((&mut step.resume, &step.label, "Nodes"))
+        }
+        SourceStep::NFromIndex(step) if source_filtered => {
+            Some((&mut step.resume, &step.label, "Index"))
+        }
+        SourceStep::EFromType(step) if source_filtered => {
+            Some((&mut step.resume, &step.label, "Edges"))
+        }
+        _ if edges_filtered => match gen_traversal.steps.first_mut().map(Separator::inner_mut) {
+            Some(GeneratedStep::OutE(step)) => Some((&mut step.resume, &step.label, "OutEdges")),
+            Some(GeneratedStep::InE(step)) => Some((&mut step.resume, &step.label, "InEdges")),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some((resume, label, source)) = resumed else {
+        generate_error!(ctx, original_query, page.loc.clone(), E636);
+        return None;
+    };
+    let cursor = PageCursor {
+        cursor,
+        source,
+        label: label.clone(),
+    };
+    *resume = Some(cursor.clone());
+
+    gen_traversal
+        .steps
+        .push(Separator::Period(GeneratedStep::Range(Range {
+            start: GeneratedValue::Primitive(GenRef::Std("0".to_string())),
+            end: size.clone(),
+        })));
+    gen_traversal.page = Some(GeneratedPage { size, cursor });
+    gen_traversal.should_collect = ShouldCollect::ToVec;
+    Some(())
+}
+
+/// Answers a `WHERE` directly on `N<T>` from the index of a date field, so only the nodes in
+/// range are read instead of every node of the type.
+///
+/// Only comparisons of a single indexed date field, alone or joined by `AND`, are rewritten.
+fn gen_index_range(
+    ctx: &Ctx,
+    gen_traversal: &GeneratedTraversal,
+    stmt: &GeneratedStatement,
+) -> Option<NFromIndexRange> {
+    let Separator::Period(SourceStep::NFromType(NFromType { label, .. })) = &gen_traversal.source_step
+    else {
+        return None;
+    };
+    if !gen_traversal.steps.is_empty() {
+        return None;
+    }
+    let comparisons = match stmt {
+        GeneratedStatement::Traversal(tr) | GeneratedStatement::BoExp(BoExp::Expr(tr)) => {
+            vec![tr]
+        }
+        GeneratedStatement::BoExp(BoExp::And(exprs)) => exprs
+            .iter()
+            .map(|expr| match expr {
+                BoExp::Expr(tr) => Some(tr),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+
+    let mut field: Option<&String> = None;
+    let (mut lower, mut upper) = (None, None);
+    for tr in comparisons {
+        if !matches!(tr.source_step.inner(), SourceStep::Anonymous) {
+            return None;
+        }
+        let [property, op] = tr.steps.as_slice() else {
+            return None;
+        };
+        let (GeneratedStep::PropertyFetch(property), GeneratedStep::BoolOp(op)) =
+            (property.inner(), op.inner())
+        else {
+            return None;
+        };
+        if field.is_some_and(|field| field != property.inner()) {
+            return None;
+        }
+        field = Some(property.inner());
+        let (bound, kind, value) = match op {
+            BoolOp::Gt(Gt { right, .. }) => (&mut lower, "Excluded", right),
+            BoolOp::Gte(Gte { right, .. }) => (&mut lower, "Included", right),
+            BoolOp::Lt(Lt { right, .. }) => (&mut upper, "Excluded", right),
+            BoolOp::Lte(Lte { right, .. }) => (&mut upper, "Included", right),
+            _ => return None,
+        };
+        if !matches!(
+            value,
+            GeneratedValue::Primitive(_) | GeneratedValue::Parameter(_)
+        ) {
+            return None;
+        }
+        // a field bounded twice on the same side is left to the filter
+        if bound
+            .replace(format!("Bound::{kind}(Value::from({value}))"))
+            .is_some()
+        {
+            return None;
+        }
+    }
+
+    let field = field?;
+    let indexed_date = ctx
+        .node_fields
+        .get(label.inner().as_str())
+        .and_then(|fields| fields.get(field.as_str()))
+        .is_some_and(|f| f.is_indexed() && f.field_type == FieldType::Date);
+    indexed_date.then(|| NFromIndexRange {
+        index: GenRef::Literal(field.clone()),
+        lower: lower.unwrap_or_else(|| "Bound::Unbounded".to_string()),
+        upper: upper.unwrap_or_else(|| "Bound::Unbounded".to_string()),
+        label: label.clone(),
+    })
+}
+
+/// Generates a date function used as a sort or grouping key, rendered over the item `val`.
+///
+/// Keys are compiled to plain function pointers, so they can read the item's properties but
+/// not parameters or variables.
+fn gen_sort_key<'a>(
+    ctx: &mut Ctx<'a>,
+    call: &'a DateFunctionCall,
+    scope: &mut HashMap<&'a str, VariableInfo>,
+    original_query: &'a Query,
+    item_ty: &Type,
+) -> Option<String> {
+    let generated = gen_date_function_call(ctx, call, scope, original_query, Some(item_ty))?;
+    if generated.reads_scope() {
+        let variable = call
+            .args
+            .iter()
+            .find_map(|arg| match &arg.expr {
+                ExpressionType::Identifier(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .unwrap_or("a variable");
+        generate_error!(
+            ctx,
+            original_query,
+            call.loc.clone(),
+            E667,
+            call.function.name(),
+            variable
+        );
+        return None;
+    }
+    Some(generated.render("val"))
+}
+
+/// Checks the values an `UPDATE` sets on enum fields name one of their variants, returning the
+/// generated values of those given as literals
+fn check_enum_update_fields(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    item_ty: &Type,
+    update: &Update,
+    scope: &HashMap<&str, VariableInfo>,
+) -> HashMap<String, GeneratedValue> {
+    let mut literals = HashMap::new();
+    for field in &update.fields {
+        let Some(enum_schema) = item_ty
+            .get_field_type_from_item_fields(ctx, &field.key)
+            .and_then(|field_type| field_enum(ctx, &field_type))
+        else {
+            continue;
+        };
+        let loc = field.value.loc.clone();
+        let variable = match &field.value.value {
+            FieldValueType::Literal(value) => {
+                check_enum_literal(ctx, original_query, loc, enum_schema, value);
+                if let Value::String(variant) = value {
+                    literals.insert(field.key.clone(), gen_enum_literal(enum_schema, variant));
+                }
+                continue;
+            }
+            FieldValueType::Expression(Expression {
+                expr: ExpressionType::StringLiteral(variant),
+                ..
+            }) => {
+                let value = Value::String(variant.clone());
+                check_enum_literal(ctx, original_query, loc, enum_schema, &value);
+                literals.insert(field.key.clone(), gen_enum_literal(enum_schema, variant));
+                continue;
+            }
+            FieldValueType::Identifier(name)
+            | FieldValueType::Expression(Expression {
+                expr: ExpressionType::Identifier(name),
+                ..
+            }) => name,
+            _ => continue,
+        };
+        // values that are not literals must already be of the enum type
+        let is_enum = |ty: &Type| {
+            matches!(ty, Type::Scalar(FieldType::Identifier(name)) if *name == enum_schema.name.1)
+        };
+        if let Some(info) = scope.get(variable.as_str())
+            && !is_enum(&info.ty)
+        {
+            generate_error!(
+                ctx,
+                original_query,
+                loc,
+                E205,
+                variable.as_str(),
+                &info.ty.get_type_name(),
+                &enum_schema.name.1,
+                item_ty.kind_str(),
+                &item_ty.get_type_name()
+            );
+        }
+    }
+    literals
+}
+
+/// Generates the value `NONE` sets on a field in an `UPDATE`, which clears an optional field.
+fn gen_none_field_value(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    item_ty: &Type,
+    field: &FieldAddition,
+) -> GeneratedValue {
+    if item_ty.is_optional_field(ctx, &field.key) {
+        return GeneratedValue::Primitive(GenRef::Std("Value::Empty".to_string()));
+    }
+    // unknown fields are reported by `field_exists_on_item_type`
+    if item_ty.item_fields_contains_key(ctx, &field.key) {
+        generate_error!(
+            ctx,
+            original_query,
+            field.value.loc.clone(),
+            E212,
+            field.key.as_str(),
+            &item_ty.get_type_name()
+        );
+    }
+    GeneratedValue::Unknown
+}
+
+/// Generates the text argument of `STARTS_WITH`, `ENDS_WITH`, `EQ_IGNORE_CASE` or `REGEX`.
+///
+/// `computed_arg` is the already generated value of a string function argument.
+fn gen_string_predicate_arg(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    expr: &Expression,
+    computed_arg: GeneratedValue,
+) -> GeneratedValue {
+    match &expr.expr {
+        ExpressionType::Identifier(id) => {
+            is_valid_identifier(ctx, original_query, expr.loc.clone(), id.as_str());
+            gen_identifier_or_param(original_query, id.as_str(), true, false)
+        }
+        ExpressionType::StringLiteral(s) => GeneratedValue::Primitive(GenRef::Literal(s.clone())),
+        ExpressionType::StringFunctionCall(_) => {
+            GeneratedValue::Primitive(GenRef::Std(format!("&{computed_arg}.as_text()")))
+        }
+        // non-string arguments were already reported as a type mismatch
+        _ => GeneratedValue::Unknown,
+    }
+}
+
+/// Generates a count argument of a step, which is either an integer literal or a parameter.
+fn gen_count_arg(ctx: &mut Ctx, original_query: &Query, expr: &Expression) -> GeneratedValue {
+    match &expr.expr {
+        ExpressionType::Identifier(id) => {
+            is_valid_identifier(ctx, original_query, expr.loc.clone(), id.as_str());
+            gen_identifier_or_param(original_query, id.as_str(), false, true)
+        }
+        ExpressionType::IntegerLiteral(val) => {
+            GeneratedValue::Primitive(GenRef::Std(val.to_string()))
+        }
+        _ => {
+            generate_error!(
+                ctx,
+                original_query,
+                expr.loc.clone(),
+                E206,
+                &expr.expr.to_string()
+            );
+            GeneratedValue::Unknown
+        }
+    }
+}
+
+/// Validates the aggregations of a `GROUP_BY` or `AGGREGATE_BY` over items of type `cur_ty`.
+///
+/// `SUM` and `AVG` are only valid on numeric fields.
+fn gen_aggregations(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    cur_ty: &Type,
+    aggregations: &[AggregationField],
+) -> Vec<GeneratedAggregation> {
+    let is_item_type = matches!(
+        cur_ty,
+        Type::Node(Some(_))
+            | Type::Nodes(Some(_))
+            | Type::Edge(Some(_))
+            | Type::Edges(Some(_))
+            | Type::Vector(Some(_))
+            | Type::Vectors(Some(_))
+    );
+    aggregations
+        .iter()
+        .map(|aggregation| {
+            if is_item_type {
+                field_exists_on_item_type(
+                    ctx,
+                    original_query,
+                    cur_ty.clone(),
+                    vec![(aggregation.property.as_str(), &aggregation.loc)],
+                );
+                if matches!(
+                    aggregation.function,
+                    AggregationFunction::Sum | AggregationFunction::Avg
+                ) && let Some(field_type) =
+                    cur_ty.get_field_type_from_item_fields(ctx, &aggregation.property)
+                    && !Type::Scalar(field_type.clone()).is_numeric()
+                {
+                    generate_error!(
+                        ctx,
+                        original_query,
+                        aggregation.loc.clone(),
+                        E211,
+                        aggregation.function.as_str(),
+                        &aggregation.property,
+                        &field_type.to_string()
+                    );
+                }
+            }
+            GeneratedAggregation {
+                name: aggregation.name.clone(),
+                kind: match aggregation.function {
+                    AggregationFunction::Sum => GeneratedAggregationKind::Sum,
+                    AggregationFunction::Avg => GeneratedAggregationKind::Avg,
+                    AggregationFunction::Min => GeneratedAggregationKind::Min,
+                    AggregationFunction::Max => GeneratedAggregationKind::Max,
+                    AggregationFunction::CountDistinct => GeneratedAggregationKind::CountDistinct,
+                    AggregationFunction::Collect => GeneratedAggregationKind::Collect,
+                },
+                property: aggregation.property.clone(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::helixc::analyzer::error_codes::ErrorCode;
+    use crate::helixc::parser::{HelixParser, write_to_temp_file};
+
+    // ============================================================================
+    // Start Node Validation Tests
+    // ============================================================================
+
+    #[test]
+    fn test_undeclared_node_type() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY test() =>
+                company <- N<Company>
+                RETURN company
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
         let parsed = HelixParser::parse_source(&content).unwrap();
         let result = crate::helixc::analyzer::analyze(&parsed);
 
@@ -2193,4 +3105,334 @@ mod tests {
         let (diagnostics, _) = result.unwrap();
         assert!(diagnostics.is_empty());
     }
+
+    // ============================================================================
+    // Degree and Centrality Tests
+    // ============================================================================
+
+    #[test]
+    fn test_degree_and_centrality_steps() {
+        let source = r#"
+            N::Person { name: String }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(id: ID) =>
+                person <- N<Person>(id)
+                following <- person::OutDegree<Knows>
+                followers <- person::InDegree<Knows>
+                triangles <- person::TriangleCount<Knows>
+                central <- N<Person>::Betweenness<Knows>(32)::RANGE(0, 10)
+                RETURN following, followers, triangles, central::{name, score}
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_score_only_after_scoring_step() {
+        let source = r#"
+            N::Person { name: String }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test() =>
+                central <- N<Person>::Betweenness<Knows>::WHERE(_::{score}::GT(0.5))
+                people <- N<Person>::WHERE(_::{score}::GT(0.5))
+                RETURN central, people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        // only the unscored traversal is rejected
+        let e202 = diagnostics.iter().filter(|d| d.error_code == ErrorCode::E202);
+        assert_eq!(e202.count(), 1);
+    }
+
+    #[test]
+    fn test_own_score_field_keeps_schema_type() {
+        let source = r#"
+            N::Person { name: String, score: I32 }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test() =>
+                people <- N<Person>::WHERE(_::{score}::GT(3))
+                central <- N<Person>::Betweenness<Knows>
+                RETURN people::{name, score}, central::{score}
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains("get_property(\"score\")"));
+        assert!(!code.contains(".score()"));
+    }
+
+    #[test]
+    fn test_degree_unknown_edge_type() {
+        let source = r#"
+            N::Person { name: String }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(id: ID) =>
+                degree <- N<Person>(id)::Degree<Follows>
+                RETURN degree
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E102));
+    }
+
+    #[test]
+    fn test_degree_on_edges_is_invalid() {
+        let source = r#"
+            N::Person { name: String }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(id: ID) =>
+                degree <- N<Person>(id)::OutE<Knows>::OutDegree<Knows>
+                RETURN degree
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E602));
+    }
+
+    // ============================================================================
+    // EgoGraph Tests
+    // ============================================================================
+
+    #[test]
+    fn test_ego_graph_step() {
+        let source = r#"
+            N::Person { name: String }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(id: ID, hops: I64) =>
+                neighbourhood <- N<Person>(id)::EgoGraph<Knows>(hops, 100)
+                everything <- N<Person>::EgoGraph(1)
+                RETURN neighbourhood, everything
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_ego_graph_unknown_edge_type() {
+        let source = r#"
+            N::Person { name: String }
+            E::Knows { From: Person, To: Person }
+
+            QUERY test(id: ID) =>
+                neighbourhood <- N<Person>(id)::EgoGraph<Knows, Follows>(2)
+                RETURN neighbourhood
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E102));
+    }
+
+    // ============================================================================
+    // DAG Tests
+    // ============================================================================
+
+    #[test]
+    fn test_dag_steps() {
+        let source = r#"
+            N::Task { name: String }
+            E::DependsOn ACYCLIC { From: Task, To: Task }
+
+            QUERY test(id: ID) =>
+                ordered <- N<Task>(id)::TopoSort<DependsOn>
+                cyclic <- N<Task>::HasCycle<DependsOn>
+                RETURN ordered::{name}, cyclic
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_topo_sort_needs_self_referential_edge() {
+        let source = r#"
+            N::Task { name: String }
+            N::Person { name: String }
+            E::AssignedTo { From: Task, To: Person }
+
+            QUERY test(id: ID) =>
+                ordered <- N<Task>(id)::TopoSort<AssignedTo>
+                RETURN ordered
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E207));
+    }
+
+    #[test]
+    fn test_group_by_aggregations() {
+        let source = r#"
+            N::Order { country: String, amount: F64, customer: String }
+
+            QUERY test() =>
+                by_country <- N<Order>::GROUP_BY(country){ total: SUM(amount), customers: COUNT_DISTINCT(customer) }
+                by_customer <- N<Order>::AGGREGATE_BY(customer){ amounts: COLLECT(amount), largest: MAX(amount) }
+                RETURN by_country, by_customer
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains(
+            "Aggregation { name: \"total\", kind: AggregationKind::Sum, property: \"amount\" }"
+        ));
+        assert!(code.contains("kind: AggregationKind::Collect"));
+    }
+
+    #[test]
+    fn test_group_by_sum_of_non_numeric_field() {
+        let source = r#"
+            N::Order { country: String, amount: F64 }
+
+            QUERY test() =>
+                by_country <- N<Order>::GROUP_BY(country){ total: SUM(country), missing: MAX(price) }
+                RETURN by_country
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E211));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E202));
+    }
+
+    #[test]
+    fn test_order_by_multiple_keys_with_range() {
+        let source = r#"
+            N::User { name: String, score: F64 }
+
+            QUERY test() =>
+                ranked <- N<User>::ORDER<Desc>(_::{score}), ORDER<Asc>(_::{name}) NULLS FIRST
+                top <- N<User>::ORDER<Desc>(_::{score})::RANGE(0, 10)
+                RETURN ranked, top
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains(
+            "order_by(&[OrderKey::desc(\"score\"), OrderKey::asc(\"name\").nulls_first()])"
+        ));
+        assert!(code.contains("order_by_top_k(&[OrderKey::desc(\"score\")], 10)"));
+    }
+
+    #[test]
+    fn test_page_resumes_source_and_returns_cursor() {
+        let source = r#"
+            N::Event { kind: String }
+
+            QUERY test(size: U32, cursor: String) =>
+                events <- N<Event>::WHERE(_::{kind}::EQ("click"))::PAGE(size, cursor)
+                RETURN events
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, generated) = result.unwrap();
+        assert!(diagnostics.is_empty());
+        let code = generated.queries[0].to_string();
+        assert!(code.contains(concat!(
+            "n_from_type_after(\"Event\", ",
+            "Cursor::resume(&data.cursor, CursorSource::Nodes, \"Event\")?)"
+        )));
+        assert!(code.contains(concat!(
+            "let events_cursor = ",
+            "Cursor::next_page(&events, data.size.clone(), CursorSource::Nodes, \"Event\")?"
+        )));
+        assert!(code.contains("\"events_cursor\": events_cursor"));
+    }
+
+    #[test]
+    fn test_page_of_unsupported_traversal() {
+        let source = r#"
+            N::Event { kind: String }
+            E::Next { From: Event, To: Event }
+
+            QUERY test(cursor: String, size: String) =>
+                next <- N<Event>::Out<Next>::PAGE(10, cursor)
+                sized <- N<Event>::PAGE(size, cursor)
+                uncursored <- N<Event>::PAGE(10, kind)
+                RETURN N<Event>::PAGE(10, cursor)
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let result = crate::helixc::analyzer::analyze(&parsed);
+
+        assert!(result.is_ok());
+        let (diagnostics, _) = result.unwrap();
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E636));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E634));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E635));
+        assert!(diagnostics.iter().any(|d| d.error_code == ErrorCode::E637));
+    }
 }
@@ -10,14 +10,17 @@ use crate::helixc::{
     analyzer::{
         diagnostic::Diagnostic,
         methods::{
+            fragment_validation::{check_fragments, fragment_queries, inline_fragments},
             migration_validation::validate_migration,
             query_validation::validate_query,
-            schema_methods::{build_field_lookups, check_schema, SchemaVersionMap},
+            schema_methods::{
+                build_field_lookups, build_union_field_lookups, check_schema, SchemaVersionMap,
+            },
         },
         types::Type,
     },
     generator::Source as GeneratedSource,
-    parser::{errors::ParserError, types::{EdgeSchema, ExpressionType, Field, Query, ReturnType, Source}},
+    parser::{errors::ParserError, types::{EdgeSchema, EnumSchema, ExpressionType, Field, FieldType, Fragment, Query, ReturnType, Source}},
 };
 use itertools::Itertools;
 use serde::Serialize;
@@ -28,9 +31,12 @@ use std::{
 };
 
 pub fn analyze(src: &Source) -> Result<(Vec<Diagnostic>, GeneratedSource), ParserError> {
-    let mut ctx = Ctx::new(src)?;
+    let (src, called_fragments) = inline_fragments(src);
+    let fragment_queries = fragment_queries(&src);
+    let mut ctx = Ctx::new(&src)?;
     ctx.check_schema()?;
     ctx.check_schema_migrations();
+    ctx.check_fragments(&fragment_queries, &called_fragments);
     ctx.check_queries();
     Ok((ctx.diagnostics, ctx.output))
 }
@@ -52,16 +58,23 @@ pub(crate) struct Ctx<'a> {
     pub(super) node_set: HashSet<&'a str>,
     pub(super) vector_set: HashSet<&'a str>,
     pub(super) edge_map: HashMap<&'a str, &'a EdgeSchema>,
+    pub(super) enum_map: HashMap<&'a str, &'a EnumSchema>,
+    /// The first `FN` definition of each name.
+    pub(super) fragments: HashMap<&'a str, &'a Fragment>,
     pub(super) node_fields: HashMap<&'a str, HashMap<&'a str, Cow<'a, Field>>>,
     pub(super) edge_fields: HashMap<&'a str, HashMap<&'a str, Cow<'a, Field>>>,
     pub(super) vector_fields: HashMap<&'a str, HashMap<&'a str, Cow<'a, Field>>>,
     pub(super) all_schemas: SchemaVersionMap<'a>,
     pub(super) diagnostics: Vec<Diagnostic>,
     pub(super) output: GeneratedSource,
+    /// Whether the items of the traversal step being validated carry the score of a search or
+    /// centrality step, which nodes only have a `score` field after.
+    pub(super) scored_items: bool,
 }
 
 pub static INTROSPECTION_DATA: OnceLock<IntrospectionData> = OnceLock::new();
 pub static SECONDARY_INDICES: OnceLock<Vec<String>> = OnceLock::new();
+pub static DATE_INDICES: OnceLock<Vec<String>> = OnceLock::new();
 
 impl<'a> Ctx<'a> {
     pub(super) fn new(src: &'a Source) -> Result<Self, ParserError> {
@@ -74,7 +87,7 @@ impl<'a> Ctx<'a> {
             ..Default::default()
         };
 
-        let ctx = Self {
+        let mut ctx = Self {
             node_set: src
                 .get_latest_schema()?
                 .node_schemas
@@ -93,6 +106,13 @@ impl<'a> Ctx<'a> {
                 .iter()
                 .map(|e| (e.name.1.as_str(), e))
                 .collect(),
+            enum_map: src
+                .get_latest_schema()?
+                .enum_schemas
+                .iter()
+                .map(|e| (e.name.1.as_str(), e))
+                .collect(),
+            fragments: src.fragments.iter().rev().map(|f| (f.name.1.as_str(), f)).collect(),
             node_fields,
             edge_fields,
             vector_fields,
@@ -100,6 +120,7 @@ impl<'a> Ctx<'a> {
             src,
             diagnostics: Vec::new(),
             output,
+            scored_items: false,
         };
 
         INTROSPECTION_DATA
@@ -122,6 +143,29 @@ impl<'a> Ctx<'a> {
                     .collect(),
             )
             .ok();
+
+        DATE_INDICES
+            .set(
+                src.get_latest_schema()?
+                    .node_schemas
+                    .iter()
+                    .flat_map(|schema| {
+                        schema
+                            .fields
+                            .iter()
+                            .filter(|f| f.is_indexed() && f.field_type == FieldType::Date)
+                            .map(|f| f.name.clone())
+                    })
+                    .dedup()
+                    .collect(),
+            )
+            .ok();
+
+        // union edge endpoints act as node types exposing the fields of all their members
+        let unions =
+            build_union_field_lookups(&src.get_latest_schema()?.edge_schemas, &ctx.node_fields);
+        ctx.node_set.extend(unions.keys());
+        ctx.node_fields.extend(unions);
         Ok(ctx)
     }
 
@@ -157,6 +201,15 @@ impl<'a> Ctx<'a> {
         }
     }
 
+    // ---------- Pass #1.75: fragments --------------------------
+    pub(super) fn check_fragments(
+        &mut self,
+        fragment_queries: &'a [Query],
+        called_fragments: &HashSet<String>,
+    ) {
+        check_fragments(self, fragment_queries, called_fragments);
+    }
+
     // ---------- Pass #2: queries -------------------------
     pub(super) fn check_queries(&mut self) {
         for q in &self.src.queries {
@@ -4,12 +4,15 @@ use crate::helixc::{
     generator::{
         queries::Parameter as GeneratedParameter,
         schemas::{
-            EdgeSchema as GeneratedEdgeSchema, NodeSchema as GeneratedNodeSchema, SchemaProperty,
+            EdgeSchema as GeneratedEdgeSchema, EnumSchema as GeneratedEnumSchema,
+            NodeSchema as GeneratedNodeSchema, SchemaProperty,
             VectorSchema as GeneratedVectorSchema,
         },
         utils::{GenRef, GeneratedType, GeneratedValue, RustType as GeneratedRustType},
     },
-    parser::types::{DefaultValue, EdgeSchema, FieldType, NodeSchema, Parameter, VectorSchema},
+    parser::types::{
+        DefaultValue, EdgeSchema, EnumSchema, FieldType, NodeSchema, Parameter, VectorSchema,
+    },
 };
 
 impl From<NodeSchema> for GeneratedNodeSchema {
@@ -21,8 +24,9 @@ impl From<NodeSchema> for GeneratedNodeSchema {
                 .into_iter()
                 .map(|f| SchemaProperty {
                     name: f.name,
+                    default_value: f.defaults.map(|d| field_default(&f.field_type, d)),
                     field_type: f.field_type.into(),
-                    default_value: f.defaults.map(|d| d.into()),
+                    is_optional: f.is_optional,
                     is_index: f.prefix,
                 })
                 .collect(),
@@ -41,8 +45,9 @@ impl From<EdgeSchema> for GeneratedEdgeSchema {
                     .into_iter()
                     .map(|f| SchemaProperty {
                         name: f.name,
+                        default_value: f.defaults.map(|d| field_default(&f.field_type, d)),
                         field_type: f.field_type.into(),
-                        default_value: f.defaults.map(|d| d.into()),
+                        is_optional: f.is_optional,
                         is_index: f.prefix,
                     })
                     .collect()
@@ -51,6 +56,15 @@ impl From<EdgeSchema> for GeneratedEdgeSchema {
     }
 }
 
+impl From<EnumSchema> for GeneratedEnumSchema {
+    fn from(generated: EnumSchema) -> Self {
+        GeneratedEnumSchema {
+            name: generated.name.1,
+            variants: generated.variants.into_iter().map(|(_, v)| v).collect(),
+        }
+    }
+}
+
 impl From<VectorSchema> for GeneratedVectorSchema {
     fn from(generated: VectorSchema) -> Self {
         GeneratedVectorSchema {
@@ -60,8 +74,9 @@ impl From<VectorSchema> for GeneratedVectorSchema {
                 .into_iter()
                 .map(|f| SchemaProperty {
                     name: f.name,
+                    default_value: f.defaults.map(|d| field_default(&f.field_type, d)),
                     field_type: f.field_type.into(),
-                    default_value: f.defaults.map(|d| d.into()),
+                    is_optional: f.is_optional,
                     is_index: f.prefix,
                 })
                 .collect(),
@@ -202,12 +217,23 @@ impl From<FieldType> for GeneratedType {
     }
 }
 
+/// The generated default of a field, those of enum fields written as the enum so that they are
+/// stored the same way as enum parameters
+fn field_default(field_type: &FieldType, default: DefaultValue) -> GeneratedValue {
+    match (field_type, default) {
+        (FieldType::Identifier(enum_name), DefaultValue::String(variant)) => {
+            GeneratedValue::Primitive(GenRef::Std(format!("{enum_name}::{variant}")))
+        }
+        (_, default) => default.into(),
+    }
+}
+
 impl From<DefaultValue> for GeneratedValue {
     fn from(generated: DefaultValue) -> Self {
         match generated {
-            DefaultValue::String(s) => GeneratedValue::Primitive(GenRef::Std(s)),
-            DefaultValue::F32(f) => GeneratedValue::Primitive(GenRef::Std(f.to_string())),
-            DefaultValue::F64(f) => GeneratedValue::Primitive(GenRef::Std(f.to_string())),
+            DefaultValue::String(s) => GeneratedValue::Primitive(GenRef::Literal(s)),
+            DefaultValue::F32(f) => GeneratedValue::Primitive(GenRef::Std(format!("{f:?}"))),
+            DefaultValue::F64(f) => GeneratedValue::Primitive(GenRef::Std(format!("{f:?}"))),
             DefaultValue::I8(i) => GeneratedValue::Primitive(GenRef::Std(i.to_string())),
             DefaultValue::I16(i) => GeneratedValue::Primitive(GenRef::Std(i.to_string())),
             DefaultValue::I32(i) => GeneratedValue::Primitive(GenRef::Std(i.to_string())),
@@ -10,6 +10,7 @@ use crate::{
         },
         parser::{location::Loc, types::*},
     },
+    protocol::value::Value,
 };
 use paste::paste;
 use std::collections::HashMap;
@@ -88,6 +89,69 @@ pub(super) fn gen_identifier_or_param(
     }
 }
 
+/// Generates the value an identifier sets on a field. An optional parameter set on an optional
+/// field is passed through as is, so leaving the parameter out stores null.
+pub(super) fn gen_field_value(
+    original_query: &Query,
+    name: &str,
+    field_is_optional: bool,
+    should_ref: bool,
+    should_clone: bool,
+) -> GeneratedValue {
+    match is_param(original_query, name) {
+        Some(param) if param.is_optional && field_is_optional => {
+            GeneratedValue::Parameter(GenRef::Std(format!("data.{name}.clone()")))
+        }
+        _ => gen_identifier_or_param(original_query, name, should_ref, should_clone),
+    }
+}
+
+/// The enum a field of type `field_type` holds, if it holds one
+pub(super) fn field_enum<'a>(ctx: &Ctx<'a>, field_type: &FieldType) -> Option<&'a EnumSchema> {
+    match field_type {
+        FieldType::Identifier(name) => ctx.enum_map.get(name.as_str()).copied(),
+        _ => None,
+    }
+}
+
+/// Checks that a literal given for an enum field names one of its variants
+pub(super) fn check_enum_literal(
+    ctx: &mut Ctx,
+    original_query: &Query,
+    loc: Loc,
+    enum_schema: &EnumSchema,
+    value: &Value,
+) {
+    if !matches!(value, Value::String(s) if enum_schema.has_variant(s)) {
+        generate_error!(
+            ctx,
+            original_query,
+            loc,
+            E213,
+            [&value.inner_stringify(), &enum_schema.name.1],
+            [&enum_schema.variant_list()]
+        );
+    }
+}
+
+/// The generated value of a literal naming an enum variant, written as the enum so that it is
+/// stored the same way as enum parameters
+pub(super) fn gen_enum_literal(enum_schema: &EnumSchema, variant: &str) -> GeneratedValue {
+    GeneratedValue::Primitive(GenRef::Std(format!("{}::{variant}", enum_schema.name.1)))
+}
+
+/// The generated value of a literal written to a field of the given type
+pub(super) fn gen_literal_field_value(
+    ctx: &Ctx,
+    field_type: &FieldType,
+    value: &Value,
+) -> GeneratedValue {
+    match (field_enum(ctx, field_type), value) {
+        (Some(enum_schema), Value::String(variant)) => gen_enum_literal(enum_schema, variant),
+        _ => GeneratedValue::Literal(GenRef::from(value.clone())),
+    }
+}
+
 pub(super) fn gen_id_access_or_param(original_query: &Query, name: &str) -> GeneratedValue {
     if let Some(param) = is_param(original_query, name) {
         GeneratedValue::Parameter(match param.is_optional {
@@ -219,6 +283,7 @@ pub(super) struct VariableInfo {
     pub is_single: bool,            // true if ToObj, false if ToVec
     pub reference_count: usize,     // How many times this variable is referenced
     pub source_var: Option<String>, // For closure parameters, the actual variable they refer to
+    pub is_scored: bool,            // Whether the items carry the score of a scoring step
 }
 
 impl VariableInfo {
@@ -228,6 +293,7 @@ impl VariableInfo {
             is_single,
             reference_count: 0,
             source_var: None,
+            is_scored: false,
         }
     }
 
@@ -237,9 +303,15 @@ impl VariableInfo {
             is_single,
             reference_count: 0,
             source_var: Some(source_var),
+            is_scored: false,
         }
     }
 
+    pub fn with_scored(mut self, is_scored: bool) -> Self {
+        self.is_scored = is_scored;
+        self
+    }
+
     pub fn increment_reference(&mut self) {
         self.reference_count += 1;
     }
@@ -272,6 +344,8 @@ pub(super) trait FieldLookup {
     #[allow(dead_code)]
     fn item_fields_contains_key_with_type(&self, ctx: &Ctx, key: &str) -> (bool, String);
     fn get_field_type_from_item_fields(&self, ctx: &Ctx, key: &str) -> Option<FieldType>;
+    /// Whether `key` is declared optional, so it may be null
+    fn is_optional_field(&self, ctx: &Ctx, key: &str) -> bool;
 }
 
 impl FieldLookup for Type {
@@ -282,7 +356,7 @@ impl FieldLookup for Type {
                 .get(node_type.as_str())
                 .map(|fields| match key {
                     "id" | "ID" | "label" => true,
-                    _ => fields.contains_key(key),
+                    _ => fields.contains_key(key) || (key == "score" && ctx.scored_items),
                 })
                 .unwrap_or(true),
             Type::Edge(Some(edge_type)) | Type::Edges(Some(edge_type)) => ctx
@@ -312,7 +386,7 @@ impl FieldLookup for Type {
                     .get(node_type.as_str())
                     .map(|fields| match key {
                         "id" | "ID" | "label" => true,
-                        _ => fields.contains_key(key),
+                        _ => fields.contains_key(key) || (key == "score" && ctx.scored_items),
                     })
                     .unwrap_or(true),
                 node_type.as_str(),
@@ -351,10 +425,12 @@ impl FieldLookup for Type {
                 .map(|fields| match key {
                     "id" | "ID" => Some(FieldType::Uuid),
                     "label" => Some(FieldType::String),
-                    _ => fields
-                        .get(key)
-                        .map(|field| Some(field.field_type.clone()))
-                        .unwrap_or(None),
+                    // A node's own `score` field wins over the score of a search step
+                    _ => match fields.get(key) {
+                        Some(field) => Some(field.field_type.clone()),
+                        None if key == "score" && ctx.scored_items => Some(FieldType::F64),
+                        None => None,
+                    },
                 })
                 .unwrap_or(None),
             Type::Edge(Some(edge_type)) | Type::Edges(Some(edge_type)) => ctx
@@ -388,4 +464,22 @@ impl FieldLookup for Type {
             _ => unreachable!("shouldve been caught eariler"),
         }
     }
+
+    fn is_optional_field(&self, ctx: &Ctx, key: &str) -> bool {
+        let fields = match self {
+            Type::Node(Some(node_type)) | Type::Nodes(Some(node_type)) => {
+                ctx.node_fields.get(node_type.as_str())
+            }
+            Type::Edge(Some(edge_type)) | Type::Edges(Some(edge_type)) => {
+                ctx.edge_fields.get(edge_type.as_str())
+            }
+            Type::Vector(Some(vector_type)) | Type::Vectors(Some(vector_type)) => {
+                ctx.vector_fields.get(vector_type.as_str())
+            }
+            _ => None,
+        };
+        fields
+            .and_then(|fields| fields.get(key))
+            .is_some_and(|field| field.is_optional)
+    }
 }
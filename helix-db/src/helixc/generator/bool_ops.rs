@@ -15,10 +15,18 @@ pub enum BoolOp {
     Neq(Neq),
     Contains(Contains),
     IsIn(IsIn),
+    /// The property is missing or null
+    IsNull,
+    IsNotNull,
+    StartsWith(StartsWith),
+    EndsWith(EndsWith),
+    EqIgnoreCase(EqIgnoreCase),
+    Regex(Regex),
 }
-impl Display for BoolOp {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
+impl BoolOp {
+    /// The check applied to a value `v`
+    pub fn check(&self) -> String {
+        match self {
             BoolOp::Gt(gt) => format!("{gt}"),
             BoolOp::Gte(gte) => format!("{gte}"),
             BoolOp::Lt(lt) => format!("{lt}"),
@@ -27,8 +35,24 @@ impl Display for BoolOp {
             BoolOp::Neq(neq) => format!("{neq}"),
             BoolOp::Contains(contains) => format!("v{contains}"),
             BoolOp::IsIn(is_in) => format!("v{is_in}"),
-        };
-        write!(f, "map_value_or(false, |v| {s})?")
+            BoolOp::IsNull => "matches!(v, Value::Empty)".to_string(),
+            BoolOp::IsNotNull => "!matches!(v, Value::Empty)".to_string(),
+            BoolOp::StartsWith(starts_with) => format!("v{starts_with}"),
+            BoolOp::EndsWith(ends_with) => format!("v{ends_with}"),
+            BoolOp::EqIgnoreCase(eq_ignore_case) => format!("v{eq_ignore_case}"),
+            BoolOp::Regex(regex) => format!("v{regex}"),
+        }
+    }
+}
+impl BoolOp {
+    /// The result when the property is missing, which only a null check accepts
+    pub fn if_missing(&self) -> bool {
+        matches!(self, BoolOp::IsNull)
+    }
+}
+impl Display for BoolOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "map_value_or({}, |v| {})?", self.if_missing(), self.check())
     }
 }
 #[derive(Clone, Debug)]
@@ -117,6 +141,46 @@ impl Display for IsIn {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct StartsWith {
+    pub value: GeneratedValue,
+}
+impl Display for StartsWith {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ".starts_with({})", self.value)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EndsWith {
+    pub value: GeneratedValue,
+}
+impl Display for EndsWith {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ".ends_with({})", self.value)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EqIgnoreCase {
+    pub value: GeneratedValue,
+}
+impl Display for EqIgnoreCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ".eq_ignore_case({})", self.value)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Regex {
+    pub value: GeneratedValue,
+}
+impl Display for Regex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, ".matches_regex({})", self.value)
+    }
+}
+
 /// Boolean expression is used for a traversal or set of traversals wrapped in AND/OR
 /// that resolve to a boolean value
 #[derive(Clone, Debug)]
@@ -126,6 +190,8 @@ pub enum BoExp {
     Or(Vec<BoExp>),
     Exists(Traversal),
     Expr(Traversal),
+    /// A boolean operation applied to a value computed per item, e.g. `YEAR(_::{created_at})`
+    Computed { value: String, op: BoolOp },
     Empty,
 }
 
@@ -223,26 +289,19 @@ impl Display for BoExp {
                         && other_steps == 0
                     {
                         // Generate optimized code: val.get_property("prop").map_or(false, |v| ...)
-                        let bool_expr = match bool_op {
-                            BoolOp::Gt(gt) => format!("{gt}"),
-                            BoolOp::Gte(gte) => format!("{gte}"),
-                            BoolOp::Lt(lt) => format!("{lt}"),
-                            BoolOp::Lte(lte) => format!("{lte}"),
-                            BoolOp::Eq(eq) => format!("{eq}"),
-                            BoolOp::Neq(neq) => format!("{neq}"),
-                            BoolOp::Contains(contains) => format!("v{contains}"),
-                            BoolOp::IsIn(is_in) => format!("v{is_in}"),
-                        };
                         return write!(
                             f,
-                            "val\n                    .get_property({})\n                    .map_or(false, |v| {})",
-                            prop, bool_expr
+                            "val\n                    .get_property({})\n                    .map_or({}, |v| {})",
+                            prop,
+                            bool_op.if_missing(),
+                            bool_op.check()
                         );
                     }
                 }
                 // Fall back to full traversal for complex expressions
                 write!(f, "{traversal}")
             }
+            BoExp::Computed { value, op } => write!(f, "{value}.{op}"),
             BoExp::Empty => write!(f, ""),
         }
     }
@@ -0,0 +1,139 @@
+use crate::helixc::{
+    generator::utils::{GenRef, GeneratedValue},
+    parser::types::DateFunction,
+};
+
+/// Generated date expression
+///
+/// Every variant renders to an owned `Value`. Dates are RFC 3339 strings, the same form date
+/// properties are stored in, so results compare directly with stored dates.
+#[derive(Debug, Clone)]
+pub enum DateExpr {
+    FunctionCall(DateFunctionCallGen),
+    Now,
+    /// A string literal; date literals are already normalised to RFC 3339
+    Literal(GenRef<String>),
+    /// A parameter or variable in scope
+    Value(GeneratedValue),
+    /// A property of the current item (`_::{property}`)
+    Property(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct DateFunctionCallGen {
+    pub function: DateFunction,
+    /// The date the function is applied to, or the text `DATE` parses
+    pub value: Box<DateExpr>,
+    /// The signed shift of `DATE_ADD` / `DATE_SUB`, in milliseconds
+    pub millis: i64,
+    /// The `DateUnit` variant `DATE_TRUNC` truncates to
+    pub unit: Option<&'static str>,
+    /// The time zone the date is read in, UTC when not given
+    pub zone: Option<GeneratedValue>,
+}
+
+impl DateExpr {
+    /// Renders the expression, reading properties from `item`
+    pub fn render(&self, item: &str) -> String {
+        match self {
+            DateExpr::FunctionCall(call) => call.render(item),
+            DateExpr::Now => "Value::from(chrono::Utc::now())".to_string(),
+            DateExpr::Literal(lit) => format!("Value::from({lit})"),
+            DateExpr::Value(value) => format!("Value::from({value})"),
+            DateExpr::Property(prop) => {
+                format!("{item}.get_property(\"{prop}\").cloned().unwrap_or_default()")
+            }
+        }
+    }
+}
+
+impl DateFunctionCallGen {
+    /// Renders the call as an expression producing a `Value`, reading properties from `item`
+    pub fn render(&self, item: &str) -> String {
+        let value = self.value.render(item);
+        let zone = match &self.zone {
+            None => "\"UTC\"".to_string(),
+            Some(GeneratedValue::Literal(zone)) => zone.to_string(),
+            Some(zone) => format!("&{zone}"),
+        };
+        match self.function {
+            DateFunction::Add | DateFunction::Sub => format!("{value}.date_add({})", self.millis),
+            DateFunction::Trunc => format!(
+                "{value}.date_trunc(DateUnit::{}, {zone})",
+                self.unit.unwrap_or("Day")
+            ),
+            DateFunction::Date => format!("{value}.to_date({zone})"),
+            DateFunction::Year => format!("{value}.date_part(DateUnit::Year, {zone})"),
+            DateFunction::Month => format!("{value}.date_part(DateUnit::Month, {zone})"),
+            DateFunction::Day => format!("{value}.date_part(DateUnit::Day, {zone})"),
+            DateFunction::Hour => format!("{value}.date_part(DateUnit::Hour, {zone})"),
+        }
+    }
+
+    /// Whether the call reads a property of the current item
+    pub fn reads_properties(&self) -> bool {
+        match self.value.as_ref() {
+            DateExpr::FunctionCall(call) => call.reads_properties(),
+            DateExpr::Property(_) => true,
+            DateExpr::Now | DateExpr::Literal(_) | DateExpr::Value(_) => false,
+        }
+    }
+
+    /// Whether the call reads parameters or variables, which a sort or grouping key cannot
+    /// capture
+    pub fn reads_scope(&self) -> bool {
+        let zone = matches!(&self.zone, Some(zone) if !matches!(zone, GeneratedValue::Literal(_)));
+        zone || match self.value.as_ref() {
+            DateExpr::FunctionCall(call) => call.reads_scope(),
+            DateExpr::Value(_) => true,
+            DateExpr::Now | DateExpr::Literal(_) | DateExpr::Property(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_date_sub_now() {
+        let call = DateFunctionCallGen {
+            function: DateFunction::Sub,
+            value: Box::new(DateExpr::Now),
+            millis: -604_800_000,
+            unit: None,
+            zone: None,
+        };
+        assert_eq!(
+            call.render("val"),
+            "Value::from(chrono::Utc::now()).date_add(-604800000)"
+        );
+        assert!(!call.reads_properties());
+        assert!(!call.reads_scope());
+    }
+
+    #[test]
+    fn test_render_nested_trunc() {
+        let call = DateFunctionCallGen {
+            function: DateFunction::Year,
+            value: Box::new(DateExpr::FunctionCall(DateFunctionCallGen {
+                function: DateFunction::Trunc,
+                value: Box::new(DateExpr::Property("created_at".to_string())),
+                millis: 0,
+                unit: Some("Month"),
+                zone: Some(GeneratedValue::Parameter(GenRef::Std("data.tz".to_string()))),
+            })),
+            millis: 0,
+            unit: None,
+            zone: Some(GeneratedValue::Literal(GenRef::Literal(
+                "Europe/London".to_string(),
+            ))),
+        };
+        assert_eq!(
+            call.render("val"),
+            "val.get_property(\"created_at\").cloned().unwrap_or_default().date_trunc(DateUnit::Month, &data.tz).date_part(DateUnit::Year, \"Europe/London\")"
+        );
+        assert!(call.reads_properties());
+        assert!(call.reads_scope());
+    }
+}
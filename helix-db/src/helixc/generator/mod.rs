@@ -10,7 +10,7 @@ use crate::{
     helixc::generator::{
         migrations::GeneratedMigration,
         queries::Query,
-        schemas::{EdgeSchema, NodeSchema, VectorSchema},
+        schemas::{EdgeSchema, EnumSchema, NodeSchema, VectorSchema},
         utils::write_headers,
     },
 };
@@ -19,6 +19,7 @@ use std::io::Write;
 use std::{fmt::Display, fs::File, io::Result, path::Path};
 
 pub mod bool_ops;
+pub mod date_functions;
 pub mod math_functions;
 pub mod migrations;
 pub mod queries;
@@ -26,6 +27,7 @@ pub mod return_values;
 pub mod schemas;
 pub mod source_steps;
 pub mod statements;
+pub mod string_functions;
 pub mod traversal_steps;
 pub mod tsdisplay;
 pub mod utils;
@@ -39,6 +41,7 @@ pub fn generate(source: Source, path: &Path) -> Result<()> {
 }
 
 pub struct Source {
+    pub enums: Vec<EnumSchema>,
     pub nodes: Vec<NodeSchema>,
     pub edges: Vec<EdgeSchema>,
     pub vectors: Vec<VectorSchema>,
@@ -50,6 +53,7 @@ pub struct Source {
 impl Default for Source {
     fn default() -> Self {
         Self {
+            enums: vec![],
             nodes: vec![],
             edges: vec![],
             vectors: vec![],
@@ -64,6 +68,16 @@ impl Display for Source {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "{}", write_headers())?;
         writeln!(f, "{}", self.config)?;
+        write!(
+            f,
+            "{}",
+            self.enums
+                .iter()
+                .map(|e| format!("{e}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )?;
+        writeln!(f)?;
         write!(
             f,
             "{}",
@@ -1,8 +1,9 @@
 use std::fmt::{self, Display};
 
 use crate::helixc::generator::{
-    return_values::{ReturnValue, ReturnValueStruct},
+    return_values::{ReturnFieldSource, ReturnValue, ReturnValueStruct},
     statements::Statement,
+    traversal_steps::{ShouldCollect, Traversal, TraversalType},
     utils::{EmbedData, GeneratedType},
 };
 
@@ -22,7 +23,10 @@ pub struct Query {
 
 impl Query {
     fn print_handler(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "#[handler]")
+        match self.is_mut {
+            true => writeln!(f, "#[handler(is_mut)]"),
+            false => writeln!(f, "#[handler]"),
+        }
     }
 
     fn print_parameters(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -92,6 +96,26 @@ impl Query {
         Ok(())
     }
 
+    /// Prints the return values that have no return struct (scalars and booleans) after the
+    /// struct-based ones, so they are not dropped from the response.
+    fn print_unstructured_return_values(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (field_name, ret_val) in &self.return_values {
+            if self
+                .return_structs
+                .iter()
+                .any(|struct_def| struct_def.source_variable == *field_name)
+            {
+                continue;
+            }
+            writeln!(f, ",")?;
+            match &ret_val.literal_value {
+                Some(lit) => write!(f, "    \"{}\": {}", field_name, lit)?,
+                None => write!(f, "    \"{}\": {}", field_name, field_name)?,
+            }
+        }
+        Ok(())
+    }
+
     fn print_txn_commit(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(
             f,
@@ -148,8 +172,349 @@ impl Query {
             )?,
         }
 
+        self.print_statements_and_response(f, false)?;
+
+        if !self.hoisted_embedding_calls.is_empty() {
+            writeln!(f, r#"}}))).await.expect("Cont Channel should be alive")"#)?;
+            writeln!(f, "}})))")?;
+        }
+        writeln!(f, "}}")?;
+        Ok(())
+    }
+
+    /// Prints `var.iter().map(..)` building the return struct of every item of a collection.
+    /// Items are wrapped in `Ok` when a field runs a nested traversal, which can fail.
+    /// Prints the items of the collection `struct_def` is built from mapped to the struct.
+    ///
+    /// A `lazy` traversal is mapped as it runs instead of the collected source variable. Its
+    /// items are results, so each mapped item is a result too.
+    fn print_collection_map(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        struct_def: &ReturnValueStruct,
+        lazy: Option<&Traversal>,
+    ) -> fmt::Result {
+        let singular_var = struct_def.source_variable.trim_end_matches('s');
+        // Check if any field is a nested traversal (needs Result handling)
+        let has_nested = struct_def.fields.iter().any(|f| f.is_nested_traversal);
+
+        match (lazy, has_nested) {
+            (None, true) => writeln!(
+                f,
+                "{}.iter().map(|{}| Ok::<_, GraphError>({} {{",
+                struct_def.source_variable, singular_var, struct_def.name
+            )?,
+            (None, false) => writeln!(
+                f,
+                "{}.iter().map(|{}| {} {{",
+                struct_def.source_variable, singular_var, struct_def.name
+            )?,
+            (Some(traversal), true) => writeln!(
+                f,
+                "{traversal}.map(|{v}| {v}.and_then(|{v}| Ok::<_, GraphError>({} {{",
+                struct_def.name,
+                v = singular_var
+            )?,
+            (Some(traversal), false) => writeln!(
+                f,
+                "{traversal}.map(|{v}| {v}.map(|{v}| {} {{",
+                struct_def.name,
+                v = singular_var
+            )?,
+        }
+
+        // Generate field assignments
+        for (field_idx, field) in struct_def.fields.iter().enumerate() {
+            let field_value = if field.is_nested_traversal {
+                // Get the nested traversal info from field_infos
+                let field_info = &struct_def.field_infos[field_idx];
+
+                // Handle scalar nested traversals with closure parameters (e.g., username: u::{name})
+                // or anonymous traversals (e.g., creatorID: _::In<Created>::ID)
+                if let crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
+                    closure_source_var: Some(closure_var),
+                    accessed_field_name: accessed_field,
+                    nested_struct_name: None,
+                    ..
+                } = &field_info.source {
+                    // Resolve "_" placeholder to actual iteration variable
+                    let resolved_var = if closure_var == "_" {
+                        singular_var
+                    } else {
+                        closure_var.as_str()
+                    };
+
+                    // This is a scalar field accessing a closure parameter or anonymous variable
+                    let field_to_access = accessed_field.as_ref()
+                        .map(|s| s.as_str())
+                        .unwrap_or(field.name.as_str());
+
+                    if field_to_access == "id" || field_to_access == "ID" {
+                        format!("uuid_str({}.id(), &arena)", resolved_var)
+                    } else if field_to_access == "label" || field_to_access == "Label" {
+                        format!("{}.label()", resolved_var)
+                    } else {
+                        format!("{}.get_property(\"{}\")", resolved_var, field_to_access)
+                    }
+                } else if let crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
+                    traversal_code: Some(trav_code),
+                    nested_struct_name: Some(nested_name),
+                    traversal_type,
+                    closure_source_var,
+                    ..
+                } = &field_info.source {
+                    // Generate nested traversal code
+                    let nested_fields = if let crate::helixc::generator::return_values::ReturnFieldType::Nested(fields) = &field_info.field_type {
+                        fields
+                    } else {
+                        panic!("Nested traversal must have Nested field type");
+                    };
+
+                    // Extract the actual source variable from the traversal type
+                    // Resolve "_" placeholder to actual iteration variable
+                    let (source_var, is_single_source) = if let Some(trav_type) = traversal_type {
+                        use crate::helixc::generator::traversal_steps::TraversalType;
+                        match trav_type {
+                            TraversalType::FromSingle(var) => {
+                                let v = var.inner();
+                                let resolved = if v == "_" { singular_var } else { v.as_str() };
+                                (resolved.to_string(), true)
+                            }
+                            TraversalType::FromIter(var) => {
+                                let v = var.inner();
+                                let resolved = if v == "_" { singular_var } else { v.as_str() };
+                                (resolved.to_string(), false)
+                            }
+                            _ => {
+                                (singular_var.to_string(), false)
+                            }
+                        }
+                    } else {
+                        (singular_var.to_string(), false)
+                    };
+
+                    // Determine if we need iter().cloned() or std::iter::once()
+                    let iterator_expr = if is_single_source {
+                        format!("std::iter::once({}.clone())", source_var)
+                    } else {
+                        format!("{}.iter().cloned()", source_var)
+                    };
+
+                    // Generate field assignments for nested struct
+                    // Check if we're in a closure context, resolve "_" placeholder
+                    let _closure_context_var = closure_source_var.as_ref()
+                        .map(|s| if s == "_" { singular_var } else { s.as_str() })
+                        .unwrap_or(singular_var);
+
+                    let mut nested_field_assigns = String::new();
+                    for nested_field in nested_fields {
+                        // Check if this nested field is itself a nested traversal with a nested struct
+                        let nested_val = if let crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
+                            traversal_code: Some(inner_trav_code),
+                            nested_struct_name: Some(inner_nested_name),
+                            ..
+                        } = &nested_field.source {
+                            // This is a deeply nested traversal - generate nested traversal code
+                            // Get the nested fields if available
+                            let inner_fields_str = if let crate::helixc::generator::return_values::ReturnFieldType::Nested(inner_fields) = &nested_field.field_type {
+                                // Generate field assignments for the deeply nested struct
+                                let mut inner_assigns = String::new();
+                                for inner_f in inner_fields {
+                                    let inner_val = if inner_f.name == "id" {
+                                        "uuid_str(inner_item.id(), &arena)".to_string()
+                                    } else if inner_f.name == "label" {
+                                        "inner_item.label()".to_string()
+                                    } else {
+                                        format!("inner_item.get_property(\"{}\")", inner_f.name)
+                                    };
+                                    inner_assigns.push_str(&format!("\n{}: {},", inner_f.name, inner_val));
+                                }
+                                format!(".map(|inner_item| inner_item.map(|inner_item| {} {{{}\n}})).collect::<Result<Vec<_>, _>>()?", inner_nested_name, inner_assigns)
+                            } else {
+                                ".collect::<Vec<_>>()".to_string()
+                            };
+                            format!("G::from_iter(&db, &txn, std::iter::once(item.clone()), &arena){}{}", inner_trav_code, inner_fields_str)
+                        } else {
+                            // Check if this field itself is a nested traversal that accesses the closure parameter
+                            // Extract both the access variable and the actual field being accessed
+                            let (access_var, accessed_field_name) = if let crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
+                                closure_source_var: Some(closure_var),
+                                accessed_field_name: accessed_field,
+                                ..
+                            } = &nested_field.source {
+                                // Use the accessed_field_name from the metadata if available,
+                                // otherwise fall back to the field name
+                                let field_to_access = accessed_field.as_ref()
+                                    .map(|s| s.as_str())
+                                    .unwrap_or(nested_field.name.as_str());
+                                (closure_var.as_str(), field_to_access)
+                            } else {
+                                ("item", nested_field.name.as_str())
+                            };
+
+                            if accessed_field_name == "id" || accessed_field_name == "ID" {
+                                format!("uuid_str({}.id(), &arena)", access_var)
+                            } else if accessed_field_name == "label" || accessed_field_name == "Label" {
+                                format!("{}.label()", access_var)
+                            } else if accessed_field_name == "from_node" {
+                                format!("uuid_str({}.from_node(), &arena)", access_var)
+                            } else if accessed_field_name == "to_node" {
+                                format!("uuid_str({}.to_node(), &arena)", access_var)
+                            } else {
+                                format!("{}.get_property(\"{}\")", access_var, accessed_field_name)
+                            }
+                        };
+                        nested_field_assigns.push_str(&format!("\n                        {}: {},", nested_field.name, nested_val));
+                    }
+
+                    // Check if any nested field is a deeply nested traversal that needs error handling
+                    let has_deeply_nested = nested_fields.iter().any(|f| matches!(
+                        f.source,
+                        crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
+                            nested_struct_name: Some(_),
+                            ..
+                        }
+                    ));
+
+                    if has_deeply_nested {
+                        // Use and_then so the closure can return Result and use ?
+                        format!("G::from_iter(&db, &txn, {}, &arena){}.map(|item| item.and_then(|item| Ok({} {{{}\n                    }}))).collect::<Result<Vec<_>, _>>()?",
+                            iterator_expr, trav_code, nested_name, nested_field_assigns)
+                    } else {
+                        format!("G::from_iter(&db, &txn, {}, &arena){}.map(|item| item.map(|item| {} {{{}\n                    }})).collect::<Result<Vec<_>, _>>()?",
+                            iterator_expr, trav_code, nested_name, nested_field_assigns)
+                    }
+                } else {
+                    "Vec::new()".to_string()
+                }
+            } else if let Some(crate::helixc::generator::return_values::ReturnFieldInfo {
+                source: crate::helixc::generator::return_values::ReturnFieldSource::Computed(call),
+                ..
+            }) = struct_def.field_infos.get(field_idx) {
+                call.render(singular_var)
+            } else if field.name == "id" {
+                format!("uuid_str({}.id(), &arena)", singular_var)
+            } else if field.name == "label" {
+                format!("{}.label()", singular_var)
+            } else if field.name == "from_node" {
+                format!("uuid_str({}.from_node(), &arena)", singular_var)
+            } else if field.name == "to_node" {
+                format!("uuid_str({}.to_node(), &arena)", singular_var)
+            } else if field.name == "data" {
+                format!("{}.data()", singular_var)
+            } else if field.name == "score" && field.is_implicit {
+                format!("{}.score()", singular_var)
+            } else {
+                // Regular schema field
+                format!("{}.get_property(\"{}\")", singular_var, field.name)
+            };
+            writeln!(f, "        {}: {},", field.name, field_value)?;
+        }
+
+        match (has_nested, lazy.is_some()) {
+            (true, true) => write!(f, "    }})))"),
+            (true, false) | (false, true) => write!(f, "    }}))"),
+            (false, false) => write!(f, "    }})"),
+        }
+    }
+
+    /// The returned collection that is streamed item by item when the client accepts a streamed
+    /// response. Only read queries returning a single collection are streamed.
+    fn streamed_collection(&self) -> Option<&ReturnValueStruct> {
+        let [struct_def] = self.return_structs.as_slice() else {
+            return None;
+        };
+        let streamable = self.use_struct_returns
+            && !self.is_mut
+            // the rest of the handler runs as a continuation, outside of the request's worker
+            && self.hoisted_embedding_calls.is_empty()
+            && self.return_values.len() == 1
+            && struct_def.is_collection
+            && !struct_def.is_aggregate;
+        streamable.then_some(struct_def)
+    }
+
+    /// The traversal of the streamed collection when it can be streamed as it runs instead of
+    /// being collected first. It must be assigned by the last statement, so no other statement
+    /// reads it, and no returned field may traverse from the whole collection.
+    fn lazy_stream_traversal(&self, struct_def: &ReturnValueStruct) -> Option<&Traversal> {
+        let Some(Statement::Assignment(assignment)) = self.statements.last() else {
+            return None;
+        };
+        let Statement::Traversal(traversal) = &*assignment.value else {
+            return None;
+        };
+        let variable = &struct_def.source_variable;
+        let reads_collection = struct_def.field_infos.iter().any(|info| {
+            matches!(
+                &info.source,
+                ReturnFieldSource::NestedTraversal {
+                    traversal_type: Some(TraversalType::FromSingle(v) | TraversalType::FromIter(v)),
+                    ..
+                } if v.inner() == variable
+            )
+        });
+        let lazy = assignment.variable.inner() == variable
+            && matches!(traversal.traversal_type, TraversalType::Ref)
+            && matches!(traversal.should_collect, ShouldCollect::ToVec)
+            && !reads_collection;
+        lazy.then_some(traversal)
+    }
+
+    /// Prints a branch writing the items of a returned collection to a `ResponseStream` one at a
+    /// time when the client accepts a streamed response, rather than building the whole response.
+    /// The items of a `lazy` traversal are written as it yields them, without collecting it.
+    fn print_response_stream(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        struct_def: &ReturnValueStruct,
+        lazy: Option<&Traversal>,
+    ) -> fmt::Result {
+        writeln!(f, "if let Some(mut stream) = ResponseStream::requested(&input.request) {{")?;
+        // the temporaries of the traversal live as long as the loop
+        let lazy = lazy.map(|traversal| Traversal {
+            should_collect: ShouldCollect::No,
+            ..traversal.clone()
+        });
+        write!(f, "for item in ")?;
+        self.print_collection_map(f, struct_def, lazy.as_ref())?;
+        writeln!(f, " {{")?;
+        match lazy.is_some() || struct_def.fields.iter().any(|f| f.is_nested_traversal) {
+            true => writeln!(f, "stream.write(&item?)?;"),
+            false => writeln!(f, "stream.write(&item)?;"),
+        }?;
+        writeln!(f, "}}")?;
+        self.print_txn_commit(f)?;
+        writeln!(f, "return stream.finish();")?;
+        writeln!(f, "}}")
+    }
+
+    /// Prints the statements of the query followed by building and returning its response.
+    /// Batch functions return the response as JSON and leave the commit to the batch.
+    fn print_statements_and_response(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        batch: bool,
+    ) -> fmt::Result {
+        let streamed = match batch {
+            true => None,
+            false => self.streamed_collection(),
+        };
+        let lazy = streamed.and_then(|struct_def| self.lazy_stream_traversal(struct_def));
+        // a lazily streamed traversal is only collected after the stream branch
+        let (statements, collected_after) = match lazy {
+            Some(_) => self.statements.split_at(self.statements.len() - 1),
+            None => (self.statements.as_slice(), &[][..]),
+        };
+
         // prints each statement
-        for statement in &self.statements {
+        for statement in statements {
+            writeln!(f, "    {statement};")?;
+        }
+        if let Some(struct_def) = streamed {
+            self.print_response_stream(f, struct_def, lazy)?;
+        }
+        for statement in collected_after {
             writeln!(f, "    {statement};")?;
         }
 
@@ -173,219 +538,11 @@ impl Query {
                     )?;
                 } else if struct_def.is_collection {
                     // Collection - generate mapping code
-                    let singular_var = struct_def.source_variable.trim_end_matches('s');
-                    // Check if any field is a nested traversal (needs Result handling)
-                    let has_nested = struct_def.fields.iter().any(|f| f.is_nested_traversal);
-
-                    if has_nested {
-                        writeln!(
-                            f,
-                            "    \"{}\": {}.iter().map(|{}| Ok::<_, GraphError>({} {{",
-                            struct_def.source_variable,
-                            struct_def.source_variable,
-                            singular_var,
-                            struct_def.name
-                        )?;
-                    } else {
-                        writeln!(
-                            f,
-                            "    \"{}\": {}.iter().map(|{}| {} {{",
-                            struct_def.source_variable,
-                            struct_def.source_variable,
-                            singular_var,
-                            struct_def.name
-                        )?;
-                    }
-
-                    // Generate field assignments
-                    for (field_idx, field) in struct_def.fields.iter().enumerate() {
-                        let field_value = if field.is_nested_traversal {
-                            // Get the nested traversal info from field_infos
-                            let field_info = &struct_def.field_infos[field_idx];
-
-                            // Handle scalar nested traversals with closure parameters (e.g., username: u::{name})
-                            // or anonymous traversals (e.g., creatorID: _::In<Created>::ID)
-                            if let crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
-                                closure_source_var: Some(closure_var),
-                                accessed_field_name: accessed_field,
-                                nested_struct_name: None,
-                                ..
-                            } = &field_info.source {
-                                // Resolve "_" placeholder to actual iteration variable
-                                let resolved_var = if closure_var == "_" {
-                                    singular_var
-                                } else {
-                                    closure_var.as_str()
-                                };
-
-                                // This is a scalar field accessing a closure parameter or anonymous variable
-                                let field_to_access = accessed_field.as_ref()
-                                    .map(|s| s.as_str())
-                                    .unwrap_or(field.name.as_str());
-
-                                if field_to_access == "id" || field_to_access == "ID" {
-                                    format!("uuid_str({}.id(), &arena)", resolved_var)
-                                } else if field_to_access == "label" || field_to_access == "Label" {
-                                    format!("{}.label()", resolved_var)
-                                } else {
-                                    format!("{}.get_property(\"{}\")", resolved_var, field_to_access)
-                                }
-                            } else if let crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
-                                traversal_code: Some(trav_code),
-                                nested_struct_name: Some(nested_name),
-                                traversal_type,
-                                closure_source_var,
-                                ..
-                            } = &field_info.source {
-                                // Generate nested traversal code
-                                let nested_fields = if let crate::helixc::generator::return_values::ReturnFieldType::Nested(fields) = &field_info.field_type {
-                                    fields
-                                } else {
-                                    panic!("Nested traversal must have Nested field type");
-                                };
-
-                                // Extract the actual source variable from the traversal type
-                                // Resolve "_" placeholder to actual iteration variable
-                                let (source_var, is_single_source) = if let Some(trav_type) = traversal_type {
-                                    use crate::helixc::generator::traversal_steps::TraversalType;
-                                    match trav_type {
-                                        TraversalType::FromSingle(var) => {
-                                            let v = var.inner();
-                                            let resolved = if v == "_" { singular_var } else { v.as_str() };
-                                            (resolved.to_string(), true)
-                                        }
-                                        TraversalType::FromIter(var) => {
-                                            let v = var.inner();
-                                            let resolved = if v == "_" { singular_var } else { v.as_str() };
-                                            (resolved.to_string(), false)
-                                        }
-                                        _ => {
-                                            (singular_var.to_string(), false)
-                                        }
-                                    }
-                                } else {
-                                    (singular_var.to_string(), false)
-                                };
-
-                                // Determine if we need iter().cloned() or std::iter::once()
-                                let iterator_expr = if is_single_source {
-                                    format!("std::iter::once({}.clone())", source_var)
-                                } else {
-                                    format!("{}.iter().cloned()", source_var)
-                                };
-
-                                // Generate field assignments for nested struct
-                                // Check if we're in a closure context, resolve "_" placeholder
-                                let _closure_context_var = closure_source_var.as_ref()
-                                    .map(|s| if s == "_" { singular_var } else { s.as_str() })
-                                    .unwrap_or(singular_var);
-
-                                let mut nested_field_assigns = String::new();
-                                for nested_field in nested_fields {
-                                    // Check if this nested field is itself a nested traversal with a nested struct
-                                    let nested_val = if let crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
-                                        traversal_code: Some(inner_trav_code),
-                                        nested_struct_name: Some(inner_nested_name),
-                                        ..
-                                    } = &nested_field.source {
-                                        // This is a deeply nested traversal - generate nested traversal code
-                                        // Get the nested fields if available
-                                        let inner_fields_str = if let crate::helixc::generator::return_values::ReturnFieldType::Nested(inner_fields) = &nested_field.field_type {
-                                            // Generate field assignments for the deeply nested struct
-                                            let mut inner_assigns = String::new();
-                                            for inner_f in inner_fields {
-                                                let inner_val = if inner_f.name == "id" {
-                                                    "uuid_str(inner_item.id(), &arena)".to_string()
-                                                } else if inner_f.name == "label" {
-                                                    "inner_item.label()".to_string()
-                                                } else {
-                                                    format!("inner_item.get_property(\"{}\")", inner_f.name)
-                                                };
-                                                inner_assigns.push_str(&format!("\n{}: {},", inner_f.name, inner_val));
-                                            }
-                                            format!(".map(|inner_item| inner_item.map(|inner_item| {} {{{}\n}})).collect::<Result<Vec<_>, _>>()?", inner_nested_name, inner_assigns)
-                                        } else {
-                                            ".collect::<Vec<_>>()".to_string()
-                                        };
-                                        format!("G::from_iter(&db, &txn, std::iter::once(item.clone()), &arena){}{}", inner_trav_code, inner_fields_str)
-                                    } else {
-                                        // Check if this field itself is a nested traversal that accesses the closure parameter
-                                        // Extract both the access variable and the actual field being accessed
-                                        let (access_var, accessed_field_name) = if let crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
-                                            closure_source_var: Some(closure_var),
-                                            accessed_field_name: accessed_field,
-                                            ..
-                                        } = &nested_field.source {
-                                            // Use the accessed_field_name from the metadata if available,
-                                            // otherwise fall back to the field name
-                                            let field_to_access = accessed_field.as_ref()
-                                                .map(|s| s.as_str())
-                                                .unwrap_or(nested_field.name.as_str());
-                                            (closure_var.as_str(), field_to_access)
-                                        } else {
-                                            ("item", nested_field.name.as_str())
-                                        };
-
-                                        if accessed_field_name == "id" || accessed_field_name == "ID" {
-                                            format!("uuid_str({}.id(), &arena)", access_var)
-                                        } else if accessed_field_name == "label" || accessed_field_name == "Label" {
-                                            format!("{}.label()", access_var)
-                                        } else if accessed_field_name == "from_node" {
-                                            format!("uuid_str({}.from_node(), &arena)", access_var)
-                                        } else if accessed_field_name == "to_node" {
-                                            format!("uuid_str({}.to_node(), &arena)", access_var)
-                                        } else {
-                                            format!("{}.get_property(\"{}\")", access_var, accessed_field_name)
-                                        }
-                                    };
-                                    nested_field_assigns.push_str(&format!("\n                        {}: {},", nested_field.name, nested_val));
-                                }
-
-                                // Check if any nested field is a deeply nested traversal that needs error handling
-                                let has_deeply_nested = nested_fields.iter().any(|f| matches!(
-                                    f.source,
-                                    crate::helixc::generator::return_values::ReturnFieldSource::NestedTraversal {
-                                        nested_struct_name: Some(_),
-                                        ..
-                                    }
-                                ));
-
-                                if has_deeply_nested {
-                                    // Use and_then so the closure can return Result and use ?
-                                    format!("G::from_iter(&db, &txn, {}, &arena){}.map(|item| item.and_then(|item| Ok({} {{{}\n                    }}))).collect::<Result<Vec<_>, _>>()?",
-                                        iterator_expr, trav_code, nested_name, nested_field_assigns)
-                                } else {
-                                    format!("G::from_iter(&db, &txn, {}, &arena){}.map(|item| item.map(|item| {} {{{}\n                    }})).collect::<Result<Vec<_>, _>>()?",
-                                        iterator_expr, trav_code, nested_name, nested_field_assigns)
-                                }
-                            } else {
-                                "Vec::new()".to_string()
-                            }
-                        } else if field.name == "id" {
-                            format!("uuid_str({}.id(), &arena)", singular_var)
-                        } else if field.name == "label" {
-                            format!("{}.label()", singular_var)
-                        } else if field.name == "from_node" {
-                            format!("uuid_str({}.from_node(), &arena)", singular_var)
-                        } else if field.name == "to_node" {
-                            format!("uuid_str({}.to_node(), &arena)", singular_var)
-                        } else if field.name == "data" {
-                            format!("{}.data()", singular_var)
-                        } else if field.name == "score" {
-                            format!("{}.score()", singular_var)
-                        } else {
-                            // Regular schema field
-                            format!("{}.get_property(\"{}\")", singular_var, field.name)
-                        };
-                        writeln!(f, "        {}: {},", field.name, field_value)?;
-                    }
-
-                    // Check if any field is a nested traversal (needs Result handling)
-                    let has_nested = struct_def.fields.iter().any(|f| f.is_nested_traversal);
-                    if has_nested {
-                        write!(f, "    }})).collect::<Result<Vec<_>, GraphError>>()?")
-                    } else {
-                        write!(f, "    }}).collect::<Vec<_>>()")
+                    write!(f, "    \"{}\": ", struct_def.source_variable)?;
+                    self.print_collection_map(f, struct_def, None)?;
+                    match struct_def.fields.iter().any(|f| f.is_nested_traversal) {
+                        true => write!(f, ".collect::<Result<Vec<_>, GraphError>>()?"),
+                        false => write!(f, ".collect::<Vec<_>>()"),
                     }?;
                 } else {
                     // Single item - direct struct construction
@@ -545,6 +702,11 @@ impl Query {
                             } else {
                                 "Vec::new()".to_string()
                             }
+                        } else if let Some(crate::helixc::generator::return_values::ReturnFieldInfo {
+                            source: crate::helixc::generator::return_values::ReturnFieldSource::Computed(call),
+                            ..
+                        }) = struct_def.field_infos.get(field_idx) {
+                            call.render(&struct_def.source_variable)
                         } else if field.name == "id" {
                             format!("uuid_str({}.id(), &arena)", struct_def.source_variable)
                         } else if field.name == "label" {
@@ -558,7 +720,7 @@ impl Query {
                             format!("uuid_str({}.to_node(), &arena)", struct_def.source_variable)
                         } else if field.name == "data" {
                             format!("{}.data()", struct_def.source_variable)
-                        } else if field.name == "score" {
+                        } else if field.name == "score" && field.is_implicit {
                             format!("{}.score()", struct_def.source_variable)
                         } else {
                             format!(
@@ -572,10 +734,10 @@ impl Query {
                     write!(f, "    }}")?;
                 }
             }
+            self.print_unstructured_return_values(f)?;
             writeln!(f)?;
             writeln!(f, "}});")?;
-            self.print_txn_commit(f)?;
-            writeln!(f, "Ok(input.request.out_fmt.create_response(&response))")?;
+            self.print_return(f, "&response", batch)?;
         } else if !self.return_values.is_empty() {
             // Legacy json! macro approach
             write!(f, "let response = json!({{")?;
@@ -623,19 +785,49 @@ impl Query {
             }
             writeln!(f)?;
             writeln!(f, "}});")?;
-            self.print_txn_commit(f)?;
-            writeln!(f, "Ok(input.request.out_fmt.create_response(&response))")?;
+            self.print_return(f, "&response", batch)?;
         } else {
-            self.print_txn_commit(f)?;
-            writeln!(f, "Ok(input.request.out_fmt.create_response(&()))")?;
+            self.print_return(f, "&()", batch)?;
         }
 
+        Ok(())
+    }
+
+    fn print_return(&self, f: &mut fmt::Formatter<'_>, value: &str, batch: bool) -> fmt::Result {
+        match batch {
+            true => writeln!(f, "batch_output({value})"),
+            false => {
+                self.print_txn_commit(f)?;
+                writeln!(f, "Ok(input.request.out_fmt.create_response({value}))")
+            }
+        }
+    }
+
+    /// Prints a function that runs the query inside the transaction of a `/batch` request
+    fn print_batch(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // embedding calls need async IO, which can't happen inside the batch transaction
         if !self.hoisted_embedding_calls.is_empty() {
-            writeln!(f, r#"}}))).await.expect("Cont Channel should be alive")"#)?;
-            writeln!(f, "}})))")?;
+            return Ok(());
         }
-        writeln!(f, "}}")?;
-        Ok(())
+
+        writeln!(f, "#[batch_handler({})]", self.name)?;
+        writeln!(
+            f,
+            "pub fn {}Batch(input: BatchInput<'_, '_>) -> Result<serde_json::Value, GraphError> {{",
+            self.name
+        )?;
+        writeln!(f, "let db = input.db;")?;
+        if !self.parameters.is_empty() {
+            writeln!(f, "let data = batch_params::<{}Input>(input.params)?;", self.name)?;
+        }
+        writeln!(f, "let arena = Bump::new();")?;
+        match self.is_mut {
+            true => writeln!(f, "let mut txn = input.txn;")?,
+            false => writeln!(f, "let txn = input.txn;")?,
+        }
+
+        self.print_statements_and_response(f, true)?;
+        writeln!(f, "}}")
     }
 
     fn print_mcp(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -657,7 +849,10 @@ impl Query {
         }
         writeln!(f, "}}")?;
 
-        writeln!(f, "#[mcp_handler]")?;
+        match self.is_mut {
+            true => writeln!(f, "#[mcp_handler(is_mut)]")?,
+            false => writeln!(f, "#[mcp_handler]")?,
+        }
         writeln!(
             f,
             "pub fn {mcp_function_name}(input: &mut MCPToolInput) -> Result<Response, GraphError> {{"
@@ -921,6 +1116,11 @@ impl Query {
                             } else {
                                 "Vec::new()".to_string()
                             }
+                        } else if let Some(crate::helixc::generator::return_values::ReturnFieldInfo {
+                            source: crate::helixc::generator::return_values::ReturnFieldSource::Computed(call),
+                            ..
+                        }) = struct_def.field_infos.get(field_idx) {
+                            call.render(singular_var)
                         } else if field.name == "id" {
                             format!("uuid_str({}.id(), &arena)", singular_var)
                         } else if field.name == "label" {
@@ -931,7 +1131,7 @@ impl Query {
                             format!("uuid_str({}.to_node(), &arena)", singular_var)
                         } else if field.name == "data" {
                             format!("{}.data()", singular_var)
-                        } else if field.name == "score" {
+                        } else if field.name == "score" && field.is_implicit {
                             format!("{}.score()", singular_var)
                         } else {
                             // Regular schema field
@@ -1105,6 +1305,11 @@ impl Query {
                             } else {
                                 "Vec::new()".to_string()
                             }
+                        } else if let Some(crate::helixc::generator::return_values::ReturnFieldInfo {
+                            source: crate::helixc::generator::return_values::ReturnFieldSource::Computed(call),
+                            ..
+                        }) = struct_def.field_infos.get(field_idx) {
+                            call.render(&struct_def.source_variable)
                         } else if field.name == "id" {
                             format!("uuid_str({}.id(), &arena)", struct_def.source_variable)
                         } else if field.name == "label" {
@@ -1118,7 +1323,7 @@ impl Query {
                             format!("uuid_str({}.to_node(), &arena)", struct_def.source_variable)
                         } else if field.name == "data" {
                             format!("{}.data()", struct_def.source_variable)
-                        } else if field.name == "score" {
+                        } else if field.name == "score" && field.is_implicit {
                             format!("{}.score()", struct_def.source_variable)
                         } else {
                             format!(
@@ -1132,6 +1337,7 @@ impl Query {
                     write!(f, "    }}")?;
                 }
             }
+            self.print_unstructured_return_values(f)?;
             writeln!(f)?;
             writeln!(f, "}});")?;
             self.print_txn_commit(f)?;
@@ -1219,6 +1425,7 @@ impl Query {
 impl Display for Query {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.print_query(f)?;
+        self.print_batch(f)?;
         self.print_mcp(f)
     }
 }
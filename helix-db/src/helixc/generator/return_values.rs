@@ -11,6 +11,7 @@ pub struct ReturnValueField {
     pub is_implicit: bool,         // id, label, from_node, to_node, data, score
     pub is_nested_traversal: bool, // Whether this field contains a nested traversal
     pub nested_struct_name: Option<String>, // Name of nested struct type if applicable
+    pub serde_attributes: Vec<String>, // e.g. when union member fields are skipped
 }
 
 impl ReturnValueField {
@@ -21,6 +22,7 @@ impl ReturnValueField {
             is_implicit: false,
             is_nested_traversal: false,
             nested_struct_name: None,
+            serde_attributes: Vec::new(),
         }
     }
 
@@ -117,6 +119,9 @@ impl ReturnValueStruct {
 
         // Generate fields
         for field in &self.fields {
+            for attribute in &field.serde_attributes {
+                output.push_str(&format!("    #[serde({attribute})]\n"));
+            }
             if self.has_lifetime {
                 output.push_str(&format!("    pub {}: {},\n", field.name, field.field_type));
             } else {
@@ -148,7 +153,7 @@ impl ReturnValueStruct {
                 format!("uuid_str({}.to_node(), &arena)", singular_var)
             } else if field.name == "data" {
                 format!("{}.data()", singular_var)
-            } else if field.name == "score" {
+            } else if field.name == "score" && field.is_implicit {
                 format!("{}.score()", singular_var)
             } else if field.is_nested_traversal {
                 // Nested traversal - will be populated by nested G::new() call
@@ -261,6 +266,7 @@ impl ReturnValueStruct {
                         ReturnFieldSource::NestedTraversal { .. }
                     ),
                     nested_struct_name: nested_name,
+                    serde_attributes: field_info.serde_attributes(),
                 }
             })
             .collect::<Vec<_>>();
@@ -352,7 +358,7 @@ pub fn generate_map_closure_body(
             "uuid_str(val.to_node(), &arena)".to_string()
         } else if field.name == "data" {
             "val.data()".to_string()
-        } else if field.name == "score" {
+        } else if field.name == "score" && field.is_implicit {
             "val.score()".to_string()
         } else if field.is_nested_traversal {
             // Nested traversal - will be populated by nested G::new() call
@@ -432,6 +438,8 @@ pub enum ReturnFieldType {
 pub enum ReturnFieldSource {
     /// Field from the schema (node/edge/vector properties)
     SchemaField,
+    /// Field from the schema of only some members of a union, left out of items of the others
+    UnionMemberField,
     /// Implicit field (id, label, from_node, to_node, data, score)
     ImplicitField,
     /// User-defined field in custom object
@@ -446,6 +454,25 @@ pub enum ReturnFieldSource {
         closure_source_var: Option<String>, // Actual variable for the closure parameter
         accessed_field_name: Option<String>, // For simple property access, the field being accessed (e.g., "name" for usr::{name})
     },
+    /// Result of a function evaluated per item (e.g. `{ name: UPPER(_::{name}) }`)
+    Computed(ComputedField),
+}
+
+/// A remapped field computed per item by a string or date function
+#[derive(Debug, Clone)]
+pub enum ComputedField {
+    String(super::string_functions::StringFunctionCallGen),
+    Date(super::date_functions::DateFunctionCallGen),
+}
+
+impl ComputedField {
+    /// Renders the field as an expression producing a `Value`, reading properties from `item`
+    pub fn render(&self, item: &str) -> String {
+        match self {
+            ComputedField::String(call) => call.render(item),
+            ComputedField::Date(call) => call.render(item),
+        }
+    }
 }
 
 impl ReturnFieldInfo {
@@ -465,6 +492,24 @@ impl ReturnFieldInfo {
         }
     }
 
+    pub fn new_union_member(name: String) -> Self {
+        Self {
+            name,
+            field_type: ReturnFieldType::Simple("Option<&'a Value>".to_string()),
+            source: ReturnFieldSource::UnionMemberField,
+        }
+    }
+
+    /// The serde attributes of the generated field
+    fn serde_attributes(&self) -> Vec<String> {
+        match &self.source {
+            ReturnFieldSource::UnionMemberField => {
+                vec!["skip_serializing_if = \"Option::is_none\"".to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+
     pub fn new_nested(name: String, fields: Vec<ReturnFieldInfo>, traversal_expr: String) -> Self {
         Self {
             name,
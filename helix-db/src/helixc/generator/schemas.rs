@@ -18,7 +18,7 @@ impl Display for NodeSchema {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "pub struct {} {{", self.name)?;
         for property in &self.properties {
-            writeln!(f, "    pub {}: {},", property.name, property.field_type)?;
+            writeln!(f, "    {property},")?;
         }
         writeln!(f, "}}")
     }
@@ -29,14 +29,7 @@ impl ToTypeScript for NodeSchema {
         result.push_str("  id: string;\n");
 
         for property in &self.properties {
-            result.push_str(&format!(
-                "  {}: {};\n",
-                property.name,
-                match &property.field_type {
-                    GeneratedType::RustType(t) => t.to_ts(),
-                    _ => unreachable!(),
-                }
-            ));
+            result.push_str(&format!("  {};\n", property.to_ts_member()));
         }
 
         result.push_str("}\n");
@@ -51,13 +44,32 @@ pub struct EdgeSchema {
     pub to: String,
     pub properties: Vec<SchemaProperty>,
 }
+impl EdgeSchema {
+    /// The Rust type of an endpoint: the node type itself, or for a union
+    /// such as `Post | Comment` an enum named after the edge and side.
+    fn endpoint_type(&self, endpoint: &str, side: &str) -> String {
+        match endpoint.contains(" | ") {
+            true => format!("{}{side}", self.name),
+            false => endpoint.to_string(),
+        }
+    }
+}
 impl Display for EdgeSchema {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (endpoint, side) in [(&self.from, "From"), (&self.to, "To")] {
+            if endpoint.contains(" | ") {
+                writeln!(f, "pub enum {} {{", self.endpoint_type(endpoint, side))?;
+                for member in endpoint.split(" | ") {
+                    writeln!(f, "    {member}({member}),")?;
+                }
+                writeln!(f, "}}")?;
+            }
+        }
         writeln!(f, "pub struct {} {{", self.name)?;
-        writeln!(f, "    pub from: {},", self.from)?;
-        writeln!(f, "    pub to: {},", self.to)?;
+        writeln!(f, "    pub from: {},", self.endpoint_type(&self.from, "From"))?;
+        writeln!(f, "    pub to: {},", self.endpoint_type(&self.to, "To"))?;
         for property in &self.properties {
-            writeln!(f, "    pub {}: {},", property.name, property.field_type)?;
+            writeln!(f, "    {property},")?;
         }
         writeln!(f, "}}")
     }
@@ -69,14 +81,7 @@ impl ToTypeScript for VectorSchema {
         result.push_str("  data: Array<number>;\n");
 
         for property in &self.properties {
-            result.push_str(&format!(
-                "  {}: {};\n",
-                property.name,
-                match &property.field_type {
-                    GeneratedType::RustType(t) => t.to_ts(),
-                    _ => unreachable!(),
-                }
-            ));
+            result.push_str(&format!("  {};\n", property.to_ts_member()));
         }
 
         result.push_str("}\n");
@@ -92,7 +97,7 @@ impl Display for VectorSchema {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "pub struct {} {{", self.name)?;
         for property in &self.properties {
-            writeln!(f, "    pub {}: {},", property.name, property.field_type)?;
+            writeln!(f, "    {property},")?;
         }
         writeln!(f, "}}")
     }
@@ -102,16 +107,7 @@ impl ToTypeScript for EdgeSchema {
         let properties_str = self
             .properties
             .iter()
-            .map(|p| {
-                format!(
-                    "    {}: {}",
-                    p.name,
-                    match &p.field_type {
-                        GeneratedType::RustType(t) => t.to_ts(),
-                        _ => unreachable!(),
-                    }
-                )
-            })
+            .map(|p| format!("    {}", p.to_ts_member()))
             .collect::<Vec<_>>()
             .join(";");
 
@@ -122,14 +118,97 @@ impl ToTypeScript for EdgeSchema {
     }
 }
 
+#[derive(Clone)]
+pub struct EnumSchema {
+    pub name: String,
+    pub variants: Vec<String>,
+}
+impl Display for EnumSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = &self.name;
+        writeln!(f, "#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]")?;
+        writeln!(f, "pub enum {name} {{")?;
+        for variant in &self.variants {
+            writeln!(f, "    {variant},")?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f, "impl {name} {{")?;
+        writeln!(f, "    pub fn as_str(&self) -> &'static str {{")?;
+        writeln!(f, "        match self {{")?;
+        for variant in &self.variants {
+            writeln!(f, "            {name}::{variant} => \"{variant}\",")?;
+        }
+        writeln!(f, "        }}")?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "    pub fn from_value(value: &Value) -> Option<Self> {{")?;
+        writeln!(f, "        match value {{")?;
+        for variant in &self.variants {
+            writeln!(
+                f,
+                "            Value::String(s) if s == \"{variant}\" => Some({name}::{variant}),"
+            )?;
+        }
+        writeln!(f, "            _ => None,")?;
+        writeln!(f, "        }}")?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "}}")?;
+        // stored by variant name, which stays put when variants are reordered and is what
+        // readers outside HQL see
+        writeln!(f, "impl From<{name}> for Value {{")?;
+        writeln!(f, "    fn from(value: {name}) -> Self {{")?;
+        writeln!(f, "        Value::String(value.as_str().to_string())")?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "}}")?;
+        writeln!(f, "impl PartialEq<{name}> for Value {{")?;
+        writeln!(f, "    fn eq(&self, other: &{name}) -> bool {{")?;
+        writeln!(f, "        {name}::from_value(self) == Some(*other)")?;
+        writeln!(f, "    }}")?;
+        writeln!(f, "}}")
+    }
+}
+impl ToTypeScript for EnumSchema {
+    fn to_typescript(&self) -> String {
+        let variants = self
+            .variants
+            .iter()
+            .map(|v| format!("\"{v}\""))
+            .collect::<Vec<_>>()
+            .join(" | ");
+        format!("type {} = {};\n", self.name, variants)
+    }
+}
+
 #[derive(Clone)]
 pub struct SchemaProperty {
     pub name: String,
     pub field_type: GeneratedType,
     pub default_value: Option<GeneratedValue>,
-    // pub is_optional: bool,
+    pub is_optional: bool,
     pub is_index: FieldPrefix,
 }
+impl Display for SchemaProperty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.is_optional {
+            true => write!(f, "pub {}: Option<{}>", self.name, self.field_type),
+            false => write!(f, "pub {}: {}", self.name, self.field_type),
+        }
+    }
+}
+impl SchemaProperty {
+    /// The property as a TypeScript member, optional members marked with `?`
+    fn to_ts_member(&self) -> String {
+        let ts_type = match &self.field_type {
+            GeneratedType::RustType(t) => t.to_ts(),
+            // enums are the only named types allowed in schema fields
+            GeneratedType::Variable(name) => name.to_string(),
+            _ => unreachable!(),
+        };
+        match self.is_optional {
+            true => format!("{}?: {}", self.name, ts_type),
+            false => format!("{}: {}", self.name, ts_type),
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -149,12 +228,14 @@ mod tests {
                     name: "name".to_string(),
                     field_type: GeneratedType::RustType(RustType::String),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "age".to_string(),
                     field_type: GeneratedType::RustType(RustType::U32),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -187,12 +268,14 @@ mod tests {
                     name: "email".to_string(),
                     field_type: GeneratedType::RustType(RustType::String),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "active".to_string(),
                     field_type: GeneratedType::RustType(RustType::Bool),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -205,6 +288,43 @@ mod tests {
         assert!(output.contains("active: boolean;"));
     }
 
+    #[test]
+    fn test_node_schema_optional_property() {
+        let schema = NodeSchema {
+            name: "User".to_string(),
+            properties: vec![SchemaProperty {
+                name: "bio".to_string(),
+                field_type: GeneratedType::RustType(RustType::String),
+                default_value: None,
+                is_optional: true,
+                is_index: FieldPrefix::Empty,
+            }],
+        };
+
+        assert!(format!("{}", schema).contains("pub bio: Option<String>,"));
+        assert!(schema.to_typescript().contains("bio?: string;"));
+    }
+
+    #[test]
+    fn test_enum_schema_display_and_typescript() {
+        let schema = EnumSchema {
+            name: "Status".to_string(),
+            variants: vec!["Active".to_string(), "Suspended".to_string()],
+        };
+
+        let output = format!("{}", schema);
+        assert!(output.contains("pub enum Status {\n    Active,\n    Suspended,\n}"));
+        assert!(output.contains("Status::Suspended => \"Suspended\","));
+        assert!(output.contains("impl PartialEq<Status> for Value {"));
+        // stored and read back by variant name
+        assert!(output.contains("        Value::String(value.as_str().to_string())\n"));
+        assert!(output.contains(
+            "            Value::String(s) if s == \"Suspended\" => Some(Status::Suspended),\n"
+        ));
+        assert!(!output.contains("Value::U8"));
+        assert_eq!(schema.to_typescript(), "type Status = \"Active\" | \"Suspended\";\n");
+    }
+
     #[test]
     fn test_node_schema_with_numeric_types() {
         let schema = NodeSchema {
@@ -214,12 +334,14 @@ mod tests {
                     name: "count".to_string(),
                     field_type: GeneratedType::RustType(RustType::I32),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "score".to_string(),
                     field_type: GeneratedType::RustType(RustType::F64),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -244,6 +366,7 @@ mod tests {
                 name: "since".to_string(),
                 field_type: GeneratedType::RustType(RustType::U32),
                 default_value: None,
+                is_optional: false,
                 is_index: FieldPrefix::Empty,
             }],
         };
@@ -270,6 +393,24 @@ mod tests {
         assert!(output.contains("pub to: User,"));
     }
 
+    #[test]
+    fn test_edge_schema_union_endpoint() {
+        let schema = EdgeSchema {
+            name: "Tagged".to_string(),
+            from: "Post | Comment".to_string(),
+            to: "Tag".to_string(),
+            properties: vec![],
+        };
+
+        let output = format!("{}", schema);
+        let union = "pub enum TaggedFrom {\n    Post(Post),\n    Comment(Comment),\n}";
+        assert!(output.contains(union));
+        assert!(output.contains("pub from: TaggedFrom,"));
+        assert!(output.contains("pub to: Tag,"));
+        assert!(!output.contains("TaggedTo"));
+        assert!(schema.to_typescript().contains("from: Post | Comment;"));
+    }
+
     #[test]
     fn test_edge_schema_typescript_generation() {
         let schema = EdgeSchema {
@@ -280,6 +421,7 @@ mod tests {
                 name: "role".to_string(),
                 field_type: GeneratedType::RustType(RustType::String),
                 default_value: None,
+                is_optional: false,
                 is_index: FieldPrefix::Empty,
             }],
         };
@@ -302,12 +444,14 @@ mod tests {
                     name: "rating".to_string(),
                     field_type: GeneratedType::RustType(RustType::F32),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "comment".to_string(),
                     field_type: GeneratedType::RustType(RustType::String),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -330,6 +474,7 @@ mod tests {
                 name: "metadata".to_string(),
                 field_type: GeneratedType::RustType(RustType::String),
                 default_value: None,
+                is_optional: false,
                 is_index: FieldPrefix::Empty,
             }],
         };
@@ -360,12 +505,14 @@ mod tests {
                     name: "source".to_string(),
                     field_type: GeneratedType::RustType(RustType::String),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
                 SchemaProperty {
                     name: "chunk_index".to_string(),
                     field_type: GeneratedType::RustType(RustType::U32),
                     default_value: None,
+                    is_optional: false,
                     is_index: FieldPrefix::Empty,
                 },
             ],
@@ -387,6 +534,7 @@ mod tests {
                 name: "is_normalized".to_string(),
                 field_type: GeneratedType::RustType(RustType::Bool),
                 default_value: None,
+                is_optional: false,
                 is_index: FieldPrefix::Empty,
             }],
         };
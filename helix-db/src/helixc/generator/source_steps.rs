@@ -1,7 +1,10 @@
 use core::fmt;
 use std::fmt::Display;
 
-use crate::helixc::generator::utils::{VecData, write_properties, write_secondary_indices};
+use crate::helix_engine::traversal_core::ops::source::add_e::EdgeCardinality;
+use crate::helixc::generator::utils::{
+    VecData, write_properties, write_properties_slice, write_secondary_indices,
+};
 
 use super::{
     bool_ops::BoExp,
@@ -14,6 +17,8 @@ pub enum SourceStep {
     Identifier(GenRef<String>),
     /// Add a node
     AddN(AddN),
+    /// Find a node by an indexed property, adding it if there is none
+    UpsertN(UpsertN),
     /// Add an edge
     AddE(AddE),
     /// Insert a vector
@@ -22,6 +27,8 @@ pub enum SourceStep {
     NFromID(NFromID),
     /// Lookup a node by index
     NFromIndex(NFromIndex),
+    /// Lookup nodes by a range of index keys
+    NFromIndexRange(NFromIndexRange),
     /// Lookup a node by type
     NFromType(NFromType),
     /// Lookup an edge by ID
@@ -62,6 +69,34 @@ impl Display for AddN {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct UpsertN {
+    /// Label of node
+    pub label: GenRef<String>,
+    /// Indexed property the node is looked up by, and its value
+    pub key: (String, GeneratedValue),
+    /// Properties set on both new and existing nodes
+    pub properties: Option<Vec<(String, GeneratedValue)>>,
+    /// Default values, only set on new nodes
+    pub defaults: Option<Vec<(String, GeneratedValue)>>,
+    /// Names of properties to index on
+    pub secondary_indices: Option<Vec<String>>,
+}
+impl Display for UpsertN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "upsert_n({}, (\"{}\", Value::from({})), {}, {}, {})",
+            self.label,
+            self.key.0,
+            self.key.1,
+            write_properties_slice(&self.properties),
+            write_properties_slice(&self.defaults),
+            write_secondary_indices(&self.secondary_indices)
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AddE {
     /// Label of edge
@@ -76,6 +111,73 @@ pub struct AddE {
     pub from_is_plural: bool,
     /// Whether to is a plural variable (needs iteration)
     pub to_is_plural: bool,
+    /// Whether the edge type is ACYCLIC, in which case edges that would create a cycle are rejected
+    pub acyclic: bool,
+    /// Cardinality declared on the edge type
+    pub cardinality: EdgeCardinality,
+    /// Whether edges exceeding the cardinality are replaced (REPLACE) instead of rejected
+    pub replace_existing: bool,
+    /// Whether an existing edge between the nodes is updated instead of adding another one
+    pub upsert: bool,
+}
+impl AddE {
+    /// Call adding one edge between the given from and to node IDs
+    fn add_edge_call(&self, from: &dyn Display, to: &dyn Display) -> String {
+        if self.cardinality != EdgeCardinality::ManyToMany {
+            let constraints = format!(
+                "EdgeConstraints {{ acyclic: {}, cardinality: EdgeCardinality::{:?}, \
+                 replace: {} }}",
+                self.acyclic, self.cardinality, self.replace_existing
+            );
+            return match self.upsert {
+                true => format!(
+                    "upsert_constrained_e({}, {}, {}, {}, {})",
+                    self.label,
+                    write_properties_slice(&self.properties),
+                    from,
+                    to,
+                    constraints
+                ),
+                false => format!(
+                    "add_constrained_edge({}, {}, {}, {}, {})",
+                    self.label,
+                    write_properties(&self.properties),
+                    from,
+                    to,
+                    constraints
+                ),
+            };
+        }
+        if self.upsert {
+            return format!(
+                "{}({}, {}, {}, {})",
+                match self.acyclic {
+                    true => "upsert_acyclic_e",
+                    false => "upsert_e",
+                },
+                self.label,
+                write_properties_slice(&self.properties),
+                from,
+                to
+            );
+        }
+        match self.acyclic {
+            true => format!(
+                "add_acyclic_edge({}, {}, {}, {})",
+                self.label,
+                write_properties(&self.properties),
+                from,
+                to
+            ),
+            false => format!(
+                "add_edge({}, {}, {}, {}, false)",
+                self.label,
+                write_properties(&self.properties),
+                from,
+                to
+            ),
+        }
+    }
 }
 impl Display for AddE {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -83,46 +185,34 @@ impl Display for AddE {
         match (self.from_is_plural, self.to_is_plural) {
             (false, false) => {
                 // Both singular - from and to already have .id() appended
-                write!(
-                    f,
-                    "add_edge({}, {}, {}, {}, false)",
-                    self.label,
-                    write_properties(&self.properties),
-                    self.from,
-                    self.to
-                )
+                write!(f, "{}", self.add_edge_call(&self.from, &self.to))
             }
             (true, false) => {
                 // From is plural - iterate over from, to already has .id()
                 write!(
                     f,
-                    "{}.iter().map(|from_val| {{\n        G::new_mut(&db, &arena, &mut txn)\n        .add_edge({}, {}, from_val.id(), {}, false)\n        .collect_to_obj()\n    }}).collect::<Result<Vec<_>,_>>()?",
+                    "{}.iter().map(|from_val| {{\n        G::new_mut(&db, &arena, &mut txn)\n        .{}\n        .collect_to_obj()\n    }}).collect::<Result<Vec<_>,_>>()?",
                     self.from,
-                    self.label,
-                    write_properties(&self.properties),
-                    self.to
+                    self.add_edge_call(&"from_val.id()", &self.to)
                 )
             }
             (false, true) => {
                 // To is plural - iterate over to, from already has .id()
                 write!(
                     f,
-                    "{}.iter().map(|to_val| {{\n        G::new_mut(&db, &arena, &mut txn)\n        .add_edge({}, {}, {}, to_val.id(), false)\n        .collect_to_obj()\n    }}).collect::<Result<Vec<_>,_>>()?",
+                    "{}.iter().map(|to_val| {{\n        G::new_mut(&db, &arena, &mut txn)\n        .{}\n        .collect_to_obj()\n    }}).collect::<Result<Vec<_>,_>>()?",
                     self.to,
-                    self.label,
-                    write_properties(&self.properties),
-                    self.from
+                    self.add_edge_call(&self.from, &"to_val.id()")
                 )
             }
             (true, true) => {
                 // Both plural - nested iteration
                 write!(
                     f,
-                    "{}.iter().flat_map(|from_val| {{\n        {}.iter().map(move |to_val| {{\n            G::new_mut(&db, &arena, &mut txn)\n            .add_edge({}, {}, from_val.id(), to_val.id(), false)\n            .collect_to_obj()\n        }})\n    }}).collect::<Result<Vec<_>,_>>()?",
+                    "{}.iter().flat_map(|from_val| {{\n        {}.iter().map(move |to_val| {{\n            G::new_mut(&db, &arena, &mut txn)\n            .{}\n            .collect_to_obj()\n        }})\n    }}).collect::<Result<Vec<_>,_>>()?",
                     self.from,
                     self.to,
-                    self.label,
-                    write_properties(&self.properties)
+                    self.add_edge_call(&"from_val.id()", &"to_val.id()")
                 )
             }
         }
@@ -168,10 +258,15 @@ impl Display for NFromID {
 pub struct NFromType {
     /// Label of nodes to lookup
     pub label: GenRef<String>,
+    /// Cursor to resume after when the traversal is paged
+    pub resume: Option<PageCursor>,
 }
 impl Display for NFromType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "n_from_type({})", self.label)
+        match &self.resume {
+            Some(resume) => write!(f, "n_from_type_after({}, {resume})", self.label),
+            None => write!(f, "n_from_type({})", self.label),
+        }
     }
 }
 
@@ -194,10 +289,35 @@ impl Display for EFromID {
 pub struct EFromType {
     /// Label of edges to lookup
     pub label: GenRef<String>,
+    /// Cursor to resume after when the traversal is paged
+    pub resume: Option<PageCursor>,
 }
 impl Display for EFromType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "e_from_type({})", self.label)
+        match &self.resume {
+            Some(resume) => write!(f, "e_from_type_after({}, {resume})", self.label),
+            None => write!(f, "e_from_type({})", self.label),
+        }
+    }
+}
+
+/// The key a paged source resumes after, decoded from the cursor of the previous page
+#[derive(Clone, Debug)]
+pub struct PageCursor {
+    /// `&str` of the cursor parameter
+    pub cursor: GeneratedValue,
+    /// Variant of `CursorSource` the cursor resumes
+    pub source: &'static str,
+    /// Label the source iterates
+    pub label: GenRef<String>,
+}
+impl Display for PageCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cursor::resume({}, CursorSource::{}, {})?",
+            self.cursor, self.source, self.label
+        )
     }
 }
 
@@ -257,10 +377,12 @@ impl Display for SourceStep {
         match self {
             SourceStep::Identifier(_) => write!(f, ""),
             SourceStep::AddN(add_n) => write!(f, "{add_n}"),
+            SourceStep::UpsertN(upsert_n) => write!(f, "{upsert_n}"),
             SourceStep::AddE(add_e) => write!(f, "{add_e}"),
             SourceStep::AddV(add_v) => write!(f, "{add_v}"),
             SourceStep::NFromID(n_from_id) => write!(f, "{n_from_id}"),
             SourceStep::NFromIndex(n_from_index) => write!(f, "{n_from_index}"),
+            SourceStep::NFromIndexRange(n_from_index_range) => write!(f, "{n_from_index_range}"),
             SourceStep::NFromType(n_from_type) => write!(f, "{n_from_type}"),
             SourceStep::EFromID(e_from_id) => write!(f, "{e_from_id}"),
             SourceStep::EFromType(e_from_type) => write!(f, "{e_from_type}"),
@@ -318,14 +440,44 @@ pub struct NFromIndex {
     pub key: GeneratedValue,
     /// Label of nodes to lookup - used for post filtering
     pub label: GenRef<String>,
+    /// Cursor to resume after when the traversal is paged
+    pub resume: Option<PageCursor>,
 }
 
 impl Display for NFromIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.resume {
+            Some(resume) => write!(
+                f,
+                "n_from_index_after({}, {}, {}, {resume})",
+                self.label, self.index, self.key
+            ),
+            None => write!(
+                f,
+                "n_from_index({}, {}, {})",
+                self.label, self.index, self.key
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NFromIndexRange {
+    /// Index to scan
+    pub index: GenRef<String>,
+    /// Rendered lower and upper `Bound<Value>` of the keys
+    pub lower: String,
+    pub upper: String,
+    /// Label of nodes to lookup - used for post filtering
+    pub label: GenRef<String>,
+}
+
+impl Display for NFromIndexRange {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "n_from_index({}, {}, {})",
-            self.label, self.index, self.key
+            "n_from_index_range({}, {}, {}, {})",
+            self.label, self.index, self.lower, self.upper
         )
     }
 }
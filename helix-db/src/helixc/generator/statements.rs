@@ -11,6 +11,7 @@ pub enum Statement {
     Drop(Drop),
     Traversal(Traversal),
     ForEach(ForEach),
+    IfElse(IfElse),
     Literal(GenRef<String>),
     Identifier(GenRef<String>),
     BoExp(BoExp),
@@ -24,6 +25,7 @@ impl Display for Statement {
             Statement::Drop(drop) => write!(f, "{drop}"),
             Statement::Traversal(traversal) => write!(f, "{traversal}"),
             Statement::ForEach(foreach) => write!(f, "{foreach}"),
+            Statement::IfElse(if_else) => write!(f, "{if_else}"),
             Statement::Literal(literal) => write!(f, "{literal}"),
             Statement::Identifier(identifier) => write!(f, "{identifier}"),
             Statement::BoExp(bo) => write!(f, "{bo}"),
@@ -48,7 +50,15 @@ pub struct Assignment {
 }
 impl Display for Assignment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "let {} = {}", self.variable, *self.value)
+        write!(f, "let {} = {}", self.variable, *self.value)?;
+        // the cursor of a paged traversal is returned alongside it
+        if let Statement::Traversal(traversal) = &*self.value
+            && let Some(page) = &traversal.page
+        {
+            let variable = self.variable.inner();
+            write!(f, ";\n    let {variable}_cursor = {}", page.next_cursor(variable))?;
+        }
+        Ok(())
     }
 }
 
@@ -89,6 +99,28 @@ impl Display for ForEach {
     }
 }
 
+#[derive(Clone)]
+pub struct IfElse {
+    pub condition: Box<Statement>,
+    pub then_statements: Vec<Statement>,
+    pub else_statements: Vec<Statement>,
+}
+impl Display for IfElse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "if {} {{", self.condition)?;
+        for statement in &self.then_statements {
+            writeln!(f, "    {statement};")?;
+        }
+        if !self.else_statements.is_empty() {
+            writeln!(f, "}} else {{")?;
+            for statement in &self.else_statements {
+                writeln!(f, "    {statement};")?;
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
 #[derive(Clone)]
 pub enum ForVariable {
     ObjectDestructure(Vec<GenRef<String>>),
@@ -226,5 +258,31 @@ mod tests {
         let var = ForLoopInVariable::Empty;
         assert_eq!(var.inner(), "");
     }
-}
 
+    // ============================================================================
+    // IfElse Tests
+    // ============================================================================
+
+    #[test]
+    fn test_if_without_else() {
+        let if_else = Statement::IfElse(IfElse {
+            condition: Box::new(Statement::Identifier(GenRef::Std("flag".to_string()))),
+            then_statements: vec![Statement::Identifier(GenRef::Std("a".to_string()))],
+            else_statements: vec![],
+        });
+        assert_eq!(format!("{}", if_else), "if flag {\n    a;\n}");
+    }
+
+    #[test]
+    fn test_if_with_else() {
+        let if_else = Statement::IfElse(IfElse {
+            condition: Box::new(Statement::Identifier(GenRef::Std("flag".to_string()))),
+            then_statements: vec![Statement::Identifier(GenRef::Std("a".to_string()))],
+            else_statements: vec![Statement::Identifier(GenRef::Std("b".to_string()))],
+        });
+        assert_eq!(
+            format!("{}", if_else),
+            "if flag {\n    a;\n} else {\n    b;\n}"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use crate::helixc::{
+    generator::utils::{GenRef, GeneratedValue},
+    parser::types::StringFunction,
+};
+
+/// Generated string expression
+///
+/// Every variant renders to an owned `Value`, so string functions can be nested freely
+/// and the result can be compared, stored by `UPDATE` or returned from a remapping.
+#[derive(Debug, Clone)]
+pub enum StringExpr {
+    FunctionCall(StringFunctionCallGen),
+    /// A string or integer literal
+    Literal(GenRef<String>),
+    /// A parameter or variable in scope
+    Value(GeneratedValue),
+    /// A property of the item being remapped (`_::{property}`)
+    Property(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct StringFunctionCallGen {
+    pub function: StringFunction,
+    pub args: Vec<StringExpr>,
+}
+
+impl StringExpr {
+    /// Renders the expression, reading properties from `item`
+    pub fn render(&self, item: &str) -> String {
+        match self {
+            StringExpr::FunctionCall(call) => call.render(item),
+            StringExpr::Literal(lit) => format!("Value::from({lit})"),
+            StringExpr::Value(value) => format!("Value::from({value})"),
+            StringExpr::Property(prop) => match prop.as_str() {
+                "id" | "ID" => format!("Value::from(uuid_str({item}.id(), &arena))"),
+                "label" | "Label" => format!("Value::from({item}.label())"),
+                _ => format!("{item}.get_property(\"{prop}\").cloned().unwrap_or_default()"),
+            },
+        }
+    }
+}
+
+impl StringFunctionCallGen {
+    /// Renders the call as an expression producing a `Value`, reading properties from `item`
+    pub fn render(&self, item: &str) -> String {
+        let args = self
+            .args
+            .iter()
+            .map(|arg| arg.render(item))
+            .collect::<Vec<_>>();
+        match self.function {
+            StringFunction::Lower => format!("{}.to_lowercase()", args[0]),
+            StringFunction::Upper => format!("{}.to_uppercase()", args[0]),
+            StringFunction::Trim => format!("{}.trim()", args[0]),
+            StringFunction::Length => format!("{}.length()", args[0]),
+            StringFunction::Substring => match args.get(2) {
+                Some(len) => format!("{}.substring(&{}, Some(&{len}))", args[0], args[1]),
+                None => format!("{}.substring(&{}, None)", args[0], args[1]),
+            },
+            StringFunction::Concat => format!("Value::concat(&[{}])", args.join(", ")),
+        }
+    }
+
+    /// Whether any argument reads a property of the current item
+    pub fn reads_properties(&self) -> bool {
+        self.args.iter().any(|arg| match arg {
+            StringExpr::FunctionCall(call) => call.reads_properties(),
+            StringExpr::Property(_) => true,
+            StringExpr::Literal(_) | StringExpr::Value(_) => false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_nested_call() {
+        let call = StringFunctionCallGen {
+            function: StringFunction::Concat,
+            args: vec![
+                StringExpr::FunctionCall(StringFunctionCallGen {
+                    function: StringFunction::Upper,
+                    args: vec![StringExpr::Property("name".to_string())],
+                }),
+                StringExpr::Literal(GenRef::Literal("-".to_string())),
+                StringExpr::Value(GeneratedValue::Parameter(GenRef::Std(
+                    "data.suffix.clone()".to_string(),
+                ))),
+            ],
+        };
+        assert_eq!(
+            call.render("user"),
+            "Value::concat(&[user.get_property(\"name\").cloned().unwrap_or_default().to_uppercase(), Value::from(\"-\"), Value::from(data.suffix.clone())])"
+        );
+        assert!(call.reads_properties());
+    }
+
+    #[test]
+    fn test_render_substring() {
+        let call = StringFunctionCallGen {
+            function: StringFunction::Substring,
+            args: vec![
+                StringExpr::Value(GeneratedValue::Parameter(GenRef::Std(
+                    "data.text.clone()".to_string(),
+                ))),
+                StringExpr::Literal(GenRef::Std("0".to_string())),
+                StringExpr::Literal(GenRef::Std("3".to_string())),
+            ],
+        };
+        assert_eq!(
+            call.render("val"),
+            "Value::from(data.text.clone()).substring(&Value::from(0), Some(&Value::from(3)))"
+        );
+        assert!(!call.reads_properties());
+    }
+}
@@ -5,7 +5,8 @@ use crate::helixc::{
 
 use super::{
     bool_ops::{BoExp, BoolOp},
-    source_steps::SourceStep,
+    source_steps::{PageCursor, SourceStep},
+    return_values::ComputedField,
     utils::{GenRef, GeneratedValue, Order, Separator},
 };
 use core::fmt;
@@ -93,7 +94,12 @@ pub struct Traversal {
     pub has_spread: bool,
     pub excluded_fields: Vec<String>,
     pub nested_traversals: std::collections::HashMap<String, NestedTraversalInfo>,
+    pub computed_fields: std::collections::HashMap<String, ComputedField>,
     pub is_reused_variable: bool,
+    /// Whether the items carry the score of a search or centrality step
+    pub is_scored: bool,
+    /// Set when the traversal ends with `PAGE`
+    pub page: Option<Page>,
 }
 
 impl Display for Traversal {
@@ -175,7 +181,10 @@ impl Default for Traversal {
             has_spread: false,
             excluded_fields: vec![],
             nested_traversals: std::collections::HashMap::new(),
+            computed_fields: std::collections::HashMap::new(),
             is_reused_variable: false,
+            is_scored: false,
+            page: None,
         }
     }
 }
@@ -253,6 +262,11 @@ pub enum Step {
     // rerankers
     RerankRRF(RerankRRF),
     RerankMMR(RerankMMR),
+
+    // degree and centrality
+    Centrality(Centrality),
+    EgoGraph(EgoGraph),
+    Dag(Dag),
 }
 impl Display for Step {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -295,6 +309,9 @@ impl Display for Step {
             Step::AggregateBy(aggregate_by) => write!(f, "{aggregate_by}"),
             Step::RerankRRF(rerank_rrf) => write!(f, "{rerank_rrf}"),
             Step::RerankMMR(rerank_mmr) => write!(f, "{rerank_mmr}"),
+            Step::Centrality(centrality) => write!(f, "{centrality}"),
+            Step::EgoGraph(ego_graph) => write!(f, "{ego_graph}"),
+            Step::Dag(dag) => write!(f, "{dag}"),
         }
     }
 }
@@ -326,6 +343,9 @@ impl Debug for Step {
             Step::AggregateBy(_) => write!(f, "AggregateBy"),
             Step::RerankRRF(_) => write!(f, "RerankRRF"),
             Step::RerankMMR(_) => write!(f, "RerankMMR"),
+            Step::Centrality(_) => write!(f, "Centrality"),
+            Step::EgoGraph(_) => write!(f, "EgoGraph"),
+            Step::Dag(_) => write!(f, "Dag"),
         }
     }
 }
@@ -398,20 +418,30 @@ impl Display for In {
 #[derive(Clone)]
 pub struct OutE {
     pub label: GenRef<String>,
+    /// Cursor to resume after when the edges of a single node are paged
+    pub resume: Option<PageCursor>,
 }
 impl Display for OutE {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "out_e({})", self.label)
+        match &self.resume {
+            Some(resume) => write!(f, "out_e_after({}, {resume})", self.label),
+            None => write!(f, "out_e({})", self.label),
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct InE {
     pub label: GenRef<String>,
+    /// Cursor to resume after when the edges of a single node are paged
+    pub resume: Option<PageCursor>,
 }
 impl Display for InE {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "in_e({})", self.label)
+        match &self.resume {
+            Some(resume) => write!(f, "in_e_after({}, {resume})", self.label),
+            None => write!(f, "in_e({})", self.label),
+        }
     }
 }
 
@@ -475,6 +505,15 @@ impl Display for WhereRef {
                         BoolOp::Neq(neq) => format!("{} != {}", value_expr, neq.right),
                         BoolOp::Contains(contains) => format!("{}{}", value_expr, contains),
                         BoolOp::IsIn(is_in) => format!("{}{}", value_expr, is_in),
+                        // reserved properties are always set
+                        BoolOp::IsNull => "false".to_string(),
+                        BoolOp::IsNotNull => "true".to_string(),
+                        BoolOp::StartsWith(starts_with) => format!("{}{}", value_expr, starts_with),
+                        BoolOp::EndsWith(ends_with) => format!("{}{}", value_expr, ends_with),
+                        BoolOp::EqIgnoreCase(eq_ignore_case) => {
+                            format!("{}{}", value_expr, eq_ignore_case)
+                        }
+                        BoolOp::Regex(regex) => format!("{}{}", value_expr, regex),
                     };
                     return write!(
                         f,
@@ -491,28 +530,20 @@ impl Display for WhereRef {
 
                 // Handle PropertyFetch with BoolOp - use get_property
                 if let (Some(prop), Some(bool_op)) = (prop, bool_op) {
-                    let bool_expr = match bool_op {
-                        BoolOp::Gt(gt) => format!("{gt}"),
-                        BoolOp::Gte(gte) => format!("{gte}"),
-                        BoolOp::Lt(lt) => format!("{lt}"),
-                        BoolOp::Lte(lte) => format!("{lte}"),
-                        BoolOp::Eq(eq) => format!("{eq}"),
-                        BoolOp::Neq(neq) => format!("{neq}"),
-                        BoolOp::Contains(contains) => format!("v{contains}"),
-                        BoolOp::IsIn(is_in) => format!("v{is_in}"),
-                    };
                     return write!(
                         f,
                         "filter_ref(|val, txn|{{
                 if let Ok(val) = val {{
                     Ok(val
                     .get_property({})
-                    .map_or(false, |v| {}))
+                    .map_or({}, |v| {}))
                 }} else {{
                     Ok(false)
                 }}
             }})",
-                        prop, bool_expr
+                        prop,
+                        bool_op.if_missing(),
+                        bool_op.check()
                     );
                 }
             }
@@ -544,17 +575,69 @@ impl Display for Range {
     }
 }
 
+/// A traversal paged with `PAGE`. The source resumes after `cursor` and a range step keeps the
+/// first `size` items, so only the cursor of the next page is left to generate.
+#[derive(Clone, Debug)]
+pub struct Page {
+    pub size: GeneratedValue,
+    pub cursor: PageCursor,
+}
+impl Page {
+    /// The cursor of the page after the one collected into `variable`
+    pub fn next_cursor(&self, variable: &str) -> String {
+        format!(
+            "Cursor::next_page(&{variable}, {}, CursorSource::{}, {})?",
+            self.size, self.cursor.source, self.cursor.label
+        )
+    }
+}
+
 #[derive(Clone)]
 pub struct OrderBy {
+    pub keys: Vec<OrderKey>,
+    /// Set when the order is followed by a range, so only the first `limit` items are kept.
+    pub limit: Option<GeneratedValue>,
+}
+impl Display for OrderBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys = self
+            .keys
+            .iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match (&self.limit, self.keys.as_slice()) {
+            (Some(limit), _) => write!(f, "order_by_top_k(&[{keys}], {limit})"),
+            (None, [key]) if !key.nulls_first && key.computed.is_none() => match key.order {
+                Order::Asc => write!(f, "order_by_asc({})", key.property),
+                Order::Desc => write!(f, "order_by_desc({})", key.property),
+            },
+            (None, _) => write!(f, "order_by(&[{keys}])"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OrderKey {
     pub property: GenRef<String>,
     pub order: Order,
+    pub nulls_first: bool,
+    /// Rendered expression over `val` the items are ordered on instead of the property
+    pub computed: Option<String>,
 }
-impl Display for OrderBy {
+impl Display for OrderKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.order {
-            Order::Asc => write!(f, "order_by_asc({})", self.property),
-            Order::Desc => write!(f, "order_by_desc({})", self.property),
+            Order::Asc => write!(f, "OrderKey::asc({})", self.property)?,
+            Order::Desc => write!(f, "OrderKey::desc({})", self.property)?,
+        }
+        if let Some(computed) = &self.computed {
+            write!(f, ".by(|val| {computed})")?;
         }
+        if self.nulls_first {
+            write!(f, ".nulls_first()")?;
+        }
+        Ok(())
     }
 }
 
@@ -562,18 +645,47 @@ impl Display for OrderBy {
 pub struct GroupBy {
     pub should_count: bool,
     pub properties: Vec<GenRef<String>>,
+    /// Keys computed per item, as names and rendered expressions over `val`
+    pub computed: Vec<(String, String)>,
+    pub aggregations: Vec<Aggregation>,
 }
 impl Display for GroupBy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.computed.is_empty() {
+            let keys = self
+                .properties
+                .iter()
+                .map(|p| format!("GroupKey::property(&{p})"))
+                .chain(self.computed.iter().map(|(name, computed)| {
+                    format!("GroupKey::computed(\"{name}\", |val| {computed})")
+                }))
+                .collect::<Vec<_>>();
+            return write!(
+                f,
+                "group_by_keys(&[{}], {}, &[{}])",
+                keys.join(", "),
+                self.should_count,
+                self.aggregations
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
         write!(
             f,
-            "group_by(&[{}], {})",
+            "group_by(&[{}], {}, &[{}])",
             self.properties
                 .iter()
                 .map(|s| s.to_string())
                 .collect::<Vec<_>>()
                 .join(","),
-            self.should_count
+            self.should_count,
+            self.aggregations
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
         )
     }
 }
@@ -582,22 +694,66 @@ impl Display for GroupBy {
 pub struct AggregateBy {
     pub should_count: bool,
     pub properties: Vec<GenRef<String>>,
+    pub aggregations: Vec<Aggregation>,
 }
 impl Display for AggregateBy {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "aggregate_by(&[{}], {})",
+            "aggregate_by(&[{}], {}, &[{}])",
             self.properties
                 .iter()
                 .map(|s| s.to_string())
                 .collect::<Vec<_>>()
                 .join(","),
-            self.should_count
+            self.should_count,
+            self.aggregations
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
         )
     }
 }
 
+#[derive(Clone)]
+pub struct Aggregation {
+    pub name: String,
+    pub kind: AggregationKind,
+    pub property: String,
+}
+impl Display for Aggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Aggregation {{ name: \"{}\", kind: AggregationKind::{}, property: \"{}\" }}",
+            self.name, self.kind, self.property
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum AggregationKind {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    CountDistinct,
+    Collect,
+}
+impl Display for AggregationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregationKind::Sum => write!(f, "Sum"),
+            AggregationKind::Avg => write!(f, "Avg"),
+            AggregationKind::Min => write!(f, "Min"),
+            AggregationKind::Max => write!(f, "Max"),
+            AggregationKind::CountDistinct => write!(f, "CountDistinct"),
+            AggregationKind::Collect => write!(f, "Collect"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ShortestPath {
     pub label: Option<GenRef<String>>,
@@ -861,3 +1017,77 @@ impl Display for RerankMMR {
         }
     }
 }
+
+#[derive(Clone)]
+pub enum CentralityKind {
+    OutDegree,
+    InDegree,
+    Degree,
+    TriangleCount,
+    Betweenness(Option<GeneratedValue>),
+}
+
+#[derive(Clone)]
+pub struct Centrality {
+    pub label: GenRef<String>,
+    pub kind: CentralityKind,
+}
+impl Display for Centrality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CentralityKind::OutDegree => write!(f, "out_degree_to_val({})?", self.label),
+            CentralityKind::InDegree => write!(f, "in_degree_to_val({})?", self.label),
+            CentralityKind::Degree => write!(f, "degree_to_val({})?", self.label),
+            CentralityKind::TriangleCount => write!(f, "triangle_count_to_val({})?", self.label),
+            CentralityKind::Betweenness(Some(samples)) => {
+                write!(f, "betweenness({}, Some({samples} as usize))", self.label)
+            }
+            CentralityKind::Betweenness(None) => write!(f, "betweenness({}, None)", self.label),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EgoGraph {
+    pub labels: Vec<GenRef<String>>,
+    pub hops: GeneratedValue,
+    pub max_nodes: Option<GeneratedValue>,
+}
+impl Display for EgoGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = self
+            .labels
+            .iter()
+            .map(|label| label.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match &self.max_nodes {
+            Some(max_nodes) => write!(
+                f,
+                "ego_graph(&[{labels}], {} as usize, Some({max_nodes} as usize))",
+                self.hops
+            ),
+            None => write!(f, "ego_graph(&[{labels}], {} as usize, None)", self.hops),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum DagKind {
+    TopoSort,
+    HasCycle,
+}
+
+#[derive(Clone)]
+pub struct Dag {
+    pub label: GenRef<String>,
+    pub kind: DagKind,
+}
+impl Display for Dag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            DagKind::TopoSort => write!(f, "topological_sort({})", self.label),
+            DagKind::HasCycle => write!(f, "has_cycle({})", self.label),
+        }
+    }
+}
@@ -26,7 +26,7 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            GenRef::Literal(t) => write!(f, "\"{t}\""),
+            GenRef::Literal(t) => write!(f, "\"{}\"", t.to_string().escape_debug()),
             GenRef::Std(t) => write!(f, "{t}"),
             GenRef::Mut(t) => write!(f, "mut {t}"),
             GenRef::Ref(t) => write!(f, "&{t}"),
@@ -98,7 +98,7 @@ where
 impl From<GenRef<String>> for String {
     fn from(value: GenRef<String>) -> Self {
         match value {
-            GenRef::Literal(s) => format!("\"{s}\""),
+            GenRef::Literal(s) => format!("\"{}\"", s.escape_debug()),
             GenRef::Std(s) => format!("\"{s}\""),
             GenRef::Ref(s) => format!("\"{s}\""),
             GenRef::Id(s) => s, // Identifiers don't need quotes
@@ -217,7 +217,7 @@ pub fn write_properties_slice(properties: &Option<Vec<(String, GeneratedValue)>>
                     .join(", ")
             )
         }
-        None => unreachable!(),
+        None => "&[]".to_string(),
     }
 }
 
@@ -412,6 +412,16 @@ impl<T: Display> Separator<T> {
             Separator::Empty(t) => t,
         }
     }
+
+    pub fn inner_mut(&mut self) -> &mut T {
+        match self {
+            Separator::Comma(t) => t,
+            Separator::Semicolon(t) => t,
+            Separator::Period(t) => t,
+            Separator::Newline(t) => t,
+            Separator::Empty(t) => t,
+        }
+    }
 }
 pub fn write_headers() -> String {
     r#"
@@ -426,7 +436,7 @@ pub fn write_headers() -> String {
 
 use bumpalo::Bump;
 use heed3::RoTxn;
-use helix_macros::{handler, tool_call, mcp_handler, migration};
+use helix_macros::{handler, batch_handler, tool_call, mcp_handler, migration};
 use helix_db::{
     helix_engine::{
         reranker::{
@@ -434,7 +444,7 @@ use helix_db::{
             fusion::{RRFReranker, MMRReranker, DistanceMethod},
         },
         traversal_core::{
-            config::{Config, GraphConfig, VectorConfig},
+            config::{ChangeLogConfig, Config, GraphConfig, VectorConfig},
             ops::{
                 bm25::search_bm25::SearchBM25Adapter,
                 g::G,
@@ -443,21 +453,26 @@ use helix_db::{
                     from_n::FromNAdapter, from_v::FromVAdapter, out::OutAdapter, out_e::OutEdgesAdapter,
                 },
                 source::{
-                    add_e::AddEAdapter,
+                    add_e::{AddEAdapter, EdgeCardinality, EdgeConstraints},
                     add_n::AddNAdapter,
                     e_from_id::EFromIdAdapter,
                     e_from_type::EFromTypeAdapter,
                     n_from_id::NFromIdAdapter,
                     n_from_index::NFromIndexAdapter,
+                    n_from_index_range::NFromIndexRangeAdapter,
                     n_from_type::NFromTypeAdapter,
+                    upsert_e::UpsertEAdapter,
+                    upsert_n::UpsertNAdapter,
                     v_from_id::VFromIdAdapter,
                     v_from_type::VFromTypeAdapter
                 },
                 util::{
                     dedup::DedupAdapter, drop::Drop, exist::Exist, filter_mut::FilterMut,
                     filter_ref::FilterRefAdapter, map::MapAdapter, paths::{PathAlgorithm, ShortestPathAdapter},
-                    range::RangeAdapter, update::UpdateAdapter, order::OrderByAdapter,
-                    aggregate::AggregateAdapter, group_by::GroupByAdapter, count::CountAdapter,
+                    range::RangeAdapter, update::UpdateAdapter, order::{OrderByAdapter, OrderKey},
+                    aggregate::AggregateAdapter, group_by::{GroupByAdapter, GroupKey}, count::CountAdapter,
+                    degree::DegreeAdapter, centrality::CentralityAdapter, subgraph::SubgraphAdapter,
+                    dag::DagAdapter, page::{Cursor, CursorSource},
                 },
                 vectors::{
                     brute_force_search::BruteForceSearchVAdapter, insert::InsertVAdapter,
@@ -472,6 +487,7 @@ use helix_db::{
     helix_gateway::{
         embedding_providers::{EmbeddingModel, get_embedding_model},
         router::router::{HandlerInput, IoContFn},
+        batch::{BatchInput, batch_output, batch_params},
         mcp::mcp::{MCPHandlerSubmission, MCPToolInput, MCPHandler}
     },
     node_matches, props, embed, embed_async,
@@ -479,9 +495,12 @@ use helix_db::{
     protocol::{
         response::Response,
         value::{casting::{cast, CastType}, Value},
+        date::DateUnit,
         format::Format,
+        stream::ResponseStream,
     },
     utils::{
+        aggregate::{Aggregation, AggregationKind},
         id::{ID, uuid_str},
         items::{Edge, Node},
         properties::ImmutablePropertiesMap,
@@ -489,6 +508,7 @@ use helix_db::{
 };
 use sonic_rs::{Deserialize, Serialize, json};
 use std::collections::{HashMap, HashSet};
+use std::ops::Bound;
 use std::sync::Arc;
 use std::time::Instant;
 use chrono::{DateTime, Utc};
@@ -523,6 +543,12 @@ mod tests {
         assert_eq!(format!("{}", genref), "\"test\"");
     }
 
+    #[test]
+    fn test_genref_literal_display_escapes() {
+        let genref = GenRef::Literal("say \"hi\"\n\\".to_string());
+        assert_eq!(format!("{}", genref), r#""say \"hi\"\n\\""#);
+    }
+
     #[test]
     fn test_genref_std_display() {
         let genref = GenRef::Std("variable".to_string());
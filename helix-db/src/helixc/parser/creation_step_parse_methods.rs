@@ -1,8 +1,10 @@
 use crate::helixc::parser::{
     HelixParser, ParserError, Rule,
     location::HasLoc,
-    types::{AddEdge, AddNode, AddVector, Embed, EvaluatesToString, VectorData},
-    utils::PairTools,
+    types::{
+        AddEdge, AddNode, AddVector, Embed, EvaluatesToString, UpsertEdge, UpsertNode, VectorData,
+    },
+    utils::{PairTools, PairsTools},
 };
 use pest::iterators::Pair;
 
@@ -148,11 +150,53 @@ impl HelixParser {
             loc: pair.loc(),
         })
     }
+
+    pub(super) fn parse_upsert_node(&self, pair: Pair<Rule>) -> Result<UpsertNode, ParserError> {
+        let loc = pair.loc();
+        let mut pairs = pair.into_inner();
+        let node_type = pairs.try_next()?.as_str().to_string();
+        let key_pair = pairs.try_next()?;
+        let key_loc = key_pair.loc();
+        let mut fields = self.parse_property_assignments(key_pair)?;
+        if fields.len() != 1 {
+            return Err(ParserError::from(format!(
+                "UpsertN must be keyed on a single field, got {} at line {} column {}",
+                fields.len(),
+                key_loc.start.line,
+                key_loc.start.column,
+            )));
+        }
+        let key = fields.keys().next().cloned().unwrap_or_default();
+        if let Some(p) = pairs.next() {
+            for (name, value) in self.parse_property_assignments(p)? {
+                fields.entry(name).or_insert(value);
+            }
+        }
+        Ok(UpsertNode {
+            key: (key_loc, key),
+            node: AddNode {
+                loc: loc.clone(),
+                node_type: Some(node_type),
+                fields: Some(fields),
+            },
+            loc,
+        })
+    }
+
+    pub(super) fn parse_upsert_edge(&self, pair: Pair<Rule>) -> Result<UpsertEdge, ParserError> {
+        Ok(UpsertEdge {
+            loc: pair.loc(),
+            edge: self.parse_add_edge(pair, false)?,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::helixc::parser::{write_to_temp_file, HelixParser};
+    use crate::helixc::parser::{
+        types::{ExpressionType, StatementType},
+        write_to_temp_file, HelixParser,
+    };
 
     // ============================================================================
     // AddNode Tests
@@ -462,4 +506,69 @@ mod tests {
         let result = HelixParser::parse_source(&content);
         assert!(result.is_ok());
     }
+
+    // ============================================================================
+    // Upsert Tests
+    // ============================================================================
+
+    #[test]
+    fn test_parse_upsert_node() {
+        let source = r#"
+            N::Person { INDEX email: String, name: String }
+
+            QUERY upsertPerson(email: String, name: String) =>
+                person <- UpsertN<Person>({email: email}, {name: name})
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let StatementType::Assignment(assignment) = &parsed.queries[0].statements[0].statement
+        else {
+            panic!("expected an assignment");
+        };
+        let ExpressionType::UpsertNode(upsert) = &assignment.value.expr else {
+            panic!("expected UpsertN");
+        };
+        assert_eq!(upsert.key.1, "email");
+        let fields = upsert.node.fields.as_ref().unwrap();
+        assert!(fields.contains_key("email"));
+        assert!(fields.contains_key("name"));
+    }
+
+    #[test]
+    fn test_parse_upsert_node_with_multiple_key_fields() {
+        let source = r#"
+            N::Person { INDEX email: String, name: String }
+
+            QUERY upsertPerson(email: String, name: String) =>
+                person <- UpsertN<Person>({email: email, name: name})
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_upsert_edge() {
+        let source = r#"
+            N::Person { name: String }
+            E::Knows { From: Person, To: Person, Properties: { since: String } }
+
+            QUERY knows(id1: ID, id2: ID, since: String) =>
+                person1 <- N<Person>(id1)
+                person2 <- N<Person>(id2)
+                UpsertE<Knows>({since: since})::From(person1)::To(person2)
+                RETURN "done"
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+        let StatementType::Expression(expr) = &parsed.queries[0].statements[2].statement else {
+            panic!("expected an expression statement");
+        };
+        assert!(matches!(expr.expr, ExpressionType::UpsertEdge(_)));
+    }
 }
@@ -5,7 +5,8 @@ use crate::{
         types::{
             Assignment, BM25Search, Embed, EvaluatesToNumber, EvaluatesToNumberType,
             EvaluatesToString, ExistsExpression, Expression, ExpressionType, ForLoop, ForLoopVars,
-            MathFunction, MathFunctionCall, SearchVector, ValueType, VectorData,
+            MathFunction, MathFunctionCall, SearchVector, StringFunction, StringFunctionCall,
+            ValueType, VectorData, ComputedPredicate, DateFunction, DateFunctionCall,
         },
         utils::{PairTools, PairsTools},
     },
@@ -125,6 +126,14 @@ impl HelixParser {
                 loc: pair.loc(),
                 expr: ExpressionType::AddEdge(self.parse_add_edge(pair, false)?),
             }),
+            Rule::UpsertN => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::UpsertNode(self.parse_upsert_node(pair)?),
+            }),
+            Rule::UpsertE => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::UpsertEdge(self.parse_upsert_edge(pair)?),
+            }),
             Rule::search_vector => Ok(Expression {
                 loc: pair.loc(),
                 expr: ExpressionType::SearchVector(self.parse_search_vector(pair)?),
@@ -141,6 +150,22 @@ impl HelixParser {
                 loc: pair.loc(),
                 expr: ExpressionType::MathFunctionCall(self.parse_math_function_call(pair)?),
             }),
+            Rule::string_function_call => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::StringFunctionCall(self.parse_string_function_call(pair)?),
+            }),
+            Rule::date_function_call => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::DateFunctionCall(self.parse_date_function_call(pair)?),
+            }),
+            Rule::now => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::Now,
+            }),
+            Rule::duration => Ok(Expression {
+                loc: pair.loc(),
+                expr: ExpressionType::Duration(self.parse_duration(pair)?),
+            }),
             _ => Err(ParserError::from(format!(
                 "Unexpected expression type: {:?}",
                 pair.as_rule()
@@ -238,7 +263,38 @@ impl HelixParser {
                     },
                 })
             }
-
+            Rule::traversal | Rule::id_traversal => Ok(Expression {
+                loc: expression.loc(),
+                expr: ExpressionType::Traversal(Box::new(self.parse_traversal(expression)?)),
+            }),
+            Rule::identifier => Ok(Expression {
+                loc: expression.loc(),
+                expr: ExpressionType::Identifier(expression.as_str().to_string()),
+            }),
+            Rule::computed_predicate => {
+                let loc = expression.loc();
+                let mut inner = expression.into_inner();
+                let call = inner.try_next()?;
+                let value = Expression {
+                    loc: call.loc(),
+                    expr: match call.as_rule() {
+                        Rule::date_function_call => {
+                            ExpressionType::DateFunctionCall(self.parse_date_function_call(call)?)
+                        }
+                        _ => ExpressionType::StringFunctionCall(
+                            self.parse_string_function_call(call)?,
+                        ),
+                    },
+                };
+                let op = self.parse_bool_operation(inner.try_next()?)?;
+                Ok(Expression {
+                    loc,
+                    expr: ExpressionType::ComputedPredicate(ComputedPredicate {
+                        value: Box::new(value),
+                        op,
+                    }),
+                })
+            }
             _ => unreachable!(),
         }
     }
@@ -572,6 +628,132 @@ impl HelixParser {
         })
     }
 
+    pub(super) fn parse_string_function_call(
+        &self,
+        pair: Pair<Rule>,
+    ) -> Result<StringFunctionCall, ParserError> {
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+
+        let function_name = inner.try_next()?.as_str();
+        let function = match function_name {
+            "LOWER" => StringFunction::Lower,
+            "UPPER" => StringFunction::Upper,
+            "TRIM" => StringFunction::Trim,
+            "LENGTH" => StringFunction::Length,
+            "SUBSTRING" => StringFunction::Substring,
+            "CONCAT" => StringFunction::Concat,
+            _ => {
+                return Err(ParserError::from(format!(
+                    "Unknown string function: {function_name}"
+                )));
+            }
+        };
+
+        // each arg is a string_function_arg wrapping the actual expression
+        let args = inner
+            .map(|arg| self.parse_expression(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (min_arity, max_arity) = function.arity();
+        if args.len() < min_arity || args.len() > max_arity {
+            let expected = match (min_arity, max_arity) {
+                (min, max) if min == max => min.to_string(),
+                (min, usize::MAX) => format!("at least {min}"),
+                (min, max) => format!("{min} to {max}"),
+            };
+            return Err(ParserError::from(format!(
+                "Function {} expects {} argument(s), but got {}",
+                function_name,
+                expected,
+                args.len()
+            )));
+        }
+
+        Ok(StringFunctionCall {
+            function,
+            args,
+            loc,
+        })
+    }
+
+    pub(super) fn parse_date_function_call(
+        &self,
+        pair: Pair<Rule>,
+    ) -> Result<DateFunctionCall, ParserError> {
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+
+        let function_name = inner.try_next()?.as_str();
+        let function = match function_name {
+            "DATE_ADD" => DateFunction::Add,
+            "DATE_SUB" => DateFunction::Sub,
+            "DATE_TRUNC" => DateFunction::Trunc,
+            "DATE" => DateFunction::Date,
+            "YEAR" => DateFunction::Year,
+            "MONTH" => DateFunction::Month,
+            "DAY" => DateFunction::Day,
+            "HOUR" => DateFunction::Hour,
+            _ => {
+                return Err(ParserError::from(format!(
+                    "Unknown date function: {function_name}"
+                )));
+            }
+        };
+
+        // each arg is a date_function_arg wrapping the actual expression
+        let args = inner
+            .map(|arg| self.parse_expression(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (min_arity, max_arity) = function.arity();
+        if args.len() < min_arity || args.len() > max_arity {
+            let expected = match (min_arity, max_arity) {
+                (min, max) if min == max => min.to_string(),
+                (min, max) => format!("{min} to {max}"),
+            };
+            return Err(ParserError::from(format!(
+                "Function {} expects {} argument(s), but got {}",
+                function_name,
+                expected,
+                args.len()
+            )));
+        }
+
+        Ok(DateFunctionCall {
+            function,
+            args,
+            loc,
+        })
+    }
+
+    /// Parses a duration literal such as `30s` or `7d` into milliseconds
+    pub(super) fn parse_duration(&self, pair: Pair<Rule>) -> Result<i64, ParserError> {
+        let text = pair.as_str();
+        let split = text
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(|| ParserError::from(format!("Duration `{text}` is missing a unit")))?;
+        let (amount, unit) = text.split_at(split);
+        let millis_per_unit: i64 = match unit {
+            "ms" => 1,
+            "s" => 1_000,
+            "m" => 60_000,
+            "h" => 3_600_000,
+            "d" => 86_400_000,
+            "w" => 604_800_000,
+            _ => {
+                return Err(ParserError::from(format!(
+                    "Unknown duration unit `{unit}` in `{text}`"
+                )));
+            }
+        };
+        amount
+            .parse::<i64>()
+            .ok()
+            .and_then(|amount| amount.checked_mul(millis_per_unit))
+            .ok_or_else(|| ParserError::from(format!("Duration `{text}` is too large")))
+    }
+
     pub(super) fn parse_math_expression(
         &self,
         pair: Pair<Rule>,
@@ -668,7 +850,10 @@ impl HelixParser {
 
 #[cfg(test)]
 mod tests {
-    use crate::helixc::parser::{write_to_temp_file, HelixParser};
+    use crate::helixc::parser::{
+        types::{ExpressionType, StatementType},
+        write_to_temp_file, HelixParser,
+    };
 
     // ============================================================================
     // Literal Expression Tests
@@ -749,6 +934,77 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_signed_and_exponent_literals() {
+        let source = r#"
+            N::Person { name: String, score: F64 }
+
+            QUERY testQuery() =>
+                offset <- -42
+                epsilon <- 1e-6
+                scaled <- -2.5E+3
+                people <- N<Person>::WHERE(_::{score}::GT(-5))
+                RETURN offset
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let values = parsed.queries[0]
+            .statements
+            .iter()
+            .filter_map(|s| match &s.statement {
+                StatementType::Assignment(a) => Some(&a.value.expr),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(matches!(values[0], ExpressionType::IntegerLiteral(-42)));
+        assert!(matches!(values[1], ExpressionType::FloatLiteral(f) if *f == 1e-6));
+        assert!(matches!(values[2], ExpressionType::FloatLiteral(f) if *f == -2500.0));
+    }
+
+    #[test]
+    fn test_parse_string_literal_escapes() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY testQuery() =>
+                value <- "say \"hi\"\n\tpath\\to \u{1F600}"
+                RETURN value
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let StatementType::Assignment(assignment) = &parsed.queries[0].statements[0].statement
+        else {
+            panic!("expected an assignment");
+        };
+        assert!(matches!(
+            &assignment.value.expr,
+            ExpressionType::StringLiteral(s) if s == "say \"hi\"\n\tpath\\to \u{1F600}"
+        ));
+    }
+
+    #[test]
+    fn test_parse_string_literal_invalid_escape() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY testQuery() =>
+                value <- "bad \q escape"
+                RETURN value
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_parse_array_literal() {
         let source = r#"
@@ -788,6 +1044,26 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_parse_null_checks_in_where_clause() {
+        let source = r#"
+            N::Person { name: String, bio: Optional<String> }
+
+            QUERY testQuery() =>
+                people <- N<Person>::WHERE(
+                    OR(
+                        _::{bio}::IS_NULL,
+                        _::{name}::IS_NOT_NULL
+                    )
+                )
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_parse_or_in_where_clause() {
         let source = r#"
@@ -1110,4 +1386,81 @@ mod tests {
         let result = HelixParser::parse_source(&content);
         assert!(result.is_ok());
     }
+
+    // ============================================================================
+    // String Function Tests
+    // ============================================================================
+
+    #[test]
+    fn test_parse_string_predicates_and_functions() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY testQuery(prefix: String) =>
+                people <- N<Person>::WHERE(OR(
+                    _::{name}::STARTS_WITH(prefix),
+                    _::{name}::ENDS_WITH("son"),
+                    _::{name}::EQ_IGNORE_CASE(LOWER(prefix)),
+                    _::{name}::REGEX("^[A-Z]")
+                ))
+                RETURN people::{ short: SUBSTRING(UPPER(_::{name}), 0, 3), size: LENGTH(_::{name}) }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_parse_string_function_arity() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY testQuery(name: String) =>
+                people <- N<Person>::WHERE(_::{name}::EQ(LOWER(name, name)))
+                RETURN people
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
+
+    // ============================================================================
+    // Date Function Tests
+    // ============================================================================
+
+    #[test]
+    fn test_parse_date_functions_and_durations() {
+        let source = r#"
+            N::Event { created_at: Date }
+
+            QUERY testQuery() =>
+                events <- N<Event>::WHERE(AND(
+                    _::{created_at}::GT(DATE_SUB(NOW, 7d)),
+                    MONTH(_::{created_at}, "Europe/London")::EQ(3)
+                ))::ORDER<Desc>(DATE_TRUNC("week", _::{created_at}))
+                days <- N<Event>::GROUP_BY(day: DATE_TRUNC("day", _::{created_at}))
+                RETURN events::{ due: DATE_ADD(_::{created_at}, 90m) }, days
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_parse_date_function_arity() {
+        let source = r#"
+            N::Event { created_at: Date }
+
+            QUERY testQuery() =>
+                events <- N<Event>::WHERE(_::{created_at}::GT(DATE_ADD(NOW)))
+                RETURN events
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
 }
@@ -2,23 +2,40 @@ use crate::helixc::parser::{
     HelixParser, ParserError, Rule,
     location::HasLoc,
     types::{
-        Aggregate, BooleanOp, BooleanOpType, Closure, Exclude, Expression, ExpressionType, FieldAddition,
-        FieldValue, FieldValueType, GraphStep, GraphStepType, GroupBy, IdType, MMRDistance, Object, OrderBy,
-        OrderByType, RerankMMR, RerankRRF, ShortestPath, ShortestPathAStar, ShortestPathBFS,
+        Aggregate, AggregationField, AggregationFunction, BooleanOp, BooleanOpType, Centrality, CentralityKind, Closure, DagStep, DateFunctionCall, DagStepKind, EgoGraph, Exclude, Expression, ExpressionType, FieldAddition,
+        FieldValue, FieldValueType, GraphStep, GraphStepType, GroupBy, IdType, MMRDistance, Object, OrderBy, OrderByKey,
+        OrderByType, Page, RerankMMR, RerankRRF, ShortestPath, ShortestPathAStar, ShortestPathBFS,
         ShortestPathDijkstras, Step, StepType, Update,
     },
     utils::{PairTools, PairsTools},
 };
 use pest::iterators::Pair;
 
+/// Grouped properties, computed keys and aggregations of a grouping step
+type Grouping = (
+    Vec<String>,
+    Vec<(String, DateFunctionCall)>,
+    Vec<AggregationField>,
+);
+
 impl HelixParser {
     /// Parses an order by step
     ///
     /// #### Example
     /// ```rs
     /// ::ORDER<Asc>(_::{age})
+    /// ::ORDER<Desc>(_::{score}), ORDER<Asc>(_::{name}) NULLS FIRST
     /// ```
     pub(super) fn parse_order_by(&self, pair: Pair<Rule>) -> Result<OrderBy, ParserError> {
+        let loc = pair.loc();
+        let keys = pair
+            .into_inner()
+            .map(|key| self.parse_order_key(key))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(OrderBy { loc, keys })
+    }
+
+    fn parse_order_key(&self, pair: Pair<Rule>) -> Result<OrderByKey, ParserError> {
         let mut inner = pair.clone().into_inner();
         let order_by_type = match inner.try_next_inner().try_next()?.as_rule() {
             Rule::asc => OrderByType::Asc,
@@ -26,10 +43,15 @@ impl HelixParser {
             _ => unreachable!(),
         };
         let expression = self.parse_expression(inner.try_next()?)?;
-        Ok(OrderBy {
+        let nulls_first = match inner.next() {
+            Some(nulls) => nulls.try_inner_next()?.as_rule() == Rule::nulls_first,
+            None => false,
+        };
+        Ok(OrderByKey {
             loc: pair.loc(),
             order_by_type,
             expression: Box::new(expression),
+            nulls_first,
         })
     }
 
@@ -51,6 +73,25 @@ impl HelixParser {
         Ok((start, end))
     }
 
+    /// Parses a page step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::PAGE(20, cursor)
+    /// ```
+    pub(super) fn parse_page(&self, pair: Pair<Rule>) -> Result<Page, ParserError> {
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+        let size = self.parse_expression(inner.try_next()?)?;
+        let cursor = inner.try_next()?;
+        let cursor = Expression {
+            loc: cursor.loc(),
+            expr: ExpressionType::Identifier(cursor.as_str().to_string()),
+        };
+
+        Ok(Page { loc, size, cursor })
+    }
+
     /// Parses a boolean operation
     ///
     /// #### Example
@@ -104,6 +145,38 @@ impl HelixParser {
                 loc: pair.loc(),
                 op: BooleanOpType::IsIn(Box::new(self.parse_expression(inner)?)),
             },
+            Rule::IS_NULL => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::IsNull,
+            },
+            Rule::IS_NOT_NULL => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::IsNotNull,
+            },
+            Rule::STARTS_WITH => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::StartsWith(Box::new(
+                    self.parse_expression(inner.try_inner_next()?)?,
+                )),
+            },
+            Rule::ENDS_WITH => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::EndsWith(Box::new(
+                    self.parse_expression(inner.try_inner_next()?)?,
+                )),
+            },
+            Rule::EQ_IGNORE_CASE => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::EqualIgnoreCase(Box::new(
+                    self.parse_expression(inner.try_inner_next()?)?,
+                )),
+            },
+            Rule::REGEX => BooleanOp {
+                loc: pair.loc(),
+                op: BooleanOpType::Regex(Box::new(
+                    self.parse_expression(inner.try_inner_next()?)?,
+                )),
+            },
             _ => return Err(ParserError::from("Invalid boolean operation")),
         };
         Ok(expr)
@@ -221,27 +294,79 @@ impl HelixParser {
 
     pub(super) fn parse_aggregate(&self, pair: Pair<Rule>) -> Result<Aggregate, ParserError> {
         let loc = pair.loc();
-        let identifiers = pair
-            .into_inner()
-            .map(|i| i.as_str().to_string())
-            .collect::<Vec<_>>();
+        let (properties, _, aggregations) = self.parse_grouping(pair)?;
 
         Ok(Aggregate {
             loc,
-            properties: identifiers,
+            properties,
+            aggregations,
         })
     }
 
     pub(super) fn parse_group_by(&self, pair: Pair<Rule>) -> Result<GroupBy, ParserError> {
         let loc = pair.loc();
-        let identifiers = pair
-            .into_inner()
-            .map(|i| i.as_str().to_string())
-            .collect::<Vec<_>>();
+        let (properties, computed, aggregations) = self.parse_grouping(pair)?;
 
         Ok(GroupBy {
             loc,
-            properties: identifiers,
+            properties,
+            computed,
+            aggregations,
+        })
+    }
+
+    /// Parses the grouped properties, the computed keys and the optional aggregation block shared
+    /// by `GROUP_BY` and `AGGREGATE_BY`. Only `GROUP_BY` accepts computed keys.
+    fn parse_grouping(&self, pair: Pair<Rule>) -> Result<Grouping, ParserError> {
+        let mut properties = Vec::new();
+        let mut computed = Vec::new();
+        let mut aggregations = Vec::new();
+        for inner in pair.into_inner() {
+            match inner.as_rule() {
+                Rule::identifier => properties.push(inner.as_str().to_string()),
+                Rule::computed_group_key => {
+                    let mut key = inner.into_inner();
+                    let name = key.try_next()?.as_str().to_string();
+                    computed.push((name, self.parse_date_function_call(key.try_next()?)?));
+                }
+                Rule::aggregations => {
+                    for field in inner.into_inner() {
+                        aggregations.push(self.parse_aggregation_field(field)?);
+                    }
+                }
+                other => {
+                    return Err(ParserError::from(format!(
+                        "Unexpected rule in grouping step: {other:?}"
+                    )));
+                }
+            }
+        }
+        Ok((properties, computed, aggregations))
+    }
+
+    fn parse_aggregation_field(&self, pair: Pair<Rule>) -> Result<AggregationField, ParserError> {
+        let loc = pair.loc();
+        let mut inner = pair.into_inner();
+        let name = inner.try_next()?.as_str().to_string();
+        let function = match inner.try_next()?.as_str() {
+            "SUM" => AggregationFunction::Sum,
+            "AVG" => AggregationFunction::Avg,
+            "MIN" => AggregationFunction::Min,
+            "MAX" => AggregationFunction::Max,
+            "COUNT_DISTINCT" => AggregationFunction::CountDistinct,
+            "COLLECT" => AggregationFunction::Collect,
+            other => {
+                return Err(ParserError::from(format!(
+                    "Unknown aggregation function: {other}"
+                )));
+            }
+        };
+        let property = inner.try_next()?.as_str().to_string();
+        Ok(AggregationField {
+            loc,
+            name,
+            function,
+            property,
         })
     }
 
@@ -268,6 +393,10 @@ impl HelixParser {
                 loc: step_pair.loc(),
                 step: StepType::Range(self.parse_range(step_pair)?),
             }),
+            Rule::page_step => Ok(Step {
+                loc: step_pair.loc(),
+                step: StepType::Page(self.parse_page(step_pair)?),
+            }),
 
             Rule::bool_operations => Ok(Step {
                 loc: step_pair.loc(),
@@ -328,6 +457,22 @@ impl HelixParser {
                 loc: step_pair.loc(),
                 step: StepType::RerankMMR(self.parse_rerank_mmr(step_pair)?),
             }),
+            Rule::centrality_step => Ok(Step {
+                loc: step_pair.loc(),
+                step: StepType::Centrality(self.parse_centrality(step_pair)?),
+            }),
+            Rule::dag_step => Ok(Step {
+                loc: step_pair.loc(),
+                step: StepType::Dag(self.parse_dag_step(step_pair)?),
+            }),
+            Rule::ego_graph => Ok(Step {
+                loc: step_pair.loc(),
+                step: StepType::EgoGraph(self.parse_ego_graph(step_pair)?),
+            }),
+            Rule::fn_call => Ok(Step {
+                loc: step_pair.loc(),
+                step: StepType::FragmentCall(self.parse_fragment_call(step_pair)?),
+            }),
             _ => Err(ParserError::from(format!(
                 "Unexpected step type: {:?}",
                 step_pair.as_rule()
@@ -677,6 +822,103 @@ impl HelixParser {
         Ok(RerankRRF { loc, k })
     }
 
+    /// Parses a degree or centrality step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::OutDegree<Follows>
+    /// ::Betweenness<Follows>(64)
+    /// ```
+    pub(super) fn parse_centrality(&self, pair: Pair<Rule>) -> Result<Centrality, ParserError> {
+        let pair = pair.try_inner_next()?;
+        let loc = pair.loc();
+        let rule = pair.as_rule();
+        let mut inner = pair.into_inner();
+        let edge_type = inner.try_next()?.try_inner_next()?.as_str().to_string();
+        let kind = match rule {
+            Rule::out_degree => CentralityKind::OutDegree,
+            Rule::in_degree => CentralityKind::InDegree,
+            Rule::degree => CentralityKind::Degree,
+            Rule::triangle_count => CentralityKind::TriangleCount,
+            Rule::betweenness => CentralityKind::Betweenness(match inner.next() {
+                Some(samples) => Some(self.parse_expression(samples)?),
+                None => None,
+            }),
+            other => {
+                return Err(ParserError::from(format!(
+                    "Unexpected centrality step: {other:?}"
+                )));
+            }
+        };
+
+        Ok(Centrality {
+            loc,
+            edge_type,
+            kind,
+        })
+    }
+
+    /// Parses a DAG step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::TopoSort<DependsOn>
+    /// ::HasCycle<DependsOn>
+    /// ```
+    pub(super) fn parse_dag_step(&self, pair: Pair<Rule>) -> Result<DagStep, ParserError> {
+        let pair = pair.try_inner_next()?;
+        let loc = pair.loc();
+        let kind = match pair.as_rule() {
+            Rule::topo_sort => DagStepKind::TopoSort,
+            Rule::has_cycle => DagStepKind::HasCycle,
+            other => {
+                return Err(ParserError::from(format!("Unexpected DAG step: {other:?}")));
+            }
+        };
+        let edge_type = pair.try_inner_next()?.try_inner_next()?.as_str().to_string();
+
+        Ok(DagStep {
+            loc,
+            edge_type,
+            kind,
+        })
+    }
+
+    /// Parses an EgoGraph step
+    ///
+    /// #### Example
+    /// ```rs
+    /// ::EgoGraph<Follows, Likes>(2, 100)
+    /// ::EgoGraph(1)
+    /// ```
+    pub(super) fn parse_ego_graph(&self, pair: Pair<Rule>) -> Result<EgoGraph, ParserError> {
+        let loc = pair.loc();
+        let mut edge_types = Vec::new();
+        let mut numbers = Vec::new();
+        for inner in pair.into_inner() {
+            match inner.as_rule() {
+                Rule::type_args => {
+                    edge_types = inner
+                        .into_inner()
+                        .map(|arg| arg.as_str().to_string())
+                        .collect();
+                }
+                _ => numbers.push(self.parse_expression(inner)?),
+            }
+        }
+        let mut numbers = numbers.into_iter();
+        let hops = numbers
+            .next()
+            .ok_or_else(|| ParserError::from("EgoGraph requires a hop count"))?;
+
+        Ok(EgoGraph {
+            loc,
+            edge_types,
+            hops,
+            max_nodes: numbers.next(),
+        })
+    }
+
     /// Parses a RerankMMR step
     ///
     /// #### Example
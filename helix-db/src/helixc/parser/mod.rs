@@ -41,6 +41,7 @@ impl HelixParser {
             source: String::new(),
             schema: HashMap::new(),
             migrations: Vec::new(),
+            fragments: Vec::new(),
             queries: Vec::new(),
         };
 
@@ -61,6 +62,7 @@ impl HelixParser {
 
             let pairs = pair.into_inner();
             let mut remaining_queries = HashSet::new();
+            let mut remaining_fragments = Vec::new();
             let mut remaining_migrations = HashSet::new();
             for pair in pairs {
                 match pair.as_rule() {
@@ -110,6 +112,7 @@ impl HelixParser {
                                             node_schemas: vec![node_schema],
                                             edge_schemas: vec![],
                                             vector_schemas: vec![],
+                                            enum_schemas: vec![],
                                         });
                                 }
                                 Rule::edge_def => {
@@ -128,6 +131,7 @@ impl HelixParser {
                                             node_schemas: vec![],
                                             edge_schemas: vec![edge_schema],
                                             vector_schemas: vec![],
+                                            enum_schemas: vec![],
                                         });
                                 }
                                 Rule::vector_def => {
@@ -146,6 +150,26 @@ impl HelixParser {
                                             node_schemas: vec![],
                                             edge_schemas: vec![],
                                             vector_schemas: vec![vector_schema],
+                                            enum_schemas: vec![],
+                                        });
+                                }
+                                Rule::enum_def => {
+                                    let enum_schema =
+                                        parser.parse_enum_def(pair.clone(), file.name.clone())?;
+                                    parser
+                                        .source
+                                        .schema
+                                        .entry(schema_version)
+                                        .and_modify(|schema| {
+                                            schema.enum_schemas.push(enum_schema.clone())
+                                        })
+                                        .or_insert(Schema {
+                                            loc: pair.loc(),
+                                            version: (pair.loc(), schema_version),
+                                            node_schemas: vec![],
+                                            edge_schemas: vec![],
+                                            vector_schemas: vec![],
+                                            enum_schemas: vec![enum_schema],
                                         });
                                 }
                                 _ => return Err(ParserError::from("Unexpected rule encountered")),
@@ -155,6 +179,9 @@ impl HelixParser {
                     Rule::migration_def => {
                         remaining_migrations.insert(pair);
                     }
+                    Rule::fn_def => {
+                        remaining_fragments.push(pair);
+                    }
                     Rule::query_def => {
                         remaining_queries.insert(pair);
                     }
@@ -168,6 +195,11 @@ impl HelixParser {
                 parser.source.migrations.push(migration);
             }
 
+            for pair in remaining_fragments {
+                let fragment = parser.parse_fragment_def(pair, file.name.clone())?;
+                parser.source.fragments.push(fragment);
+            }
+
             for pair in remaining_queries {
                 parser
                     .source
@@ -179,6 +211,7 @@ impl HelixParser {
             source.schema.extend(parser.source.schema);
             source.queries.extend(parser.source.queries);
             source.migrations.extend(parser.source.migrations);
+            source.fragments.extend(parser.source.fragments);
             Ok(())
         })?;
 
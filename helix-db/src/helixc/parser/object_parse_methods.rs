@@ -25,7 +25,7 @@ impl HelixParser {
 
                 let prop_val = match value_pair.as_rule() {
                     Rule::string_literal => Ok(ValueType::new(
-                        Value::from(value_pair.as_str().to_string()),
+                        Value::String(self.parse_string_literal(value_pair.clone())?),
                         value_pair.loc(),
                     )),
                     Rule::integer => value_pair
@@ -2,7 +2,10 @@ use crate::helixc::parser::{
     HelixParser, Rule,
     location::HasLoc,
     ParserError,
-    types::{BuiltInMacro, Parameter, Query, Statement, StatementType},
+    types::{
+        BuiltInMacro, Fragment, FragmentCall, IfElse, Parameter, Query, Statement, StatementType,
+    },
+    utils::{PairTools, PairsTools},
 };
 use pest::iterators::Pair;
 use std::collections::HashSet;
@@ -61,6 +64,40 @@ impl HelixParser {
         })
     }
 
+    pub(super) fn parse_fragment_def(
+        &self,
+        pair: Pair<Rule>,
+        filepath: String,
+    ) -> Result<Fragment, ParserError> {
+        let original_fragment = pair.as_str().to_string();
+        let loc = pair.loc_with_filepath(filepath);
+        let mut pairs = pair.into_inner();
+        let name_pair = pairs.try_next()?;
+        let name = (name_pair.loc(), name_pair.as_str().to_string());
+        let parameters = self.parse_parameters(pairs.try_next()?)?;
+        let body_pair = pairs.try_next()?;
+        let body = match body_pair.as_rule() {
+            Rule::anonymous_traversal => self.parse_anon_traversal(body_pair)?,
+            _ => self.parse_traversal(body_pair)?,
+        };
+
+        Ok(Fragment {
+            name,
+            parameters,
+            body,
+            original_fragment,
+            loc,
+        })
+    }
+
+    pub(super) fn parse_fragment_call(&self, pair: Pair<Rule>) -> Result<FragmentCall, ParserError> {
+        let loc = pair.loc();
+        let mut pairs = pair.into_inner();
+        let name = pairs.try_next()?.as_str().to_string();
+        let args = pairs.map(|arg| (arg.loc(), arg.as_str().to_string())).collect();
+        Ok(FragmentCall { name, args, loc })
+    }
+
     pub(super) fn parse_parameters(&self, pair: Pair<Rule>) -> Result<Vec<Parameter>, ParserError> {
         let mut seen = HashSet::new();
         pair.clone()
@@ -148,6 +185,10 @@ impl HelixParser {
                     loc: p.loc(),
                     statement: StatementType::ForLoop(self.parse_for_loop(p)?),
                 }),
+                Rule::if_stmt => Ok(Statement {
+                    loc: p.loc(),
+                    statement: StatementType::IfElse(self.parse_if_else(p)?),
+                }),
                 _ => Err(ParserError::from(format!(
                     "Unexpected statement type in query body: {:?}",
                     p.as_rule()
@@ -155,6 +196,32 @@ impl HelixParser {
             })
             .collect()
     }
+
+    pub(super) fn parse_if_else(&self, pair: Pair<Rule>) -> Result<IfElse, ParserError> {
+        let loc = pair.loc();
+        let mut pairs = pair.into_inner();
+        let condition = self.parse_boolean_expression(pairs.try_next()?)?;
+        let then_statements = self.parse_query_body(pairs.try_next()?)?;
+        let else_statements = match pairs.next() {
+            Some(else_branch) => {
+                let branch = else_branch.try_inner_next()?;
+                match branch.as_rule() {
+                    Rule::if_stmt => vec![Statement {
+                        loc: branch.loc(),
+                        statement: StatementType::IfElse(self.parse_if_else(branch)?),
+                    }],
+                    _ => self.parse_query_body(branch)?,
+                }
+            }
+            None => Vec::new(),
+        };
+        Ok(IfElse {
+            condition,
+            then_statements,
+            else_statements,
+            loc,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -494,6 +561,40 @@ mod tests {
         assert_eq!(parsed.queries[0].statements.len(), 1);
     }
 
+    #[test]
+    fn test_parse_query_with_if_else() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY upsertPerson(name: String) =>
+                IF EXISTS(N<Person>::WHERE(_::{name}::EQ(name))) {
+                    person <- N<Person>::WHERE(_::{name}::EQ(name))
+                } ELSE IF true {
+                    created <- AddN<Person>({name: name})
+                } ELSE {
+                    other <- N<Person>
+                }
+                RETURN "done"
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        assert_eq!(parsed.queries[0].statements.len(), 1);
+        let StatementType::IfElse(if_else) = &parsed.queries[0].statements[0].statement else {
+            panic!("expected an IF statement");
+        };
+        assert_eq!(if_else.then_statements.len(), 1);
+        assert_eq!(if_else.else_statements.len(), 1);
+        let StatementType::IfElse(else_if) = &if_else.else_statements[0].statement else {
+            panic!("expected ELSE IF to parse as a nested IF statement");
+        };
+        assert_eq!(else_if.then_statements.len(), 1);
+        assert_eq!(else_if.else_statements.len(), 1);
+    }
+
     #[test]
     fn test_parse_query_mixed_optional_required_parameters() {
         let source = r#"
@@ -535,4 +636,48 @@ mod tests {
             crate::helixc::parser::types::FieldType::Object(_)
         ));
     }
+
+    #[test]
+    fn test_parse_fragment_definitions_and_calls() {
+        use crate::helixc::parser::types::{ExpressionType, StartNode, StepType};
+
+        let source = r#"
+            N::User { name: String }
+            N::Doc { title: String }
+            E::Owns { From: User, To: Doc }
+
+            FN owned_docs(u: ID) => N<User>(u)::Out<Owns>
+            FN titled(t: String) => _::WHERE(_::{title}::EQ(t))
+
+            QUERY getDocs(id: ID, title: String) =>
+                docs <- owned_docs(id)::titled(title)
+                RETURN docs
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let parsed = HelixParser::parse_source(&content).unwrap();
+
+        assert_eq!(parsed.fragments.len(), 2);
+        assert_eq!(parsed.fragments[0].name.1, "owned_docs");
+        assert_eq!(parsed.fragments[0].parameters.len(), 1);
+        assert!(matches!(parsed.fragments[0].body.start, StartNode::Node { .. }));
+        assert!(matches!(parsed.fragments[1].body.start, StartNode::Anonymous));
+
+        let StatementType::Assignment(assignment) = &parsed.queries[0].statements[0].statement
+        else {
+            panic!("expected an assignment");
+        };
+        let ExpressionType::Traversal(traversal) = &assignment.value.expr else {
+            panic!("expected a traversal");
+        };
+        let StartNode::FragmentCall(call) = &traversal.start else {
+            panic!("expected a fragment call");
+        };
+        assert_eq!(call.name, "owned_docs");
+        assert_eq!(call.args[0].1, "id");
+        assert!(matches!(
+            &traversal.steps[0].step,
+            StepType::FragmentCall(call) if call.name == "titled" && call.args[0].1 == "title"
+        ));
+    }
 }
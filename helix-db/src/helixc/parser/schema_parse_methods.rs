@@ -2,14 +2,15 @@ use std::collections::HashMap;
 
 use crate::helixc::parser::{
     HelixParser, ParserError, Rule,
-    location::HasLoc,
+    location::{HasLoc, Loc},
     types::{
-        DefaultValue, EdgeSchema, Field, FieldPrefix, FieldType, Migration, MigrationItem,
-        MigrationItemMapping, MigrationPropertyMapping, NodeSchema, Source, ValueCast,
-        VectorSchema,
+        DefaultValue, EdgeSchema, EnumSchema, Field, FieldPrefix, FieldType, Migration,
+        MigrationItem, MigrationItemMapping, MigrationPropertyMapping, NodeSchema, Source,
+        ValueCast, VectorSchema,
     },
     utils::{PairTools, PairsTools},
 };
+use crate::helix_engine::traversal_core::ops::source::add_e::EdgeCardinality;
 use pest::iterators::{Pair, Pairs};
 
 impl HelixParser {
@@ -43,6 +44,25 @@ impl HelixParser {
         })
     }
 
+    pub(super) fn parse_enum_def(
+        &self,
+        pair: Pair<Rule>,
+        filepath: String,
+    ) -> Result<EnumSchema, ParserError> {
+        let mut pairs = pair.clone().into_inner();
+        let name = pairs.try_next()?.as_str().to_string();
+        let variants = pairs
+            .try_next()?
+            .into_inner()
+            .map(|p| (p.loc(), p.as_str().to_string()))
+            .collect();
+        Ok(EnumSchema {
+            name: (pair.loc(), name),
+            variants,
+            loc: pair.loc_with_filepath(filepath),
+        })
+    }
+
     pub(super) fn parse_node_body(
         &self,
         pair: Pair<Rule>,
@@ -230,7 +250,9 @@ impl HelixParser {
                     pairs.next();
                     let default_value = match pair.into_inner().next() {
                         Some(pair) => match pair.as_rule() {
-                            Rule::string_literal => DefaultValue::String(pair.as_str().to_string()),
+                            Rule::string_literal => {
+                                DefaultValue::String(self.parse_string_literal(pair)?)
+                            }
                             Rule::float => {
                                 match field_type {
                                     FieldType::F32 => DefaultValue::F32(
@@ -421,10 +443,18 @@ impl HelixParser {
                 for field in field.try_inner_next()?.into_inner() {
                     let (field_name, field_type) = {
                         let mut field_pair = field.clone().into_inner();
-                        (
-                            field_pair.try_next()?.as_str().to_string(),
-                            field_pair.try_next_inner().try_next()?,
-                        )
+                        let field_name = field_pair.try_next()?.as_str().to_string();
+                        let field_type = field_pair.try_next()?;
+                        // parameters are made optional with `name?: T` on the parameter itself
+                        if matches!(
+                            field_type.as_rule(),
+                            Rule::optional_param | Rule::optional_type
+                        ) {
+                            return Err(ParserError::from(format!(
+                                "Field `{field_name}` of an object parameter cannot be optional"
+                            )));
+                        }
+                        (field_name, field_type.try_inner_next()?)
                     };
                     let field_type = self.parse_field_type(field_type, Some(&self.source))?;
                     fields.insert(field_name, field_type);
@@ -446,22 +476,28 @@ impl HelixParser {
         filepath: String,
     ) -> Result<Field, ParserError> {
         let mut pairs = pair.clone().into_inner();
-        // structure is index? ~ identifier ~ ":" ~ param_type
+        // structure is index? ~ identifier ~ optional_param? ~ ":" ~ (optional_type | param_type)
         let prefix: FieldPrefix = match pairs.clone().try_next()?.as_rule() {
             Rule::index => {
                 pairs.try_next()?;
                 FieldPrefix::Index
             }
-            // Rule::optional => {
-            //     pairs.next().unwrap();
-            //     FieldPrefix::Optional
-            // }
             _ => FieldPrefix::Empty,
         };
         let name = pairs.try_next()?.as_str().to_string();
 
-        let field_type =
-            self.parse_field_type(pairs.try_next_inner().try_next()?, Some(&self.source))?;
+        let mut is_optional = pairs
+            .peek()
+            .is_some_and(|p| p.as_rule() == Rule::optional_param);
+        if is_optional {
+            pairs.try_next()?;
+        }
+        let mut field_type = pairs.try_next()?;
+        if field_type.as_rule() == Rule::optional_type {
+            is_optional = true;
+            field_type = field_type.try_inner_next()?;
+        }
+        let field_type = self.parse_field_type(field_type.try_inner_next()?, Some(&self.source))?;
 
         let defaults = self.parse_default_value(&mut pairs, &field_type)?;
 
@@ -470,6 +506,7 @@ impl HelixParser {
             defaults,
             name,
             field_type,
+            is_optional,
             loc: pair.loc_with_filepath(filepath),
         })
     }
@@ -481,17 +518,30 @@ impl HelixParser {
     ) -> Result<EdgeSchema, ParserError> {
         let mut pairs = pair.clone().into_inner();
         let name = pairs.try_next()?.as_str().to_string();
+        let acyclic = pairs.peek().is_some_and(|p| p.as_rule() == Rule::acyclic);
+        if acyclic {
+            pairs.try_next()?;
+        }
+        let cardinality = match pairs.peek() {
+            Some(p) if p.as_rule() == Rule::cardinality => match pairs.try_next()?.as_str() {
+                "ONE_TO_ONE" => EdgeCardinality::OneToOne,
+                "ONE_TO_MANY" => EdgeCardinality::OneToMany,
+                "MANY_TO_ONE" => EdgeCardinality::ManyToOne,
+                _ => EdgeCardinality::ManyToMany,
+            },
+            _ => EdgeCardinality::ManyToMany,
+        };
+        let replace_existing = pairs
+            .peek()
+            .is_some_and(|p| p.as_rule() == Rule::replace_existing);
+        if replace_existing {
+            pairs.try_next()?;
+        }
         let body = pairs.try_next()?;
         let mut body_pairs = body.into_inner();
 
-        let from = {
-            let pair = body_pairs.try_next()?;
-            (pair.loc(), pair.as_str().to_string())
-        };
-        let to = {
-            let pair = body_pairs.try_next()?;
-            (pair.loc(), pair.as_str().to_string())
-        };
+        let (from, from_types) = self.parse_edge_endpoint(body_pairs.try_next()?);
+        let (to, to_types) = self.parse_edge_endpoint(body_pairs.try_next()?);
         let properties = match body_pairs.next() {
             Some(pair) => Some(self.parse_properties(pair, filepath.clone())?),
             None => None,
@@ -501,10 +551,28 @@ impl HelixParser {
             name: (pair.loc(), name),
             from,
             to,
+            from_types,
+            to_types,
             properties,
+            acyclic,
+            cardinality,
+            replace_existing,
             loc: pair.loc_with_filepath(filepath),
         })
     }
+    /// Parses `From:`/`To:` endpoints, which name one type or a union such as `Post | Comment`.
+    fn parse_edge_endpoint(&self, pair: Pair<Rule>) -> ((Loc, String), Vec<(Loc, String)>) {
+        let types = pair
+            .clone()
+            .into_inner()
+            .map(|p| (p.loc(), p.as_str().to_string()))
+            .collect::<Vec<_>>();
+        // members are sorted so a union has one name whatever order it is written in
+        let mut names = types.iter().map(|(_, name)| name.as_str()).collect::<Vec<_>>();
+        names.sort_unstable();
+        let name = names.join(" | ");
+        ((pair.loc(), name), types)
+    }
     pub(super) fn parse_properties(
         &self,
         pair: Pair<Rule>,
@@ -625,6 +693,70 @@ mod tests {
         assert!(schema.node_schemas[0].fields[3].defaults.is_some());
     }
 
+    #[test]
+    fn test_parse_node_definition_with_signed_and_escaped_defaults() {
+        let source = r#"
+            N::Reading {
+                label: String DEFAULT "line \"one\"\nline two",
+                offset: I32 DEFAULT -7,
+                threshold: F64 DEFAULT 1.5e-3
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let fields = &parsed.schema.get(&1).unwrap().node_schemas[0].fields;
+        assert!(matches!(
+            &fields[0].defaults,
+            Some(DefaultValue::String(s)) if s == "line \"one\"\nline two"
+        ));
+        assert!(matches!(fields[1].defaults, Some(DefaultValue::I32(-7))));
+        assert!(matches!(fields[2].defaults, Some(DefaultValue::F64(f)) if f == 1.5e-3));
+    }
+
+    #[test]
+    fn test_parse_node_definition_optional_fields() {
+        let source = r#"
+            N::Person {
+                name: String,
+                bio: Optional<String>,
+                INDEX nickname?: String,
+                tags?: [String]
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let fields = &parsed.schema.get(&1).unwrap().node_schemas[0].fields;
+        assert!(!fields[0].is_optional);
+        assert!(fields[1].is_optional);
+        assert_eq!(fields[1].field_type, FieldType::String);
+        assert!(fields[2].is_optional);
+        assert!(matches!(fields[2].prefix, FieldPrefix::Index));
+        assert!(fields[3].is_optional);
+        assert!(matches!(fields[3].field_type, FieldType::Array(_)));
+    }
+
+    #[test]
+    fn test_parse_object_parameter_rejects_optional_fields() {
+        let source = r#"
+            N::Person { name: String }
+
+            QUERY add(person: { name: Optional<String> }) =>
+                RETURN person
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_parse_node_definition_array_type() {
         let source = r#"
@@ -794,6 +926,97 @@ mod tests {
         assert_eq!(schema.edge_schemas[0].to.1, "Person");
     }
 
+    #[test]
+    fn test_parse_edge_definition_union_endpoint() {
+        let source = r#"
+            N::Post { title: String }
+            N::Comment { body: String }
+            N::Tag { name: String }
+
+            E::Tagged {
+                From: Post | Comment,
+                To: Tag
+            }
+
+            E::Mentions {
+                From: Tag,
+                To: Comment | Post
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let edge = &parsed.schema.get(&1).unwrap().edge_schemas[0];
+        // the union is named by its sorted members, which keep their declared order
+        assert_eq!(edge.from.1, "Comment | Post");
+        let from_types = edge.from_types.iter().map(|(_, t)| t.as_str()).collect::<Vec<_>>();
+        assert_eq!(from_types, vec!["Post", "Comment"]);
+        assert_eq!(edge.to.1, "Tag");
+        assert_eq!(edge.to_types.len(), 1);
+        assert!(edge.is_from("Comment"));
+        assert!(edge.is_from("Comment | Post"));
+        assert!(!edge.is_from("Tag"));
+        assert!(!edge.is_to("Post"));
+        let mentions = &parsed.schema.get(&1).unwrap().edge_schemas[1];
+        assert_eq!(mentions.to.1, edge.from.1);
+    }
+
+    #[test]
+    fn test_parse_edge_definition_acyclic() {
+        let source = r#"
+            N::Task { name: String }
+
+            E::DependsOn ACYCLIC {
+                From: Task,
+                To: Task
+            }
+
+            E::Blocks {
+                From: Task,
+                To: Task
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let schema = parsed.schema.get(&1).unwrap();
+        assert_eq!(schema.edge_schemas[0].name.1, "DependsOn");
+        assert!(schema.edge_schemas[0].acyclic);
+        assert_eq!(schema.edge_schemas[0].from.1, "Task");
+        assert!(!schema.edge_schemas[1].acyclic);
+    }
+
+    #[test]
+    fn test_parse_edge_definition_cardinality() {
+        let source = r#"
+            N::User { name: String }
+            N::City { name: String }
+
+            E::LivesIn MANY_TO_ONE { From: User, To: City }
+            E::MovedTo ACYCLIC ONE_TO_MANY REPLACE { From: City, To: City }
+            E::Visited { From: User, To: City }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let edges = &parsed.schema.get(&1).unwrap().edge_schemas;
+        assert_eq!(edges[0].cardinality, EdgeCardinality::ManyToOne);
+        assert!(!edges[0].replace_existing);
+        assert!(edges[1].acyclic);
+        assert_eq!(edges[1].cardinality, EdgeCardinality::OneToMany);
+        assert!(edges[1].replace_existing);
+        assert_eq!(edges[2].cardinality, EdgeCardinality::ManyToMany);
+    }
+
     #[test]
     fn test_parse_edge_definition_invalid_missing_from_to() {
         let source = r#"
@@ -833,6 +1056,51 @@ mod tests {
         assert_eq!(schema.vector_schemas[0].fields.len(), 2);
     }
 
+    // ============================================================================
+    // Enum Definition Tests
+    // ============================================================================
+
+    #[test]
+    fn test_parse_enum_definition() {
+        let source = r#"
+            ENUM Status { Active, Suspended, Deleted, }
+
+            N::User {
+                status: Status DEFAULT "Active",
+                previous?: Status
+            }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        let schema = parsed.schema.get(&1).unwrap();
+        assert_eq!(schema.enum_schemas.len(), 1);
+        assert_eq!(schema.enum_schemas[0].name.1, "Status");
+        let variants = schema.enum_schemas[0]
+            .variants
+            .iter()
+            .map(|(_, v)| v.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(variants, ["Active", "Suspended", "Deleted"]);
+        let fields = &schema.node_schemas[0].fields;
+        assert_eq!(fields[0].field_type, FieldType::Identifier("Status".to_string()));
+        assert!(fields[1].is_optional);
+    }
+
+    #[test]
+    fn test_parse_enum_variant_must_be_capitalized() {
+        let source = r#"
+            ENUM Status { active }
+        "#;
+
+        let content = write_to_temp_file(vec![source]);
+        let result = HelixParser::parse_source(&content);
+        assert!(result.is_err());
+    }
+
     // ============================================================================
     // Multiple Schemas Test
     // ============================================================================
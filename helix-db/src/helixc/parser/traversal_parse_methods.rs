@@ -104,7 +104,9 @@ impl HelixParser {
                                             loc: val.loc(),
                                         },
                                         Rule::string_literal => ValueType::Literal {
-                                            value: Value::from(val.as_str()),
+                                            value: Value::String(
+                                                self.parse_string_literal(val.clone())?,
+                                            ),
                                             loc: val.loc(),
                                         },
                                         Rule::integer => ValueType::Literal {
@@ -192,6 +194,7 @@ impl HelixParser {
             }
             Rule::identifier => Ok(StartNode::Identifier(pair.as_str().to_string())),
             Rule::search_vector => Ok(StartNode::SearchVector(self.parse_search_vector(pair)?)),
+            Rule::fn_call => Ok(StartNode::FragmentCall(self.parse_fragment_call(pair)?)),
             Rule::start_vector => {
                 let pairs = pair.into_inner();
                 let mut vector_type = String::new();
@@ -251,7 +254,9 @@ impl HelixParser {
                                         loc: value_inner.loc(),
                                     },
                                     Rule::string_literal => ValueType::Literal {
-                                        value: Value::from(value_inner.as_str()),
+                                        value: Value::String(
+                                            self.parse_string_literal(value_inner.clone())?,
+                                        ),
                                         loc: value_inner.loc(),
                                     },
                                     Rule::integer => ValueType::Literal {
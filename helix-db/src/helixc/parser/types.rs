@@ -1,5 +1,9 @@
 use super::location::Loc;
-use crate::{helixc::parser::{errors::ParserError, HelixParser}, protocol::value::Value};
+use crate::{
+    helix_engine::traversal_core::ops::source::add_e::EdgeCardinality,
+    helixc::parser::{errors::ParserError, HelixParser},
+    protocol::value::Value,
+};
 use chrono::{DateTime, NaiveDate, Utc};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
@@ -30,6 +34,7 @@ impl Default for HelixParser {
                 source: String::new(),
                 schema: HashMap::new(),
                 migrations: Vec::new(),
+                fragments: Vec::new(),
                 queries: Vec::new(),
             },
         }
@@ -42,6 +47,7 @@ pub struct Source {
     pub source: String,
     pub schema: HashMap<usize, Schema>,
     pub migrations: Vec<Migration>,
+    pub fragments: Vec<Fragment>,
     pub queries: Vec<Query>,
 }
 
@@ -73,6 +79,7 @@ pub struct Schema {
     pub node_schemas: Vec<NodeSchema>,
     pub edge_schemas: Vec<EdgeSchema>,
     pub vector_schemas: Vec<VectorSchema>,
+    pub enum_schemas: Vec<EnumSchema>,
 }
 
 #[derive(Debug, Clone)]
@@ -92,12 +99,65 @@ pub struct VectorSchema {
 #[derive(Debug, Clone)]
 pub struct EdgeSchema {
     pub name: (Loc, String),
+    /// The source type, with the members of a union endpoint sorted and joined by ` | `
     pub from: (Loc, String),
+    /// The target type, with the members of a union endpoint sorted and joined by ` | `
     pub to: (Loc, String),
+    /// Each type named in the `From:` endpoint
+    pub from_types: Vec<(Loc, String)>,
+    /// Each type named in the `To:` endpoint
+    pub to_types: Vec<(Loc, String)>,
     pub properties: Option<Vec<Field>>,
+    /// Whether adding an edge that would create a cycle of this edge type is rejected
+    pub acyclic: bool,
+    /// How many edges of this type each source and target node may have
+    pub cardinality: EdgeCardinality,
+    /// Whether an edge exceeding the cardinality replaces the existing edges instead of
+    /// being rejected
+    pub replace_existing: bool,
     pub loc: Loc,
 }
 
+impl EdgeSchema {
+    /// Whether every node type in `ty` (a single type or a ` | ` union) is a source of this edge
+    pub fn is_from(&self, ty: &str) -> bool {
+        Self::accepts(&self.from_types, ty)
+    }
+
+    /// Whether every node type in `ty` (a single type or a ` | ` union) is a target of this edge
+    pub fn is_to(&self, ty: &str) -> bool {
+        Self::accepts(&self.to_types, ty)
+    }
+
+    fn accepts(endpoint: &[(Loc, String)], ty: &str) -> bool {
+        ty.split(" | ")
+            .all(|member| endpoint.iter().any(|(_, name)| name == member))
+    }
+}
+
+/// A closed set of named values, e.g. `ENUM Status { Active, Suspended }`
+#[derive(Debug, Clone)]
+pub struct EnumSchema {
+    pub name: (Loc, String),
+    pub variants: Vec<(Loc, String)>,
+    pub loc: Loc,
+}
+
+impl EnumSchema {
+    pub fn has_variant(&self, value: &str) -> bool {
+        self.variants.iter().any(|(_, v)| v == value)
+    }
+
+    /// The variants as a readable list, e.g. "`Active`, `Suspended`"
+    pub fn variant_list(&self) -> String {
+        self.variants
+            .iter()
+            .map(|(_, v)| format!("`{v}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Migration {
     pub from_version: (Loc, usize),
@@ -163,6 +223,8 @@ pub struct Field {
     pub defaults: Option<DefaultValue>,
     pub name: String,
     pub field_type: FieldType,
+    /// Declared as `Optional<T>` or `name?: T`, so the field may be null
+    pub is_optional: bool,
     pub loc: Loc,
 }
 impl Field {
@@ -193,7 +255,6 @@ pub enum DefaultValue {
 #[derive(Debug, Clone)]
 pub enum FieldPrefix {
     Index,
-    Optional,
     Empty,
 }
 impl FieldPrefix {
@@ -395,6 +456,28 @@ pub struct Query {
     pub loc: Loc,
 }
 
+/// A `FN` definition: a named traversal that queries invoke by name.
+///
+/// Calls are inlined before analysis, with the parameters renamed to the caller's arguments.
+#[derive(Debug, Clone)]
+pub struct Fragment {
+    pub name: (Loc, String),
+    pub parameters: Vec<Parameter>,
+    pub body: Traversal,
+    pub original_fragment: String,
+    pub loc: Loc,
+}
+
+/// A call to a [`Fragment`], either starting a traversal or as one of its steps.
+///
+/// Each argument names a parameter or variable of the caller.
+#[derive(Debug, Clone)]
+pub struct FragmentCall {
+    pub name: String,
+    pub args: Vec<(Loc, String)>,
+    pub loc: Loc,
+}
+
 #[derive(Debug, Clone)]
 pub struct Parameter {
     pub name: (Loc, String),
@@ -415,6 +498,7 @@ pub enum StatementType {
     Expression(Expression),
     Drop(Expression),
     ForLoop(ForLoop),
+    IfElse(IfElse),
 }
 
 #[derive(Debug, Clone)]
@@ -432,6 +516,17 @@ pub struct ForLoop {
     pub loc: Loc,
 }
 
+/// An `IF cond { ... } ELSE { ... }` block.
+///
+/// `ELSE IF` is parsed as an else branch holding a single nested [`IfElse`] statement.
+#[derive(Debug, Clone)]
+pub struct IfElse {
+    pub condition: Expression,
+    pub then_statements: Vec<Statement>,
+    pub else_statements: Vec<Statement>,
+    pub loc: Loc,
+}
+
 #[derive(Debug, Clone)]
 pub enum ForLoopVars {
     Identifier {
@@ -566,6 +661,117 @@ pub struct MathFunctionCall {
     pub loc: Loc,
 }
 
+/// String function types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringFunction {
+    Lower,
+    Upper,
+    Trim,
+    Length,
+    Substring, // SUBSTRING(s, start) or SUBSTRING(s, start, length)
+    Concat,    // Variadic
+}
+
+impl StringFunction {
+    /// Returns the smallest and largest number of arguments this function takes
+    pub fn arity(&self) -> (usize, usize) {
+        match self {
+            StringFunction::Lower
+            | StringFunction::Upper
+            | StringFunction::Trim
+            | StringFunction::Length => (1, 1),
+            StringFunction::Substring => (2, 3),
+            StringFunction::Concat => (1, usize::MAX),
+        }
+    }
+
+    /// Returns the function name as a string
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringFunction::Lower => "LOWER",
+            StringFunction::Upper => "UPPER",
+            StringFunction::Trim => "TRIM",
+            StringFunction::Length => "LENGTH",
+            StringFunction::Substring => "SUBSTRING",
+            StringFunction::Concat => "CONCAT",
+        }
+    }
+}
+
+/// String function call AST node
+#[derive(Debug, Clone)]
+pub struct StringFunctionCall {
+    pub function: StringFunction,
+    pub args: Vec<Expression>,
+    pub loc: Loc,
+}
+
+/// Date function types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateFunction {
+    Add,   // DATE_ADD(date, duration)
+    Sub,   // DATE_SUB(date, duration)
+    Trunc, // DATE_TRUNC(unit, date) or DATE_TRUNC(unit, date, timezone)
+    Date,  // DATE(text) or DATE(text, timezone)
+    Year,
+    Month,
+    Day,
+    Hour,
+}
+
+impl DateFunction {
+    /// Returns the smallest and largest number of arguments this function takes
+    pub fn arity(&self) -> (usize, usize) {
+        match self {
+            DateFunction::Add | DateFunction::Sub => (2, 2),
+            DateFunction::Trunc => (2, 3),
+            DateFunction::Date
+            | DateFunction::Year
+            | DateFunction::Month
+            | DateFunction::Day
+            | DateFunction::Hour => (1, 2),
+        }
+    }
+
+    /// Returns the function name as a string
+    pub fn name(&self) -> &'static str {
+        match self {
+            DateFunction::Add => "DATE_ADD",
+            DateFunction::Sub => "DATE_SUB",
+            DateFunction::Trunc => "DATE_TRUNC",
+            DateFunction::Date => "DATE",
+            DateFunction::Year => "YEAR",
+            DateFunction::Month => "MONTH",
+            DateFunction::Day => "DAY",
+            DateFunction::Hour => "HOUR",
+        }
+    }
+
+    /// Whether the function extracts a number from a date rather than producing a date
+    pub fn is_extraction(&self) -> bool {
+        matches!(
+            self,
+            DateFunction::Year | DateFunction::Month | DateFunction::Day | DateFunction::Hour
+        )
+    }
+}
+
+/// Date function call AST node
+#[derive(Debug, Clone)]
+pub struct DateFunctionCall {
+    pub function: DateFunction,
+    pub args: Vec<Expression>,
+    pub loc: Loc,
+}
+
+/// A boolean operation applied to the result of a function call,
+/// e.g. `YEAR(_::{created_at})::EQ(2024)`
+#[derive(Debug, Clone)]
+pub struct ComputedPredicate {
+    pub value: Box<Expression>,
+    pub op: BooleanOp,
+}
+
 #[derive(Clone)]
 pub enum ExpressionType {
     Traversal(Box<Traversal>),
@@ -579,12 +785,20 @@ pub enum ExpressionType {
     AddVector(AddVector),
     AddNode(AddNode),
     AddEdge(AddEdge),
+    UpsertNode(UpsertNode),
+    UpsertEdge(UpsertEdge),
     Not(Box<Expression>),
     And(Vec<Expression>),
     Or(Vec<Expression>),
     SearchVector(SearchVector),
     BM25Search(BM25Search),
     MathFunctionCall(MathFunctionCall),
+    StringFunctionCall(StringFunctionCall),
+    DateFunctionCall(DateFunctionCall),
+    ComputedPredicate(ComputedPredicate),
+    Now,
+    /// A duration literal such as `7d`, in milliseconds
+    Duration(i64),
     Empty,
 }
 
@@ -610,12 +824,19 @@ impl Debug for ExpressionType {
             ExpressionType::AddVector(av) => write!(f, "AddVector({av:?})"),
             ExpressionType::AddNode(an) => write!(f, "AddNode({an:?})"),
             ExpressionType::AddEdge(ae) => write!(f, "AddEdge({ae:?})"),
+            ExpressionType::UpsertNode(un) => write!(f, "UpsertNode({un:?})"),
+            ExpressionType::UpsertEdge(ue) => write!(f, "UpsertEdge({ue:?})"),
             ExpressionType::Not(expr) => write!(f, "Not({expr:?})"),
             ExpressionType::And(exprs) => write!(f, "And({exprs:?})"),
             ExpressionType::Or(exprs) => write!(f, "Or({exprs:?})"),
             ExpressionType::SearchVector(sv) => write!(f, "SearchVector({sv:?})"),
             ExpressionType::BM25Search(bm25) => write!(f, "BM25Search({bm25:?})"),
             ExpressionType::MathFunctionCall(mfc) => write!(f, "MathFunctionCall({mfc:?})"),
+            ExpressionType::StringFunctionCall(sfc) => write!(f, "StringFunctionCall({sfc:?})"),
+            ExpressionType::DateFunctionCall(dfc) => write!(f, "DateFunctionCall({dfc:?})"),
+            ExpressionType::ComputedPredicate(cp) => write!(f, "ComputedPredicate({cp:?})"),
+            ExpressionType::Now => write!(f, "Now"),
+            ExpressionType::Duration(ms) => write!(f, "Duration({ms})"),
             ExpressionType::Empty => write!(f, "Empty"),
         }
     }
@@ -634,12 +855,19 @@ impl Display for ExpressionType {
             ExpressionType::AddVector(av) => write!(f, "AddVector({av:?})"),
             ExpressionType::AddNode(an) => write!(f, "AddNode({an:?})"),
             ExpressionType::AddEdge(ae) => write!(f, "AddEdge({ae:?})"),
+            ExpressionType::UpsertNode(un) => write!(f, "UpsertNode({un:?})"),
+            ExpressionType::UpsertEdge(ue) => write!(f, "UpsertEdge({ue:?})"),
             ExpressionType::Not(expr) => write!(f, "Not({expr:?})"),
             ExpressionType::And(exprs) => write!(f, "And({exprs:?})"),
             ExpressionType::Or(exprs) => write!(f, "Or({exprs:?})"),
             ExpressionType::SearchVector(sv) => write!(f, "SearchVector({sv:?})"),
             ExpressionType::BM25Search(bm25) => write!(f, "BM25Search({bm25:?})"),
             ExpressionType::MathFunctionCall(mfc) => write!(f, "{}({:?})", mfc.function.name(), mfc.args),
+            ExpressionType::StringFunctionCall(sfc) => write!(f, "{}({:?})", sfc.function.name(), sfc.args),
+            ExpressionType::DateFunctionCall(dfc) => write!(f, "{}({:?})", dfc.function.name(), dfc.args),
+            ExpressionType::ComputedPredicate(cp) => write!(f, "{}::{:?}", cp.value.expr, cp.op),
+            ExpressionType::Now => write!(f, "NOW"),
+            ExpressionType::Duration(ms) => write!(f, "{ms}ms"),
             ExpressionType::Empty => write!(f, "Empty"),
         }
     }
@@ -676,6 +904,7 @@ pub enum StartNode {
     },
     SearchVector(SearchVector),
     Identifier(String),
+    FragmentCall(FragmentCall),
     Anonymous,
 }
 
@@ -693,21 +922,65 @@ pub enum OrderByType {
 
 #[derive(Debug, Clone)]
 pub struct OrderBy {
+    pub loc: Loc,
+    /// The keys to order by, with ties in a key broken by the keys after it.
+    pub keys: Vec<OrderByKey>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderByKey {
     pub loc: Loc,
     pub order_by_type: OrderByType,
     pub expression: Box<Expression>,
+    pub nulls_first: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct Aggregate {
     pub loc: Loc,
-    pub properties: Vec<String>
+    pub properties: Vec<String>,
+    pub aggregations: Vec<AggregationField>,
 }
 
 #[derive(Debug, Clone)]
 pub struct GroupBy {
     pub loc: Loc,
-    pub properties: Vec<String>
+    pub properties: Vec<String>,
+    /// Keys computed from each item, e.g. `day: DATE_TRUNC("day", _::{created_at})`
+    pub computed: Vec<(String, DateFunctionCall)>,
+    pub aggregations: Vec<AggregationField>,
+}
+
+/// A named aggregation in the `{ name: SUM(property) }` block of a `GROUP_BY` or `AGGREGATE_BY`.
+#[derive(Debug, Clone)]
+pub struct AggregationField {
+    pub loc: Loc,
+    pub name: String,
+    pub function: AggregationFunction,
+    pub property: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregationFunction {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    CountDistinct,
+    Collect,
+}
+
+impl AggregationFunction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AggregationFunction::Sum => "SUM",
+            AggregationFunction::Avg => "AVG",
+            AggregationFunction::Min => "MIN",
+            AggregationFunction::Max => "MAX",
+            AggregationFunction::CountDistinct => "COUNT_DISTINCT",
+            AggregationFunction::Collect => "COLLECT",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -723,6 +996,53 @@ pub struct RerankMMR {
     pub distance: Option<MMRDistance>,
 }
 
+#[derive(Debug, Clone)]
+pub struct Centrality {
+    pub loc: Loc,
+    pub edge_type: String,
+    pub kind: CentralityKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum CentralityKind {
+    OutDegree,
+    InDegree,
+    Degree,
+    TriangleCount,
+    /// Optional number of sampled sources
+    Betweenness(Option<Expression>),
+}
+
+#[derive(Debug, Clone)]
+pub struct DagStep {
+    pub loc: Loc,
+    pub edge_type: String,
+    pub kind: DagStepKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum DagStepKind {
+    TopoSort,
+    HasCycle,
+}
+
+#[derive(Debug, Clone)]
+pub struct EgoGraph {
+    pub loc: Loc,
+    /// Edge labels to follow, every label when empty
+    pub edge_types: Vec<String>,
+    pub hops: Expression,
+    pub max_nodes: Option<Expression>,
+}
+
+/// `PAGE(size, cursor)`, a page of a traversal resumed from the cursor of the previous page
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub loc: Loc,
+    pub size: Expression,
+    pub cursor: Expression,
+}
+
 #[derive(Debug, Clone)]
 pub enum MMRDistance {
     Cosine,
@@ -743,6 +1063,7 @@ pub enum StepType {
     Exclude(Exclude),
     Closure(Closure),
     Range((Expression, Expression)),
+    Page(Page),
     OrderBy(OrderBy),
     Aggregate(Aggregate),
     GroupBy(GroupBy),
@@ -750,6 +1071,10 @@ pub enum StepType {
     First,
     RerankRRF(RerankRRF),
     RerankMMR(RerankMMR),
+    Centrality(Centrality),
+    EgoGraph(EgoGraph),
+    Dag(DagStep),
+    FragmentCall(FragmentCall),
 }
 impl PartialEq<StepType> for StepType {
     fn eq(&self, other: &StepType) -> bool {
@@ -768,12 +1093,17 @@ impl PartialEq<StepType> for StepType {
                 | (&StepType::Exclude(_), &StepType::Exclude(_))
                 | (&StepType::Closure(_), &StepType::Closure(_))
                 | (&StepType::Range(_), &StepType::Range(_))
+                | (&StepType::Page(_), &StepType::Page(_))
                 | (&StepType::OrderBy(_), &StepType::OrderBy(_))
                 | (&StepType::AddEdge(_), &StepType::AddEdge(_))
                 | (&StepType::Aggregate(_), &StepType::Aggregate(_))
                 | (&StepType::GroupBy(_), &StepType::GroupBy(_))
                 | (&StepType::RerankRRF(_), &StepType::RerankRRF(_))
                 | (&StepType::RerankMMR(_), &StepType::RerankMMR(_))
+                | (&StepType::Centrality(_), &StepType::Centrality(_))
+                | (&StepType::EgoGraph(_), &StepType::EgoGraph(_))
+                | (&StepType::Dag(_), &StepType::Dag(_))
+                | (&StepType::FragmentCall(_), &StepType::FragmentCall(_))
         )
     }
 }
@@ -907,6 +1237,12 @@ pub enum BooleanOpType {
     NotEqual(Box<Expression>),
     Contains(Box<Expression>),
     IsIn(Box<Expression>),
+    IsNull,
+    IsNotNull,
+    StartsWith(Box<Expression>),
+    EndsWith(Box<Expression>),
+    EqualIgnoreCase(Box<Expression>),
+    Regex(Box<Expression>),
 }
 
 #[derive(Debug, Clone)]
@@ -991,6 +1327,24 @@ pub struct AddEdge {
     pub from_identifier: bool,
 }
 
+/// `UpsertN<Type>({key: value}, {field: value, ...})`
+#[derive(Debug, Clone)]
+pub struct UpsertNode {
+    pub loc: Loc,
+    /// The indexed field the node is looked up by
+    pub key: (Loc, String),
+    /// The node added when none has the key, holding the key and the other fields
+    pub node: AddNode,
+}
+
+/// `UpsertE<Type>({field: value, ...})::From(a)::To(b)`
+#[derive(Debug, Clone)]
+pub struct UpsertEdge {
+    pub loc: Loc,
+    /// The edge added when none connects the two nodes
+    pub edge: AddEdge,
+}
+
 #[derive(Debug, Clone)]
 pub struct EdgeConnection {
     pub loc: Loc,
@@ -54,9 +54,7 @@ impl HelixParser {
             .next()
             .ok_or_else(|| ParserError::from("Empty string literal"))?;
 
-        let mut literal = inner.as_str().to_string();
-        literal.retain(|c| c != '"');
-        Ok(literal)
+        unescape_string(inner.as_str())
     }
 
     pub(super) fn parse_to_from(&self, pair: Pair<Rule>) -> Result<EdgeConnection, ParserError> {
@@ -83,6 +81,49 @@ impl HelixParser {
     }
 }
 
+/// Resolves the escape sequences of a string literal's contents (`\"`, `\\`, `\n`, `\t`, `\r`,
+/// `\0` and `\u{..}`) into the string the query uses at runtime. The generator escapes it again
+/// when it emits it, as a `GenRef::Literal` written with `escape_debug`.
+fn unescape_string(literal: &str) -> Result<String, ParserError> {
+    let mut unescaped = String::with_capacity(literal.len());
+    let mut chars = literal.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => unescaped.push('"'),
+            Some('\\') => unescaped.push('\\'),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some('0') => unescaped.push('\0'),
+            Some('u') => {
+                let code = chars
+                    .by_ref()
+                    .skip_while(|c| *c == '{')
+                    .take_while(|c| *c != '}')
+                    .collect::<String>();
+                let c = u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| {
+                        ParserError::from(format!("Invalid unicode escape '\\u{{{code}}}'"))
+                    })?;
+                unescaped.push(c);
+            }
+            Some(other) => {
+                return Err(ParserError::from(format!(
+                    "Invalid escape sequence '\\{other}'"
+                )));
+            }
+            None => return Err(ParserError::from("Unterminated escape sequence")),
+        }
+    }
+    Ok(unescaped)
+}
+
 pub trait PairTools<'a> {
     /// Equivalent to into_inner().next()
     #[track_caller]
@@ -3,11 +3,17 @@
 //! This is a wrapper around a chrono DateTime<Utc>.
 //!
 //! It is used to deserialize a string date or numeric timestamp into a chrono DateTime<Utc>.
+//! Dates are truncated and split into parts in a [`DateZone`], so calendar boundaries follow the
+//! caller's time zone rather than UTC.
 
 use core::fmt;
 use std::ops::Deref;
 
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
 use serde::{Deserializer, Serialize, de::Visitor};
 use sonic_rs::Deserialize;
 
@@ -26,26 +32,68 @@ impl Date {
         self.0.to_rfc3339()
     }
 
+    /// Parses a date from text.
+    ///
+    /// Accepts RFC 3339 timestamps, plain dates and times without an offset, and either of the
+    /// latter followed by a time zone name (`2024-03-10 09:00 America/New_York`). Text without
+    /// an offset or zone name is read in `zone`.
+    pub fn parse_in(text: &str, zone: DateZone) -> Result<Self, DateError> {
+        let text = text.trim();
+        if let Ok(date) = text.parse::<DateTime<Utc>>() {
+            return Ok(Date(date));
+        }
+        let (local, zone) = match text.rsplit_once(' ') {
+            Some((local, name)) => match DateZone::parse(name) {
+                Some(named) => (local.trim_end(), named),
+                None => (text, zone),
+            },
+            None => (text, zone),
+        };
+        let local = parse_naive(local)
+            .ok_or_else(|| DateError::ParseError(format!("`{text}` is not a valid date")))?;
+        zone.from_local(&local)
+            .map(Date)
+            .ok_or_else(|| DateError::ParseError(format!("`{text}` does not exist in {zone}")))
+    }
+
+    /// Truncates the date to the start of the `unit` it falls in, as seen in `zone`.
+    /// Weeks start on Monday.
+    pub fn truncate(&self, unit: DateUnit, zone: DateZone) -> Option<Self> {
+        let local = zone.to_local(&self.0);
+        let date = local.date();
+        let truncated = match unit {
+            DateUnit::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1)?.and_time(NaiveTime::MIN),
+            DateUnit::Month => {
+                NaiveDate::from_ymd_opt(date.year(), date.month(), 1)?.and_time(NaiveTime::MIN)
+            }
+            DateUnit::Week => date.week(Weekday::Mon).first_day().and_time(NaiveTime::MIN),
+            DateUnit::Day => date.and_time(NaiveTime::MIN),
+            DateUnit::Hour => date.and_hms_opt(local.hour(), 0, 0)?,
+            DateUnit::Minute => date.and_hms_opt(local.hour(), local.minute(), 0)?,
+            DateUnit::Second => date.and_hms_opt(local.hour(), local.minute(), local.second())?,
+        };
+        zone.from_local(&truncated).map(Date)
+    }
+
+    /// The `unit` part of the date as seen in `zone`, e.g. the month number for
+    /// [`DateUnit::Month`]. Weeks are ISO week numbers.
+    pub fn part(&self, unit: DateUnit, zone: DateZone) -> i64 {
+        let local = zone.to_local(&self.0);
+        (match unit {
+            DateUnit::Year => return local.year() as i64,
+            DateUnit::Month => local.month(),
+            DateUnit::Week => local.iso_week().week(),
+            DateUnit::Day => local.day(),
+            DateUnit::Hour => local.hour(),
+            DateUnit::Minute => local.minute(),
+            DateUnit::Second => local.second(),
+        }) as i64
+    }
+
     /// Creates a new Date from a Value.
     pub fn new(date: &Value) -> Result<Self, DateError> {
         match date {
-            Value::String(date) => {
-                let date = match date.parse::<DateTime<Utc>>() {
-                    Ok(date) => date.with_timezone(&Utc),
-                    Err(e) => match date.parse::<NaiveDate>() {
-                        Ok(date) => match date.and_hms_opt(0, 0, 0) {
-                            Some(date) => date.and_utc(),
-                            None => {
-                                return Err(DateError::ParseError(e.to_string()));
-                            }
-                        },
-                        Err(e) => {
-                            return Err(DateError::ParseError(e.to_string()));
-                        }
-                    },
-                };
-                Ok(Date(date))
-            }
+            Value::String(date) => Date::parse_in(date, DateZone::default()),
             Value::I64(date) => {
                 let date = match DateTime::from_timestamp(*date, 0) {
                     Some(date) => date,
@@ -89,21 +137,7 @@ impl<'de> Visitor<'de> for DateVisitor {
     where
         E: serde::de::Error,
     {
-        let date = match v.parse::<DateTime<Utc>>() {
-            Ok(date) => date.with_timezone(&Utc),
-            Err(e) => match v.parse::<NaiveDate>() {
-                Ok(date) => match date.and_hms_opt(0, 0, 0) {
-                    Some(date) => date.and_utc(),
-                    None => {
-                        return Err(E::custom(e.to_string()));
-                    }
-                },
-                Err(e) => {
-                    return Err(E::custom(e.to_string()));
-                }
-            },
-        };
-        Ok(Date(date))
+        Date::parse_in(v, DateZone::default()).map_err(E::custom)
     }
 
     /// Visits a i64 and parses it into a chrono DateTime<Utc>.
@@ -160,6 +194,120 @@ impl fmt::Display for DateError {
     }
 }
 
+/// Parses a date or date and time without an offset, using `T` or a space as the separator.
+fn parse_naive(text: &str) -> Option<NaiveDateTime> {
+    const FORMATS: [&str; 4] = [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            text.parse::<NaiveDate>()
+                .ok()
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+}
+
+/// A calendar unit that dates are truncated to or split into.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DateUnit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateUnit {
+    /// Parses a unit name such as `"day"` or `"days"`, ignoring case.
+    pub fn parse(unit: &str) -> Option<Self> {
+        let unit = unit.trim().to_ascii_lowercase();
+        Some(match unit.strip_suffix('s').unwrap_or(&unit) {
+            "year" => DateUnit::Year,
+            "month" => DateUnit::Month,
+            "week" => DateUnit::Week,
+            "day" => DateUnit::Day,
+            "hour" => DateUnit::Hour,
+            "minute" => DateUnit::Minute,
+            "second" => DateUnit::Second,
+            _ => return None,
+        })
+    }
+}
+
+/// The time zone a date is read or split in: UTC, a fixed offset such as `+05:30`, or an IANA
+/// zone such as `Europe/London` whose offset follows daylight saving time.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DateZone {
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+impl Default for DateZone {
+    fn default() -> Self {
+        DateZone::Fixed(FixedOffset::east_opt(0).expect("zero offset is valid"))
+    }
+}
+
+impl DateZone {
+    /// Parses `UTC`, `Z`, an offset (`+05:30`, `-0800`) or an IANA zone name.
+    pub fn parse(zone: &str) -> Option<Self> {
+        let zone = zone.trim();
+        if zone.eq_ignore_ascii_case("utc") || zone.eq_ignore_ascii_case("z") {
+            return Some(DateZone::default());
+        }
+        if zone.starts_with(['+', '-']) {
+            return zone.parse::<FixedOffset>().ok().map(DateZone::Fixed);
+        }
+        zone.parse::<Tz>().ok().map(DateZone::Named)
+    }
+
+    /// The wall-clock time of `date` in this zone.
+    pub fn to_local(&self, date: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            DateZone::Fixed(offset) => date.with_timezone(offset).naive_local(),
+            DateZone::Named(tz) => date.with_timezone(tz).naive_local(),
+        }
+    }
+
+    /// The instant a wall-clock time in this zone refers to. Ambiguous times resolve to the
+    /// earlier instant; times skipped by a daylight saving change do not exist.
+    pub fn from_local(&self, local: &NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            DateZone::Fixed(offset) => offset
+                .from_local_datetime(local)
+                .earliest()
+                .map(|date| date.with_timezone(&Utc)),
+            DateZone::Named(tz) => tz
+                .from_local_datetime(local)
+                .earliest()
+                .map(|date| date.with_timezone(&Utc)),
+        }
+    }
+}
+
+impl fmt::Display for DateZone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateZone::Fixed(offset) if offset.local_minus_utc() == 0 => write!(f, "UTC"),
+            DateZone::Fixed(offset) => write!(f, "{offset}"),
+            DateZone::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for Date {
+    fn from(date: DateTime<Utc>) -> Self {
+        Date(date)
+    }
+}
+
 impl Deref for Date {
     type Target = DateTime<Utc>;
 
@@ -209,6 +357,66 @@ mod tests {
         assert_eq!(serialized, "\"2021-01-01T00:00:00+00:00\"");
     }
 
+    #[test]
+    fn test_parse_in_zone() {
+        let new_york = DateZone::parse("America/New_York").unwrap();
+        let date = Date::parse_in("2024-07-01 09:30", new_york).unwrap();
+        assert_eq!(date.to_rfc3339(), "2024-07-01T13:30:00+00:00");
+
+        // an explicit offset or zone name wins over the default zone
+        let date = Date::parse_in("2024-07-01T09:30:00+02:00", new_york).unwrap();
+        assert_eq!(date.to_rfc3339(), "2024-07-01T07:30:00+00:00");
+        let date = Date::parse_in("2024-01-01T09:30:00 Asia/Tokyo", DateZone::default()).unwrap();
+        assert_eq!(date.to_rfc3339(), "2024-01-01T00:30:00+00:00");
+
+        // 02:30 is skipped when New York springs forward
+        assert!(Date::parse_in("2024-03-10 02:30", new_york).is_err());
+        assert!(Date::parse_in("not a date", new_york).is_err());
+    }
+
+    #[test]
+    fn test_zone_parse() {
+        assert_eq!(DateZone::parse("utc"), Some(DateZone::default()));
+        assert_eq!(
+            DateZone::parse("+05:30").unwrap().to_string(),
+            "+05:30"
+        );
+        assert_eq!(
+            DateZone::parse("Europe/London").unwrap().to_string(),
+            "Europe/London"
+        );
+        assert_eq!(DateZone::parse("Mars/Olympus"), None);
+    }
+
+    #[test]
+    fn test_truncate_and_part() {
+        let date = Date::new(&Value::String("2024-03-14T01:45:30Z".to_string())).unwrap();
+        let utc = DateZone::default();
+        let truncated = |unit| date.truncate(unit, utc).unwrap().to_rfc3339();
+        assert_eq!(truncated(DateUnit::Year), "2024-01-01T00:00:00+00:00");
+        assert_eq!(truncated(DateUnit::Month), "2024-03-01T00:00:00+00:00");
+        assert_eq!(truncated(DateUnit::Week), "2024-03-11T00:00:00+00:00");
+        assert_eq!(truncated(DateUnit::Day), "2024-03-14T00:00:00+00:00");
+        assert_eq!(truncated(DateUnit::Hour), "2024-03-14T01:00:00+00:00");
+        assert_eq!(date.part(DateUnit::Day, utc), 14);
+        assert_eq!(date.part(DateUnit::Week, utc), 11);
+
+        // in Los Angeles it is still the evening of the 13th
+        let los_angeles = DateZone::parse("America/Los_Angeles").unwrap();
+        assert_eq!(date.part(DateUnit::Day, los_angeles), 13);
+        assert_eq!(
+            date.truncate(DateUnit::Day, los_angeles).unwrap().to_rfc3339(),
+            "2024-03-13T07:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_unit_parse() {
+        assert_eq!(DateUnit::parse("Day"), Some(DateUnit::Day));
+        assert_eq!(DateUnit::parse("weeks"), Some(DateUnit::Week));
+        assert_eq!(DateUnit::parse("fortnight"), None);
+    }
+
     #[test]
     fn test_rfc3339_deserialization() {
         let date = Date::new(&Value::String("2021-01-01T00:00:00Z".to_string())).unwrap();
@@ -1,4 +1,6 @@
-use axum::{body::Body, response::IntoResponse};
+use axum::{body::Body, http::StatusCode, response::IntoResponse};
+use serde::Serialize;
+use sonic_rs::{Value, json};
 use thiserror::Error;
 
 use crate::{
@@ -14,18 +16,199 @@ pub enum HelixError {
     Vector(#[from] VectorError),
     #[error("Couldn't find `{name}` of type {ty:?}")]
     NotFound { ty: RequestType, name: String },
+    #[error("Missing or invalid API key")]
+    Unauthorized,
+    #[error("API key is not permitted to run `{name}`")]
+    Forbidden { name: String },
+    #[error("`{name}` timed out before it started running")]
+    RequestTimeout { name: String },
+    #[error("The database is not ready: {0}")]
+    NotReady(String),
+    #[error("The change log is not enabled")]
+    ChangeLogDisabled,
+    #[error("Changes after {since} were pruned, the oldest kept change is {oldest}")]
+    ChangesExpired { since: u64, oldest: u64 },
+    #[error("Batch step {step} (`{query}`) failed: {source}")]
+    BatchStep {
+        step: usize,
+        query: String,
+        source: Box<HelixError>,
+    },
+}
+
+/// The JSON body returned for every failed request.
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    /// Stable, machine-readable error code, e.g. `NODE_NOT_FOUND`
+    pub code: &'static str,
+    /// Human-readable description of the error
+    pub message: String,
+    /// Extra structured context, `null` when there is none
+    pub details: Value,
+}
+
+impl HelixError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            HelixError::Graph(e) => graph_error_status(e),
+            HelixError::Vector(e) => vector_error_status(e),
+            HelixError::NotFound { .. } => StatusCode::NOT_FOUND,
+            HelixError::Unauthorized => StatusCode::UNAUTHORIZED,
+            HelixError::Forbidden { .. } => StatusCode::FORBIDDEN,
+            HelixError::RequestTimeout { .. } => StatusCode::REQUEST_TIMEOUT,
+            HelixError::NotReady(_) => StatusCode::SERVICE_UNAVAILABLE,
+            HelixError::ChangeLogDisabled => StatusCode::NOT_FOUND,
+            HelixError::ChangesExpired { .. } => StatusCode::GONE,
+            HelixError::BatchStep { source, .. } => source.status(),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            HelixError::Graph(e) => graph_error_code(e),
+            HelixError::Vector(e) => vector_error_code(e),
+            HelixError::NotFound {
+                ty: RequestType::Query | RequestType::Batch,
+                ..
+            } => "QUERY_NOT_FOUND",
+            HelixError::NotFound {
+                ty: RequestType::MCP,
+                ..
+            } => "MCP_TOOL_NOT_FOUND",
+            HelixError::Unauthorized => "UNAUTHORIZED",
+            HelixError::Forbidden { .. } => "FORBIDDEN",
+            HelixError::RequestTimeout { .. } => "REQUEST_TIMEOUT",
+            HelixError::NotReady(_) => "NOT_READY",
+            HelixError::ChangeLogDisabled => "CHANGE_LOG_DISABLED",
+            HelixError::ChangesExpired { .. } => "CHANGES_EXPIRED",
+            HelixError::BatchStep { source, .. } => source.code(),
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            HelixError::Graph(GraphError::ParamNotFound(param)) => json!({ "param": param }),
+            HelixError::Vector(
+                VectorError::VectorNotFound(id) | VectorError::VectorAlreadyDeleted(id),
+            ) => json!({ "id": id }),
+            HelixError::NotFound { ty, name } => {
+                json!({ "type": format!("{ty:?}"), "name": name })
+            }
+            HelixError::Forbidden { name } | HelixError::RequestTimeout { name } => {
+                json!({ "name": name })
+            }
+            HelixError::ChangesExpired { since, oldest } => {
+                json!({ "since": since, "oldest": oldest })
+            }
+            HelixError::BatchStep {
+                step,
+                query,
+                source,
+            } => json!({ "step": step, "query": query, "details": source.details() }),
+            _ => Value::new(),
+        }
+    }
+
+    pub fn to_body(&self) -> ErrorBody {
+        ErrorBody {
+            code: self.code(),
+            message: self.to_string(),
+            details: self.details(),
+        }
+    }
+}
+
+fn graph_error_status(err: &GraphError) -> StatusCode {
+    match err {
+        GraphError::DecodeError(_)
+        | GraphError::ConversionError(_)
+        | GraphError::ParamNotFound(_)
+        | GraphError::InvalidCursor(_) => StatusCode::BAD_REQUEST,
+        GraphError::NodeNotFound
+        | GraphError::EdgeNotFound
+        | GraphError::LabelNotFound
+        | GraphError::ShortestPathNotFound => StatusCode::NOT_FOUND,
+        GraphError::CycleDetected(_)
+        | GraphError::CardinalityViolation(_)
+        | GraphError::MultipleNodesWithSameId
+        | GraphError::MultipleEdgesWithSameId => StatusCode::CONFLICT,
+        GraphError::InvalidNode | GraphError::SliceLengthError => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        GraphError::QueryTimeout => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn graph_error_code(err: &GraphError) -> &'static str {
+    match err {
+        GraphError::Io(_) => "IO_ERROR",
+        GraphError::GraphConnectionError(..) => "GRAPH_CONNECTION_ERROR",
+        GraphError::StorageConnectionError(..) => "STORAGE_CONNECTION_ERROR",
+        GraphError::StorageError(_) => "STORAGE_ERROR",
+        GraphError::TraversalError(_) => "TRAVERSAL_ERROR",
+        GraphError::ConversionError(_) => "CONVERSION_ERROR",
+        GraphError::DecodeError(_) => "DECODE_ERROR",
+        GraphError::EdgeNotFound => "EDGE_NOT_FOUND",
+        GraphError::NodeNotFound => "NODE_NOT_FOUND",
+        GraphError::LabelNotFound => "LABEL_NOT_FOUND",
+        GraphError::VectorError(_) => "VECTOR_ERROR",
+        GraphError::MultipleNodesWithSameId => "DUPLICATE_NODE_ID",
+        GraphError::MultipleEdgesWithSameId => "DUPLICATE_EDGE_ID",
+        GraphError::InvalidNode => "INVALID_NODE",
+        GraphError::ConfigFileNotFound => "CONFIG_FILE_NOT_FOUND",
+        GraphError::SliceLengthError => "SLICE_LENGTH_ERROR",
+        GraphError::ShortestPathNotFound => "PATH_NOT_FOUND",
+        GraphError::EmbeddingError(_) => "EMBEDDING_ERROR",
+        GraphError::ParamNotFound(_) => "MISSING_PARAMETER",
+        GraphError::RerankerError(_) => "RERANKER_ERROR",
+        GraphError::CycleDetected(_) => "CYCLE_DETECTED",
+        GraphError::CardinalityViolation(_) => "CARDINALITY_VIOLATION",
+        GraphError::QueryTimeout => "QUERY_TIMEOUT",
+        GraphError::InvalidCursor(_) => "INVALID_CURSOR",
+        GraphError::Default | GraphError::New(_) | GraphError::Empty | GraphError::IoNeeded(_) => {
+            "INTERNAL_ERROR"
+        }
+    }
+}
+
+fn vector_error_status(err: &VectorError) -> StatusCode {
+    match err {
+        VectorError::VectorNotFound(_) | VectorError::VectorDeleted => StatusCode::NOT_FOUND,
+        VectorError::VectorAlreadyDeleted(_) => StatusCode::CONFLICT,
+        VectorError::InvalidVectorLength | VectorError::InvalidVectorData => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        VectorError::ConversionError(_) => StatusCode::BAD_REQUEST,
+        VectorError::QueryTimeout => StatusCode::GATEWAY_TIMEOUT,
+        VectorError::EntryPointNotFound | VectorError::VectorCoreError(_) => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+fn vector_error_code(err: &VectorError) -> &'static str {
+    match err {
+        VectorError::VectorNotFound(_) => "VECTOR_NOT_FOUND",
+        VectorError::VectorDeleted => "VECTOR_DELETED",
+        VectorError::InvalidVectorLength => "INVALID_VECTOR_LENGTH",
+        VectorError::InvalidVectorData => "INVALID_VECTOR_DATA",
+        VectorError::EntryPointNotFound => "ENTRY_POINT_NOT_FOUND",
+        VectorError::ConversionError(_) => "CONVERSION_ERROR",
+        VectorError::VectorCoreError(_) => "VECTOR_CORE_ERROR",
+        VectorError::VectorAlreadyDeleted(_) => "VECTOR_ALREADY_DELETED",
+        VectorError::QueryTimeout => "QUERY_TIMEOUT",
+    }
 }
 
 impl IntoResponse for HelixError {
     fn into_response(self) -> axum::response::Response {
-        let body = self.to_string();
-        let code = match &self {
-            HelixError::Graph(_) | HelixError::Vector(_) => 500,
-            HelixError::NotFound { .. } => 404,
-        };
+        let body = sonic_rs::to_vec(&self.to_body())
+            .unwrap_or_else(|_| panic!("Should be able to serialize HelixError: {self}"));
 
         axum::response::Response::builder()
-            .status(code)
+            .status(self.status())
+            .header("Content-Type", "application/json")
             .body(Body::from(body))
             .unwrap_or_else(|_| panic!("Should be able to turn HelixError into Response: {self}"))
     }
@@ -34,6 +217,7 @@ impl IntoResponse for HelixError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sonic_rs::JsonValueTrait;
 
     // ============================================================================
     // HelixError Variant Tests
@@ -102,7 +286,7 @@ mod tests {
         let helix_err = HelixError::from(graph_err);
 
         let response = helix_err.into_response();
-        assert_eq!(response.status(), 500);
+        assert_eq!(response.status(), 400);
     }
 
     #[test]
@@ -111,7 +295,92 @@ mod tests {
         let helix_err = HelixError::from(vector_err);
 
         let response = helix_err.into_response();
-        assert_eq!(response.status(), 500);
+        assert_eq!(response.status(), 422);
+    }
+
+    #[test]
+    fn test_helix_error_status_mapping() {
+        let cases = [
+            (GraphError::ParamNotFound("id"), StatusCode::BAD_REQUEST),
+            (GraphError::InvalidCursor("stale".into()), StatusCode::BAD_REQUEST),
+            (GraphError::NodeNotFound, StatusCode::NOT_FOUND),
+            (GraphError::EdgeNotFound, StatusCode::NOT_FOUND),
+            (GraphError::CycleDetected("a -> b".into()), StatusCode::CONFLICT),
+            (GraphError::CardinalityViolation("x".into()), StatusCode::CONFLICT),
+            (GraphError::InvalidNode, StatusCode::UNPROCESSABLE_ENTITY),
+            (GraphError::StorageError("disk".into()), StatusCode::INTERNAL_SERVER_ERROR),
+            (GraphError::New("oops".into()), StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+        for (err, status) in cases {
+            assert_eq!(HelixError::from(err).status(), status);
+        }
+        assert_eq!(
+            HelixError::from(VectorError::VectorNotFound("1".into())).status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    // ============================================================================
+    // Error Envelope Tests
+    // ============================================================================
+
+    async fn response_json(error: HelixError) -> (StatusCode, sonic_rs::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "application/json"
+        );
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, sonic_rs::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_error_envelope_node_not_found() {
+        let (status, body) = response_json(GraphError::NodeNotFound.into()).await;
+        assert_eq!(status, 404);
+        assert_eq!(body["code"].as_str(), Some("NODE_NOT_FOUND"));
+        assert_eq!(body["message"].as_str(), Some("Node not found"));
+        assert!(body["details"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_error_envelope_missing_param_details() {
+        let (status, body) = response_json(GraphError::ParamNotFound("user_id").into()).await;
+        assert_eq!(status, 400);
+        assert_eq!(body["code"].as_str(), Some("MISSING_PARAMETER"));
+        assert_eq!(body["details"]["param"].as_str(), Some("user_id"));
+    }
+
+    #[tokio::test]
+    async fn test_error_envelope_route_not_found() {
+        let error = HelixError::NotFound {
+            ty: RequestType::MCP,
+            name: "missing_tool".to_string(),
+        };
+        let (status, body) = response_json(error).await;
+        assert_eq!(status, 404);
+        assert_eq!(body["code"].as_str(), Some("MCP_TOOL_NOT_FOUND"));
+        assert_eq!(body["details"]["type"].as_str(), Some("MCP"));
+        assert_eq!(body["details"]["name"].as_str(), Some("missing_tool"));
+
+        let error = HelixError::NotFound {
+            ty: RequestType::Query,
+            name: "missing_query".to_string(),
+        };
+        let (_, body) = response_json(error).await;
+        assert_eq!(body["code"].as_str(), Some("QUERY_NOT_FOUND"));
+    }
+
+    #[tokio::test]
+    async fn test_error_envelope_conflict() {
+        let error = GraphError::CardinalityViolation("user already has a city".to_string());
+        let (status, body) = response_json(error.into()).await;
+        assert_eq!(status, 409);
+        assert_eq!(body["code"].as_str(), Some("CARDINALITY_VIOLATION"));
+        assert!(body["message"].as_str().unwrap().contains("user already has a city"));
     }
 
     // ============================================================================
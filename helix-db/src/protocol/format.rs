@@ -17,6 +17,13 @@ pub enum Format {
     /// The current implementation uses sonic_rs
     #[default]
     Json,
+    /// MessagePack, encoded with rmp_serde in human-readable mode so that values, IDs and dates
+    /// have the same shape as in JSON
+    MessagePack,
+    /// CBOR (RFC 8949), encoded with ciborium
+    /// ciborium always uses its compact mode, so values are transcoded through a serde_json tree
+    /// to keep the same shape as in JSON
+    Cbor,
 }
 
 /// Methods using to format for serialization/deserialization
@@ -31,6 +38,19 @@ impl Format {
     pub fn serialize<T: Serialize>(self, val: &T) -> Cow<'_, [u8]> {
         match self {
             Format::Json => sonic_rs::to_vec(val).unwrap().into(),
+            Format::MessagePack => {
+                let mut buf = Vec::new();
+                let mut serializer = rmp_serde::Serializer::new(&mut buf)
+                    .with_struct_map()
+                    .with_human_readable();
+                val.serialize(&mut serializer).unwrap();
+                buf.into()
+            }
+            Format::Cbor => {
+                let mut buf = Vec::new();
+                ciborium::into_writer(&serde_json::to_value(val).unwrap(), &mut buf).unwrap();
+                buf.into()
+            }
         }
     }
 
@@ -46,6 +66,9 @@ impl Format {
                 let encoded = sonic_rs::to_vec(val)?;
                 writer.write_all(&encoded).await?;
             }
+            Format::MessagePack | Format::Cbor => {
+                writer.write_all(&self.serialize(val)).await?;
+            }
         }
         Ok(())
     }
@@ -54,9 +77,63 @@ impl Format {
         Response {
             body: self.serialize(val).to_vec(),
             fmt: self,
+            stream: None,
+        }
+    }
+
+    /// Appends the value to `buf` as one item of a streamed response, see [`super::stream`].
+    /// Items are framed so they can be read one at a time: JSON as one line each (NDJSON),
+    /// MessagePack behind its length as a big-endian u32, and CBOR as a CBOR sequence (RFC 8742).
+    pub fn serialize_item<T: Serialize>(
+        self,
+        val: &T,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), GraphError> {
+        match self {
+            Format::Json => {
+                sonic_rs::to_writer(&mut *buf, val)?;
+                buf.push(b'\n');
+            }
+            Format::MessagePack => {
+                let start = buf.len();
+                buf.extend_from_slice(&[0; 4]);
+                let mut serializer = rmp_serde::Serializer::new(&mut *buf)
+                    .with_struct_map()
+                    .with_human_readable();
+                val.serialize(&mut serializer)
+                    .map_err(|e| GraphError::ConversionError(format!("msgpack error: {e}")))?;
+                let len = u32::try_from(buf.len() - start - 4).map_err(|_| {
+                    GraphError::ConversionError("streamed item is over 4GiB".to_string())
+                })?;
+                buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+            }
+            Format::Cbor => {
+                let tree = serde_json::to_value(val)
+                    .map_err(|e| GraphError::ConversionError(format!("serde_json error: {e}")))?;
+                ciborium::into_writer(&tree, &mut *buf)
+                    .map_err(|e| GraphError::ConversionError(format!("cbor error: {e}")))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Content type of a streamed response made of items written by [`Format::serialize_item`]
+    pub fn stream_content_type(self) -> &'static str {
+        match self {
+            Format::Json => "application/x-ndjson",
+            Format::MessagePack => "application/x-msgpack-stream",
+            Format::Cbor => "application/cbor-seq",
         }
     }
 
+    /// Parses the content type of a streamed response, ignoring parameters like [`FromStr`]
+    pub fn from_stream_content_type(s: &str) -> Option<Format> {
+        let mime = s.split(';').next().unwrap_or_default().trim();
+        [Format::Json, Format::MessagePack, Format::Cbor]
+            .into_iter()
+            .find(|fmt| mime.eq_ignore_ascii_case(fmt.stream_content_type()))
+    }
+
     /// Deserialize the provided value
     /// Returns a MaybeOwned::Borrowed if using a zero-copy format
     /// or a MaybeOwned::Owned otherwise
@@ -69,6 +146,9 @@ impl Format {
                 sonic_rs::from_slice::<T>(val)
                     .map_err(|e| GraphError::DecodeError(e.to_string()))?,
             )),
+            Format::MessagePack | Format::Cbor => {
+                Ok(MaybeOwned::Owned(self.deserialize_owned(val)?))
+            }
         }
     }
 
@@ -77,6 +157,17 @@ impl Format {
         match self {
             Format::Json => Ok(sonic_rs::from_slice::<T>(val)
                 .map_err(|e| GraphError::DecodeError(e.to_string()))?),
+            Format::MessagePack => {
+                let mut deserializer =
+                    rmp_serde::Deserializer::from_read_ref(val).with_human_readable();
+                T::deserialize(&mut deserializer)
+                    .map_err(|e| GraphError::DecodeError(e.to_string()))
+            }
+            Format::Cbor => {
+                let tree: serde_json::Value = ciborium::from_reader(val)
+                    .map_err(|e| GraphError::DecodeError(e.to_string()))?;
+                T::deserialize(tree).map_err(|e| GraphError::DecodeError(e.to_string()))
+            }
         }
     }
 }
@@ -84,9 +175,15 @@ impl Format {
 impl FromStr for Format {
     type Err = ();
 
+    /// Parses a MIME type, ignoring parameters such as `; charset=utf-8`
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
+        let mime = s.split(';').next().unwrap_or_default().trim();
+        match mime.to_ascii_lowercase().as_str() {
             "application/json" => Ok(Format::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Ok(Format::MessagePack)
+            }
+            "application/cbor" => Ok(Format::Cbor),
             _ => Err(()),
         }
     }
@@ -96,6 +193,8 @@ impl Display for Format {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Format::Json => write!(f, "application/json"),
+            Format::MessagePack => write!(f, "application/msgpack"),
+            Format::Cbor => write!(f, "application/cbor"),
         }
     }
 }
@@ -259,10 +358,91 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_format_stream_content_type_roundtrip() {
+        for fmt in [Format::Json, Format::MessagePack, Format::Cbor] {
+            let parsed = Format::from_stream_content_type(fmt.stream_content_type());
+            assert_eq!(parsed, Some(fmt));
+        }
+        assert_eq!(
+            Format::from_stream_content_type("Application/X-NDJSON; charset=utf-8"),
+            Some(Format::Json)
+        );
+        assert_eq!(Format::from_stream_content_type("application/json"), None);
+    }
+
+    #[test]
+    fn test_format_serialize_item_framing() {
+        let items = [
+            TestData {
+                name: "a".to_string(),
+                value: 1,
+            },
+            TestData {
+                name: "b".to_string(),
+                value: 2,
+            },
+        ];
+
+        let mut buf = Vec::new();
+        for item in &items {
+            Format::Json.serialize_item(item, &mut buf).unwrap();
+        }
+        let lines: Vec<TestData> = std::str::from_utf8(&buf)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, items);
+
+        let mut buf = Vec::new();
+        for item in &items {
+            Format::MessagePack.serialize_item(item, &mut buf).unwrap();
+        }
+        let len = u32::from_be_bytes(buf[..4].try_into().unwrap()) as usize;
+        let first: TestData = Format::MessagePack
+            .deserialize_owned(&buf[4..4 + len])
+            .unwrap();
+        assert_eq!(first, items[0]);
+        let second: TestData = Format::MessagePack
+            .deserialize_owned(&buf[8 + len..])
+            .unwrap();
+        assert_eq!(second, items[1]);
+
+        let mut buf = Vec::new();
+        for item in &items {
+            Format::Cbor.serialize_item(item, &mut buf).unwrap();
+        }
+        let mut reader = &buf[..];
+        for item in &items {
+            let decoded: TestData = ciborium::from_reader(&mut reader).unwrap();
+            assert_eq!(&decoded, item);
+        }
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_format_from_str_binary_formats() {
+        for mime in ["application/msgpack", "application/x-msgpack", "application/vnd.msgpack"] {
+            assert_eq!(mime.parse::<Format>(), Ok(Format::MessagePack));
+        }
+        assert_eq!("application/cbor".parse::<Format>(), Ok(Format::Cbor));
+        assert_eq!("Application/CBOR".parse::<Format>(), Ok(Format::Cbor));
+        assert_eq!(
+            "application/json; charset=utf-8".parse::<Format>(),
+            Ok(Format::Json)
+        );
+    }
+
     #[test]
     fn test_format_display() {
         let fmt = Format::Json;
         assert_eq!(fmt.to_string(), "application/json");
+        assert_eq!(Format::MessagePack.to_string(), "application/msgpack");
+        assert_eq!(Format::Cbor.to_string(), "application/cbor");
+        for fmt in [Format::Json, Format::MessagePack, Format::Cbor] {
+            assert_eq!(fmt.to_string().parse::<Format>(), Ok(fmt));
+        }
     }
 
     #[test]
@@ -271,6 +451,97 @@ mod tests {
         assert!(matches!(fmt, Format::Json));
     }
 
+    // ============================================================================
+    // Binary format tests
+    // ============================================================================
+
+    /// What a client sends: IDs and dates are strings on the wire
+    #[derive(Serialize)]
+    struct ClientInput {
+        id: String,
+        name: String,
+        embedding: Vec<f64>,
+        created_at: String,
+        props: sonic_rs::Value,
+    }
+
+    /// How a generated handler declares the same input
+    #[derive(Debug, Deserialize)]
+    struct QueryInput {
+        id: crate::utils::id::ID,
+        name: String,
+        embedding: Vec<f64>,
+        created_at: crate::protocol::date::Date,
+        props: crate::protocol::value::Value,
+    }
+
+    fn client_input() -> ClientInput {
+        ClientInput {
+            id: "00000000-0000-0000-0123-456789abcdef".to_string(),
+            name: "binary".to_string(),
+            embedding: (0..1536).map(|i| i as f64 / 7.0).collect(),
+            created_at: "2024-05-01T12:30:00+00:00".to_string(),
+            props: sonic_rs::json!({"tags": ["a", "b"], "score": 3}),
+        }
+    }
+
+    #[test]
+    fn test_format_binary_roundtrip() {
+        use crate::protocol::value::Value;
+
+        let original = client_input();
+        for fmt in [Format::MessagePack, Format::Cbor] {
+            let bytes = fmt.serialize(&original);
+            let result: MaybeOwned<QueryInput> = fmt.deserialize(&bytes).unwrap();
+            assert_eq!(*result.id, 0x0123_4567_89ab_cdef_u128, "{fmt}");
+            assert_eq!(result.name, "binary", "{fmt}");
+            assert_eq!(result.embedding, original.embedding, "{fmt}");
+            assert_eq!(result.created_at.to_rfc3339(), original.created_at, "{fmt}");
+            let Value::Object(props) = &result.props else {
+                panic!("{fmt}: expected an object, got {:?}", result.props);
+            };
+            assert_eq!(props.get("score"), Some(&Value::I64(3)), "{fmt}");
+            assert_eq!(
+                props.get("tags"),
+                Some(&Value::Array(vec![Value::from("a"), Value::from("b")])),
+                "{fmt}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_binary_matches_json_shape() {
+        // responses decode to the same document whichever format the client asked for
+        let response = sonic_rs::json!({
+            "user": {"id": "00000000-0000-0000-0123-456789abcdef", "age": 31, "tags": ["a"]},
+            "score": 0.5,
+            "missing": null
+        });
+        let json: serde_json::Value =
+            serde_json::from_slice(&Format::Json.serialize(&response)).unwrap();
+        for fmt in [Format::MessagePack, Format::Cbor] {
+            let decoded: serde_json::Value =
+                fmt.deserialize_owned(&fmt.serialize(&response)).unwrap();
+            assert_eq!(decoded, json, "{fmt}");
+        }
+    }
+
+    #[test]
+    fn test_format_binary_embeddings_are_smaller() {
+        let original = client_input();
+        let json_len = Format::Json.serialize(&original).len();
+        assert!(Format::MessagePack.serialize(&original).len() < json_len);
+        assert!(Format::Cbor.serialize(&original).len() < json_len);
+    }
+
+    #[test]
+    fn test_format_deserialize_invalid_binary() {
+        for fmt in [Format::MessagePack, Format::Cbor] {
+            let result: Result<TestData, GraphError> = fmt.deserialize_owned(&[0xc1, 0xff]);
+            assert!(matches!(result, Err(GraphError::DecodeError(_))), "{fmt}");
+        }
+    }
+
     // ============================================================================
     // MaybeOwned tests
     // ============================================================================
@@ -3,6 +3,7 @@ pub mod error;
 pub mod format;
 pub mod request;
 pub mod response;
+pub mod stream;
 pub mod custom_serde;
 pub mod value;
 
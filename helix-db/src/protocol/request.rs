@@ -3,6 +3,7 @@ use reqwest::{
     StatusCode,
     header::{ACCEPT, CONTENT_TYPE},
 };
+use std::time::Instant;
 use tokio::sync::oneshot;
 use tracing::error;
 
@@ -10,7 +11,8 @@ use crate::protocol::{Format, HelixError, Response};
 
 pub type RetChan = oneshot::Sender<Result<Response, HelixError>>;
 
-pub type ReqMsg = (Request, RetChan);
+/// The request, the deadline it must finish by and where to send the reply
+pub type ReqMsg = (Request, Option<Instant>, RetChan);
 
 #[derive(Debug, Clone)]
 pub struct Request {
@@ -20,12 +22,16 @@ pub struct Request {
     pub body: Bytes,
     pub in_fmt: Format,
     pub out_fmt: Format,
+    /// Whether the client accepts a streamed response, see [`crate::protocol::stream`]
+    pub stream: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RequestType {
     Query,
     MCP,
+    /// Several queries run in one transaction, see [`crate::helix_gateway::batch`]
+    Batch,
 }
 
 impl<S> FromRequest<S> for Request
@@ -64,12 +70,13 @@ where
             None => Format::default(),
         };
 
-        let out_fmt = match headers.get(ACCEPT) {
+        let (out_fmt, stream) = match headers.get(ACCEPT) {
             Some(v) => match v.to_str() {
-                Ok(s) => s.parse().unwrap_or_default(),
+                // the first listed type that can be produced, otherwise JSON
+                Ok(s) => s.split(',').find_map(accepted).unwrap_or_default(),
                 Err(_) => return Err(StatusCode::BAD_REQUEST),
             },
-            None => Format::default(),
+            None => Default::default(),
         };
 
         let body = match Bytes::from_request(req, state).await {
@@ -85,12 +92,21 @@ where
             body,
             in_fmt,
             out_fmt,
+            stream,
         };
 
         Ok(out)
     }
 }
 
+/// The format of a type listed in `Accept` and whether it's a streamed one
+fn accepted(mime: &str) -> Option<(Format, bool)> {
+    match mime.parse() {
+        Ok(fmt) => Some((fmt, false)),
+        Err(()) => Format::from_stream_content_type(mime).map(|fmt| (fmt, true)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,6 +124,7 @@ mod tests {
             body: body.clone(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         assert_eq!(request.name, "test_query");
@@ -124,6 +141,7 @@ mod tests {
             body: body.clone(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let cloned = request.clone();
@@ -139,6 +157,7 @@ mod tests {
             body: Bytes::from("test"),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         let debug_str = format!("{:?}", request);
@@ -199,6 +218,7 @@ mod tests {
             body: Bytes::new(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         assert!(request.body.is_empty());
@@ -215,6 +235,7 @@ mod tests {
             body: body.clone(),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         assert_eq!(request.body.len(), 10_000);
@@ -228,8 +249,83 @@ mod tests {
             body: Bytes::from("test"),
             in_fmt: Format::Json,
             out_fmt: Format::Json,
+            stream: false,
         };
 
         assert!(request.name.contains("世界"));
     }
+
+    // ============================================================================
+    // Format Negotiation Tests
+    // ============================================================================
+
+    async fn extract(
+        content_type: Option<&str>,
+        accept: Option<&str>,
+    ) -> Result<Request, StatusCode> {
+        let mut builder = axum::extract::Request::builder().method("POST").uri("/query");
+        if let Some(ct) = content_type {
+            builder = builder.header(CONTENT_TYPE, ct);
+        }
+        if let Some(a) = accept {
+            builder = builder.header(ACCEPT, a);
+        }
+        let req = builder.body(axum::body::Body::from("body")).unwrap();
+        Request::from_request(req, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_from_request_defaults_to_json() {
+        let request = extract(None, None).await.unwrap();
+        assert_eq!(request.in_fmt, Format::Json);
+        assert_eq!(request.out_fmt, Format::Json);
+    }
+
+    #[tokio::test]
+    async fn test_from_request_binary_formats() {
+        let request = extract(Some("application/msgpack"), Some("application/cbor"))
+            .await
+            .unwrap();
+        assert_eq!(request.in_fmt, Format::MessagePack);
+        assert_eq!(request.out_fmt, Format::Cbor);
+    }
+
+    #[tokio::test]
+    async fn test_from_request_accept_list_picks_first_supported() {
+        let request = extract(None, Some("text/html, application/cbor, application/json"))
+            .await
+            .unwrap();
+        assert_eq!(request.out_fmt, Format::Cbor);
+
+        let request = extract(None, Some("text/html, */*")).await.unwrap();
+        assert_eq!(request.out_fmt, Format::Json);
+    }
+
+    #[tokio::test]
+    async fn test_from_request_stream_formats() {
+        let request = extract(None, None).await.unwrap();
+        assert!(!request.stream);
+
+        let request = extract(None, Some("application/x-ndjson")).await.unwrap();
+        assert_eq!(request.out_fmt, Format::Json);
+        assert!(request.stream);
+
+        let request = extract(None, Some("text/html, application/x-msgpack-stream, */*"))
+            .await
+            .unwrap();
+        assert_eq!(request.out_fmt, Format::MessagePack);
+        assert!(request.stream);
+
+        let request = extract(None, Some("application/cbor, application/cbor-seq"))
+            .await
+            .unwrap();
+        assert_eq!(request.out_fmt, Format::Cbor);
+        assert!(!request.stream);
+    }
+
+    #[tokio::test]
+    async fn test_from_request_unsupported_content_type() {
+        let err = extract(Some("text/plain"), None).await.unwrap_err();
+        assert_eq!(err, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
 }
@@ -1,19 +1,30 @@
 use axum::response::IntoResponse;
 use reqwest::header::CONTENT_TYPE;
 
-use crate::protocol::Format;
+use crate::protocol::{
+    Format,
+    stream::{Chunks, chunks_body},
+};
 #[derive(Debug)]
 pub struct Response {
     pub body: Vec<u8>,
     pub fmt: Format,
+    /// Set for streamed responses, whose body is received from here instead of `body`
+    pub stream: Option<Chunks>,
 }
 
 impl IntoResponse for Response {
     fn into_response(self) -> axum::response::Response {
-        axum::response::Response::builder()
-            .header(CONTENT_TYPE, self.fmt.to_string())
-            .body(axum::body::Body::from(self.body))
-            .expect("Should be able to construct response")
+        let builder = axum::response::Response::builder();
+        match self.stream {
+            Some(chunks) => builder
+                .header(CONTENT_TYPE, self.fmt.stream_content_type())
+                .body(chunks_body(chunks)),
+            None => builder
+                .header(CONTENT_TYPE, self.fmt.to_string())
+                .body(axum::body::Body::from(self.body)),
+        }
+        .expect("Should be able to construct response")
     }
 }
 
@@ -31,6 +42,7 @@ mod tests {
         let response = Response {
             body: body.clone(),
             fmt: Format::Json,
+            stream: None,
         };
 
         assert_eq!(response.body, body);
@@ -42,6 +54,7 @@ mod tests {
         let response = Response {
             body: vec![],
             fmt: Format::Json,
+            stream: None,
         };
 
         assert!(response.body.is_empty());
@@ -53,6 +66,7 @@ mod tests {
         let response = Response {
             body: large_body.clone(),
             fmt: Format::Json,
+            stream: None,
         };
 
         assert_eq!(response.body.len(), 50_000);
@@ -63,6 +77,7 @@ mod tests {
         let response = Response {
             body: vec![1, 2, 3],
             fmt: Format::Json,
+            stream: None,
         };
 
         let debug_str = format!("{:?}", response);
@@ -80,6 +95,7 @@ mod tests {
         let response = Response {
             body: body.clone(),
             fmt: Format::Json,
+            stream: None,
         };
 
         let axum_response = response.into_response();
@@ -96,6 +112,7 @@ mod tests {
         let response = Response {
             body: body.clone(),
             fmt: Format::Json,
+            stream: None,
         };
 
         let _ = response.into_response();
@@ -112,6 +129,7 @@ mod tests {
         let response = Response {
             body: utf8_text.clone(),
             fmt: Format::Json,
+            stream: None,
         };
 
         assert_eq!(response.body, utf8_text);
@@ -123,6 +141,7 @@ mod tests {
         let response = Response {
             body: binary_data.clone(),
             fmt: Format::Json,
+            stream: None,
         };
 
         assert_eq!(response.body, binary_data);
@@ -0,0 +1,187 @@
+//! Streamed responses, for queries returning collections too large to build in memory.
+//!
+//! Clients opt in by accepting the [`Format::stream_content_type`] of a format, e.g.
+//! `application/x-ndjson`. Handlers then write the items of the collection to a
+//! [`ResponseStream`] one at a time while they still hold their read transaction. Items are sent
+//! to the HTTP body in chunks through a bounded channel, so a slow client blocks the worker
+//! instead of growing a buffer. A client that takes no chunk for the send timeout of the router,
+//! [`STREAM_SEND_TIMEOUT`] by default, fails the stream so it can't hold the worker forever.
+//!
+//! The response starts once the first chunk is full, so errors before then get the usual status
+//! code. Errors after that abort the body, which clients see as a truncated response.
+
+use std::{
+    cell::RefCell,
+    time::{Duration, Instant},
+};
+
+use axum::body::{Body, Bytes};
+use futures_util::stream;
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    helix_engine::{deadline::check_deadline, types::GraphError},
+    protocol::{Format, Request, Response, request::RetChan},
+};
+
+/// Bytes of items buffered before they are sent to the client
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Chunks waiting to be sent before the handler blocks
+const CHUNK_QUEUE: usize = 8;
+
+/// How often a handler blocked on a slow client checks its deadline
+const DEADLINE_POLL: Duration = Duration::from_millis(100);
+
+/// How long a handler waits for the client to take a chunk before failing the stream
+pub const STREAM_SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Chunks of a streamed body, ended by `None`. Bodies closed before their end are aborted.
+pub type Chunks = flume::Receiver<Option<Bytes>>;
+
+/// Where the handler running on this thread sends its streamed response
+struct Sink {
+    /// Taken when the response starts
+    ret_chan: Option<RetChan>,
+    chunks: Option<flume::Sender<Option<Bytes>>>,
+    send_timeout: Duration,
+}
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+/// Runs the handler of a request that accepts a streamed response, letting it start the response
+/// through `ret_chan` before it returns. Sending a chunk fails after `send_timeout`.
+///
+/// Gives back the result of the handler and `ret_chan` if it didn't start streaming. Otherwise the
+/// response is already on its way, so an error aborts its body and anything else is dropped.
+pub fn run_streaming(
+    ret_chan: RetChan,
+    send_timeout: Duration,
+    handler: impl FnOnce() -> Result<Response, GraphError>,
+) -> Option<(Result<Response, GraphError>, RetChan)> {
+    let previous = SINK.replace(Some(Sink {
+        ret_chan: Some(ret_chan),
+        chunks: None,
+        send_timeout,
+    }));
+    let res = handler();
+    let sink = SINK
+        .replace(previous)
+        .expect("the sink should be set while the handler runs");
+
+    match sink.ret_chan {
+        Some(ret_chan) => Some((res, ret_chan)),
+        None => {
+            if let Err(e) = res {
+                // dropping the sender before the end of the body aborts it
+                error!(?e, "Streamed response failed after it started");
+            }
+            None
+        }
+    }
+}
+
+/// Writes the items of a streamed response, see the [module docs](self)
+pub struct ResponseStream {
+    fmt: Format,
+    buf: Vec<u8>,
+}
+
+impl ResponseStream {
+    /// Returns a stream if the client accepts one and the handler runs in [`run_streaming`],
+    /// which isn't the case for the parts of handlers that continue after async IO
+    pub fn requested(request: &Request) -> Option<ResponseStream> {
+        let in_sink = SINK.with_borrow(|sink| sink.is_some());
+        (request.stream && in_sink).then(|| ResponseStream {
+            fmt: request.out_fmt,
+            buf: Vec::with_capacity(STREAM_CHUNK_SIZE),
+        })
+    }
+
+    pub fn write<T: Serialize>(&mut self, item: &T) -> Result<(), GraphError> {
+        self.fmt.serialize_item(item, &mut self.buf)?;
+        if self.buf.len() >= STREAM_CHUNK_SIZE {
+            let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(STREAM_CHUNK_SIZE));
+            self.send(Some(chunk.into()))?;
+        }
+        Ok(())
+    }
+
+    /// Sends the remaining items and ends the response.
+    ///
+    /// The response was sent when it started, so the one returned is only there to be returned
+    /// by the handler and is dropped by [`run_streaming`].
+    pub fn finish(mut self) -> Result<Response, GraphError> {
+        if !self.buf.is_empty() {
+            let chunk = std::mem::take(&mut self.buf);
+            self.send(Some(chunk.into()))?;
+        }
+        self.send(None)?;
+        Ok(Response {
+            body: Vec::new(),
+            fmt: self.fmt,
+            stream: None,
+        })
+    }
+
+    /// Sends a chunk, starting the response first if it hasn't started yet.
+    /// Blocks while the client is behind, until the deadline of the request or the send timeout
+    /// passes.
+    fn send(&mut self, chunk: Option<Bytes>) -> Result<(), GraphError> {
+        check_deadline()?;
+        let closed = || GraphError::New("the client closed the streamed response".to_string());
+        SINK.with_borrow_mut(|sink| {
+            let sink = sink
+                .as_mut()
+                .expect("ResponseStream is only created inside run_streaming");
+            if let Some(ret_chan) = sink.ret_chan.take() {
+                let (tx, rx) = flume::bounded(CHUNK_QUEUE);
+                let response = Response {
+                    body: Vec::new(),
+                    fmt: self.fmt,
+                    stream: Some(rx),
+                };
+                ret_chan.send(Ok(response)).map_err(|_| closed())?;
+                sink.chunks = Some(tx);
+            }
+            let tx = sink.chunks.as_ref().expect("set when the response started");
+
+            let give_up = Instant::now() + sink.send_timeout;
+            let mut chunk = chunk;
+            loop {
+                match tx.send_timeout(chunk, DEADLINE_POLL) {
+                    Ok(()) => return Ok(()),
+                    Err(flume::SendTimeoutError::Timeout(c)) => {
+                        check_deadline()?;
+                        if Instant::now() >= give_up {
+                            return Err(GraphError::New(format!(
+                                "the client took no chunk of the streamed response for {:?}",
+                                sink.send_timeout
+                            )));
+                        }
+                        chunk = c;
+                    }
+                    Err(flume::SendTimeoutError::Disconnected(_)) => return Err(closed()),
+                }
+            }
+        })
+    }
+}
+
+/// The HTTP body of a streamed response
+pub fn chunks_body(chunks: Chunks) -> Body {
+    Body::from_stream(stream::unfold(Some(chunks), |chunks| async move {
+        let chunks = chunks?;
+        match chunks.recv_async().await {
+            Ok(Some(chunk)) => Some((Ok(chunk), Some(chunks))),
+            Ok(None) => None,
+            Err(_) => Some((
+                Err(std::io::Error::other("the query failed after its response started")),
+                None,
+            )),
+        }
+    }))
+}
@@ -1,6 +1,6 @@
 use crate::debug_println;
 use crate::helix_gateway::mcp::tools::{FilterValues, Operator};
-use crate::protocol::date::Date;
+use crate::protocol::date::{Date, DateUnit, DateZone};
 use crate::utils::id::ID;
 use crate::{helix_engine::types::GraphError, helixc::generator::utils::GenRef};
 use chrono::{DateTime, Utc};
@@ -15,6 +15,15 @@ use std::{
     collections::HashMap,
     fmt::{self},
 };
+
+/// Most compiled regular expressions kept per thread by [`Value::matches_regex`]
+pub const REGEX_CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    static REGEX_CACHE: std::cell::RefCell<HashMap<String, Option<regex::Regex>>> =
+        std::cell::RefCell::new(HashMap::new());
+}
+
 /// A flexible value type that can represent various property values in nodes and edges.
 /// Handles both JSON and binary serialisation formats via custom implementaions of the Serialize and Deserialize traits.
 #[derive(Clone, Debug, Default)]
@@ -129,6 +138,160 @@ impl Value {
         self.inner_str().contains(needle)
     }
 
+    /// The string form of a primitive value, or `None` for empty, array and object values.
+    fn text(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Value::Array(_) | Value::Object(_) | Value::Empty => None,
+            _ => Some(self.inner_str()),
+        }
+    }
+
+    /// Like `inner_str`, but returns an empty string instead of panicking on non-primitives.
+    pub fn as_text(&self) -> Cow<'_, str> {
+        self.text().unwrap_or_default()
+    }
+
+    /// Checks if the string form of this value starts with the prefix.
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.text().is_some_and(|s| s.starts_with(prefix))
+    }
+
+    /// Checks if the string form of this value ends with the suffix.
+    pub fn ends_with(&self, suffix: &str) -> bool {
+        self.text().is_some_and(|s| s.ends_with(suffix))
+    }
+
+    /// Compares the string form of this value with `other`, ignoring case.
+    pub fn eq_ignore_case(&self, other: &str) -> bool {
+        self.text()
+            .is_some_and(|s| s.to_lowercase() == other.to_lowercase())
+    }
+
+    /// Checks if the string form of this value matches the regular expression.
+    /// Compiled patterns are cached per thread, up to [`REGEX_CACHE_CAPACITY`] of them before
+    /// the cache is cleared; an invalid pattern never matches.
+    pub fn matches_regex(&self, pattern: &str) -> bool {
+        let Some(text) = self.text() else {
+            return false;
+        };
+        REGEX_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.len() >= REGEX_CACHE_CAPACITY && !cache.contains_key(pattern) {
+                cache.clear();
+            }
+            cache
+                .entry(pattern.to_string())
+                .or_insert_with(|| regex::Regex::new(pattern).ok())
+                .as_ref()
+                .is_some_and(|re| re.is_match(&text))
+        })
+    }
+
+    /// LOWER: lowercases the string form of this value.
+    pub fn to_lowercase(&self) -> Value {
+        self.text()
+            .map_or(Value::Empty, |s| Value::String(s.to_lowercase()))
+    }
+
+    /// UPPER: uppercases the string form of this value.
+    pub fn to_uppercase(&self) -> Value {
+        self.text()
+            .map_or(Value::Empty, |s| Value::String(s.to_uppercase()))
+    }
+
+    /// TRIM: strips leading and trailing whitespace from the string form of this value.
+    pub fn trim(&self) -> Value {
+        self.text()
+            .map_or(Value::Empty, |s| Value::String(s.trim().to_string()))
+    }
+
+    /// LENGTH: the number of characters in the string form of this value.
+    pub fn length(&self) -> Value {
+        self.text()
+            .map_or(Value::Empty, |s| Value::I64(s.chars().count() as i64))
+    }
+
+    /// SUBSTRING: takes `len` characters (or the rest of the string) starting at character `start`.
+    /// Out of range bounds are clamped, so this never panics.
+    pub fn substring(&self, start: &Value, len: Option<&Value>) -> Value {
+        let to_usize = |v: &Value| -> usize {
+            match v {
+                Value::I8(i) => (*i).max(0) as usize,
+                Value::I16(i) => (*i).max(0) as usize,
+                Value::I32(i) => (*i).max(0) as usize,
+                Value::I64(i) => (*i).max(0) as usize,
+                Value::U8(u) => *u as usize,
+                Value::U16(u) => *u as usize,
+                Value::U32(u) => *u as usize,
+                Value::U64(u) => *u as usize,
+                _ => 0,
+            }
+        };
+        let Some(s) = self.text() else {
+            return Value::Empty;
+        };
+        let chars = s.chars().skip(to_usize(start));
+        Value::String(match len {
+            Some(len) => chars.take(to_usize(len)).collect(),
+            None => chars.collect(),
+        })
+    }
+
+    /// CONCAT: joins the string forms of the values. Empty if any value is empty.
+    pub fn concat(values: &[Value]) -> Value {
+        let mut out = String::new();
+        for value in values {
+            match value.text() {
+                Some(s) => out.push_str(&s),
+                None => return Value::Empty,
+            }
+        }
+        Value::String(out)
+    }
+
+    /// The date this value holds, read the same way as a `Date` parameter.
+    pub fn as_datetime(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Value::Date(date) => Some(**date),
+            Value::Empty => None,
+            _ => Date::new(self).ok().map(|date| *date),
+        }
+    }
+
+    /// DATE_ADD / DATE_SUB: shifts the date by `millis` milliseconds.
+    pub fn date_add(&self, millis: i64) -> Value {
+        self.as_datetime()
+            .zip(chrono::TimeDelta::try_milliseconds(millis))
+            .and_then(|(date, delta)| date.checked_add_signed(delta))
+            .map_or(Value::Empty, Value::from)
+    }
+
+    /// DATE_TRUNC: the start of the `unit` the date falls in, as seen in the time zone `zone`.
+    pub fn date_trunc(&self, unit: DateUnit, zone: &str) -> Value {
+        self.as_datetime()
+            .zip(DateZone::parse(zone))
+            .and_then(|(date, zone)| Date::from(date).truncate(unit, zone))
+            .map_or(Value::Empty, |date| Value::from(*date))
+    }
+
+    /// YEAR / MONTH / DAY / HOUR: the `unit` part of the date in the time zone `zone`.
+    pub fn date_part(&self, unit: DateUnit, zone: &str) -> Value {
+        self.as_datetime()
+            .zip(DateZone::parse(zone))
+            .map_or(Value::Empty, |(date, zone)| {
+                Value::I64(Date::from(date).part(unit, zone))
+            })
+    }
+
+    /// DATE: parses the string form of this value as a date, reading dates without an offset
+    /// in the time zone `zone`.
+    pub fn to_date(&self, zone: &str) -> Value {
+        self.text()
+            .zip(DateZone::parse(zone))
+            .and_then(|(text, zone)| Date::parse_in(&text, zone).ok())
+            .map_or(Value::Empty, |date| Value::from(*date))
+    }
+
     #[inline]
     #[allow(unused_variables)] // default is not used but needed for function signature
     pub fn map_value_or(
@@ -185,6 +348,16 @@ impl Ord for Value {
                     | Value::U128(_)
             )
         };
+        let is_numeric = |value: &Value| -> bool {
+            is_integer(value) || matches!(value, Value::F32(_) | Value::F64(_))
+        };
+        let to_f64 = |value: &Value| -> Option<f64> {
+            match value {
+                Value::F32(v) => Some(*v as f64),
+                Value::F64(v) => Some(*v),
+                other => to_i128(other).map(|v| v as f64),
+            }
+        };
 
         match (self, other) {
             (Value::String(s), Value::String(o)) => s.cmp(o),
@@ -211,6 +384,10 @@ impl Ord for Value {
                     _ => unreachable!(),
                 },
             },
+            (s, o) if is_numeric(s) && is_numeric(o) => match (to_f64(s), to_f64(o)) {
+                (Some(s), Some(o)) => s.partial_cmp(&o).unwrap_or(Ordering::Equal),
+                _ => Ordering::Equal,
+            },
             (_, _) => Ordering::Equal,
         }
     }
@@ -1044,6 +1221,17 @@ where
     }
 }
 
+/// `None` is stored as `Value::Empty`, the null of optional fields
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    #[inline]
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Value::Empty, Into::into)
+    }
+}
+
 impl From<chrono::DateTime<Utc>> for Value {
     #[inline]
     fn from(dt: chrono::DateTime<Utc>) -> Self {
@@ -1733,6 +1921,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_value_from_option() {
+        assert_eq!(Value::from(Some("bio".to_string())), Value::String("bio".to_string()));
+        assert_eq!(Value::from(Some(7u32)), Value::U32(7));
+        assert!(matches!(Value::from(None::<String>), Value::Empty));
+    }
+
     #[test]
     fn test_value_from_datetime() {
         let dt = Utc::now();
@@ -1846,6 +2041,14 @@ mod tests {
         assert_eq!(Value::Empty.cmp(&Value::Empty), Ordering::Equal);
     }
 
+    #[test]
+    fn test_value_ordering_mixed_numeric_types() {
+        assert!(Value::I32(2) < Value::F64(2.5));
+        assert!(Value::F64(2.5) < Value::U64(3));
+        assert!(Value::F32(1.5) < Value::F64(1.75));
+        assert_eq!(Value::I64(2).cmp(&Value::F64(2.0)), Ordering::Equal);
+    }
+
     #[test]
     fn test_value_ordering_mixed_types() {
         // Non-comparable types should return Equal
@@ -1931,6 +2134,91 @@ mod tests {
         Value::I32(42).as_str();
     }
 
+    #[test]
+    fn test_string_predicates() {
+        let value = Value::String("Hello World".to_string());
+        assert!(value.starts_with("Hello"));
+        assert!(!value.starts_with("World"));
+        assert!(value.ends_with("World"));
+        assert!(value.eq_ignore_case("hello world"));
+        assert!(!value.eq_ignore_case("hello"));
+        assert!(value.matches_regex("^H.*d$"));
+        assert!(!value.matches_regex("^\\d+$"));
+        // invalid patterns never match
+        assert!(!value.matches_regex("("));
+        assert!(!Value::Empty.starts_with(""));
+    }
+
+    #[test]
+    fn test_regex_cache_is_bounded() {
+        let value = Value::String("42".to_string());
+        for i in 0..REGEX_CACHE_CAPACITY * 3 {
+            assert!(value.matches_regex(&format!("^\\d+$|^{i}x$")));
+            assert!(REGEX_CACHE.with(|cache| cache.borrow().len()) <= REGEX_CACHE_CAPACITY);
+        }
+        // a pattern still cached is reused without clearing the others
+        let cached = REGEX_CACHE.with(|cache| cache.borrow().len());
+        assert!(value.matches_regex(&format!("^\\d+$|^{}x$", REGEX_CACHE_CAPACITY * 3 - 1)));
+        assert_eq!(REGEX_CACHE.with(|cache| cache.borrow().len()), cached);
+    }
+
+    #[test]
+    fn test_string_functions() {
+        let value = Value::String("  Héllo  ".to_string());
+        assert_eq!(value.trim(), Value::String("Héllo".to_string()));
+        assert_eq!(value.trim().to_uppercase(), Value::String("HÉLLO".to_string()));
+        assert_eq!(value.trim().to_lowercase(), Value::String("héllo".to_string()));
+        assert_eq!(value.trim().length(), Value::I64(5));
+        assert_eq!(
+            value.trim().substring(&Value::I32(1), Some(&Value::I32(3))),
+            Value::String("éll".to_string())
+        );
+        assert_eq!(
+            value.trim().substring(&Value::I32(3), None),
+            Value::String("lo".to_string())
+        );
+        assert_eq!(
+            value.trim().substring(&Value::I32(10), Some(&Value::I32(-1))),
+            Value::String(String::new())
+        );
+        assert_eq!(
+            Value::concat(&[Value::from("id-"), Value::I32(7)]),
+            Value::String("id-7".to_string())
+        );
+        assert_eq!(Value::concat(&[Value::from("a"), Value::Empty]), Value::Empty);
+        assert_eq!(Value::Empty.to_lowercase(), Value::Empty);
+    }
+
+    #[test]
+    fn test_date_functions() {
+        let created = Value::from("2024-03-14T01:45:30Z");
+        assert_eq!(
+            created.date_add(-7 * 86_400_000),
+            Value::from("2024-03-07T01:45:30+00:00")
+        );
+        assert_eq!(
+            created.date_trunc(DateUnit::Month, "UTC"),
+            Value::from("2024-03-01T00:00:00+00:00")
+        );
+        assert_eq!(
+            created.date_trunc(DateUnit::Day, "America/Los_Angeles"),
+            Value::from("2024-03-13T07:00:00+00:00")
+        );
+        assert_eq!(created.date_part(DateUnit::Year, "UTC"), Value::I64(2024));
+        assert_eq!(created.date_part(DateUnit::Hour, "+05:30"), Value::I64(7));
+        assert_eq!(
+            Value::from("2024-03-14 09:00").to_date("Asia/Tokyo"),
+            Value::from("2024-03-14T00:00:00+00:00")
+        );
+
+        // truncated dates still compare with stored dates
+        assert!(created.date_trunc(DateUnit::Day, "UTC") < created);
+
+        assert_eq!(created.date_part(DateUnit::Day, "Nowhere/Special"), Value::Empty);
+        assert_eq!(Value::from("yesterday").date_add(1_000), Value::Empty);
+        assert_eq!(Value::Empty.date_part(DateUnit::Year, "UTC"), Value::Empty);
+    }
+
     // ============================================================================
     // Serialization/Deserialization
     // ============================================================================
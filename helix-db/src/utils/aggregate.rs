@@ -2,12 +2,17 @@ use std::collections::{HashMap, HashSet};
 
 use serde::Serialize;
 
-use crate::helix_engine::traversal_core::traversal_value::TraversalValue;
+use crate::{
+    helix_engine::{traversal_core::traversal_value::TraversalValue, types::GraphError},
+    protocol::value::Value,
+};
 
 #[derive(Clone, Default, Serialize)]
 pub struct AggregateItem<'arena> {
     pub values: HashSet<TraversalValue<'arena>>,
     pub count: i32,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub aggregates: HashMap<String, Value>,
 }
 
 #[derive(Clone, Serialize)]
@@ -24,3 +29,269 @@ impl<'arena> Aggregate<'arena> {
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregationKind {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    CountDistinct,
+    Collect,
+}
+
+impl AggregationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AggregationKind::Sum => "SUM",
+            AggregationKind::Avg => "AVG",
+            AggregationKind::Min => "MIN",
+            AggregationKind::Max => "MAX",
+            AggregationKind::CountDistinct => "COUNT_DISTINCT",
+            AggregationKind::Collect => "COLLECT",
+        }
+    }
+}
+
+/// An aggregation computed over `property` for every group, returned under `name`.
+#[derive(Clone, Copy, Debug)]
+pub struct Aggregation<'a> {
+    pub name: &'a str,
+    pub kind: AggregationKind,
+    pub property: &'a str,
+}
+
+/// The running state of an [`Aggregation`] for a single group.
+///
+/// Items are folded in one at a time so a group never needs its items kept around to be
+/// aggregated. Items missing the property are skipped.
+#[derive(Clone, Debug)]
+pub enum Accumulator {
+    Sum(NumericSum),
+    Avg(NumericSum),
+    Min(Option<Value>),
+    Max(Option<Value>),
+    CountDistinct(HashSet<String>),
+    Collect(Vec<Value>),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NumericSum {
+    int: i128,
+    float: f64,
+    is_float: bool,
+    count: usize,
+}
+
+impl NumericSum {
+    fn add(&mut self, value: &Value) -> bool {
+        match value {
+            Value::I8(v) => self.int += *v as i128,
+            Value::I16(v) => self.int += *v as i128,
+            Value::I32(v) => self.int += *v as i128,
+            Value::I64(v) => self.int += *v as i128,
+            Value::U8(v) => self.int += *v as i128,
+            Value::U16(v) => self.int += *v as i128,
+            Value::U32(v) => self.int += *v as i128,
+            Value::U64(v) => self.int += *v as i128,
+            Value::U128(v) => self.int = self.int.saturating_add_unsigned(*v),
+            Value::F32(v) => {
+                self.float += *v as f64;
+                self.is_float = true;
+            }
+            Value::F64(v) => {
+                self.float += *v;
+                self.is_float = true;
+            }
+            _ => return false,
+        }
+        self.count += 1;
+        true
+    }
+
+    fn total(&self) -> Value {
+        if self.is_float {
+            Value::F64(self.float + self.int as f64)
+        } else {
+            i64::try_from(self.int)
+                .map(Value::I64)
+                .unwrap_or(Value::F64(self.int as f64))
+        }
+    }
+}
+
+impl Accumulator {
+    pub fn new(kind: AggregationKind) -> Self {
+        match kind {
+            AggregationKind::Sum => Accumulator::Sum(NumericSum::default()),
+            AggregationKind::Avg => Accumulator::Avg(NumericSum::default()),
+            AggregationKind::Min => Accumulator::Min(None),
+            AggregationKind::Max => Accumulator::Max(None),
+            AggregationKind::CountDistinct => Accumulator::CountDistinct(HashSet::new()),
+            AggregationKind::Collect => Accumulator::Collect(Vec::new()),
+        }
+    }
+
+    /// Folds the value of `aggregation.property` on an item into the accumulator.
+    pub fn update(
+        &mut self,
+        aggregation: &Aggregation,
+        value: Option<&Value>,
+    ) -> Result<(), GraphError> {
+        let value = match value {
+            Some(Value::Empty) | None => return Ok(()),
+            Some(value) => value,
+        };
+        match self {
+            Accumulator::Sum(sum) | Accumulator::Avg(sum) => {
+                if !sum.add(value) {
+                    return Err(GraphError::TraversalError(format!(
+                        "cannot {} property `{}` of type {}",
+                        aggregation.kind.as_str(),
+                        aggregation.property,
+                        value.to_variant_string()
+                    )));
+                }
+            }
+            Accumulator::Min(min) => {
+                if min.as_ref().is_none_or(|min| value < min) {
+                    *min = Some(value.clone());
+                }
+            }
+            Accumulator::Max(max) => {
+                if max.as_ref().is_none_or(|max| value > max) {
+                    *max = Some(value.clone());
+                }
+            }
+            Accumulator::CountDistinct(seen) => {
+                seen.insert(format!(
+                    "{}:{}",
+                    value.to_variant_string(),
+                    value.inner_stringify()
+                ));
+            }
+            Accumulator::Collect(values) => values.push(value.clone()),
+        }
+        Ok(())
+    }
+
+    /// The aggregated value, or [`Value::Empty`] if no item in the group had the property.
+    pub fn finish(self) -> Value {
+        match self {
+            Accumulator::Sum(sum) if sum.count == 0 => Value::Empty,
+            Accumulator::Sum(sum) => sum.total(),
+            Accumulator::Avg(sum) if sum.count == 0 => Value::Empty,
+            Accumulator::Avg(sum) => {
+                let total = if sum.is_float {
+                    sum.float + sum.int as f64
+                } else {
+                    sum.int as f64
+                };
+                Value::F64(total / sum.count as f64)
+            }
+            Accumulator::Min(value) | Accumulator::Max(value) => value.unwrap_or(Value::Empty),
+            Accumulator::CountDistinct(seen) => Value::I64(seen.len() as i64),
+            Accumulator::Collect(values) => Value::Array(values),
+        }
+    }
+}
+
+/// Runs `aggregations` over the items of a single group.
+#[derive(Clone, Debug, Default)]
+pub struct GroupAccumulators(Vec<Accumulator>);
+
+impl GroupAccumulators {
+    pub fn new(aggregations: &[Aggregation]) -> Self {
+        Self(
+            aggregations
+                .iter()
+                .map(|aggregation| Accumulator::new(aggregation.kind))
+                .collect(),
+        )
+    }
+
+    pub fn update(
+        &mut self,
+        aggregations: &[Aggregation],
+        item: &TraversalValue,
+    ) -> Result<(), GraphError> {
+        for (accumulator, aggregation) in self.0.iter_mut().zip(aggregations) {
+            accumulator.update(aggregation, item.get_property(aggregation.property))?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(self, aggregations: &[Aggregation]) -> HashMap<String, Value> {
+        self.0
+            .into_iter()
+            .zip(aggregations)
+            .map(|(accumulator, aggregation)| (aggregation.name.to_string(), accumulator.finish()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(kind: AggregationKind, values: &[Value]) -> Value {
+        let aggregation = Aggregation {
+            name: "result",
+            kind,
+            property: "amount",
+        };
+        let mut accumulator = Accumulator::new(kind);
+        for value in values {
+            accumulator.update(&aggregation, Some(value)).unwrap();
+        }
+        accumulator.update(&aggregation, None).unwrap();
+        accumulator.finish()
+    }
+
+    #[test]
+    fn test_sum_and_avg() {
+        let ints = [Value::I32(1), Value::I64(2), Value::U8(3)];
+        assert!(matches!(run(AggregationKind::Sum, &ints), Value::I64(6)));
+        assert_eq!(run(AggregationKind::Avg, &ints), Value::F64(2.0));
+
+        let mixed = [Value::I32(1), Value::F64(0.5)];
+        assert_eq!(run(AggregationKind::Sum, &mixed), Value::F64(1.5));
+    }
+
+    #[test]
+    fn test_min_max_count_distinct_collect() {
+        let values = [Value::I32(3), Value::I32(1), Value::I32(3)];
+        assert_eq!(run(AggregationKind::Min, &values), Value::I32(1));
+        assert_eq!(run(AggregationKind::Max, &values), Value::I32(3));
+        assert!(matches!(
+            run(AggregationKind::CountDistinct, &values),
+            Value::I64(2)
+        ));
+        assert_eq!(
+            run(AggregationKind::Collect, &values),
+            Value::Array(values.to_vec())
+        );
+    }
+
+    #[test]
+    fn test_empty_group() {
+        assert!(matches!(run(AggregationKind::Sum, &[]), Value::Empty));
+        assert!(matches!(run(AggregationKind::Max, &[]), Value::Empty));
+        assert!(matches!(
+            run(AggregationKind::CountDistinct, &[]),
+            Value::I64(0)
+        ));
+    }
+
+    #[test]
+    fn test_sum_non_numeric() {
+        let aggregation = Aggregation {
+            name: "total",
+            kind: AggregationKind::Sum,
+            property: "name",
+        };
+        let mut accumulator = Accumulator::new(AggregationKind::Sum);
+        let result = accumulator.update(&aggregation, Some(&Value::String("a".to_string())));
+        assert!(matches!(result, Err(GraphError::TraversalError(_))));
+    }
+}
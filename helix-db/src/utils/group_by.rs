@@ -8,6 +8,8 @@ use crate::protocol::value::Value;
 pub struct GroupByItem {
     pub values: HashMap<String, Value>,
     pub count: i32,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub aggregates: HashMap<String, Value>,
 }
 
 #[derive(Clone, Serialize)]
@@ -5,4 +5,5 @@ pub mod items;
 pub mod label_hash;
 pub mod properties;
 pub mod styled_string;
+pub mod subgraph;
 pub mod tqdm;
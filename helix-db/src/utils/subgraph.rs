@@ -0,0 +1,67 @@
+//! Subgraph returned by the `EgoGraph` step.
+//!
+//! It is serialised in the same `{nodes, edges}` shape as the `/nodes-edges` builtin, built by
+//! [`graph_node_json`] and [`graph_edge_json`], with the label and properties of each item added.
+
+use serde::{Serialize, ser::Error};
+use sonic_rs::{Value, json};
+
+use crate::utils::{
+    id::ID,
+    items::{Edge, Node},
+};
+
+/// A deduplicated set of nodes and the edges between them.
+#[derive(Clone, Default)]
+pub struct Subgraph<'arena> {
+    pub nodes: Vec<Node<'arena>>,
+    pub edges: Vec<Edge<'arena>>,
+}
+
+/// A node of a `{nodes, edges}` graph, titled by its id
+pub fn graph_node_json(id: u128) -> Value {
+    let id = ID::from(id).stringify();
+    json!({
+        "id": id.clone(),
+        "title": id
+    })
+}
+
+/// An edge of a `{nodes, edges}` graph, titled by its id
+pub fn graph_edge_json(id: u128, from: u128, to: u128) -> Value {
+    let id = ID::from(id).stringify();
+    json!({
+        "from": ID::from(from).stringify(),
+        "to": ID::from(to).stringify(),
+        "title": id.clone(),
+        "id": id
+    })
+}
+
+impl<'arena> Serialize for Subgraph<'arena> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let properties = |properties| sonic_rs::to_value(properties).map_err(S::Error::custom);
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let mut json = graph_node_json(node.id);
+            json["label"] = json!(node.label);
+            json["properties"] = properties(&node.properties)?;
+            nodes.push(json);
+        }
+        let mut edges = Vec::with_capacity(self.edges.len());
+        for edge in &self.edges {
+            let mut json = graph_edge_json(edge.id, edge.from_node, edge.to_node);
+            json["label"] = json!(edge.label);
+            json["properties"] = properties(&edge.properties)?;
+            edges.push(json);
+        }
+        json!({
+            "nodes": nodes,
+            "edges": edges
+        })
+        .serialize(serializer)
+    }
+}
@@ -9,7 +9,8 @@ use syn::{
 };
 
 #[proc_macro_attribute]
-pub fn handler(_args: TokenStream, item: TokenStream) -> TokenStream {
+pub fn handler(args: TokenStream, item: TokenStream) -> TokenStream {
+    let constructor = handler_constructor(args);
     let input_fn = parse_macro_input!(item as ItemFn);
     let fn_name = &input_fn.sig.ident;
     let fn_name_str = fn_name.to_string();
@@ -28,7 +29,7 @@ pub fn handler(_args: TokenStream, item: TokenStream) -> TokenStream {
         static #static_name: () = {
             inventory::submit! {
                 ::helix_db::helix_gateway::router::router::HandlerSubmission(
-                    ::helix_db::helix_gateway::router::router::Handler::new(
+                    ::helix_db::helix_gateway::router::router::Handler::#constructor(
                         #fn_name_str,
                         #fn_name
                     )
@@ -39,8 +40,19 @@ pub fn handler(_args: TokenStream, item: TokenStream) -> TokenStream {
     expanded.into()
 }
 
+/// `#[handler(is_mut)]` registers a handler that writes to the graph
+fn handler_constructor(args: TokenStream) -> Ident {
+    let args = args.to_string();
+    match args.trim() {
+        "" => quote::format_ident!("new"),
+        "is_mut" => quote::format_ident!("new_mut"),
+        other => panic!("unsupported handler argument: {other}"),
+    }
+}
+
 #[proc_macro_attribute]
-pub fn mcp_handler(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn mcp_handler(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let constructor = handler_constructor(attr);
     let input_fn = parse_macro_input!(item as ItemFn);
     let fn_name = &input_fn.sig.ident;
     let fn_name_str = fn_name.to_string();
@@ -58,7 +70,7 @@ pub fn mcp_handler(_attr: TokenStream, item: TokenStream) -> TokenStream {
         static #static_name: () = {
             inventory::submit! {
                 MCPHandlerSubmission(
-                    MCPHandler::new(
+                    MCPHandler::#constructor(
                         #fn_name_str,
                         #fn_name
                     )
@@ -69,6 +81,39 @@ pub fn mcp_handler(_attr: TokenStream, item: TokenStream) -> TokenStream {
     expanded.into()
 }
 
+/// `#[batch_handler(query_name)]` registers the function running `query_name` in a batch
+#[proc_macro_attribute]
+pub fn batch_handler(args: TokenStream, item: TokenStream) -> TokenStream {
+    let query_name = args.to_string().trim().to_string();
+    if query_name.is_empty() {
+        panic!("batch_handler expects the name of the query it runs");
+    }
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let fn_name = &input_fn.sig.ident;
+    let static_name = quote::format_ident!(
+        "_BATCH_HANDLER_REGISTRATION_{}",
+        fn_name.to_string().to_uppercase()
+    );
+
+    let expanded = quote! {
+        #input_fn
+
+        #[doc(hidden)]
+        #[used]
+        static #static_name: () = {
+            inventory::submit! {
+                ::helix_db::helix_gateway::batch::BatchHandlerSubmission(
+                    ::helix_db::helix_gateway::batch::BatchHandler::new(
+                        #query_name,
+                        #fn_name
+                    )
+                )
+            }
+        };
+    };
+    expanded.into()
+}
+
 #[proc_macro_attribute]
 pub fn get_handler(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
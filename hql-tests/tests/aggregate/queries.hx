@@ -28,4 +28,12 @@ QUERY CreateUser (name: String, age: U8, email: String) =>
         age: age,
         email: email
     })
-    RETURN user
\ No newline at end of file
+    RETURN user
+
+QUERY UserStatsByName () =>
+    stats <- N<User>::GROUP_BY(name){ total_age: SUM(age), avg_age: AVG(age), youngest: MIN(age), oldest: MAX(age), emails: COUNT_DISTINCT(email), ages: COLLECT(age) }
+    RETURN stats
+
+QUERY UsersWithStatsByAge () =>
+    users <- N<User>::AGGREGATE_BY(age){ names: COLLECT(name) }
+    RETURN users
@@ -0,0 +1,32 @@
+QUERY recent_events() =>
+    events <- N<Event>::WHERE(_::{created_at}::GT(DATE_SUB(NOW, 7d)))
+    RETURN events
+
+QUERY events_between(since: Date, until: Date) =>
+    events <- N<Event>::WHERE(AND(_::{created_at}::GTE(since), _::{created_at}::LT(until)))
+    RETURN events
+
+QUERY updated_recently() =>
+    events <- N<Event>::WHERE(_::{updated_at}::GT(DATE_SUB(NOW, 2h)))::ORDER<Desc>(DATE_TRUNC("day", _::{updated_at}))
+    RETURN events
+
+QUERY events_in_year(year: I64) =>
+    events <- N<Event>::WHERE(YEAR(_::{created_at}, "Europe/London")::EQ(year))
+    RETURN events
+
+QUERY daily_totals() =>
+    totals <- N<Event>::GROUP_BY(day: DATE_TRUNC("day", _::{created_at}, "America/New_York"))
+    RETURN totals
+
+QUERY event_dates() =>
+    events <- N<Event>::RANGE(0, 10)
+    RETURN events::{
+        name,
+        day: DATE("2024-03-10 03:30", "America/New_York"),
+        month: MONTH(_::{created_at}),
+        due: DATE_ADD(_::{created_at}, 1w)
+    }
+
+QUERY touch_event(id: ID) =>
+    event <- N<Event>(id)::UPDATE({updated_at: DATE_ADD(NOW, 30m)})
+    RETURN event
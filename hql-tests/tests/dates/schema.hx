@@ -0,0 +1,6 @@
+N::Event {
+    name: String,
+    INDEX created_at: Date,
+    updated_at: Date,
+    amount: I64,
+}
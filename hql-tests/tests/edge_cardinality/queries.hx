@@ -0,0 +1,11 @@
+QUERY moveTo(user_id: ID, city_id: ID) =>
+    home <- AddE<LivesIn>::From(user_id)::To(city_id)
+    RETURN home
+
+QUERY placeOrder(order_id: ID, user_id: ID) =>
+    placed <- AddE<PlacedBy>::From(order_id)::To(user_id)
+    RETURN placed
+
+QUERY electMayor(user_id: ID, city_id: ID) =>
+    mayor <- UpsertE<Mayor>::From(user_id)::To(city_id)
+    RETURN mayor
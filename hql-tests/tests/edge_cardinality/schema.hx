@@ -0,0 +1,26 @@
+N::User {
+    name: String,
+}
+
+N::City {
+    name: String,
+}
+
+N::Order {
+    total: F64,
+}
+
+E::LivesIn MANY_TO_ONE REPLACE {
+    From: User,
+    To: City,
+}
+
+E::PlacedBy MANY_TO_ONE {
+    From: Order,
+    To: User,
+}
+
+E::Mayor ONE_TO_ONE {
+    From: User,
+    To: City,
+}
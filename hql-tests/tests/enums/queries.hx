@@ -0,0 +1,27 @@
+QUERY addUser(email: String, status: Status, role?: Role) =>
+    user <- AddN<User>({email: email, status: status, role: role})
+    RETURN user
+
+QUERY addAdmin(email: String) =>
+    user <- AddN<User>({email: email, status: "Active", role: "Admin"})
+    RETURN user
+
+QUERY invite(from: ID, to: ID, role: Role) =>
+    invited <- AddE<Invited>({role: role})::From(from)::To(to)
+    RETURN invited
+
+QUERY setStatus(id: ID, status: Status) =>
+    user <- N<User>(id)::UPDATE({status: status})
+    RETURN user
+
+QUERY suspend(id: ID) =>
+    user <- N<User>(id)::UPDATE({status: "Suspended"})
+    RETURN user
+
+QUERY usersWithStatus(status: Status) =>
+    users <- N<User>::WHERE(_::{status}::EQ(status))
+    RETURN users
+
+QUERY activeUsers() =>
+    users <- N<User>::WHERE(_::{status}::NEQ("Deleted"))
+    RETURN users
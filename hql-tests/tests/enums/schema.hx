@@ -0,0 +1,21 @@
+ENUM Status {
+    Active,
+    Suspended,
+    Deleted,
+}
+
+ENUM Role { Admin, Member }
+
+N::User {
+    INDEX email: String,
+    status: Status DEFAULT "Active",
+    role?: Role,
+}
+
+E::Invited {
+    From: User,
+    To: User,
+    Properties: {
+        role: Role,
+    }
+}
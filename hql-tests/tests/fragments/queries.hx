@@ -0,0 +1,10 @@
+FN visible_docs(u: ID) => N<User>(u)::Out<Member>::Out<Owns>
+FN popular(min: I64) => _::WHERE(_::{views}::GTE(min))
+
+QUERY getVisibleDocs(user_id: ID) =>
+    docs <- visible_docs(user_id)
+    RETURN docs
+
+QUERY getPopularDocs(user_id: ID, min: I64) =>
+    docs <- visible_docs(user_id)::popular(min)::RANGE(0, 10)
+    RETURN docs
@@ -0,0 +1,5 @@
+N::User { name: String, email: String }
+N::Team { name: String }
+N::Doc { title: String, views: I64 }
+E::Member { From: User, To: Team }
+E::Owns { From: Team, To: Doc }
@@ -0,0 +1,19 @@
+QUERY upsertUser(name: String, age: U32) =>
+    IF EXISTS(N<User>({name: name})) {
+        updated <- N<User>({name: name})::UPDATE({age: age})
+    } ELSE {
+        created <- AddN<User>({name: name, age: age})
+    }
+    RETURN "done"
+
+QUERY followIfNew(from: ID, to: ID, force: Boolean) =>
+    a <- N<User>(from)
+    b <- N<User>(to)
+    IF !EXISTS(a::Out<Follows>::WHERE(_::ID::EQ(to))) {
+        e <- AddE<Follows>::From(a)::To(b)
+    } ELSE IF force {
+        d <- a::OutE<Follows>
+    } ELSE {
+        c <- a::Out<Follows>
+    }
+    RETURN a, b
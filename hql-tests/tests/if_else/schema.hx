@@ -0,0 +1,9 @@
+N::User {
+    INDEX name: String,
+    age: U32,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+}
@@ -0,0 +1,18 @@
+QUERY addReading(title: String) =>
+    reading <- AddN<Reading>({title: title, value: 2.5E+2, offset: -3})
+    RETURN reading
+
+QUERY belowEpsilon() =>
+    readings <- N<Reading>::WHERE(_::{value}::LT(1e-6))
+    RETURN readings
+
+QUERY negativeOffsets() =>
+    readings <- N<Reading>::WHERE(_::{offset}::GT(-5))
+    RETURN readings
+
+QUERY quoted() =>
+    reading <- N<Reading>({title: "say \"hi\"\n"})
+    RETURN reading
+
+QUERY literalNumber() =>
+    RETURN -42
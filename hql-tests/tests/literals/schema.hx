@@ -0,0 +1,6 @@
+N::Reading {
+    INDEX title: String,
+    note: String DEFAULT "n/a\t(\"unset\")",
+    value: F64 DEFAULT -1e-3,
+    offset: I32 DEFAULT -7
+}
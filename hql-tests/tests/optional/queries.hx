@@ -0,0 +1,23 @@
+QUERY addUser(email: String, name: String, bio?: String, age?: U32) =>
+    user <- AddN<User>({email: email, name: name, bio: bio, age: age})
+    RETURN user
+
+QUERY follow(from: ID, to: ID, note?: String) =>
+    follows <- AddE<Follows>({note: note})::From(from)::To(to)
+    RETURN follows
+
+QUERY setBio(id: ID, bio?: String) =>
+    user <- N<User>(id)::UPDATE({bio: bio})
+    RETURN user
+
+QUERY clearBio(id: ID) =>
+    user <- N<User>(id)::UPDATE({bio: NONE})
+    RETURN user
+
+QUERY usersWithoutBio() =>
+    users <- N<User>::WHERE(_::{bio}::IS_NULL)
+    RETURN users
+
+QUERY usersWithAge() =>
+    users <- N<User>::WHERE(AND(_::{age}::IS_NOT_NULL, _::{name}::IS_NOT_NULL))
+    RETURN users
@@ -0,0 +1,14 @@
+N::User {
+    INDEX email: String,
+    name: String,
+    bio: Optional<String>,
+    age?: U32,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+    Properties: {
+        note?: String,
+    }
+}
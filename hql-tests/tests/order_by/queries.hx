@@ -21,3 +21,7 @@ QUERY GetOrder() =>
     userByAge2 <- N<User>::ORDER<Asc>(_::{created_at})
     RETURN userByAge, userByAge2
 
+
+QUERY GetOldestUsers(limit: U32) =>
+    users <- N<User>::ORDER<Desc>(_::{age}), ORDER<Asc>(_::{name}) NULLS FIRST::RANGE(0, limit)
+    RETURN users
@@ -0,0 +1,25 @@
+QUERY findByPrefix(prefix: String) =>
+    users <- N<User>::WHERE(_::{name}::STARTS_WITH(prefix))
+    RETURN users
+
+QUERY findByDomain(domain: String) =>
+    users <- N<User>::WHERE(AND(_::{email}::ENDS_WITH(domain), _::{email}::REGEX("^[a-z.]+@")))
+    RETURN users
+
+QUERY findByName(name: String) =>
+    users <- N<User>::WHERE(_::{name}::EQ_IGNORE_CASE(name))
+    exact <- N<User>::WHERE(_::{name}::EQ(LOWER(TRIM(name))))
+    RETURN users, exact
+
+QUERY listUsers() =>
+    users <- N<User>
+    RETURN users::{
+        shout: UPPER(_::{name}),
+        initials: SUBSTRING(_::{name}, 0, 2),
+        nameLength: LENGTH(_::{name}),
+        handle: CONCAT(_::{name}, "@", _::{id})
+    }
+
+QUERY renameUser(id: ID, name: String) =>
+    user <- N<User>(id)::UPDATE({name: CONCAT(UPPER(SUBSTRING(name, 0, 1)), LOWER(SUBSTRING(name, 1)))})
+    RETURN user
@@ -0,0 +1 @@
+N::User { name: String, email: String, age: I32 }
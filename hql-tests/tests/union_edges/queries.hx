@@ -0,0 +1,28 @@
+QUERY tagPost(post_id: ID, tag_id: ID) =>
+    tagged <- AddE<Tagged>::From(post_id)::To(tag_id)
+    RETURN tagged
+
+QUERY taggedItems(tag_id: ID) =>
+    items <- N<Tag>(tag_id)::In<Tagged>
+    RETURN items
+
+QUERY taggedBodies(tag_id: ID) =>
+    bodies <- N<Tag>(tag_id)::In<Tagged>::WHERE(_::{body}::CONTAINS("helix"))::{label, body, title}
+    RETURN bodies
+
+QUERY postTags(post_id: ID) =>
+    tags <- N<Post>(post_id)::Out<Tagged>
+    RETURN tags
+
+QUERY commentTags(comment_id: ID) =>
+    tags <- N<Comment>(comment_id)::Out<Tagged>
+    RETURN tags
+
+QUERY mentioned(comment_id: ID) =>
+    items <- N<Comment>(comment_id)::Out<Mentions>
+    mention_edges <- N<Comment>(comment_id)::OutE<Mentions>::ToN
+    RETURN items, mention_edges
+
+QUERY mentionedTags(comment_id: ID) =>
+    tags <- N<Comment>(comment_id)::Out<Mentions>::Out<Tagged>
+    RETURN tags
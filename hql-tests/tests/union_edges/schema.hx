@@ -0,0 +1,23 @@
+N::Post {
+    title: String,
+    body: String,
+}
+
+N::Comment {
+    body: String,
+    upvotes: U32,
+}
+
+N::Tag {
+    name: String,
+}
+
+E::Tagged {
+    From: Post | Comment,
+    To: Tag,
+}
+
+E::Mentions {
+    From: Comment,
+    To: Post | Comment,
+}
@@ -0,0 +1,20 @@
+QUERY upsertUser(email: String, name: String) =>
+    user <- UpsertN<User>({email: email}, {name: name})
+    RETURN user
+
+QUERY touchUser(email: String) =>
+    user <- UpsertN<User>({email: email})
+    RETURN user
+
+QUERY follow(from: ID, to: ID, since: I64) =>
+    a <- N<User>(from)
+    b <- N<User>(to)
+    e <- UpsertE<Follows>({since: since})::From(a)::To(b)
+    d <- UpsertE<DependsOn>::From(a)::To(b)
+    RETURN e, d
+
+QUERY followAll(from: ID) =>
+    a <- N<User>(from)
+    others <- N<User>
+    UpsertE<Follows>({since: 1})::From(a)::To(others)
+    RETURN a
@@ -0,0 +1,18 @@
+N::User {
+    INDEX email: String,
+    name: String,
+    created_at: Date DEFAULT NOW,
+}
+
+E::Follows {
+    From: User,
+    To: User,
+    Properties: {
+        since: I64,
+    }
+}
+
+E::DependsOn ACYCLIC {
+    From: User,
+    To: User,
+}